@@ -0,0 +1,162 @@
+//! Cache für API-Antworten (Speicher + `cache/api/` auf der Platte), geschlüsselt
+//! nach Anfrage-URL. Frische Einträge ersparen wiederholte Anfragen beim Stöbern
+//! und bei Update-Checks; ältere dienen als Rückfall, wenn das Netz fehlt.
+
+use anyhow::Result;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+/// Suchergebnisse ändern sich schnell (Downloads, Sortierung)
+pub const SEARCH_TTL: Duration = Duration::from_secs(10 * 60);
+pub const VERSIONS_TTL: Duration = Duration::from_secs(30 * 60);
+
+/// So alt darf ein Eintrag sein, der ohne Netz noch ausgeliefert wird
+const OFFLINE_MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+const MEMORY_ENTRIES: usize = 256;
+
+/// Abstand zwischen zwei Aufräumläufen des Platten-Caches
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Zeitpunkt (Unix-Sekunden) des letzten Aufräumens
+static LAST_PRUNE: std::sync::atomic::AtomicI64 = std::sync::atomic::AtomicI64::new(0);
+
+#[derive(Clone, Serialize, Deserialize)]
+struct Entry {
+    fetched_at: i64,
+    body: String,
+}
+
+impl Entry {
+    fn younger_than(&self, age: Duration) -> bool {
+        chrono::Utc::now().timestamp() - self.fetched_at < age.as_secs() as i64
+    }
+}
+
+fn memory() -> &'static Mutex<HashMap<String, Entry>> {
+    static MEMORY: OnceLock<Mutex<HashMap<String, Entry>>> = OnceLock::new();
+    MEMORY.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+pub fn cache_dir() -> PathBuf {
+    crate::config::defaults::launcher_dir().join("cache").join("api")
+}
+
+fn entry_path(key: &str) -> PathBuf {
+    cache_dir().join(format!("{}.json", hex::encode(Sha256::digest(key.as_bytes()))))
+}
+
+async fn load(key: &str) -> Option<Entry> {
+    if let Some(entry) = memory().lock().ok()?.get(key) {
+        return Some(entry.clone());
+    }
+    let content = tokio::fs::read_to_string(entry_path(key)).await.ok()?;
+    let entry: Entry = serde_json::from_str(&content).ok()?;
+    remember(key, &entry);
+    Some(entry)
+}
+
+fn remember(key: &str, entry: &Entry) {
+    let Ok(mut memory) = memory().lock() else { return };
+    if memory.len() >= MEMORY_ENTRIES && !memory.contains_key(key) {
+        // Ältesten Eintrag verdrängen; auf der Platte bleibt er erhalten
+        if let Some(oldest) = memory.iter().min_by_key(|(_, e)| e.fetched_at).map(|(k, _)| k.clone()) {
+            memory.remove(&oldest);
+        }
+    }
+    memory.insert(key.to_string(), entry.clone());
+}
+
+/// Entfernt Einträge, die älter als `OFFLINE_MAX_AGE` sind – sie würden nie mehr
+/// ausgeliefert. Blockierend; gibt die Anzahl gelöschter Dateien zurück.
+pub fn prune_expired() -> usize {
+    if let Ok(mut memory) = memory().lock() {
+        memory.retain(|_, entry| entry.younger_than(OFFLINE_MAX_AGE));
+    }
+    let Ok(entries) = std::fs::read_dir(cache_dir()) else { return 0 };
+    let mut removed = 0;
+    for entry in entries.flatten() {
+        let expired = entry.metadata()
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_some_and(|age| age > OFFLINE_MAX_AGE);
+        if expired && std::fs::remove_file(entry.path()).is_ok() {
+            removed += 1;
+        }
+    }
+    if removed > 0 {
+        tracing::debug!("Pruned {} expired API cache entries", removed);
+    }
+    removed
+}
+
+/// Räumt höchstens einmal pro `PRUNE_INTERVAL` im Hintergrund auf
+fn prune_if_due() {
+    use std::sync::atomic::Ordering;
+    let now = chrono::Utc::now().timestamp();
+    let last = LAST_PRUNE.load(Ordering::Relaxed);
+    if now - last < PRUNE_INTERVAL.as_secs() as i64
+        || LAST_PRUNE.compare_exchange(last, now, Ordering::Relaxed, Ordering::Relaxed).is_err()
+    {
+        return;
+    }
+    tokio::task::spawn_blocking(prune_expired);
+}
+
+async fn store(key: &str, body: String) {
+    let entry = Entry { fetched_at: chrono::Utc::now().timestamp(), body };
+    remember(key, &entry);
+    let write = async {
+        tokio::fs::create_dir_all(cache_dir()).await?;
+        tokio::fs::write(entry_path(key), serde_json::to_vec(&entry)?).await?;
+        anyhow::Ok(())
+    };
+    if let Err(e) = write.await {
+        tracing::debug!("Could not write API cache entry: {}", e);
+    }
+    prune_if_due();
+}
+
+/// Liefert die Antwort aus dem Cache, solange sie jünger als `ttl` ist, sonst über
+/// `fetch` (Rohtext der Antwort). Schlägt `fetch` fehl, wird ein bis zu einem Tag
+/// alter Eintrag verwendet.
+pub async fn cached_json<T, F, Fut>(key: &str, ttl: Duration, fetch: F) -> Result<T>
+where
+    T: DeserializeOwned,
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<String>>,
+{
+    let cached = load(key).await;
+    if let Some(entry) = cached.as_ref().filter(|e| e.younger_than(ttl)) {
+        if let Ok(value) = serde_json::from_str(&entry.body) {
+            return Ok(value);
+        }
+    }
+
+    match fetch().await {
+        Ok(body) => {
+            let value = serde_json::from_str(&body)?;
+            store(key, body).await;
+            Ok(value)
+        }
+        Err(e) => {
+            let stale = cached
+                .filter(|entry| entry.younger_than(OFFLINE_MAX_AGE))
+                .and_then(|entry| serde_json::from_str(&entry.body).ok());
+            match stale {
+                Some(value) => {
+                    tracing::warn!("Request failed ({}), using cached response for {}", e, key);
+                    Ok(value)
+                }
+                None => Err(e),
+            }
+        }
+    }
+}
@@ -42,17 +42,18 @@ impl CurseForgeClient {
             url.push_str(&format!("&gameVersion={}", version));
         }
 
-        let response = self.client
-            .get(&url)
-            .header("x-api-key", api_key)
-            .send()
-            .await?;
+        let cf_response: CurseForgeResponse<Vec<CurseForgeMod>> = crate::api::cache::cached_json(&url, crate::api::cache::SEARCH_TTL, || async {
+            let response = self.client
+                .get(&url)
+                .header("x-api-key", api_key)
+                .send()
+                .await?;
 
-        if !response.status().is_success() {
-            bail!("CurseForge API request failed: {}", response.status());
-        }
-
-        let cf_response: CurseForgeResponse<Vec<CurseForgeMod>> = response.json().await?;
+            if !response.status().is_success() {
+                bail!("CurseForge API request failed: {}", response.status());
+            }
+            Ok(response.text().await?)
+        }).await?;
 
         let mods = cf_response.data.into_iter().map(|cf_mod| {
             let slug = cf_mod.slug.clone();
@@ -84,6 +85,7 @@ impl CurseForgeClient {
                 wiki_url: None,
                 discord_url: None,
                 gallery: vec![],
+                also_on: vec![],
             }
         }).collect();
 
@@ -133,8 +135,155 @@ impl CurseForgeClient {
             wiki_url: None,
             discord_url: None,
             gallery: vec![],
+            also_on: vec![],
+        })
+    }
+
+    /// Identifiziert Dateien anhand ihrer Fingerprints (`utils::murmur2::curseforge_fingerprint`)
+    pub async fn match_fingerprints(&self, fingerprints: &[u32]) -> Result<Vec<FingerprintMatch>> {
+        let api_key = self.check_api_key()?;
+        if fingerprints.is_empty() {
+            return Ok(Vec::new());
+        }
+        let url = format!("{}/fingerprints/{}", CURSEFORGE_API_BASE, MINECRAFT_GAME_ID);
+
+        let response = self.client
+            .post(&url)
+            .header("x-api-key", api_key)
+            .json(&serde_json::json!({ "fingerprints": fingerprints }))
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            bail!("CurseForge fingerprint request failed: {}", response.status());
+        }
+
+        let cf_response: CurseForgeResponse<FingerprintMatches> = response.json().await?;
+        Ok(cf_response.data.exact_matches)
+    }
+
+    /// Name und Logo mehrerer Projekte in einer Anfrage
+    pub async fn get_mod_names(&self, mod_ids: &[i32]) -> Result<Vec<(i32, String, Option<String>)>> {
+        let api_key = self.check_api_key()?;
+        if mod_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let response = self.client
+            .post(format!("{}/mods", CURSEFORGE_API_BASE))
+            .header("x-api-key", api_key)
+            .json(&serde_json::json!({ "modIds": mod_ids }))
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            bail!("CurseForge mods request failed: {}", response.status());
+        }
+
+        let cf_response: CurseForgeResponse<Vec<CurseForgeMod>> = response.json().await?;
+        Ok(cf_response.data.into_iter().map(|m| (m.id, m.name, m.logo.map(|l| l.url))).collect())
+    }
+
+    /// Projekt-ID → Klasse (6 = Mod, 12 = Resource Pack, 6552 = Shader Pack)
+    pub async fn get_mod_classes(&self, mod_ids: &[i32]) -> Result<std::collections::HashMap<i32, i32>> {
+        let api_key = self.check_api_key()?;
+        if mod_ids.is_empty() {
+            return Ok(Default::default());
+        }
+
+        let response = self.client
+            .post(format!("{}/mods", CURSEFORGE_API_BASE))
+            .header("x-api-key", api_key)
+            .json(&serde_json::json!({ "modIds": mod_ids }))
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            bail!("CurseForge mods request failed: {}", response.status());
+        }
+
+        let cf_response: CurseForgeResponse<Vec<CurseForgeMod>> = response.json().await?;
+        Ok(cf_response.data.into_iter().filter_map(|m| Some((m.id, m.class_id?))).collect())
+    }
+
+    /// Mehrere Dateien (z.B. aus einem Modpack-Manifest) in einer Anfrage
+    pub async fn get_files(&self, file_ids: &[i32]) -> Result<Vec<CurseForgeFile>> {
+        let api_key = self.check_api_key()?;
+        if file_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let response = self.client
+            .post(format!("{}/mods/files", CURSEFORGE_API_BASE))
+            .header("x-api-key", api_key)
+            .json(&serde_json::json!({ "fileIds": file_ids }))
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            bail!("CurseForge files request failed: {}", response.status());
+        }
+
+        let cf_response: CurseForgeResponse<Vec<CurseForgeFile>> = response.json().await?;
+        Ok(cf_response.data)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FingerprintMatches {
+    #[serde(default)]
+    exact_matches: Vec<FingerprintMatch>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FingerprintMatch {
+    /// Projekt-ID
+    pub id: i32,
+    pub file: CurseForgeFile,
+    #[serde(default)]
+    pub latest_files: Vec<CurseForgeFile>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurseForgeFile {
+    pub id: i32,
+    pub mod_id: i32,
+    pub display_name: String,
+    pub file_name: String,
+    #[serde(default)]
+    pub file_fingerprint: u32,
+    /// Minecraft-Versionen und Loader gemischt, z.B. ["1.20.1", "Fabric"]
+    #[serde(default)]
+    pub game_versions: Vec<String>,
+    /// Fehlt, wenn der Autor Downloads über Drittanbieter deaktiviert hat
+    #[serde(default)]
+    pub download_url: Option<String>,
+    #[serde(default)]
+    pub hashes: Vec<CurseForgeFileHash>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CurseForgeFileHash {
+    pub value: String,
+    /// 1 = SHA-1, 2 = MD5
+    pub algo: i32,
+}
+
+impl CurseForgeFile {
+    pub fn sha1(&self) -> Option<&str> {
+        self.hashes.iter().find(|h| h.algo == 1).map(|h| h.value.as_str())
+    }
+
+    /// Download-URL, bei fehlender `downloadUrl` über das CDN abgeleitet
+    pub fn resolved_download_url(&self) -> String {
+        self.download_url.clone().unwrap_or_else(|| {
+            format!("https://edge.forgecdn.net/files/{}/{}/{}", self.id / 1000, self.id % 1000, self.file_name)
         })
     }
+
+    pub fn supports(&self, minecraft_version: &str, loader: &str) -> bool {
+        self.game_versions.iter().any(|v| v == minecraft_version)
+            && (loader == "vanilla" || self.game_versions.iter().any(|v| v.eq_ignore_ascii_case(loader)))
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -146,6 +295,8 @@ struct CurseForgeResponse<T> {
 #[serde(rename_all = "camelCase")]
 struct CurseForgeMod {
     id: i32,
+    #[serde(default)]
+    class_id: Option<i32>,
     slug: String,
     name: String,
     summary: String,
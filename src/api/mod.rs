@@ -1,4 +1,6 @@
+pub mod cache;
 pub mod client;
+pub mod rate_limit;
 pub mod modrinth;
 pub mod curseforge;
 pub mod mojang;
@@ -2,11 +2,19 @@
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use crate::api::cache;
 use crate::api::client::ApiClient;
+use crate::api::rate_limit::RateLimiter;
 use crate::types::mod_info::{ModInfo, ModVersion, ModSource, ModSearchQuery, ModFile, FileHashes, ModDependency, DependencyType};
 
 const MODRINTH_API_BASE: &str = "https://api.modrinth.com/v2";
 
+/// "Trending" = Projekte aus diesem Zeitraum, nach Downloads sortiert
+const TRENDING_WINDOW_DAYS: i64 = 90;
+
+/// Gemeinsam für alle `ModrinthClient`-Instanzen, das Limit gilt pro IP
+static RATE_LIMITER: RateLimiter = RateLimiter::new();
+
 pub struct ModrinthClient {
     client: ApiClient,
 }
@@ -18,12 +26,38 @@ impl ModrinthClient {
         })
     }
 
+    /// GET über das gemeinsame Rate-Limit (wartet bzw. wiederholt bei 429)
+    pub async fn get(&self, url: &str) -> Result<reqwest::Response> {
+        RATE_LIMITER.send(|| self.client.get_client().get(url)).await
+    }
+
+    /// Wie `get`, mit Query-Parametern
+    pub async fn get_query(&self, url: &str, query: &[(&str, &str)]) -> Result<reqwest::Response> {
+        RATE_LIMITER.send(|| self.client.get_client().get(url).query(query)).await
+    }
+
+    async fn get_json<T: serde::de::DeserializeOwned>(&self, url: &str) -> Result<T> {
+        Ok(self.get(url).await?.error_for_status()?.json().await?)
+    }
+
+    /// Wie `get_json`, aber über den Antwort-Cache
+    async fn get_json_cached<T: serde::de::DeserializeOwned>(&self, url: &str, ttl: std::time::Duration) -> Result<T> {
+        cache::cached_json(url, ttl, || async {
+            Ok(self.get(url).await?.error_for_status()?.text().await?)
+        }).await
+    }
+
     pub async fn get_categories(&self) -> Result<Vec<ModrinthCategory>> {
         let url = format!("{}/tag/category", MODRINTH_API_BASE);
-        let categories: Vec<ModrinthCategory> = self.client.get_json(&url).await?;
+        let categories: Vec<ModrinthCategory> = self.get_json_cached(&url, cache::VERSIONS_TTL).await?;
         Ok(categories)
     }
 
+    pub async fn get_licenses(&self) -> Result<Vec<ModrinthLicense>> {
+        let url = format!("{}/tag/license", MODRINTH_API_BASE);
+        self.get_json_cached(&url, cache::VERSIONS_TTL).await
+    }
+
     pub async fn search_mods(&self, query: &ModSearchQuery) -> Result<Vec<ModInfo>> {
         // Sortierung für Modrinth API
         let index = match query.sort_by {
@@ -76,47 +110,84 @@ impl ModrinthClient {
             }
         }
 
+        if let Some(license) = query.license.as_deref().filter(|l| !l.is_empty()) {
+            facets.push(format!("[\"license:{}\"]", license.to_lowercase()));
+        }
+
+        // Umgebung: client_side/server_side = required | optional | unsupported
+        for (facet, value) in [("client_side", &query.client_side), ("server_side", &query.server_side)] {
+            match value.as_deref() {
+                None | Some("") => {}
+                Some(side @ ("required" | "optional" | "unsupported")) => {
+                    facets.push(format!("[\"{}:{}\"]", facet, side));
+                }
+                Some(other) => tracing::warn!("Ignoring unknown {} filter: {}", facet, other),
+            }
+        }
+
         // Nur Mods (keine Modpacks etc.)
         facets.push("[\"project_type:mod\"]".to_string());
 
         if !facets.is_empty() {
-            url.push_str(&format!("&facets=[{}]", facets.join(",")));
+            let facets = format!("[{}]", facets.join(","));
+            url.push_str(&format!("&facets={}", urlencoding::encode(&facets)));
         }
 
-        let response: ModrinthSearchResponse = self.client.get_json(&url).await?;
+        let response: ModrinthSearchResponse = self.get_json_cached(&url, cache::SEARCH_TTL).await?;
 
-        let mods = response.hits.into_iter().map(|hit| ModInfo {
-            id: hit.project_id,
-            slug: hit.slug.clone(),
-            name: hit.title,
-            description: hit.description,
-            body: None,
-            icon_url: Some(hit.icon_url),
-            author: hit.author,
-            downloads: hit.downloads as u64,
-            followers: None,
-            categories: hit.categories,
-            source: ModSource::Modrinth,
-            versions: hit.versions.clone(),
-            game_versions: hit.versions,
-            loaders: vec![],
-            project_url: format!("https://modrinth.com/mod/{}", hit.slug),
-            updated_at: hit.date_modified,
-            client_side: hit.client_side,
-            server_side: hit.server_side,
-            source_url: None,
-            issues_url: None,
-            wiki_url: None,
-            discord_url: None,
-            gallery: vec![],
-        }).collect();
+        let mods = response.hits.into_iter().map(|hit| hit.into_mod_info("mod")).collect();
 
         Ok(mods)
     }
 
+    /// Startseiten-Listen ohne Suchbegriff (`project_type`: mod, resourcepack, shader, modpack)
+    pub async fn browse(
+        &self,
+        project_type: &str,
+        list: BrowseList,
+        game_version: Option<&str>,
+        loader: Option<&str>,
+        limit: u32,
+    ) -> Result<Vec<ModInfo>> {
+        let mut facets = vec![format!("[\"project_type:{}\"]", project_type)];
+        if let Some(version) = game_version.filter(|v| !v.is_empty()) {
+            facets.push(format!("[\"versions:{}\"]", version));
+        }
+        if let Some(loader) = loader.filter(|l| !l.is_empty()) {
+            facets.push(format!("[\"categories:{}\"]", loader));
+        }
+        let index = match list {
+            BrowseList::Popular => "downloads",
+            BrowseList::Updated => "updated",
+            BrowseList::Trending => {
+                // Neue Projekte mit den meisten Downloads
+                let since = chrono::Utc::now().timestamp() - TRENDING_WINDOW_DAYS * 24 * 60 * 60;
+                facets.push(format!("[\"created_timestamp>{}\"]", since));
+                "downloads"
+            }
+        };
+
+        let facets = format!("[{}]", facets.join(","));
+        let url = format!(
+            "{}/search?limit={}&index={}&facets={}",
+            MODRINTH_API_BASE,
+            limit,
+            index,
+            urlencoding::encode(&facets)
+        );
+        // Der Zeitstempel im Trending-Filter ändert sich sekündlich; als Cache-Schlüssel
+        // zählt deshalb die Anfrage ohne ihn
+        let key = format!("browse:{}:{:?}:{:?}:{:?}:{}", project_type, list, game_version, loader, limit);
+        let response: ModrinthSearchResponse = cache::cached_json(&key, cache::SEARCH_TTL, || async {
+            Ok(self.get(&url).await?.error_for_status()?.text().await?)
+        }).await?;
+
+        Ok(response.hits.into_iter().map(|hit| hit.into_mod_info(project_type)).collect())
+    }
+
     pub async fn get_mod(&self, mod_id: &str) -> Result<ModInfo> {
         let url = format!("{}/project/{}", MODRINTH_API_BASE, mod_id);
-        let project: ModrinthProject = self.client.get_json(&url).await?;
+        let project: ModrinthProject = self.get_json(&url).await?;
 
         Ok(ModInfo {
             id: project.id,
@@ -146,46 +217,28 @@ impl ModrinthClient {
                 title: img.title,
                 description: img.description,
             }).collect(),
+            also_on: vec![],
         })
     }
 
     pub async fn get_versions(&self, mod_id: &str) -> Result<Vec<ModVersion>> {
         let url = format!("{}/project/{}/version", MODRINTH_API_BASE, mod_id);
-        let versions: Vec<ModrinthVersion> = self.client.get_json(&url).await?;
-
-        let mod_versions = versions.into_iter().map(|v| ModVersion {
-            id: v.id.clone(),
-            mod_id: v.project_id,
-            name: v.name,
-            version_number: v.version_number,
-            game_versions: v.game_versions,
-            loaders: v.loaders,
-            files: v.files.into_iter().map(|f| ModFile {
-                url: f.url,
-                filename: f.filename,
-                primary: f.primary,
-                size: f.size as u64,
-                hashes: FileHashes {
-                    sha1: f.hashes.sha1,
-                    sha512: f.hashes.sha512,
-                },
-            }).collect(),
-            dependencies: v.dependencies.into_iter().map(|d| ModDependency {
-                mod_id: d.project_id.unwrap_or_default(),
-                dependency_type: match d.dependency_type.as_str() {
-                    "required" => DependencyType::Required,
-                    "optional" => DependencyType::Optional,
-                    "incompatible" => DependencyType::Incompatible,
-                    "embedded" => DependencyType::Embedded,
-                    _ => DependencyType::Optional,
-                },
-            }).collect(),
-            published: v.date_published,
-            version_type: Some(v.version_type),
-            downloads: Some(v.downloads as u64),
-        }).collect();
+        let versions: Vec<ModrinthVersion> = self.get_json_cached(&url, cache::VERSIONS_TTL).await?;
+
+        Ok(versions.into_iter().map(ModrinthVersion::into_mod_version).collect())
+    }
 
-        Ok(mod_versions)
+    /// Versionen zu den SHA-1-Hashes lokaler Dateien (unbekannte Dateien fehlen im Ergebnis)
+    pub async fn get_versions_by_hashes(&self, sha1_hashes: &[String]) -> Result<std::collections::HashMap<String, ModVersion>> {
+        let url = format!("{}/version_files", MODRINTH_API_BASE);
+        let body = serde_json::json!({ "hashes": sha1_hashes, "algorithm": "sha1" });
+        let versions: std::collections::HashMap<String, ModrinthVersion> = RATE_LIMITER
+            .send(|| self.client.get_client().post(&url).json(&body))
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(versions.into_iter().map(|(hash, v)| (hash, v.into_mod_version())).collect())
     }
 }
 
@@ -200,7 +253,8 @@ struct ModrinthSearchHit {
     slug: String,
     title: String,
     description: String,
-    icon_url: String,
+    #[serde(default)]
+    icon_url: Option<String>,
     author: String,
     downloads: i64,
     categories: Vec<String>,
@@ -212,6 +266,37 @@ struct ModrinthSearchHit {
     server_side: Option<String>,
 }
 
+impl ModrinthSearchHit {
+    fn into_mod_info(self, project_type: &str) -> ModInfo {
+        ModInfo {
+            id: self.project_id,
+            slug: self.slug.clone(),
+            name: self.title,
+            description: self.description,
+            body: None,
+            icon_url: self.icon_url,
+            author: self.author,
+            downloads: self.downloads as u64,
+            followers: None,
+            categories: self.categories,
+            source: ModSource::Modrinth,
+            versions: self.versions.clone(),
+            game_versions: self.versions,
+            loaders: vec![],
+            project_url: format!("https://modrinth.com/{}/{}", project_type, self.slug),
+            updated_at: self.date_modified,
+            client_side: self.client_side,
+            server_side: self.server_side,
+            source_url: None,
+            issues_url: None,
+            wiki_url: None,
+            discord_url: None,
+            gallery: vec![],
+            also_on: vec![],
+        }
+    }
+}
+
 #[derive(Debug, Deserialize)]
 struct ModrinthProject {
     id: String,
@@ -274,6 +359,42 @@ struct ModrinthVersion {
     downloads: i64,
 }
 
+impl ModrinthVersion {
+    fn into_mod_version(self) -> ModVersion {
+        ModVersion {
+            id: self.id,
+            mod_id: self.project_id,
+            name: self.name,
+            version_number: self.version_number,
+            game_versions: self.game_versions,
+            loaders: self.loaders,
+            files: self.files.into_iter().map(|f| ModFile {
+                url: f.url,
+                filename: f.filename,
+                primary: f.primary,
+                size: f.size as u64,
+                hashes: FileHashes {
+                    sha1: f.hashes.sha1,
+                    sha512: f.hashes.sha512,
+                },
+            }).collect(),
+            dependencies: self.dependencies.into_iter().map(|d| ModDependency {
+                mod_id: d.project_id.unwrap_or_default(),
+                dependency_type: match d.dependency_type.as_str() {
+                    "required" => DependencyType::Required,
+                    "optional" => DependencyType::Optional,
+                    "incompatible" => DependencyType::Incompatible,
+                    "embedded" => DependencyType::Embedded,
+                    _ => DependencyType::Optional,
+                },
+            }).collect(),
+            published: self.date_published,
+            version_type: Some(self.version_type),
+            downloads: Some(self.downloads as u64),
+        }
+    }
+}
+
 #[derive(Debug, Deserialize)]
 struct ModrinthFile {
     url: String,
@@ -303,3 +424,16 @@ pub struct ModrinthCategory {
     pub header: String,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ModrinthLicense {
+    pub short: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BrowseList {
+    Popular,
+    Trending,
+    Updated,
+}
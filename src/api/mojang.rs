@@ -3,7 +3,7 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use crate::api::client::ApiClient;
-use crate::types::version::{MinecraftVersion, VersionType};
+use crate::types::version::{java_version_for_release, MinecraftVersion, VersionType};
 
 const VERSION_MANIFEST_URL: &str = "https://piston-meta.mojang.com/mc/game/version_manifest_v2.json";
 
@@ -21,18 +21,17 @@ impl MojangClient {
     pub async fn get_version_manifest(&self) -> Result<Vec<MinecraftVersion>> {
         let manifest: VersionManifest = self.client.get_json(VERSION_MANIFEST_URL).await?;
 
-        let versions = manifest.versions.into_iter().map(|v| MinecraftVersion {
-            id: v.id,
-            version_type: match v.version_type.as_str() {
-                "release" => VersionType::Release,
-                "snapshot" => VersionType::Snapshot,
-                "old_beta" => VersionType::OldBeta,
-                "old_alpha" => VersionType::OldAlpha,
-                _ => VersionType::Release,
-            },
-            release_time: v.release_time,
-            url: Some(v.url),
-        }).collect();
+        // Java-Version aus bereits geladenen Version-JSONs, sonst über das Release-Datum
+        let versions = crate::utils::compression::run_blocking(move || {
+            Ok(manifest.versions.into_iter().map(|v| MinecraftVersion {
+                java_version: crate::core::minecraft::cached_java_version(&v.id)
+                    .unwrap_or_else(|| java_version_for_release(&v.release_time)),
+                id: v.id,
+                version_type: VersionType::parse(&v.version_type).unwrap_or(VersionType::Release),
+                release_time: v.release_time,
+                url: Some(v.url),
+            }).collect())
+        }).await?;
 
         Ok(versions)
     }
@@ -108,6 +107,10 @@ pub struct Rule {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OsRule {
     pub name: Option<String>,
+    #[serde(default)]
+    pub arch: Option<String>,
+    #[serde(default)]
+    pub version: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
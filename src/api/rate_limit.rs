@@ -0,0 +1,102 @@
+//! Rate-Limit für APIs mit `X-RateLimit-*`-Headern (Modrinth: 300 Anfragen/Minute).
+//! Anfragen werden in eine Warteschlange gestellt, sobald das Kontingent aufgebraucht
+//! ist, und bei 429 mit Backoff wiederholt – Massen-Operationen brechen so nicht ab.
+
+use anyhow::{Result, bail};
+use reqwest::{RequestBuilder, Response, StatusCode};
+use reqwest::header::HeaderMap;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+const MAX_RETRIES: u32 = 5;
+
+/// Wartezeit bei 429 ohne verwertbare Header (verdoppelt sich pro Versuch)
+const BASE_BACKOFF: Duration = Duration::from_secs(2);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+#[derive(Default)]
+struct Window {
+    remaining: Option<u32>,
+    reset_at: Option<Instant>,
+}
+
+#[derive(Default)]
+pub struct RateLimiter {
+    window: Mutex<Window>,
+}
+
+fn header_u64(headers: &HeaderMap, name: &str) -> Option<u64> {
+    headers.get(name)?.to_str().ok()?.trim().parse().ok()
+}
+
+impl RateLimiter {
+    pub const fn new() -> Self {
+        Self { window: Mutex::const_new(Window { remaining: None, reset_at: None }) }
+    }
+
+    /// Wartet, bis das aktuelle Fenster wieder Anfragen erlaubt. Das Lock wird
+    /// während des Wartens gehalten, damit alle anderen Anfragen dahinter anstehen.
+    async fn acquire(&self) {
+        let mut window = self.window.lock().await;
+        if window.remaining == Some(0) {
+            if let Some(reset_at) = window.reset_at {
+                let wait = reset_at.saturating_duration_since(Instant::now());
+                if !wait.is_zero() {
+                    tracing::info!("Rate limit reached, waiting {:?}", wait);
+                    tokio::time::sleep(wait).await;
+                }
+            }
+            *window = Window::default();
+        }
+        // Anfrage vorab abziehen, bis die Antwort den echten Stand liefert
+        if let Some(remaining) = window.remaining.as_mut() {
+            *remaining = remaining.saturating_sub(1);
+        }
+    }
+
+    async fn update(&self, headers: &HeaderMap) {
+        let remaining = header_u64(headers, "x-ratelimit-remaining");
+        let reset = header_u64(headers, "x-ratelimit-reset");
+        if remaining.is_none() && reset.is_none() {
+            return;
+        }
+        let mut window = self.window.lock().await;
+        window.remaining = remaining.map(|r| r as u32);
+        window.reset_at = reset.map(|secs| Instant::now() + Duration::from_secs(secs));
+    }
+
+    /// Sperrt alle Anfragen bis `until` (nach einem 429)
+    async fn block_until(&self, until: Instant) {
+        let mut window = self.window.lock().await;
+        window.remaining = Some(0);
+        window.reset_at = Some(window.reset_at.map_or(until, |r| r.max(until)));
+    }
+
+    /// Sendet eine Anfrage unter Beachtung des Limits. `build` erzeugt die Anfrage
+    /// für jeden Versuch neu (ein `RequestBuilder` ist nicht wiederverwendbar).
+    pub async fn send(&self, build: impl Fn() -> RequestBuilder) -> Result<Response> {
+        let mut attempt = 0;
+        loop {
+            self.acquire().await;
+            let response = build().send().await?;
+            self.update(response.headers()).await;
+
+            if response.status() != StatusCode::TOO_MANY_REQUESTS {
+                return Ok(response);
+            }
+            attempt += 1;
+            if attempt > MAX_RETRIES {
+                bail!("Rate limit exceeded for {} after {} retries", response.url(), MAX_RETRIES);
+            }
+
+            let headers = response.headers();
+            let wait = header_u64(headers, "retry-after")
+                .or_else(|| header_u64(headers, "x-ratelimit-reset"))
+                .map(Duration::from_secs)
+                .unwrap_or_else(|| BASE_BACKOFF * 2u32.pow(attempt - 1))
+                .clamp(Duration::from_secs(1), MAX_BACKOFF);
+            tracing::warn!("429 from {}, retrying in {:?} ({}/{})", response.url(), wait, attempt, MAX_RETRIES);
+            self.block_until(Instant::now() + wait).await;
+        }
+    }
+}
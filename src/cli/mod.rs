@@ -0,0 +1,218 @@
+//! Kommandozeilen-Modus für Automatisierung (z.B. Server-Admins die Instanzen vorbereiten).
+//! Alle Befehle geben maschinenlesbares JSON auf stdout aus.
+//!
+//! ```text
+//! lion-launcher update-all-mods <profil-id>
+//! lion-launcher verify-profile <profil-id>
+//! lion-launcher export-profile <profil-id> <ziel.zip> [--include-saves]
+//! ```
+
+use serde_json::json;
+use crate::core::profiles::ProfileManager;
+use crate::types::profile::Profile;
+
+const COMMANDS: [&str; 4] = ["update-all-mods", "verify-profile", "export-profile", "help"];
+
+/// Führt einen CLI-Befehl aus, falls das erste Argument einer ist.
+/// Gibt den Exit-Code zurück, oder `None` wenn die GUI gestartet werden soll.
+pub fn run_from_args() -> Option<i32> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let command = args.first()?.trim_start_matches("--").to_string();
+    if !COMMANDS.contains(&command.as_str()) {
+        return None;
+    }
+    attach_parent_console();
+
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(rt) => rt,
+        Err(e) => {
+            print_result(&command, Err(e.to_string()));
+            return Some(1);
+        }
+    };
+
+    let result = runtime.block_on(run(&command, &args[1..]));
+    let code = if result.is_ok() { 0 } else { 1 };
+    print_result(&command, result);
+    Some(code)
+}
+
+/// Release-Builds unter Windows laufen im GUI-Subsystem ohne Konsole; damit die JSON-Ausgabe
+/// im aufrufenden Terminal landet, wird dessen Konsole übernommen
+#[cfg(windows)]
+fn attach_parent_console() {
+    const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn AttachConsole(process_id: u32) -> i32;
+    }
+    // Schlägt fehl, wenn bereits eine Konsole vorhanden ist (Debug-Build), dann ist nichts zu tun
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+#[cfg(not(windows))]
+fn attach_parent_console() {}
+
+fn print_result(command: &str, result: Result<serde_json::Value, String>) {
+    let output = match result {
+        Ok(value) => json!({ "ok": true, "command": command, "result": value }),
+        Err(error) => json!({ "ok": false, "command": command, "error": error }),
+    };
+    println!("{}", serde_json::to_string_pretty(&output).unwrap_or_default());
+}
+
+async fn run(command: &str, args: &[String]) -> Result<serde_json::Value, String> {
+    match command {
+        "update-all-mods" => update_all_mods(load_profile(args.first()).await?).await,
+        "verify-profile" => verify_profile(load_profile(args.first()).await?).await,
+        "export-profile" => {
+            let profile = load_profile(args.first()).await?;
+            let output = args.get(1).ok_or_else(|| "Missing output path".to_string())?;
+            let include_saves = args.iter().any(|a| a == "--include-saves");
+            export_profile(profile, output.into(), include_saves).await
+        }
+        _ => Ok(json!({ "commands": COMMANDS })),
+    }
+}
+
+async fn load_profile(profile_id: Option<&String>) -> Result<Profile, String> {
+    let profile_id = profile_id.ok_or_else(|| "Missing profile id".to_string())?;
+    let profiles = ProfileManager::new()
+        .map_err(|e| e.to_string())?
+        .load_profiles()
+        .await
+        .map_err(|e| e.to_string())?;
+    profiles.get_profile(profile_id)
+        .cloned()
+        .ok_or_else(|| crate::utils::error::profile_not_found(profile_id))
+}
+
+/// Aktualisiert alle Modrinth-Mods (laut Inhalts-Datenbank) auf die neueste passende Version
+async fn update_all_mods(profile: Profile) -> Result<serde_json::Value, String> {
+    use crate::core::mods::ModManager;
+    use crate::types::mod_info::ModSource;
+
+    if profile.archived {
+        return Err(format!("Profile {} is archived", profile.name));
+    }
+    let manager = ModManager::new(None).map_err(|e| e.to_string())?;
+    let loader = profile.loader.loader.to_string().to_lowercase();
+    let mut updated = Vec::new();
+    let mut failed = Vec::new();
+    let mut up_to_date = 0;
+
+    for record in crate::core::mods::content_db::installed(&profile.game_dir) {
+        if record.source.as_deref() != Some("modrinth") {
+            continue;
+        }
+        let Some(mod_id) = record.mod_id else { continue };
+        let current = record.version.unwrap_or_default();
+
+        let versions = match manager.get_mod_versions_raw(&mod_id, ModSource::Modrinth).await {
+            Ok(v) => v,
+            Err(e) => {
+                failed.push(json!({ "mod_id": mod_id, "error": e.to_string() }));
+                continue;
+            }
+        };
+
+        // Modrinth liefert die neueste Version zuerst
+        let latest = versions.iter().find(|v| {
+            v.game_versions.contains(&profile.minecraft_version)
+                && v.loaders.iter().any(|l| l.to_lowercase() == loader)
+        });
+
+        match latest {
+            Some(latest) if latest.version_number != current => {
+                let result = crate::gui::install_mod(
+                    profile.id.clone(),
+                    mod_id.clone(),
+                    Some(latest.id.clone()),
+                    "modrinth".to_string(),
+                ).await;
+                match result {
+                    Ok(()) => updated.push(json!({ "mod_id": mod_id, "from": current, "to": latest.version_number })),
+                    Err(e) => failed.push(json!({ "mod_id": mod_id, "error": e })),
+                }
+            }
+            _ => up_to_date += 1,
+        }
+    }
+
+    Ok(json!({
+        "profile_id": profile.id,
+        "updated": updated,
+        "failed": failed,
+        "up_to_date": up_to_date,
+    }))
+}
+
+/// Prüft ein Profil: Spielordner, Client-JAR, lesbare Mod-JARs und fehlende Abhängigkeiten
+async fn verify_profile(profile: Profile) -> Result<serde_json::Value, String> {
+    let mut issues: Vec<serde_json::Value> = Vec::new();
+
+    if !profile.game_dir.exists() {
+        issues.push(json!({ "kind": "missing_game_dir", "path": profile.game_dir }));
+    }
+
+    let client_jar = crate::config::defaults::versions_dir()
+        .join(&profile.minecraft_version)
+        .join(format!("{}.jar", profile.minecraft_version));
+    if !client_jar.exists() {
+        issues.push(json!({ "kind": "client_not_installed", "version": profile.minecraft_version }));
+    }
+
+    let mods_dir = profile.game_dir.join("mods");
+    let scan = tokio::task::spawn_blocking(move || {
+        let mut corrupt = Vec::new();
+        if let Ok(entries) = std::fs::read_dir(&mods_dir) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.extension().is_some_and(|e| e == "jar") {
+                    let readable = std::fs::File::open(&path)
+                        .ok()
+                        .and_then(|f| zip::ZipArchive::new(f).ok())
+                        .is_some();
+                    if !readable {
+                        corrupt.push(entry.file_name().to_string_lossy().to_string());
+                    }
+                }
+            }
+        }
+        let missing = crate::core::mods::dependencies::find_missing_dependencies(&mods_dir);
+        (corrupt, missing)
+    }).await.map_err(|e| e.to_string())?;
+
+    let (corrupt, missing) = scan;
+    issues.extend(corrupt.into_iter().map(|f| json!({ "kind": "corrupt_mod", "file": f })));
+    issues.extend(missing.into_iter().map(|d| json!({
+        "kind": "missing_dependency",
+        "mod_id": d.mod_id,
+        "required_by": d.required_by,
+    })));
+
+    Ok(json!({
+        "profile_id": profile.id,
+        "valid": issues.is_empty(),
+        "issues": issues,
+    }))
+}
+
+async fn export_profile(profile: Profile, output: std::path::PathBuf, include_saves: bool) -> Result<serde_json::Value, String> {
+    let profile_id = profile.id.clone();
+    let summary = tokio::task::spawn_blocking(move || {
+        crate::core::profiles::export_profile_archive(&profile, &output, include_saves)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())?;
+
+    Ok(json!({
+        "profile_id": profile_id,
+        "path": summary.path,
+        "files": summary.files,
+        "bytes": summary.bytes,
+    }))
+}
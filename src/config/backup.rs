@@ -0,0 +1,179 @@
+//! Rotierende Sicherungen von profiles.json, config.json und auth.json (`backups/config/`).
+//! Vor jedem Schreiben wird der bisherige Stand gesichert, geschrieben wird über eine
+//! temporäre Datei – ein Absturz beim Speichern hinterlässt so nie eine halbe Datei.
+
+use anyhow::{Result, bail};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// Sicherungen pro Datei
+const MAX_BACKUPS: usize = 10;
+
+/// Höchstens eine Sicherung pro Datei in diesem Zeitraum – häufiges Speichern
+/// (z.B. Spielzeit-Statistiken) würde sonst die Rotation leeren
+const MIN_BACKUP_INTERVAL_SECS: i64 = 60;
+
+/// Nur diese Dateien im Datenordner werden gesichert bzw. wiederhergestellt
+const BACKED_UP_FILES: [&str; 3] = ["profiles.json", "config.json", "auth.json"];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigBackup {
+    /// Dateiname der Sicherung, für `restore`
+    pub name: String,
+    /// Gesicherte Datei (profiles.json / config.json / auth.json)
+    pub file: String,
+    pub created: chrono::DateTime<chrono::Utc>,
+    pub size: u64,
+}
+
+pub fn backups_dir() -> PathBuf {
+    crate::config::defaults::launcher_dir().join("backups").join("config")
+}
+
+/// `profiles.json.20260101-120000123.bak` → ("profiles.json", Zeitpunkt)
+fn parse_name(name: &str) -> Option<(&str, chrono::DateTime<chrono::Utc>)> {
+    let (file, stamp) = name.strip_suffix(".bak")?.rsplit_once('.')?;
+    let time = chrono::NaiveDateTime::parse_from_str(stamp, "%Y%m%d-%H%M%S%3f").ok()?;
+    BACKED_UP_FILES.contains(&file).then(|| (file, time.and_utc()))
+}
+
+fn backups_of(file: &str) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(backups_dir()) else { return Vec::new() };
+    let mut backups: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .and_then(parse_name)
+                .is_some_and(|(f, _)| f == file)
+        })
+        .collect();
+    // Zeitstempel im Namen sortieren lexikografisch richtig
+    backups.sort();
+    backups
+}
+
+/// Sichert `path` (falls vorhanden, seit der letzten Sicherung geändert und diese älter als
+/// `MIN_BACKUP_INTERVAL_SECS`) und entfernt die ältesten Sicherungen über `MAX_BACKUPS`
+pub fn backup(path: &Path) -> Result<()> {
+    write_backup(path, true)
+}
+
+/// `throttle = false` sichert auch kurz nach der letzten Sicherung (vor einer Wiederherstellung)
+fn write_backup(path: &Path, throttle: bool) -> Result<()> {
+    let Some(file) = path.file_name().and_then(|n| n.to_str()) else { return Ok(()) };
+
+    let mut backups = backups_of(file);
+    let last_created = backups.last()
+        .and_then(|last| last.file_name()?.to_str().and_then(parse_name).map(|(_, created)| created));
+    if throttle && last_created.is_some_and(|created| (chrono::Utc::now() - created).num_seconds() < MIN_BACKUP_INTERVAL_SECS) {
+        return Ok(());
+    }
+
+    let Ok(current) = std::fs::read(path) else { return Ok(()) };
+    if backups.last().and_then(|last| std::fs::read(last).ok()).as_ref() == Some(&current) {
+        return Ok(());
+    }
+
+    std::fs::create_dir_all(backups_dir())?;
+    let stamp = chrono::Utc::now().format("%Y%m%d-%H%M%S%3f");
+    let target = backups_dir().join(format!("{}.{}.bak", file, stamp));
+    std::fs::write(&target, &current)?;
+    backups.push(target);
+
+    let excess = backups.len().saturating_sub(MAX_BACKUPS);
+    for old in &backups[..excess] {
+        std::fs::remove_file(old).ok();
+    }
+    Ok(())
+}
+
+/// `backup` auf einem Blocking-Thread; Fehler werden nur protokolliert.
+/// Aufrufer mit eigener Sperre sichern vor dem Sperren und schreiben danach mit `write_atomic`.
+pub async fn backup_async(path: &Path) {
+    let owned = path.to_path_buf();
+    if let Err(e) = crate::utils::compression::run_blocking(move || backup(&owned)).await {
+        tracing::warn!("Could not back up {:?}: {}", path, e);
+    }
+}
+
+/// Sichert die bisherige Datei und schreibt den neuen Inhalt atomar
+pub async fn write_with_backup(path: &Path, content: &str) -> Result<()> {
+    backup_async(path).await;
+    write_atomic(path, content).await
+}
+
+/// Schreibt über eine temporäre Datei, ohne zu sichern
+pub async fn write_atomic(path: &Path, content: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    let temp = path.with_extension("json.tmp");
+    tokio::fs::write(&temp, content).await?;
+    tokio::fs::rename(&temp, path).await?;
+    Ok(())
+}
+
+/// Wie `write_with_backup`, für synchrone Aufrufer
+pub fn write_with_backup_blocking(path: &Path, content: &str) -> Result<()> {
+    if let Err(e) = backup(path) {
+        tracing::warn!("Could not back up {:?}: {}", path, e);
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let temp = path.with_extension("json.tmp");
+    std::fs::write(&temp, content)?;
+    std::fs::rename(&temp, path)?;
+    Ok(())
+}
+
+/// Alle Sicherungen, neueste zuerst
+pub fn list() -> Vec<ConfigBackup> {
+    let mut backups: Vec<ConfigBackup> = BACKED_UP_FILES
+        .iter()
+        .flat_map(|file| backups_of(file))
+        .filter_map(|path| {
+            let name = path.file_name()?.to_str()?.to_string();
+            let (file, created) = parse_name(&name)?;
+            Some(ConfigBackup {
+                file: file.to_string(),
+                created,
+                size: std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0),
+                name,
+            })
+        })
+        .collect();
+    backups.sort_by_key(|b| std::cmp::Reverse(b.created));
+    backups
+}
+
+/// Stellt eine Sicherung wieder her; der aktuelle Stand wird vorher selbst gesichert.
+/// Liefert die wiederhergestellte Datei (profiles.json / config.json / auth.json).
+pub fn restore(name: &str) -> Result<String> {
+    let Some((file, _)) = parse_name(name) else {
+        bail!("Unknown backup: {}", name);
+    };
+    let source = backups_dir().join(name);
+    let content = std::fs::read_to_string(&source)?;
+    // Kaputte Sicherungen nicht über eine funktionierende Datei legen
+    serde_json::from_str::<serde_json::Value>(&content)?;
+
+    let target = crate::config::defaults::launcher_dir().join(file);
+    write_backup(&target, false)?;
+    let temp = target.with_extension("json.tmp");
+    std::fs::write(&temp, content)?;
+    std::fs::rename(&temp, &target)?;
+    tracing::info!("Restored {} from backup {}", file, name);
+    Ok(file.to_string())
+}
+
+/// Neueste Sicherung von `file`, deren Inhalt `is_valid` besteht
+pub fn latest_valid(file: &str, is_valid: impl Fn(&str) -> bool) -> Option<String> {
+    backups_of(file).into_iter().rev().find_map(|path| {
+        let name = path.file_name()?.to_str()?.to_string();
+        let content = std::fs::read_to_string(&path).ok()?;
+        is_valid(&content).then_some(name)
+    })
+}
@@ -1,11 +1,56 @@
 use std::path::PathBuf;
 
-pub fn launcher_dir() -> PathBuf {
+/// Aktueller Datenordner; `None` = noch nicht ermittelt
+static LAUNCHER_DIR: std::sync::RwLock<Option<PathBuf>> = std::sync::RwLock::new(None);
+
+/// Standard-Datenordner des Systems. Enthält nach einer Migration nur noch `data_location.json`.
+pub fn default_launcher_dir() -> PathBuf {
     directories::ProjectDirs::from("com", "lionlauncher", "Lion-Launcher")
         .map(|dirs| dirs.data_dir().to_path_buf())
         .unwrap_or_else(|| PathBuf::from(".lion-launcher"))
 }
 
+fn location_file() -> PathBuf {
+    default_launcher_dir().join("data_location.json")
+}
+
+/// Verweis auf einen verschobenen Datenordner (`migrate_launcher_data`)
+fn relocated_dir() -> Option<PathBuf> {
+    let content = std::fs::read_to_string(location_file()).ok()?;
+    let value: serde_json::Value = serde_json::from_str(&content).ok()?;
+    let path = PathBuf::from(value.get("path")?.as_str()?);
+    path.is_dir().then_some(path)
+}
+
+pub fn launcher_dir() -> PathBuf {
+    if let Some(dir) = LAUNCHER_DIR.read().ok().and_then(|dir| dir.clone()) {
+        return dir;
+    }
+    let dir = relocated_dir().unwrap_or_else(default_launcher_dir);
+    if let Ok(mut cached) = LAUNCHER_DIR.write() {
+        *cached = Some(dir.clone());
+    }
+    dir
+}
+
+/// Setzt den Datenordner (schreibt bzw. entfernt den Verweis im Standardordner)
+pub fn set_launcher_dir(dir: &std::path::Path) -> std::io::Result<()> {
+    if dir == default_launcher_dir() {
+        match std::fs::remove_file(location_file()) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e),
+            _ => {}
+        }
+    } else {
+        std::fs::create_dir_all(default_launcher_dir())?;
+        let content = serde_json::json!({ "path": dir.to_string_lossy() }).to_string();
+        std::fs::write(location_file(), content)?;
+    }
+    if let Ok(mut cached) = LAUNCHER_DIR.write() {
+        *cached = Some(dir.to_path_buf());
+    }
+    Ok(())
+}
+
 pub fn data_dir() -> PathBuf {
     launcher_dir()
 }
@@ -30,10 +75,20 @@ pub fn mods_cache_dir() -> PathBuf {
     launcher_dir().join("cache").join("mods")
 }
 
+/// Content-addressierter Speicher für per Hardlink geteilte Dateien (SHA-256)
+pub fn content_store_dir() -> PathBuf {
+    launcher_dir().join("store")
+}
+
 pub fn skins_dir() -> PathBuf {
     launcher_dir().join("skins")
 }
 
+/// Gecachte Mojang-Metadaten (Versions-Manifest, Version-JSONs)
+pub fn meta_dir() -> PathBuf {
+    launcher_dir().join("meta")
+}
+
 pub fn java_dir() -> PathBuf {
     launcher_dir().join("java")
 }
@@ -0,0 +1,38 @@
+use std::sync::{OnceLock, RwLock};
+
+use super::schema::LauncherConfig;
+
+/// Schalter aus config.json, der synchron (ohne `get_config`) gelesen werden kann.
+/// Beim ersten Zugriff wird er aus der gespeicherten Konfiguration gelesen, danach
+/// setzen `save_config` bzw. das Wiederherstellen eines Backups ihn über `set`.
+pub struct ConfigFlag {
+    value: OnceLock<RwLock<bool>>,
+    read: fn(&LauncherConfig) -> bool,
+}
+
+impl ConfigFlag {
+    pub const fn new(read: fn(&LauncherConfig) -> bool) -> Self {
+        Self { value: OnceLock::new(), read }
+    }
+
+    fn lock(&self) -> &RwLock<bool> {
+        self.value.get_or_init(|| {
+            let config_path = super::defaults::launcher_dir().join("config.json");
+            let value = std::fs::read_to_string(config_path)
+                .ok()
+                .and_then(|c| serde_json::from_str::<LauncherConfig>(&c).ok())
+                .is_some_and(|c| (self.read)(&c));
+            RwLock::new(value)
+        })
+    }
+
+    pub fn get(&self) -> bool {
+        self.lock().read().map(|v| *v).unwrap_or(false)
+    }
+
+    pub fn set(&self, value: bool) {
+        if let Ok(mut current) = self.lock().write() {
+            *current = value;
+        }
+    }
+}
@@ -1,2 +1,5 @@
+pub mod backup;
 pub mod defaults;
+pub mod flag;
 pub mod schema;
+pub mod transfer;
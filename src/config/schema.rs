@@ -8,6 +8,37 @@ pub struct LauncherConfig {
     pub game_settings: GameSettings,
     pub mod_sources: ModSources,
     pub appearance: AppearanceSettings,
+    #[serde(default)]
+    pub sync: SyncSettings,
+    #[serde(default)]
+    pub cloud_sync: CloudSyncSettings,
+    /// Identische Mods/Resourcepacks per Hardlink aus dem Content-Store teilen
+    #[serde(default)]
+    pub hardlink_dedup: bool,
+    #[serde(default)]
+    pub login: LoginSettings,
+    /// Was mit dem Launcher-Fenster passiert, wenn ein Spiel startet
+    #[serde(default)]
+    pub launch_behavior: LaunchBehavior,
+    #[serde(default)]
+    pub telemetry: TelemetrySettings,
+    /// Vorhandene Libraries und Client-JARs beim Start per SHA-1 prüfen (langsamer)
+    #[serde(default)]
+    pub verify_existing: bool,
+}
+
+/// Verhalten des Launcher-Fensters beim Spielstart
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LaunchBehavior {
+    #[default]
+    KeepOpen,
+    /// Minimieren, nach dem Beenden des Spiels wieder anzeigen
+    Minimize,
+    /// In den Tray minimieren (Fenster ausblenden), nach dem Beenden des Spiels wieder anzeigen
+    Hide,
+    /// Launcher beenden, das Spiel läuft weiter
+    Close,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -38,6 +69,124 @@ pub struct AppearanceSettings {
     pub language: String,
 }
 
+/// Welche options.txt-Keys bei der Settings-Synchronisation berücksichtigt werden.
+/// Einträge mit `*` am Ende gelten als Präfix (z.B. `key_*`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncSettings {
+    /// Keys die nie synchronisiert werden (bleiben profil-spezifisch)
+    #[serde(default = "default_exclude_keys")]
+    pub exclude_keys: Vec<String>,
+    /// Wenn nicht leer: nur diese Keys werden synchronisiert
+    #[serde(default)]
+    pub include_keys: Vec<String>,
+    /// Mod-Konfigurationsdateien (relativ zum Spielordner, z.B. `config/sodium-options.json`)
+    /// die zwischen Profilen mit `sync_configs` geteilt werden
+    #[serde(default)]
+    pub config_files: Vec<ConfigFileSync>,
+    /// Wie widersprüchliche Werte zwischen Profilen aufgelöst werden
+    #[serde(default)]
+    pub strategy: SyncStrategy,
+    /// Profil-ID dessen Werte bei `SyncStrategy::MasterProfile` immer gewinnen
+    #[serde(default)]
+    pub master_profile: Option<String>,
+}
+
+/// Konfliktauflösung bei der options.txt-Synchronisation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncStrategy {
+    /// Die zuletzt geänderte options.txt gewinnt
+    #[default]
+    NewestWins,
+    /// Das festgelegte Master-Profil gewinnt immer
+    MasterProfile,
+    /// Bei Konflikten nicht mergen, sondern die Unterschiede der GUI melden
+    Prompt,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigFileSync {
+    pub path: String,
+    #[serde(default)]
+    pub strategy: FileConflictStrategy,
+}
+
+/// Verhalten beim Microsoft-Login (Device Code)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LoginSettings {
+    /// Code beim Start des Logins in die Zwischenablage kopieren
+    pub copy_code: bool,
+    /// Bestätigungsseite automatisch im Browser öffnen
+    pub auto_open_browser: bool,
+}
+
+impl Default for LoginSettings {
+    fn default() -> Self {
+        Self { copy_code: true, auto_open_browser: false }
+    }
+}
+
+/// Anonyme Nutzungsstatistik – nur mit ausdrücklicher Zustimmung
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TelemetrySettings {
+    /// Schalter: aus = nichts wird gezählt oder gesendet, gesammelte Daten werden gelöscht
+    pub enabled: bool,
+    /// Empfänger der Berichte; ohne Endpunkt wird nur lokal gezählt
+    pub endpoint: Option<String>,
+}
+
+/// Zugangsdaten für die optionale Cloud-Synchronisation (WebDAV)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CloudSyncSettings {
+    pub enabled: bool,
+    /// Basis-URL des Sync-Ordners, z.B. `https://cloud.example.com/remote.php/dav/files/user/lion`
+    pub webdav_url: String,
+    pub username: String,
+    /// Nur zur Übergabe beim Speichern – abgelegt wird es im Schlüsselbund des Systems
+    pub password: String,
+}
+
+/// Wie Konflikte beim Datei-Sync aufgelöst werden
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileConflictStrategy {
+    /// Die zuletzt geänderte Datei aller Profile gewinnt
+    #[default]
+    Newest,
+    /// Nur kopieren wenn das Profil die Datei noch nicht hat
+    KeepExisting,
+}
+
+impl SyncSettings {
+    /// Sortiert gesammelte options.txt-Einträge so, dass der Gewinner zuletzt kommt.
+    /// Erwartet bereits nach Änderungszeit (älteste zuerst) sortierte Einträge.
+    pub fn order_by_priority<T>(&self, entries: &mut [T], profile_id: impl Fn(&T) -> &str) {
+        if self.strategy == SyncStrategy::MasterProfile {
+            if let Some(master) = &self.master_profile {
+                // Stabile Sortierung: Master ans Ende, Rest bleibt nach Zeit sortiert
+                entries.sort_by_key(|e| profile_id(e) == master.as_str());
+            }
+        }
+    }
+
+    /// Prüft ob ein Key profil-spezifisch bleibt (nicht synchronisiert wird)
+    pub fn is_excluded(&self, key: &str) -> bool {
+        if self.exclude_keys.iter().any(|p| key_matches(p, key)) {
+            return true;
+        }
+        !self.include_keys.is_empty() && !self.include_keys.iter().any(|p| key_matches(p, key))
+    }
+}
+
+fn key_matches(pattern: &str, key: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => key.starts_with(prefix),
+        None => pattern == key,
+    }
+}
+
 impl Default for LauncherConfig {
     fn default() -> Self {
         Self {
@@ -46,6 +195,13 @@ impl Default for LauncherConfig {
             game_settings: GameSettings::default(),
             mod_sources: ModSources::default(),
             appearance: AppearanceSettings::default(),
+            sync: SyncSettings::default(),
+            cloud_sync: CloudSyncSettings::default(),
+            hardlink_dedup: false,
+            login: LoginSettings::default(),
+            launch_behavior: LaunchBehavior::default(),
+            telemetry: TelemetrySettings::default(),
+            verify_existing: false,
         }
     }
 }
@@ -83,3 +239,19 @@ impl Default for AppearanceSettings {
         }
     }
 }
+
+fn default_exclude_keys() -> Vec<String> {
+    vec!["version".to_string()]
+}
+
+impl Default for SyncSettings {
+    fn default() -> Self {
+        Self {
+            exclude_keys: default_exclude_keys(),
+            include_keys: Vec::new(),
+            config_files: Vec::new(),
+            strategy: SyncStrategy::NewestWins,
+            master_profile: None,
+        }
+    }
+}
@@ -0,0 +1,78 @@
+//! Export/Import der Launcher-Einstellungen für mehrere Rechner. Accounts werden nur
+//! ohne Tokens exportiert: Offline-Accounts sind danach sofort nutzbar, Microsoft-Accounts
+//! müssen sich auf dem neuen Rechner neu anmelden. Zugangsdaten aus der Konfiguration
+//! (Cloud-Sync-Passwort, CurseForge-API-Key) werden ebenfalls nicht exportiert.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::schema::LauncherConfig;
+use crate::core::auth::MinecraftAccount;
+
+pub const FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SettingsExport {
+    pub format_version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub launcher_version: String,
+    pub config: LauncherConfig,
+    #[serde(default)]
+    pub accounts: Vec<ExportedAccount>,
+}
+
+/// Account ohne Zugangsdaten
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedAccount {
+    pub uuid: String,
+    pub username: String,
+    pub is_microsoft: bool,
+    #[serde(default)]
+    pub active: bool,
+}
+
+impl ExportedAccount {
+    pub fn new(account: &MinecraftAccount, active: bool) -> Self {
+        Self {
+            uuid: account.uuid.clone(),
+            username: account.username.clone(),
+            is_microsoft: account.is_microsoft,
+            active,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SettingsImportResult {
+    pub accounts_added: usize,
+    /// Microsoft-Accounts, die sich auf diesem Rechner neu anmelden müssen
+    pub needs_login: Vec<String>,
+}
+
+impl SettingsExport {
+    pub fn new(mut config: LauncherConfig, accounts: Vec<ExportedAccount>) -> Self {
+        config.cloud_sync.password.clear();
+        config.mod_sources.curseforge_api_key = None;
+        Self {
+            format_version: FORMAT_VERSION,
+            exported_at: Utc::now(),
+            launcher_version: env!("CARGO_PKG_VERSION").to_string(),
+            config,
+            accounts,
+        }
+    }
+
+    /// Übernimmt die importierte Konfiguration, behält aber rechnerspezifische Werte
+    /// (Datenordner, nicht vorhandener Java-Pfad) und die nicht exportierten Zugangsdaten
+    /// der lokalen Konfiguration
+    pub fn merged_config(&self, local: &LauncherConfig) -> LauncherConfig {
+        let mut config = self.config.clone();
+        config.launcher_dir = local.launcher_dir.clone();
+        config.cloud_sync.password = local.cloud_sync.password.clone();
+        config.mod_sources.curseforge_api_key = local.mod_sources.curseforge_api_key.clone();
+        if config.game_settings.java_path.as_ref().is_some_and(|p| !p.exists()) {
+            config.game_settings.java_path = local.game_settings.java_path.clone();
+        }
+        config
+    }
+}
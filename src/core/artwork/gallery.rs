@@ -0,0 +1,170 @@
+//! Lokaler Cache für Galerie-Bilder der Projektseiten (`cache/gallery/`). Die CDN-URLs
+//! ändern sich nicht, einmal geladene Bilder bleiben daher gültig. Pro Projekt wird
+//! zusätzlich die Galerie-Liste gespeichert, damit die Seite auch offline Bilder zeigt.
+//! An die Oberfläche gehen die Bilder als data:-URLs, lokale Pfade kann der Webview nicht laden.
+
+use anyhow::{Result, bail};
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Größere Dateien sind keine Screenshots
+const MAX_IMAGE_BYTES: usize = 15 * 1024 * 1024;
+
+/// Ab dieser Größe werden die am längsten ungenutzten Bilder entfernt
+const MAX_CACHE_BYTES: u64 = 500 * 1024 * 1024;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedGalleryImage {
+    pub url: String,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    /// Bild als data:-URL, falls es geladen werden konnte (wird nicht im Index gespeichert)
+    #[serde(default, skip_deserializing)]
+    pub data_url: Option<String>,
+}
+
+pub fn gallery_dir() -> PathBuf {
+    crate::config::defaults::launcher_dir().join("cache").join("gallery")
+}
+
+fn url_key(url: &str) -> String {
+    hex::encode(Sha256::digest(url.as_bytes()))
+}
+
+fn safe_project_id(project_id: &str) -> String {
+    project_id.chars().filter(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_').collect()
+}
+
+fn index_path(project_id: &str) -> PathBuf {
+    gallery_dir().join("projects").join(format!("{}.json", safe_project_id(project_id)))
+}
+
+/// Bereits gecachte Datei zu einer URL (Endung je nach Bildformat)
+fn find_cached(url: &str) -> Option<PathBuf> {
+    let key = url_key(url);
+    ["png", "jpg", "gif", "webp"]
+        .iter()
+        .map(|ext| gallery_dir().join(format!("{}.{}", key, ext)))
+        .find(|path| path.is_file())
+}
+
+/// Lokaler Pfad des Bildes; lädt es beim ersten Aufruf herunter
+pub async fn cached_image(client: &reqwest::Client, url: &str) -> Result<PathBuf> {
+    if let Some(path) = find_cached(url) {
+        // Zugriffszeit für die Bereinigung auffrischen
+        if let Ok(file) = std::fs::File::options().append(true).open(&path) {
+            file.set_modified(std::time::SystemTime::now()).ok();
+        }
+        return Ok(path);
+    }
+
+    let response = client.get(url).send().await?.error_for_status()?;
+    if response.content_length().is_some_and(|len| len > MAX_IMAGE_BYTES as u64) {
+        bail!("Image {} is too large ({} MB)", url, response.content_length().unwrap_or(0) / 1024 / 1024);
+    }
+    // Content-Length kann fehlen oder falsch sein – auch beim Lesen begrenzen
+    let mut bytes = Vec::new();
+    let mut stream = response.bytes_stream();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk?;
+        if bytes.len() + chunk.len() > MAX_IMAGE_BYTES {
+            bail!("Image {} is larger than {} MB", url, MAX_IMAGE_BYTES / 1024 / 1024);
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+    let Some(extension) = super::image_extension(&bytes) else {
+        bail!("{} is not a supported image", url);
+    };
+
+    tokio::fs::create_dir_all(gallery_dir()).await?;
+    let path = gallery_dir().join(format!("{}.{}", url_key(url), extension));
+    let part = path.with_extension("part");
+    tokio::fs::write(&part, &bytes).await?;
+    tokio::fs::rename(&part, &path).await?;
+    Ok(path)
+}
+
+/// Lädt die Bilder einer Galerie (4 gleichzeitig, Reihenfolge bleibt erhalten) und merkt
+/// sich die Liste für den Offline-Fall
+pub async fn cache_gallery(project_id: &str, images: Vec<CachedGalleryImage>) -> Vec<CachedGalleryImage> {
+    let client = reqwest::Client::builder()
+        .user_agent("Lion-Launcher/1.0")
+        .timeout(Duration::from_secs(30))
+        .build()
+        .unwrap_or_default();
+
+    let images: Vec<CachedGalleryImage> = futures_util::stream::iter(images)
+        .map(|mut image| {
+            let client = &client;
+            async move {
+                match cached_image(client, &image.url).await {
+                    Ok(path) => image.data_url = super::file_data_url(&path),
+                    Err(e) => tracing::debug!("Could not cache gallery image {}: {}", image.url, e),
+                }
+                image
+            }
+        })
+        .buffered(4)
+        .collect()
+        .await;
+
+    let index = index_path(project_id);
+    let write = async {
+        tokio::fs::create_dir_all(index.parent().unwrap_or(&gallery_dir())).await?;
+        let entries: Vec<CachedGalleryImage> = images.iter()
+            .map(|image| CachedGalleryImage { data_url: None, ..image.clone() })
+            .collect();
+        tokio::fs::write(&index, serde_json::to_vec(&entries)?).await?;
+        anyhow::Ok(())
+    };
+    if let Err(e) = write.await {
+        tracing::debug!("Could not store gallery index for {}: {}", project_id, e);
+    }
+
+    crate::utils::compression::run_blocking(|| {
+        prune(MAX_CACHE_BYTES);
+        Ok(())
+    })
+    .await
+    .ok();
+    images
+}
+
+/// Zuletzt gespeicherte Galerie eines Projekts, nur mit vorhandenen Dateien
+pub fn offline_gallery(project_id: &str) -> Option<Vec<CachedGalleryImage>> {
+    let content = std::fs::read_to_string(index_path(project_id)).ok()?;
+    let mut images: Vec<CachedGalleryImage> = serde_json::from_str(&content).ok()?;
+    for image in &mut images {
+        image.data_url = find_cached(&image.url).and_then(|path| super::file_data_url(&path));
+    }
+    Some(images)
+}
+
+/// Entfernt die am längsten nicht genutzten Bilder, bis der Cache unter `max_bytes` liegt
+fn prune(max_bytes: u64) {
+    let Ok(entries) = std::fs::read_dir(gallery_dir()) else { return };
+    let mut files: Vec<(PathBuf, u64, std::time::SystemTime)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let meta = entry.metadata().ok().filter(|m| m.is_file())?;
+            Some((entry.path(), meta.len(), meta.modified().ok()?))
+        })
+        .collect();
+
+    let mut total: u64 = files.iter().map(|(_, size, _)| size).sum();
+    if total <= max_bytes {
+        return;
+    }
+    files.sort_by_key(|(_, _, modified)| *modified);
+    for (path, size, _) in files {
+        if total <= max_bytes {
+            break;
+        }
+        if std::fs::remove_file(&path).is_ok() {
+            total -= size;
+        }
+    }
+}
@@ -0,0 +1,141 @@
+#![allow(dead_code)]
+
+//! Banner- und Hintergrundbilder für Profile. Die Bilder werden in den Launcher-Daten
+//! abgelegt, auf eine sinnvolle Maximalgröße verkleinert und einheitlich als PNG gespeichert.
+//! Angenommen werden PNG, JPEG, WebP und GIF.
+
+pub mod gallery;
+
+use anyhow::{bail, Context, Result};
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+/// Maximale Größe hochgeladener Bilder (vor dem Verkleinern)
+const MAX_IMAGE_BYTES: usize = 20 * 1024 * 1024;
+
+/// Maximale Kantenlänge laut Bild-Header – größere Bilder werden gar nicht erst dekodiert
+const MAX_IMAGE_DIMENSION: u32 = 16384;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArtworkKind {
+    Banner,
+    Background,
+}
+
+impl ArtworkKind {
+    pub fn parse(kind: &str) -> Option<Self> {
+        match kind {
+            "banner" => Some(ArtworkKind::Banner),
+            "background" => Some(ArtworkKind::Background),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            ArtworkKind::Banner => "banner",
+            ArtworkKind::Background => "background",
+        }
+    }
+
+    fn max_size(&self) -> (u32, u32) {
+        match self {
+            ArtworkKind::Banner => (1280, 480),
+            ArtworkKind::Background => (1920, 1080),
+        }
+    }
+}
+
+pub fn artwork_dir(profile_id: &str) -> PathBuf {
+    crate::config::defaults::launcher_dir().join("artwork").join(profile_id)
+}
+
+/// Erkennt das Bildformat anhand der Magic Bytes
+pub(crate) fn image_extension(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.starts_with(&[0x89, b'P', b'N', b'G']) {
+        Some("png")
+    } else if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some("jpg")
+    } else if bytes.starts_with(b"GIF8") {
+        Some("gif")
+    } else if bytes.len() > 12 && &bytes[..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+        Some("webp")
+    } else {
+        None
+    }
+}
+
+/// Bild als data:-URL – der Webview darf lokale Dateien nicht über ihren Pfad laden
+pub fn data_url(bytes: &[u8]) -> Option<String> {
+    use base64::Engine as _;
+
+    let mime = match image_extension(bytes)? {
+        "jpg" => "image/jpeg",
+        "png" => "image/png",
+        "gif" => "image/gif",
+        _ => "image/webp",
+    };
+    Some(format!("data:{};base64,{}", mime, base64::engine::general_purpose::STANDARD.encode(bytes)))
+}
+
+/// Wie `data_url`, liest die Datei vorher ein
+pub fn file_data_url(path: &Path) -> Option<String> {
+    data_url(&std::fs::read(path).ok()?)
+}
+
+/// Dekodiert ein Bild, verkleinert es bei Bedarf auf `max_w`×`max_h` und kodiert es als PNG.
+/// Die Abmessungen werden vor dem Dekodieren aus dem Header geprüft.
+fn normalize_image(bytes: &[u8], max_w: u32, max_h: u32) -> Result<Vec<u8>> {
+    let reader = image::ImageReader::new(Cursor::new(bytes)).with_guessed_format()?;
+    let format = reader.format().context("Unsupported image format")?;
+    let (w, h) = reader.into_dimensions().context("Could not read image header")?;
+    if w > MAX_IMAGE_DIMENSION || h > MAX_IMAGE_DIMENSION {
+        bail!("Image is too large ({}×{} pixels)", w, h);
+    }
+    if format == image::ImageFormat::Png && w <= max_w && h <= max_h {
+        return Ok(bytes.to_vec());
+    }
+
+    let mut reader = image::ImageReader::with_format(Cursor::new(bytes), format);
+    let mut limits = image::Limits::default();
+    limits.max_image_width = Some(MAX_IMAGE_DIMENSION);
+    limits.max_image_height = Some(MAX_IMAGE_DIMENSION);
+    reader.limits(limits);
+    let mut image = reader.decode().context("Could not decode image")?;
+    if w > max_w || h > max_h {
+        image = image.resize(max_w, max_h, image::imageops::FilterType::Triangle);
+    }
+
+    let mut out = Vec::new();
+    image.write_to(&mut Cursor::new(&mut out), image::ImageFormat::Png)?;
+    Ok(out)
+}
+
+/// Speichert ein Profilbild und gibt den lokalen Pfad zurück.
+/// Vorherige Bilder derselben Art werden ersetzt.
+pub fn store_artwork(profile_id: &str, kind: ArtworkKind, bytes: &[u8]) -> Result<PathBuf> {
+    if bytes.len() > MAX_IMAGE_BYTES {
+        bail!("Image is too large ({} MB)", bytes.len() / 1024 / 1024);
+    }
+    if image_extension(bytes).is_none() {
+        bail!("Unsupported image format");
+    }
+
+    let (max_w, max_h) = kind.max_size();
+    let png = normalize_image(bytes, max_w, max_h)?;
+
+    let dir = artwork_dir(profile_id);
+    std::fs::create_dir_all(&dir)?;
+    remove_artwork(profile_id, kind);
+
+    let path = dir.join(format!("{}.png", kind.name()));
+    std::fs::write(&path, png)?;
+    Ok(path)
+}
+
+/// Entfernt alle gespeicherten Bilder einer Art (auch ältere in anderen Formaten)
+pub fn remove_artwork(profile_id: &str, kind: ArtworkKind) {
+    for extension in ["png", "jpg", "gif", "webp"] {
+        let _ = std::fs::remove_file(artwork_dir(profile_id).join(format!("{}.{}", kind.name(), extension)));
+    }
+}
@@ -0,0 +1,99 @@
+//! Lokaler Cache für Account-Köpfe und Skins (`cache/avatars/`), damit die
+//! Account-Liste auch offline Bilder zeigt. Einträge werden nach `REFRESH_AFTER`
+//! im Hintergrund neu geladen; bis dahin bleibt die alte Datei gültig.
+
+use anyhow::{Result, bail};
+use std::path::PathBuf;
+use std::time::Duration;
+
+const REFRESH_AFTER: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Größe der gecachten Kopf-Bilder (wie `get_head_url(uuid, 64)`)
+const HEAD_SIZE: u32 = 64;
+
+pub fn avatars_dir() -> PathBuf {
+    crate::config::defaults::launcher_dir().join("cache").join("avatars")
+}
+
+fn safe_uuid(uuid: &str) -> String {
+    uuid.chars().filter(|c| c.is_ascii_hexdigit() || *c == '-').collect()
+}
+
+pub fn head_path(uuid: &str) -> PathBuf {
+    avatars_dir().join(format!("{}_head.png", safe_uuid(uuid)))
+}
+
+pub fn skin_path(uuid: &str) -> PathBuf {
+    avatars_dir().join(format!("{}_skin.png", safe_uuid(uuid)))
+}
+
+/// Pfad der Datei, falls bereits gecacht
+pub fn cached(path: PathBuf) -> Option<PathBuf> {
+    path.is_file().then_some(path)
+}
+
+fn is_stale(path: &std::path::Path) -> bool {
+    std::fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.elapsed().ok())
+        .is_none_or(|age| age > REFRESH_AFTER)
+}
+
+async fn download(client: &reqwest::Client, url: &str, target: &std::path::Path) -> Result<()> {
+    let response = client.get(url).send().await?;
+    if !response.status().is_success() {
+        bail!("{} returned {}", url, response.status());
+    }
+    let bytes = response.bytes().await?;
+    if !bytes.starts_with(b"\x89PNG") {
+        bail!("{} did not return a PNG image", url);
+    }
+    // Erst vollständig schreiben, dann umbenennen – sonst sieht die GUI halbe Bilder
+    let part = target.with_extension("png.part");
+    tokio::fs::write(&part, &bytes).await?;
+    tokio::fs::rename(&part, target).await?;
+    Ok(())
+}
+
+/// Lädt Kopf und Skin eines Accounts neu, wenn sie fehlen oder veraltet sind (`force`: immer)
+pub async fn refresh(client: &reqwest::Client, uuid: &str, force: bool) -> Result<()> {
+    tokio::fs::create_dir_all(avatars_dir()).await?;
+
+    let head = head_path(uuid);
+    if force || is_stale(&head) {
+        download(client, &super::get_head_url(uuid, HEAD_SIZE), &head).await?;
+    }
+    let skin = skin_path(uuid);
+    if force || is_stale(&skin) {
+        download(client, &super::get_full_skin_url(uuid), &skin).await?;
+    }
+    Ok(())
+}
+
+/// Aktualisiert veraltete Einträge mehrerer Accounts; Fehler (z.B. offline) werden nur geloggt
+pub async fn refresh_stale(uuids: Vec<String>) {
+    let stale: Vec<String> = uuids.into_iter()
+        .filter(|uuid| is_stale(&head_path(uuid)) || is_stale(&skin_path(uuid)))
+        .collect();
+    if stale.is_empty() {
+        return;
+    }
+
+    let client = reqwest::Client::builder()
+        .user_agent("Lion-Launcher/1.0")
+        .timeout(Duration::from_secs(15))
+        .build()
+        .unwrap_or_default();
+    for uuid in stale {
+        if let Err(e) = refresh(&client, &uuid, false).await {
+            tracing::debug!("Could not refresh avatar of {}: {}", uuid, e);
+        }
+    }
+}
+
+/// Entfernt die gecachten Bilder eines Accounts
+pub fn remove(uuid: &str) {
+    std::fs::remove_file(head_path(uuid)).ok();
+    std::fs::remove_file(skin_path(uuid)).ok();
+}
@@ -0,0 +1,110 @@
+//! Zustand des laufenden Device-Code-Logins. Es gibt immer höchstens einen Flow;
+//! Polls werden auf das von Microsoft vorgegebene Intervall begrenzt und
+//! gleichzeitige Polls desselben Flows verhindert.
+
+use std::time::{Duration, Instant};
+
+use super::DeviceCodeFlow;
+
+/// Erhöhung des Intervalls bei "slow_down" (RFC 8628, Abschnitt 3.5)
+const SLOW_DOWN_STEP: Duration = Duration::from_secs(5);
+
+/// Ein laufender Flow wird bei erneutem Start wiederverwendet, solange er noch so lange gilt
+const REUSE_MIN_REMAINING: Duration = Duration::from_secs(60);
+
+struct ActiveLogin {
+    flow: DeviceCodeFlow,
+    expires_at: Instant,
+    interval: Duration,
+    next_poll_at: Instant,
+    polling: bool,
+}
+
+/// Warum ein Poll nicht an Microsoft weitergegeben wird
+#[derive(Debug, PartialEq, Eq)]
+pub enum PollRejection {
+    /// Kein Flow mit diesem Device Code aktiv (abgebrochen oder ersetzt)
+    NotActive,
+    Expired,
+    /// Intervall noch nicht abgelaufen oder ein anderer Poll läuft gerade
+    TooEarly { retry_after_ms: u64 },
+}
+
+#[derive(Default)]
+pub struct DeviceLoginTracker {
+    active: Option<ActiveLogin>,
+}
+
+impl DeviceLoginTracker {
+    fn active_for(&mut self, device_code: &str) -> Option<&mut ActiveLogin> {
+        self.active.as_mut().filter(|a| a.flow.device_code == device_code)
+    }
+
+    /// Laufender Flow, falls er noch ausreichend lange gültig ist
+    pub fn reusable(&self) -> Option<DeviceCodeFlow> {
+        self.active.as_ref()
+            .filter(|a| a.expires_at.saturating_duration_since(Instant::now()) > REUSE_MIN_REMAINING)
+            .map(|a| a.flow.clone())
+    }
+
+    pub fn current(&self) -> Option<&DeviceCodeFlow> {
+        self.active.as_ref().map(|a| &a.flow)
+    }
+
+    /// Setzt einen neuen Flow und ersetzt einen eventuell laufenden
+    pub fn start(&mut self, flow: DeviceCodeFlow) {
+        let now = Instant::now();
+        // Microsoft liefert 5s; 0 oder fehlend wäre ein Poll-Sturm
+        let interval = Duration::from_secs(flow.interval.max(1));
+        if self.active.is_some() {
+            tracing::info!("Replacing pending Microsoft login");
+        }
+        self.active = Some(ActiveLogin {
+            expires_at: now + Duration::from_secs(flow.expires_in),
+            next_poll_at: now + interval,
+            interval,
+            polling: false,
+            flow,
+        });
+    }
+
+    /// Beendet den laufenden Flow (Abbruch, Erfolg oder endgültiger Fehler)
+    pub fn clear(&mut self) -> bool {
+        self.active.take().is_some()
+    }
+
+    /// Beendet den Flow nur, wenn er noch der mit diesem Device Code ist
+    pub fn finish(&mut self, device_code: &str) {
+        if self.active_for(device_code).is_some() {
+            self.active = None;
+        }
+    }
+
+    /// Prüft, ob jetzt gepollt werden darf, und markiert den Poll als laufend
+    pub fn begin_poll(&mut self, device_code: &str) -> Result<(), PollRejection> {
+        let now = Instant::now();
+        let active = self.active_for(device_code).ok_or(PollRejection::NotActive)?;
+        if now >= active.expires_at {
+            self.active = None;
+            return Err(PollRejection::Expired);
+        }
+        if active.polling || now < active.next_poll_at {
+            let wait = active.next_poll_at.saturating_duration_since(now).max(active.interval / 2);
+            return Err(PollRejection::TooEarly { retry_after_ms: wait.as_millis() as u64 });
+        }
+        active.polling = true;
+        Ok(())
+    }
+
+    /// Gibt den Poll frei und plant den nächsten; bei `slow_down` mit längerem Intervall
+    pub fn end_poll(&mut self, device_code: &str, slow_down: bool) {
+        if let Some(active) = self.active_for(device_code) {
+            if slow_down {
+                active.interval += SLOW_DOWN_STEP;
+                tracing::info!("Microsoft requested slow_down, polling every {:?}", active.interval);
+            }
+            active.polling = false;
+            active.next_poll_at = Instant::now() + active.interval;
+        }
+    }
+}
@@ -1,5 +1,8 @@
 #![allow(dead_code)]
 
+pub mod avatars;
+pub mod device_flow;
+
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -40,6 +43,18 @@ pub struct DeviceCodeFlow {
     pub message: String,
 }
 
+/// Ergebnis eines Token-Polls im Device Code Flow
+pub enum TokenPoll {
+    /// Nutzer hat den Code noch nicht bestätigt
+    Pending,
+    /// Zu häufig gepollt – Intervall muss um 5 Sekunden erhöht werden (RFC 8628)
+    SlowDown,
+    Complete(MinecraftAccount),
+    /// Endgültig gescheitert (Code abgelaufen, Zugriff verweigert oder Xbox/Minecraft-Anmeldung
+    /// fehlgeschlagen) – weiteres Pollen ist sinnlos
+    Failed(String),
+}
+
 // Device Code Response
 #[derive(Debug, Deserialize)]
 struct DeviceCodeResponse {
@@ -98,6 +113,23 @@ struct CapeInfo {
     state: String,
 }
 
+impl MinecraftProfileResponse {
+    /// URLs des aktiven Skins und Capes
+    fn active_textures(&self) -> (Option<String>, Option<String>) {
+        let skin_url = self.skins
+            .as_ref()
+            .and_then(|s| s.iter().find(|skin| skin.state == "ACTIVE"))
+            .map(|s| s.url.clone());
+
+        let cape_url = self.capes
+            .as_ref()
+            .and_then(|c| c.iter().find(|cape| cape.state == "ACTIVE"))
+            .map(|c| c.url.clone());
+
+        (skin_url, cape_url)
+    }
+}
+
 pub struct MinecraftAuth {
     client: reqwest::Client,
 }
@@ -159,8 +191,9 @@ impl MinecraftAuth {
         })
     }
 
-    /// Pollt für Token nachdem User den Code eingegeben hat
-    pub async fn poll_for_token(&self, device_code: &str) -> Result<Option<MinecraftAccount>> {
+    /// Pollt für Token nachdem User den Code eingegeben hat.
+    /// `Err` sind vorübergehende Fehler (Netz, unerwartete Antwort) – der Flow läuft weiter.
+    pub async fn poll_for_token(&self, device_code: &str) -> Result<TokenPoll> {
         let params = [
             ("client_id", AZURE_CLIENT_ID),
             ("device_code", device_code),
@@ -175,29 +208,29 @@ impl MinecraftAuth {
 
         let text = response.text().await?;
 
-        // Prüfe auf "authorization_pending" Fehler
-        if text.contains("authorization_pending") {
-            return Ok(None); // Noch nicht autorisiert, weiter pollen
-        }
-
-        if text.contains("expired_token") {
-            return Err(anyhow::anyhow!("Device code abgelaufen"));
+        // Fehlercode laut RFC 8628, Abschnitt 3.5; nur expired_token und access_denied beenden den Flow
+        #[derive(Deserialize)]
+        struct PollError {
+            error: String,
         }
-
-        if text.contains("access_denied") {
-            return Err(anyhow::anyhow!("Zugriff verweigert"));
+        if let Ok(PollError { error }) = serde_json::from_str::<PollError>(&text) {
+            return match error.as_str() {
+                "authorization_pending" => Ok(TokenPoll::Pending),
+                "slow_down" => Ok(TokenPoll::SlowDown),
+                "expired_token" => Ok(TokenPoll::Failed("Device code abgelaufen".to_string())),
+                "access_denied" => Ok(TokenPoll::Failed("Zugriff verweigert".to_string())),
+                other => Err(anyhow::anyhow!("Unerwartete Antwort beim Login: {}", other)),
+            };
         }
 
         let token: TokenResponse = serde_json::from_str(&text)?;
 
-        if token.error.is_some() {
-            return Ok(None); // Noch nicht fertig
+        // Token erfolgreich - jetzt Xbox Live Auth. Der Device Code ist damit verbraucht,
+        // ein Fehler hier lässt sich durch erneutes Pollen nicht beheben.
+        match self.complete_auth(&token.access_token, token.refresh_token).await {
+            Ok(account) => Ok(TokenPoll::Complete(account)),
+            Err(e) => Ok(TokenPoll::Failed(e.to_string())),
         }
-
-        // Token erfolgreich - jetzt Xbox Live Auth
-        let account = self.complete_auth(&token.access_token, token.refresh_token).await?;
-
-        Ok(Some(account))
     }
 
     /// Komplettiert die Auth nach Erhalt des Microsoft Tokens
@@ -220,15 +253,7 @@ impl MinecraftAuth {
         let profile = self.get_minecraft_profile(&mc_token.access_token).await?;
         tracing::info!("Got Minecraft profile: {}", profile.name);
 
-        let skin_url = profile.skins
-            .as_ref()
-            .and_then(|s| s.iter().find(|skin| skin.state == "ACTIVE"))
-            .map(|s| s.url.clone());
-
-        let cape_url = profile.capes
-            .as_ref()
-            .and_then(|c| c.iter().find(|cape| cape.state == "ACTIVE"))
-            .map(|c| c.url.clone());
+        let (skin_url, cape_url) = profile.active_textures();
 
         Ok(MinecraftAccount {
             uuid: profile.id,
@@ -321,12 +346,38 @@ impl MinecraftAuth {
             .header("Authorization", format!("Bearer {}", access_token))
             .send()
             .await?
+            .error_for_status()?
             .json()
             .await?;
 
         Ok(response)
     }
 
+    /// Prüft über die Entitlements ob der Account Minecraft: Java Edition besitzt.
+    /// Ohne Besitz kann das Spiel nur im Demo-Modus gestartet werden.
+    pub async fn check_game_ownership(&self, access_token: &str) -> Result<bool> {
+        #[derive(Deserialize)]
+        struct Entitlements {
+            #[serde(default)]
+            items: Vec<EntitlementItem>,
+        }
+        #[derive(Deserialize)]
+        struct EntitlementItem {
+            name: String,
+        }
+
+        let entitlements: Entitlements = self.client
+            .get("https://api.minecraftservices.com/entitlements/mcstore")
+            .header("Authorization", format!("Bearer {}", access_token))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        Ok(entitlements.items.iter().any(|item| item.name == "game_minecraft" || item.name == "product_minecraft"))
+    }
+
     /// Refresh Token verwenden um neuen Access Token zu bekommen
     pub async fn refresh_auth(&self, refresh_token: &str) -> Result<MinecraftAccount> {
         let params = [
@@ -347,13 +398,38 @@ impl MinecraftAuth {
         self.complete_auth(&token_response.access_token, token_response.refresh_token).await
     }
 
+    /// Lädt Name, Skin und Cape eines gespeicherten Accounts neu, ohne erneuten Login.
+    /// Ist das Access-Token abgelaufen oder wird abgelehnt, wird es über das Refresh-Token erneuert.
+    pub async fn refresh_profile(&self, account: &MinecraftAccount) -> Result<MinecraftAccount> {
+        let token_valid = account.expires_at.is_some_and(|t| t > Utc::now() + Duration::minutes(5));
+        if token_valid {
+            match self.get_minecraft_profile(&account.access_token).await {
+                Ok(profile) => {
+                    let (skin_url, cape_url) = profile.active_textures();
+                    if profile.name != account.username {
+                        tracing::info!("Account {} was renamed to {}", account.username, profile.name);
+                    }
+                    return Ok(MinecraftAccount {
+                        username: profile.name,
+                        skin_url,
+                        cape_url,
+                        ..account.clone()
+                    });
+                }
+                Err(e) => tracing::warn!("Profile request failed, refreshing token: {}", e),
+            }
+        }
+
+        let refresh_token = account.refresh_token.as_deref()
+            .ok_or_else(|| anyhow::anyhow!("Kein Refresh-Token vorhanden – bitte neu anmelden"))?;
+        // refresh_auth holt das Profil mit dem neuen Token gleich mit
+        self.refresh_auth(refresh_token).await
+    }
+
     /// Offline Account erstellen
     pub fn create_offline_account(username: &str) -> MinecraftAccount {
-        // Generiere eine konsistente UUID basierend auf dem Username
-        let uuid = uuid::Uuid::new_v5(&uuid::Uuid::NAMESPACE_DNS, username.as_bytes());
-
         MinecraftAccount {
-            uuid: uuid.to_string().replace("-", ""),
+            uuid: offline_uuid(username).simple().to_string(),
             username: username.to_string(),
             access_token: "0".to_string(),
             refresh_token: None,
@@ -365,6 +441,62 @@ impl MinecraftAuth {
     }
 }
 
+/// Offline-UUID wie in Vanilla (`UUID.nameUUIDFromBytes("OfflinePlayer:" + name)`, MD5/Version 3).
+/// Offline-Server leiten daraus Spielerdaten und OP-Rechte ab.
+pub fn offline_uuid(username: &str) -> uuid::Uuid {
+    use md5::{Digest, Md5};
+
+    let mut bytes: [u8; 16] = Md5::digest(format!("OfflinePlayer:{}", username).as_bytes()).into();
+    bytes[6] = (bytes[6] & 0x0f) | 0x30;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    uuid::Uuid::from_bytes(bytes)
+}
+
+/// Frühere Offline-UUID des Launchers (UUIDv5 des Namens) – nur für die Migration
+pub fn legacy_offline_uuid(username: &str) -> uuid::Uuid {
+    uuid::Uuid::new_v5(&uuid::Uuid::NAMESPACE_DNS, username.as_bytes())
+}
+
+/// Stellt Offline-Accounts mit alter UUIDv5 auf die Vanilla-UUID um.
+/// Gibt die Paare (alt, neu) zurück, damit Spielerdaten mitgezogen werden können.
+pub fn migrate_offline_uuids(state: &mut AuthState) -> Vec<(uuid::Uuid, uuid::Uuid)> {
+    let mut migrated = Vec::new();
+    for account in state.accounts.iter_mut().filter(|a| !a.is_microsoft) {
+        let legacy = legacy_offline_uuid(&account.username);
+        if account.uuid != legacy.simple().to_string() {
+            continue;
+        }
+        let new_uuid = offline_uuid(&account.username);
+        tracing::info!("Migrating offline account {} to vanilla UUID {}", account.username, new_uuid);
+        if state.active_account.as_deref() == Some(account.uuid.as_str()) {
+            state.active_account = Some(new_uuid.simple().to_string());
+        }
+        account.uuid = new_uuid.simple().to_string();
+        migrated.push((legacy, new_uuid));
+    }
+    migrated
+}
+
+/// Benennt `playerdata`/`stats`/`advancements` der Welten von der alten auf die neue UUID um
+/// (nur wenn für die neue UUID noch nichts existiert)
+pub fn migrate_world_player_files(game_dirs: &[std::path::PathBuf], old: uuid::Uuid, new: uuid::Uuid) {
+    let (old, new) = (old.hyphenated().to_string(), new.hyphenated().to_string());
+    for saves in game_dirs.iter().map(|dir| dir.join("saves")) {
+        let Ok(worlds) = std::fs::read_dir(&saves) else { continue };
+        for world in worlds.flatten().map(|w| w.path()) {
+            for (sub, ext) in [("playerdata", "dat"), ("stats", "json"), ("advancements", "json")] {
+                let from = world.join(sub).join(format!("{}.{}", old, ext));
+                let to = world.join(sub).join(format!("{}.{}", new, ext));
+                if from.exists() && !to.exists() {
+                    if let Err(e) = std::fs::rename(&from, &to) {
+                        tracing::warn!("Could not migrate {:?}: {}", from, e);
+                    }
+                }
+            }
+        }
+    }
+}
+
 /// Skin-URL für Kopf-Avatar generieren (via mc-heads.net - zuverlässiger als Crafatar)
 pub fn get_head_url(uuid: &str, size: u32) -> String {
     // mc-heads.net ist zuverlässiger als crafatar
@@ -380,3 +512,32 @@ pub fn get_skin_render_url(uuid: &str) -> String {
 pub fn get_full_skin_url(uuid: &str) -> String {
     format!("https://mc-heads.net/skin/{}", uuid)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offline_uuid_matches_vanilla() {
+        // UUID.nameUUIDFromBytes("OfflinePlayer:Notch".getBytes())
+        assert_eq!(offline_uuid("Notch").to_string(), "b50ad385-829d-3141-a216-7e7d7539ba7f");
+    }
+
+    #[test]
+    fn md5_matches_rfc_1321_vectors() {
+        use md5::{Digest, Md5};
+
+        let vectors = [
+            ("", "d41d8cd98f00b204e9800998ecf8427e"),
+            ("a", "0cc175b9c0f1b6a831c399e269772661"),
+            ("abc", "900150983cd24fb0d6963f7d28e17f72"),
+            ("message digest", "f96b697d7cb7938d525a2f31aaf161d0"),
+            ("abcdefghijklmnopqrstuvwxyz", "c3fcd3d76192e4007dfb496cca67e13b"),
+            ("ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789", "d174ab98d277d9f5a5611c2c9f419d9f"),
+            ("12345678901234567890123456789012345678901234567890123456789012345678901234567890", "57edf4a22be3c955ac49da2e2107b67a"),
+        ];
+        for (input, expected) in vectors {
+            assert_eq!(hex::encode(Md5::digest(input.as_bytes())), expected, "MD5(\"{}\")", input);
+        }
+    }
+}
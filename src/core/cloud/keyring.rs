@@ -0,0 +1,97 @@
+//! Ablage des WebDAV-Passworts im Schlüsselbund des Betriebssystems statt in config.json.
+//!
+//! - Linux: Secret Service (GNOME Keyring, KWallet) über `secret-tool`
+//! - macOS: Schlüsselbund über `security`
+//! - Windows: DPAPI (an das Benutzerkonto gebunden), verschlüsselt in `cloud_sync.secret`
+//!
+//! Das Passwort wird den Tools immer über stdin übergeben, nie als Argument.
+
+use anyhow::{bail, Context, Result};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+const SERVICE: &str = "Lion-Launcher";
+const ACCOUNT: &str = "cloud-sync";
+
+fn secret_file() -> std::path::PathBuf {
+    crate::config::defaults::launcher_dir().join("cloud_sync.secret")
+}
+
+/// Führt ein Tool aus, schreibt `input` nach stdin und gibt stdout zurück.
+/// `Ok(None)` wenn das Tool ohne Fehlermeldung fehlschlägt (Eintrag nicht vorhanden).
+fn run(program: &str, args: &[&str], input: &str) -> Result<Option<String>> {
+    let mut command = Command::new(program);
+    command.args(args).stdin(Stdio::piped()).stdout(Stdio::piped()).stderr(Stdio::piped());
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        command.creation_flags(0x08000000); // CREATE_NO_WINDOW
+    }
+
+    let mut child = command.spawn().with_context(|| format!("{} ist nicht verfügbar", program))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(input.as_bytes())?;
+    }
+    let output = child.wait_with_output()?;
+    if output.status.success() {
+        return Ok(Some(String::from_utf8_lossy(&output.stdout).into_owned()));
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    if stderr.is_empty() || stderr.contains("could not be found") {
+        return Ok(None);
+    }
+    bail!("{} fehlgeschlagen: {}", program, stderr)
+}
+
+/// Argument für den interaktiven Modus von `security` quoten
+fn security_quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+const POWERSHELL_PROTECT: &str = "Add-Type -AssemblyName System.Security; \
+    [Console]::InputEncoding = [Text.UTF8Encoding]::new($false); \
+    $p = [Console]::In.ReadToEnd(); \
+    [Convert]::ToBase64String([Security.Cryptography.ProtectedData]::Protect(\
+    [Text.Encoding]::UTF8.GetBytes($p), $null, 'CurrentUser'))";
+
+const POWERSHELL_UNPROTECT: &str = "Add-Type -AssemblyName System.Security; \
+    [Console]::OutputEncoding = [Text.UTF8Encoding]::new($false); \
+    $b = [Convert]::FromBase64String([Console]::In.ReadToEnd().Trim()); \
+    [Console]::Out.Write([Text.Encoding]::UTF8.GetString(\
+    [Security.Cryptography.ProtectedData]::Unprotect($b, $null, 'CurrentUser')))";
+
+/// Speichert das Passwort (ersetzt einen vorhandenen Eintrag)
+pub fn store_password(password: &str) -> Result<()> {
+    if cfg!(windows) {
+        let encrypted = run("powershell", &["-NoProfile", "-NonInteractive", "-Command", POWERSHELL_PROTECT], password)?
+            .context("DPAPI-Verschlüsselung fehlgeschlagen")?;
+        std::fs::write(secret_file(), encrypted.trim())?;
+    } else if cfg!(target_os = "macos") {
+        let command = format!(
+            "add-generic-password -U -s {} -a {} -w {}\n",
+            security_quote(SERVICE), security_quote(ACCOUNT), security_quote(password)
+        );
+        run("security", &["-i"], &command)?;
+    } else {
+        let label = format!("{} Cloud-Sync", SERVICE);
+        run("secret-tool", &["store", "--label", &label, "service", SERVICE, "account", ACCOUNT], password)?
+            .context("Kein Schlüsselbund (Secret Service) erreichbar")?;
+    }
+    Ok(())
+}
+
+/// Liest das gespeicherte Passwort, `None` wenn keines hinterlegt ist
+pub fn load_password() -> Result<Option<String>> {
+    let password = if cfg!(windows) {
+        let Ok(encrypted) = std::fs::read_to_string(secret_file()) else {
+            return Ok(None);
+        };
+        run("powershell", &["-NoProfile", "-NonInteractive", "-Command", POWERSHELL_UNPROTECT], &encrypted)?
+    } else if cfg!(target_os = "macos") {
+        run("security", &["find-generic-password", "-s", SERVICE, "-a", ACCOUNT, "-w"], "")?
+            .map(|p| p.strip_suffix('\n').unwrap_or(&p).to_string())
+    } else {
+        run("secret-tool", &["lookup", "service", SERVICE, "account", ACCOUNT], "")?
+    };
+    Ok(password.filter(|p| !p.is_empty()))
+}
@@ -0,0 +1,376 @@
+#![allow(dead_code)]
+
+//! Optionale Cloud-Synchronisation der Launcher-Daten über einen
+//! benutzereigenen WebDAV-Server (Nextcloud, ownCloud, Synology, ...).
+//!
+//! Synchronisiert werden profiles.json, shared_options.txt sowie pro Profil
+//! options.txt, servers.dat und eine generierte Mod-Liste (modlist.json).
+//! Konflikte (lokal UND remote seit dem letzten Sync geändert) werden nicht
+//! automatisch aufgelöst, sondern im Report zurückgegeben.
+//!
+//! Rechnerspezifische Profilfelder (Spielordner, Java-Pfad, Bilder) bleiben lokal,
+//! das Passwort liegt im Schlüsselbund des Betriebssystems (siehe `keyring`).
+//! Als Backend wird nur WebDAV unterstützt; S3 ist bewusst nicht vorgesehen.
+
+pub mod keyring;
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use crate::config::schema::CloudSyncSettings;
+use crate::core::profiles::ProfileManager;
+
+/// Name der generierten Mod-Liste im Spielordner
+pub const MOD_LIST_FILE: &str = "modlist.json";
+
+const PROFILES_FILE: &str = "profiles.json";
+
+/// Profilfelder, die pro Rechner verschieden sind: werden nicht hochgeladen und beim
+/// Download aus der lokalen profiles.json übernommen, sonst springen die Pfade bei
+/// jedem Sync zwischen den Rechnern hin und her
+const MACHINE_LOCAL_FIELDS: [&str; 6] = ["game_dir", "archive_path", "java_path", "icon_path", "banner_path", "background_path"];
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CloudSyncReport {
+    pub uploaded: Vec<String>,
+    pub downloaded: Vec<String>,
+    pub conflicts: Vec<String>,
+    pub unchanged: usize,
+}
+
+/// Lokaler Stand des letzten erfolgreichen Syncs (remote-Pfad → SHA-256)
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SyncState {
+    hashes: HashMap<String, String>,
+}
+
+fn state_file() -> PathBuf {
+    crate::config::defaults::launcher_dir().join("cloud_sync_state.json")
+}
+
+fn load_state() -> SyncState {
+    std::fs::read_to_string(state_file())
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+async fn save_state(state: &SyncState) -> Result<()> {
+    crate::config::backup::write_atomic(&state_file(), &serde_json::to_string_pretty(state)?).await
+}
+
+fn hash(data: &[u8]) -> String {
+    hex::encode(Sha256::digest(data))
+}
+
+#[derive(Clone, Copy)]
+enum SyncAction {
+    Upload,
+    Download,
+    Conflict,
+}
+
+/// Ein zu synchronisierendes Element: Pfad auf dem Server ↔ lokale Datei
+struct SyncItem {
+    remote: String,
+    local: PathBuf,
+}
+
+struct WebDavClient {
+    client: reqwest::Client,
+    base_url: String,
+    username: String,
+    password: String,
+}
+
+impl WebDavClient {
+    async fn new(settings: &CloudSyncSettings) -> Result<Self> {
+        if !settings.enabled {
+            bail!("Cloud-Sync ist deaktiviert");
+        }
+        if settings.webdav_url.trim().is_empty() {
+            bail!("Keine WebDAV-URL konfiguriert");
+        }
+        // Ältere config.json enthalten das Passwort noch im Klartext
+        let password = if settings.password.is_empty() {
+            crate::utils::compression::run_blocking(keyring::load_password).await?.unwrap_or_default()
+        } else {
+            settings.password.clone()
+        };
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(30))
+            .build()?;
+        Ok(Self {
+            client,
+            base_url: settings.webdav_url.trim_end_matches('/').to_string(),
+            username: settings.username.clone(),
+            password,
+        })
+    }
+
+    fn url(&self, remote: &str) -> String {
+        format!("{}/{}", self.base_url, remote)
+    }
+
+    async fn get(&self, remote: &str) -> Result<Option<Vec<u8>>> {
+        let response = self.client.get(self.url(remote))
+            .basic_auth(&self.username, Some(&self.password))
+            .send()
+            .await?;
+        match response.status() {
+            reqwest::StatusCode::NOT_FOUND => Ok(None),
+            s if s.is_success() => Ok(Some(response.bytes().await?.to_vec())),
+            s => bail!("WebDAV GET {} fehlgeschlagen: {}", remote, s),
+        }
+    }
+
+    async fn put(&self, remote: &str, data: Vec<u8>) -> Result<()> {
+        // Übergeordnete Verzeichnisse anlegen (MKCOL schlägt fehl wenn sie schon existieren – ok)
+        let mut dir = String::new();
+        let parts: Vec<&str> = remote.split('/').collect();
+        for part in &parts[..parts.len().saturating_sub(1)] {
+            dir = if dir.is_empty() { part.to_string() } else { format!("{}/{}", dir, part) };
+            let _ = self.client.request(reqwest::Method::from_bytes(b"MKCOL")?, self.url(&dir))
+                .basic_auth(&self.username, Some(&self.password))
+                .send()
+                .await;
+        }
+
+        let response = self.client.put(self.url(remote))
+            .basic_auth(&self.username, Some(&self.password))
+            .body(data)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            bail!("WebDAV PUT {} fehlgeschlagen: {}", remote, response.status());
+        }
+        Ok(())
+    }
+}
+
+/// Schreibt die Mod-Liste eines Profils (Dateiname, Status, Metadaten) als modlist.json
+fn write_mod_list(game_dir: &std::path::Path) -> Result<()> {
+    let mods_dir = game_dir.join("mods");
+    let mut mods: Vec<serde_json::Value> = Vec::new();
+
+    if let Ok(entries) = std::fs::read_dir(&mods_dir) {
+        for entry in entries.flatten() {
+            let filename = entry.file_name().to_string_lossy().to_string();
+            if !filename.ends_with(".jar") && !filename.ends_with(".jar.disabled") {
+                continue;
+            }
+            let meta = crate::core::mods::content_db::get(game_dir, &filename)
+                .and_then(|record| serde_json::to_value(record).ok());
+            mods.push(serde_json::json!({
+                "filename": filename,
+                "disabled": filename.ends_with(".disabled"),
+                "meta": meta,
+            }));
+        }
+    }
+    mods.sort_by(|a, b| a["filename"].as_str().cmp(&b["filename"].as_str()));
+
+    std::fs::write(game_dir.join(MOD_LIST_FILE), serde_json::to_string_pretty(&mods)?)?;
+    Ok(())
+}
+
+/// Sammelt alle zu synchronisierenden Dateien
+async fn collect_items() -> Result<Vec<SyncItem>> {
+    let launcher_dir = crate::config::defaults::launcher_dir();
+    let mut items = vec![
+        SyncItem { remote: PROFILES_FILE.to_string(), local: launcher_dir.join(PROFILES_FILE) },
+        SyncItem { remote: "shared_options.txt".to_string(), local: crate::config::defaults::shared_settings_file() },
+    ];
+
+    let profiles = ProfileManager::new()?.load_profiles().await?;
+    for profile in profiles.profiles.iter().filter(|p| !p.archived) {
+        if let Err(e) = write_mod_list(&profile.game_dir) {
+            tracing::warn!("Could not write mod list for {}: {}", profile.name, e);
+        }
+        for file in ["options.txt", "servers.dat", MOD_LIST_FILE] {
+            items.push(SyncItem {
+                remote: format!("profiles/{}/{}", profile.id, file),
+                local: profile.game_dir.join(file),
+            });
+        }
+    }
+
+    Ok(items)
+}
+
+/// Liest die lokale Datei; bei profiles.json ohne die rechnerspezifischen Felder
+async fn read_local(item: &SyncItem) -> Result<Option<Vec<u8>>> {
+    let Ok(data) = tokio::fs::read(&item.local).await else {
+        return Ok(None);
+    };
+    if item.remote != PROFILES_FILE {
+        return Ok(Some(data));
+    }
+    let mut value: serde_json::Value = serde_json::from_slice(&data)?;
+    let profiles = value.get_mut("profiles").and_then(|p| p.as_array_mut());
+    for profile in profiles.into_iter().flatten().filter_map(|p| p.as_object_mut()) {
+        for field in MACHINE_LOCAL_FIELDS {
+            profile.remove(field);
+        }
+    }
+    Ok(Some(serde_json::to_vec_pretty(&value)?))
+}
+
+/// Schreibt eine heruntergeladene Datei. profiles.json wird mit den lokalen Pfaden
+/// zusammengeführt; neue Profile bekommen den Standard-Profilordner.
+async fn write_local(item: &SyncItem, data: &[u8]) -> Result<()> {
+    if item.remote != PROFILES_FILE {
+        if let Some(parent) = item.local.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(&item.local, data).await?;
+        return Ok(());
+    }
+
+    let mut remote: serde_json::Value = serde_json::from_slice(data)?;
+    let local: serde_json::Value = match tokio::fs::read(&item.local).await {
+        Ok(content) => serde_json::from_slice(&content).unwrap_or_default(),
+        Err(_) => serde_json::Value::Null,
+    };
+    let local_profiles: HashMap<&str, &serde_json::Map<String, serde_json::Value>> = local.get("profiles")
+        .and_then(|p| p.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|p| Some((p.get("id")?.as_str()?, p.as_object()?)))
+        .collect();
+
+    let profiles = remote.get_mut("profiles").and_then(|p| p.as_array_mut());
+    for profile in profiles.into_iter().flatten().filter_map(|p| p.as_object_mut()) {
+        let id = profile.get("id").and_then(|id| id.as_str()).unwrap_or_default().to_string();
+        // Die ID stammt vom Server und wird zum Ordnernamen unter profiles/
+        if !is_safe_profile_id(&id) {
+            bail!("Ungültige Profil-ID in der Remote-Datei: {:?}", id);
+        }
+        let existing = local_profiles.get(id.as_str());
+        for field in MACHINE_LOCAL_FIELDS {
+            match existing.and_then(|p| p.get(field)) {
+                Some(value) => profile.insert(field.to_string(), value.clone()),
+                None => profile.remove(field),
+            };
+        }
+        if !profile.contains_key("game_dir") {
+            let game_dir = crate::config::defaults::profiles_dir().join(&id);
+            tokio::fs::create_dir_all(game_dir.join("mods")).await?;
+            profile.insert("game_dir".to_string(), serde_json::to_value(game_dir)?);
+        }
+    }
+
+    let profiles: crate::types::profile::ProfileList = serde_json::from_value(remote)?;
+    ProfileManager::new()?.save_profiles(&profiles).await
+}
+
+/// Nur ein einzelner, normaler Pfadbestandteil (kein "..", keine Trennzeichen, nicht absolut)
+fn is_safe_profile_id(id: &str) -> bool {
+    let mut components = std::path::Path::new(id).components();
+    matches!((components.next(), components.next()), (Some(std::path::Component::Normal(_)), None))
+        && !id.contains(['/', '\\'])
+}
+
+/// Führt einen manuellen Sync-Durchlauf aus
+pub async fn sync_now(settings: &CloudSyncSettings) -> Result<CloudSyncReport> {
+    let client = WebDavClient::new(settings).await?;
+    let mut state = load_state();
+    let mut report = CloudSyncReport::default();
+
+    // profiles.json zuerst – neu heruntergeladene Profile sollen im selben Durchlauf mitkommen
+    let mut items = collect_items().await?;
+    let mut profiles_downloaded = false;
+    let mut index = 0;
+
+    while index < items.len() {
+        let item = &items[index];
+        index += 1;
+
+        let local = read_local(item).await?;
+        let remote = client.get(&item.remote).await?;
+        let local_hash = local.as_deref().map(hash);
+        let remote_hash = remote.as_deref().map(hash);
+        let last = state.hashes.get(&item.remote).cloned();
+
+        if local_hash == remote_hash {
+            if let Some(h) = local_hash {
+                state.hashes.insert(item.remote.clone(), h);
+            }
+            report.unchanged += 1;
+            continue;
+        }
+
+        let local_changed = local_hash != last;
+        let remote_changed = remote_hash != last;
+
+        let action = match (&local, &remote) {
+            (Some(_), None) => SyncAction::Upload,
+            (None, Some(_)) => SyncAction::Download,
+            _ if local_changed && !remote_changed => SyncAction::Upload,
+            _ if remote_changed && !local_changed => SyncAction::Download,
+            _ => SyncAction::Conflict,
+        };
+
+        match (action, local, remote) {
+            (SyncAction::Upload, Some(data), _) => {
+                client.put(&item.remote, data).await?;
+                state.hashes.insert(item.remote.clone(), local_hash.unwrap_or_default());
+                report.uploaded.push(item.remote.clone());
+            }
+            (SyncAction::Download, _, Some(data)) => {
+                write_local(item, &data).await?;
+                state.hashes.insert(item.remote.clone(), remote_hash.unwrap_or_default());
+                report.downloaded.push(item.remote.clone());
+                if item.remote == PROFILES_FILE {
+                    profiles_downloaded = true;
+                }
+            }
+            _ => {
+                tracing::warn!("Cloud sync conflict: {}", item.remote);
+                report.conflicts.push(item.remote.clone());
+            }
+        }
+
+        // Nach neuem profiles.json die Liste der Profil-Dateien neu aufbauen
+        if profiles_downloaded && index == 1 {
+            let mut refreshed = collect_items().await?;
+            refreshed.drain(..1);
+            items.truncate(1);
+            items.extend(refreshed);
+        }
+    }
+
+    save_state(&state).await?;
+    tracing::info!(
+        "Cloud sync finished: {} up, {} down, {} conflicts, {} unchanged",
+        report.uploaded.len(), report.downloaded.len(), report.conflicts.len(), report.unchanged
+    );
+    Ok(report)
+}
+
+/// Löst einen Konflikt auf: `keep_local = true` lädt die lokale Datei hoch,
+/// sonst wird die Remote-Version lokal übernommen.
+pub async fn resolve_conflict(settings: &CloudSyncSettings, remote_path: &str, keep_local: bool) -> Result<()> {
+    let client = WebDavClient::new(settings).await?;
+    let items = collect_items().await?;
+    let item = items.iter()
+        .find(|i| i.remote == remote_path)
+        .ok_or_else(|| anyhow::anyhow!("Unbekannter Sync-Pfad: {}", remote_path))?;
+
+    let mut state = load_state();
+    if keep_local {
+        let data = read_local(item).await?
+            .ok_or_else(|| anyhow::anyhow!("Lokale Datei existiert nicht: {}", remote_path))?;
+        state.hashes.insert(item.remote.clone(), hash(&data));
+        client.put(&item.remote, data).await?;
+    } else {
+        let data = client.get(&item.remote).await?
+            .ok_or_else(|| anyhow::anyhow!("Datei existiert nicht auf dem Server: {}", remote_path))?;
+        write_local(item, &data).await?;
+        state.hashes.insert(item.remote.clone(), hash(&data));
+    }
+    save_state(&state).await?;
+    Ok(())
+}
@@ -0,0 +1,292 @@
+//! Erkennung von Grafikkarte und Treiber (glxinfo/vulkaninfo unter Linux, WMI unter Windows,
+//! system_profiler unter macOS) und Hinweise zu bekannten Problem-Kombinationen.
+
+use serde::Serialize;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+/// Externe Tools können ohne Display hängen bleiben
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Ab dieser Mesa-Version laufen Iris/OptiFine-Shader stabil
+const MIN_MESA_FOR_SHADERS: (u32, u32) = (23, 0);
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct GpuInfo {
+    pub name: String,
+    pub vendor: Option<String>,
+    pub driver: Option<String>,
+    pub driver_version: Option<String>,
+    pub opengl_version: Option<String>,
+    pub vulkan_api_version: Option<String>,
+    /// Herkunft der Angaben ("glxinfo", "vulkaninfo", "wmi", "system_profiler")
+    pub source: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct GpuReport {
+    pub gpus: Vec<GpuInfo>,
+    /// Version des NVIDIA-Kernelmoduls (Linux), falls geladen
+    pub nvidia_kernel_module: Option<String>,
+    pub hints: Vec<String>,
+}
+
+/// Führt ein Tool mit Zeitlimit aus und gibt stdout zurück (None wenn nicht vorhanden oder fehlgeschlagen)
+fn run_command(program: &str, args: &[&str]) -> Option<String> {
+    let mut command = Command::new(program);
+    command.args(args).stdin(Stdio::null());
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        // CREATE_NO_WINDOW
+        command.creation_flags(0x08000000);
+    }
+
+    // output() liest stdout während das Tool läuft – mit try_wait() blockierte vulkaninfo,
+    // sobald der Pipe-Puffer voll war. Ein hängendes Tool bleibt im Hintergrund-Thread zurück.
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        tx.send(command.output()).ok();
+    });
+    let output = match rx.recv_timeout(COMMAND_TIMEOUT) {
+        Ok(Ok(output)) if output.status.success() => output,
+        Ok(_) => return None,
+        Err(_) => {
+            tracing::warn!("{} timed out", program);
+            return None;
+        }
+    };
+    String::from_utf8(output.stdout).ok()
+}
+
+/// Wert einer Zeile "Schlüssel: Wert" bzw. "Schlüssel = Wert"
+fn field(line: &str, key: &str) -> Option<String> {
+    let rest = line.trim().strip_prefix(key)?;
+    let value = rest.trim_start().strip_prefix([':', '='])?.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+fn parse_glxinfo(output: &str) -> Option<GpuInfo> {
+    let mut info = GpuInfo { source: "glxinfo".to_string(), ..Default::default() };
+    for line in output.lines() {
+        if let Some(v) = field(line, "OpenGL vendor string") {
+            info.vendor = Some(v);
+        } else if let Some(v) = field(line, "OpenGL renderer string") {
+            info.name = v;
+        } else if let Some(v) = field(line, "OpenGL core profile version string") {
+            info.opengl_version = Some(v);
+        } else if let Some(v) = field(line, "OpenGL version string") {
+            info.opengl_version.get_or_insert(v);
+        }
+    }
+    if info.name.is_empty() {
+        return None;
+    }
+    // "4.6 (Core Profile) Mesa 23.2.1" bzw. "4.6.0 NVIDIA 550.54.14"
+    let driver = info.opengl_version.as_deref().and_then(|v| {
+        ["Mesa", "NVIDIA"].iter().find_map(|marker| {
+            let (_, rest) = v.split_once(&format!("{} ", marker))?;
+            Some((marker.to_string(), rest.split_whitespace().next()?.to_string()))
+        })
+    });
+    if let Some((name, version)) = driver {
+        info.driver = Some(name);
+        info.driver_version = Some(version);
+    }
+    Some(info)
+}
+
+/// `vulkaninfo --summary` listet pro GPU einen Block mit deviceName, driverName, ...
+fn parse_vulkaninfo(output: &str) -> Vec<GpuInfo> {
+    let mut gpus: Vec<GpuInfo> = Vec::new();
+    for line in output.lines() {
+        if let Some(v) = field(line, "apiVersion") {
+            gpus.push(GpuInfo { source: "vulkaninfo".to_string(), vulkan_api_version: Some(v), ..Default::default() });
+        }
+        let Some(gpu) = gpus.last_mut() else { continue };
+        if let Some(v) = field(line, "deviceName") {
+            gpu.name = v;
+        } else if let Some(v) = field(line, "driverName") {
+            gpu.driver = Some(v);
+        } else if let Some(v) = field(line, "driverInfo") {
+            gpu.driver_version = Some(v);
+        }
+    }
+    gpus.retain(|g| !g.name.is_empty());
+    gpus
+}
+
+fn detect_linux(report: &mut GpuReport) {
+    if let Some(info) = run_command("glxinfo", &["-B"]).as_deref().and_then(parse_glxinfo) {
+        report.gpus.push(info);
+    }
+    if let Some(output) = run_command("vulkaninfo", &["--summary"]) {
+        for gpu in parse_vulkaninfo(&output) {
+            // glxinfo hängt Zusätze an ("GeForce RTX 3070/PCIe/SSE2") – dieselbe GPU nicht doppelt listen
+            if !report.gpus.iter().any(|g| g.name.starts_with(&gpu.name)) {
+                report.gpus.push(gpu);
+            }
+        }
+    }
+    // "NVRM version: NVIDIA UNIX x86_64 Kernel Module  550.54.14  Thu Feb 22 ..."
+    report.nvidia_kernel_module = std::fs::read_to_string("/proc/driver/nvidia/version")
+        .ok()
+        .and_then(|c| {
+            let line = c.lines().next()?.to_string();
+            let (_, rest) = line.split_once("Kernel Module")?;
+            rest.split_whitespace().next().map(String::from)
+        });
+}
+
+fn detect_windows(report: &mut GpuReport) {
+    let script = "Get-CimInstance Win32_VideoController | \
+        Select-Object Name,AdapterCompatibility,DriverVersion | ConvertTo-Json -Compress";
+    let Some(output) = run_command("powershell", &["-NoProfile", "-NonInteractive", "-Command", script]) else {
+        return;
+    };
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(output.trim()) else {
+        return;
+    };
+    // Bei nur einer Grafikkarte liefert ConvertTo-Json ein Objekt statt eines Arrays
+    let controllers = match json {
+        serde_json::Value::Array(items) => items,
+        other => vec![other],
+    };
+    for controller in controllers {
+        let text = |key: &str| controller.get(key).and_then(|v| v.as_str()).map(String::from);
+        report.gpus.push(GpuInfo {
+            name: text("Name").unwrap_or_default(),
+            vendor: text("AdapterCompatibility"),
+            driver_version: text("DriverVersion"),
+            source: "wmi".to_string(),
+            ..Default::default()
+        });
+    }
+}
+
+fn detect_macos(report: &mut GpuReport) {
+    let Some(output) = run_command("system_profiler", &["SPDisplaysDataType", "-json"]) else {
+        return;
+    };
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(&output) else {
+        return;
+    };
+    let displays = json.get("SPDisplaysDataType").and_then(|v| v.as_array()).cloned().unwrap_or_default();
+    for display in displays {
+        let text = |key: &str| display.get(key).and_then(|v| v.as_str()).map(String::from);
+        report.gpus.push(GpuInfo {
+            name: text("sppci_model").or_else(|| text("_name")).unwrap_or_default(),
+            vendor: text("spdisplays_vendor"),
+            source: "system_profiler".to_string(),
+            ..Default::default()
+        });
+    }
+}
+
+/// "23.2.1" → (23, 2)
+fn major_minor(version: &str) -> Option<(u32, u32)> {
+    let mut parts = version.split_whitespace().next()?.split(['.', '-']);
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next().and_then(|p| p.parse().ok()).unwrap_or(0);
+    Some((major, minor))
+}
+
+/// Nutzt das Profil Shader (Shader Packs vorhanden oder Iris/OptiFine/Oculus installiert)?
+fn uses_shaders(game_dir: &Path) -> bool {
+    let has_entries = |dir: &Path| std::fs::read_dir(dir).map(|mut e| e.next().is_some()).unwrap_or(false);
+    let shader_mod = std::fs::read_dir(game_dir.join("mods")).map(|entries| {
+        entries.flatten().any(|e| {
+            let name = e.file_name().to_string_lossy().to_lowercase();
+            name.ends_with(".jar") && ["iris", "optifine", "oculus"].iter().any(|m| name.contains(m))
+        })
+    });
+    has_entries(&game_dir.join("shaderpacks")) || shader_mod.unwrap_or(false)
+}
+
+fn collect_hints(report: &GpuReport, game_dir: Option<&Path>) -> Vec<String> {
+    let mut hints = Vec::new();
+
+    if report.gpus.is_empty() {
+        hints.push("Keine Grafikkarte erkannt – unter Linux mesa-utils (glxinfo) bzw. vulkan-tools installieren".to_string());
+        return hints;
+    }
+
+    for gpu in &report.gpus {
+        let name = gpu.name.to_lowercase();
+        if ["llvmpipe", "softpipe", "lavapipe", "microsoft basic render", "gdi generic"].iter().any(|s| name.contains(s)) {
+            hints.push(format!(
+                "{} ist ein Software-Renderer – Minecraft läuft ohne GPU-Beschleunigung. Grafiktreiber installieren bzw. aktualisieren",
+                gpu.name
+            ));
+        }
+
+        if let Some((major, minor)) = gpu.opengl_version.as_deref().and_then(major_minor) {
+            if (major, minor) < (3, 2) {
+                hints.push(format!(
+                    "{} unterstützt nur OpenGL {}.{} – Minecraft 1.17+ benötigt mindestens OpenGL 3.2",
+                    gpu.name, major, minor
+                ));
+            }
+        }
+
+        if gpu.driver.as_deref() == Some("Mesa") {
+            let mesa = gpu.driver_version.as_deref().and_then(major_minor);
+            if let (Some(version), Some(dir)) = (mesa, game_dir) {
+                if version < MIN_MESA_FOR_SHADERS && uses_shaders(dir) {
+                    hints.push(format!(
+                        "Mesa {}.{} ist veraltet – mit Shader Packs sind Abstürze und Grafikfehler bekannt (empfohlen: Mesa {}.{}+)",
+                        version.0, version.1, MIN_MESA_FOR_SHADERS.0, MIN_MESA_FOR_SHADERS.1
+                    ));
+                }
+            }
+        }
+    }
+
+    // Unterschiedliche Versionen von Kernelmodul und Userspace-Treiber → GLX "BadValue" beim Start
+    if let Some(kernel) = &report.nvidia_kernel_module {
+        let userspace = report.gpus.iter()
+            .find(|g| g.driver.as_deref() == Some("NVIDIA"))
+            .and_then(|g| g.driver_version.as_ref());
+        if let Some(userspace) = userspace {
+            if userspace != kernel {
+                hints.push(format!(
+                    "NVIDIA-Kernelmodul ({}) und Treiber ({}) haben verschiedene Versionen – System neu starten",
+                    kernel, userspace
+                ));
+            }
+        }
+    }
+
+    // Hybrid-Grafik unter Windows: Java landet oft auf der integrierten GPU
+    if report.gpus.iter().any(|g| g.source == "wmi") {
+        let vendors: Vec<String> = report.gpus.iter()
+            .filter_map(|g| g.vendor.as_deref().map(str::to_lowercase))
+            .collect();
+        let integrated = vendors.iter().any(|v| v.contains("intel"));
+        let dedicated = vendors.iter().any(|v| v.contains("nvidia") || v.contains("advanced micro devices"));
+        if integrated && dedicated {
+            hints.push(
+                "Mehrere Grafikkarten erkannt – in den Windows-Grafikeinstellungen javaw.exe auf \"Hohe Leistung\" stellen"
+                    .to_string(),
+            );
+        }
+    }
+
+    hints
+}
+
+/// Sammelt GPU- und Treiberinfos samt Hinweisen. `game_dir` aktiviert profilbezogene
+/// Prüfungen (z.B. Shader Packs). Blockierend, über `run_blocking` aufrufen.
+pub fn detect(game_dir: Option<&Path>) -> GpuReport {
+    let mut report = GpuReport::default();
+    match std::env::consts::OS {
+        "linux" => detect_linux(&mut report),
+        "windows" => detect_windows(&mut report),
+        "macos" => detect_macos(&mut report),
+        _ => {}
+    }
+    report.hints = collect_hints(&report, game_dir);
+    tracing::debug!("GPU detection: {} devices, {} hints", report.gpus.len(), report.hints.len());
+    report
+}
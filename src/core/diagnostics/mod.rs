@@ -0,0 +1,327 @@
+#![allow(dead_code)]
+
+pub mod gpu;
+
+use anyhow::Result;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use crate::types::profile::Profile;
+
+/// Platzhalter für entfernte Geheimnisse (Tokens, API-Keys)
+const REDACTED: &str = "<redacted>";
+
+/// Maximale Größe einzelner Log-Dateien im Bundle (die letzten Bytes werden behalten)
+const MAX_LOG_BYTES: usize = 4 * 1024 * 1024;
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SystemInfo {
+    pub launcher_version: String,
+    pub os: String,
+    pub os_version: Option<String>,
+    pub arch: String,
+    pub cpu_cores: usize,
+    pub total_memory_mb: u64,
+    pub available_memory_mb: u64,
+}
+
+/// Sammelt grundlegende Systeminformationen für Bug-Reports
+pub fn collect_system_info() -> SystemInfo {
+    use sysinfo::System;
+
+    let mut sys = System::new();
+    sys.refresh_memory();
+    sys.refresh_cpu();
+
+    SystemInfo {
+        launcher_version: env!("CARGO_PKG_VERSION").to_string(),
+        os: std::env::consts::OS.to_string(),
+        os_version: System::long_os_version(),
+        arch: std::env::consts::ARCH.to_string(),
+        cpu_cores: sys.cpus().len(),
+        total_memory_mb: sys.total_memory() / 1024 / 1024,
+        available_memory_mb: sys.available_memory() / 1024 / 1024,
+    }
+}
+
+/// Entfernt Access-Tokens, Refresh-Tokens und API-Keys aus einem Text.
+/// Arbeitet auf dem ganzen Puffer, damit auch kompaktes JSON mit mehreren Feldern pro
+/// Zeile und über Zeilenumbrüche verteilte Argumente erfasst werden.
+///
+/// Erkannt werden:
+/// - JSON-Felder deren Name "token", "api_key", "secret" oder "password" enthält
+/// - Kommandozeilen-Argumente `--accessToken <wert>`
+/// - JWTs (beginnen mit `eyJ`) und `Bearer <wert>`
+pub fn scrub_secrets(text: &str) -> String {
+    scrub_words(&scrub_json_values(text))
+}
+
+/// Ersetzt jeden String-Wert `"key": "wert"` mit geheimem Key, egal wo im Text er steht
+/// (auch als escaptes JSON `\"key\":\"wert\"` innerhalb eines Log-Strings)
+fn scrub_json_values(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut out = String::with_capacity(text.len());
+    let mut copied = 0;
+    let mut i = 0;
+    let skip_blank = |mut j: usize| {
+        while j < bytes.len() && (bytes[j] == b' ' || bytes[j] == b'\t') {
+            j += 1;
+        }
+        j
+    };
+
+    while i < bytes.len() {
+        // i steht auf dem schließenden Anführungszeichen eines möglichen Keys
+        if bytes[i] != b'"' {
+            i += 1;
+            continue;
+        }
+        let colon = skip_blank(i + 1);
+        if bytes.get(colon) != Some(&b':') {
+            i += 1;
+            continue;
+        }
+        let mut quote = skip_blank(colon + 1);
+        let escaped = bytes.get(quote) == Some(&b'\\');
+        if escaped {
+            quote += 1;
+        }
+        if bytes.get(quote) != Some(&b'"') {
+            i += 1;
+            continue;
+        }
+
+        let key_start = text[..i].rfind(['"', '\n']).filter(|&s| bytes[s] == b'"');
+        let value_start = quote + 1;
+        let value_len = if escaped {
+            escaped_string_len(&text[value_start..])
+        } else {
+            json_string_len(&text[value_start..])
+        };
+        let (Some(key_start), Some(value_len)) = (key_start, value_len) else {
+            i += 1;
+            continue;
+        };
+
+        if is_secret_key(&text[key_start + 1..i].to_lowercase()) {
+            out.push_str(&text[copied..value_start]);
+            out.push_str(REDACTED);
+            copied = value_start + value_len;
+        }
+        i = value_start + value_len;
+    }
+    out.push_str(&text[copied..]);
+    out
+}
+
+/// Länge eines JSON-Strings bis zum schließenden Anführungszeichen (None am Zeilenende)
+fn json_string_len(s: &str) -> Option<usize> {
+    let mut escaped = false;
+    for (idx, c) in s.char_indices() {
+        match c {
+            '\n' => return None,
+            '"' if !escaped => return Some(idx),
+            '\\' => escaped = !escaped,
+            _ => escaped = false,
+        }
+    }
+    None
+}
+
+/// Wie `json_string_len` für escaptes JSON (`\"wert\"`): Länge bis zum `\"`, None am Zeilenende
+fn escaped_string_len(s: &str) -> Option<usize> {
+    let bytes = s.as_bytes();
+    for (idx, &b) in bytes.iter().enumerate() {
+        match b {
+            b'\n' => return None,
+            b'\\' if bytes.get(idx + 1) == Some(&b'"') => return Some(idx),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Geht Wort für Wort durch den ganzen Text; Leerraum (auch Zeilenumbrüche) bleibt erhalten,
+/// sodass `--accessToken` am Zeilenende den Wert in der nächsten Zeile erfasst
+fn scrub_words(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut redact_next = false;
+    for piece in text.split_inclusive(char::is_whitespace) {
+        let word = piece.trim_end_matches(char::is_whitespace);
+        let separator = &piece[word.len()..];
+        if word.is_empty() {
+            out.push_str(separator);
+            continue;
+        }
+        if redact_next {
+            out.push_str(REDACTED);
+            out.push_str(separator);
+            redact_next = false;
+            continue;
+        }
+
+        let trimmed = word.trim_matches(|c: char| c == '"' || c == '\'' || c == ',' || c == '[' || c == ']');
+        if trimmed == "--accessToken" || trimmed.eq_ignore_ascii_case("bearer") {
+            redact_next = true;
+            out.push_str(word);
+        } else if trimmed.starts_with("eyJ") && trimmed.len() > 20 {
+            out.push_str(&word.replace(trimmed, REDACTED));
+        } else if let Some((k, _)) = trimmed.split_once('=').filter(|(k, _)| is_secret_key(&k.to_lowercase())) {
+            out.push_str(&word.replace(trimmed, &format!("{}={}", k, REDACTED)));
+        } else {
+            out.push_str(word);
+        }
+        out.push_str(separator);
+    }
+    out
+}
+
+fn is_secret_key(key: &str) -> bool {
+    key.contains("token")
+        || key.contains("api_key")
+        || key.contains("apikey")
+        || key.contains("secret")
+        || key.contains("password")
+}
+
+/// Liest eine Textdatei; bei sehr großen Dateien nur das Ende
+fn read_tail(path: &Path) -> Option<String> {
+    let bytes = std::fs::read(path).ok()?;
+    let start = bytes.len().saturating_sub(MAX_LOG_BYTES);
+    Some(String::from_utf8_lossy(&bytes[start..]).to_string())
+}
+
+/// Listet die Dateien im mods-Ordner eines Profils (inkl. deaktivierter Mods)
+fn collect_mod_list(game_dir: &Path) -> String {
+    let mods_dir = game_dir.join("mods");
+    let Ok(entries) = std::fs::read_dir(&mods_dir) else {
+        return "(kein mods-Ordner)".to_string();
+    };
+
+    let mut lines: Vec<String> = entries
+        .flatten()
+        .filter(|e| e.path().is_file())
+        .map(|e| {
+            let name = e.file_name().to_string_lossy().to_string();
+            let size = e.metadata().map(|m| m.len()).unwrap_or(0);
+            let state = if name.ends_with(".disabled") { "disabled" } else { "enabled" };
+            format!("{} [{}] {} bytes", name, state, size)
+        })
+        .collect();
+    lines.sort();
+
+    if lines.is_empty() {
+        "(keine Mods installiert)".to_string()
+    } else {
+        lines.join("\n")
+    }
+}
+
+/// Findet den neuesten Crash-Report eines Profils
+fn latest_crash_report(game_dir: &Path) -> Option<PathBuf> {
+    let crash_dir = game_dir.join("crash-reports");
+    let mut entries: Vec<_> = std::fs::read_dir(&crash_dir)
+        .ok()?
+        .flatten()
+        .filter(|e| e.path().extension().is_some_and(|ext| ext == "txt"))
+        .collect();
+    entries.sort_by_key(|e| e.metadata().and_then(|m| m.modified()).ok());
+    entries.last().map(|e| e.path())
+}
+
+/// Erstellt ein Support-Bundle (ZIP) mit Launcher-Logs, Profil-Logs, Mod-Liste,
+/// Konfiguration und Systeminfos. Alle Textdateien werden vorher von Tokens bereinigt.
+pub fn create_support_bundle(profile: Option<&Profile>, launcher_logs: &str, output: &Path) -> Result<PathBuf> {
+    let mut entries: Vec<(String, String)> = Vec::new();
+
+    let system_info = collect_system_info();
+    entries.push(("system.json".to_string(), serde_json::to_string_pretty(&system_info)?));
+    entries.push(("launcher.log".to_string(), launcher_logs.to_string()));
+
+    let gpu_report = gpu::detect(profile.map(|p| p.game_dir.as_path()));
+    entries.push(("gpu.json".to_string(), serde_json::to_string_pretty(&gpu_report)?));
+
+    let config_path = crate::config::defaults::launcher_dir().join("config.json");
+    if let Some(config) = read_tail(&config_path) {
+        entries.push(("config.json".to_string(), config));
+    }
+
+    if let Some(profile) = profile {
+        entries.push(("profile/profile.json".to_string(), serde_json::to_string_pretty(profile)?));
+        entries.push(("profile/mods.txt".to_string(), collect_mod_list(&profile.game_dir)));
+
+        let logs_dir = profile.game_dir.join("logs");
+        for name in ["latest.log", "debug.log"] {
+            if let Some(content) = read_tail(&logs_dir.join(name)) {
+                entries.push((format!("profile/{}", name), content));
+            }
+        }
+
+        if let Some(crash) = latest_crash_report(&profile.game_dir) {
+            if let Some(content) = read_tail(&crash) {
+                let name = crash.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                entries.push((format!("profile/crash-reports/{}", name), content));
+            }
+        }
+    }
+
+    if let Some(parent) = output.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let file = std::fs::File::create(output)?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::FileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+
+    for (name, content) in &entries {
+        zip.start_file(name.as_str(), options)?;
+        zip.write_all(scrub_secrets(content).as_bytes())?;
+    }
+    zip.finish()?;
+
+    tracing::info!("Support bundle created with {} files: {:?}", entries.len(), output);
+    Ok(output.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::scrub_secrets;
+
+    #[test]
+    fn scrubs_compact_json_with_several_fields() {
+        assert_eq!(
+            scrub_secrets(r#"{"name":"Steve","access_token":"abc.def","refresh_token": "xyz"}"#),
+            r#"{"name":"Steve","access_token":"<redacted>","refresh_token": "<redacted>"}"#
+        );
+        assert_eq!(
+            scrub_secrets("  \"accessToken\": \"abc\",\n  \"x\": \"token\""),
+            "  \"accessToken\": \"<redacted>\",\n  \"x\": \"token\""
+        );
+    }
+
+    #[test]
+    fn scrubs_escaped_json_inside_a_log_line() {
+        assert_eq!(
+            scrub_secrets(r#"[12:00:00] [INFO] body="{\"api_key\":\"k123\",\"a\":\"b\"}""#),
+            r#"[12:00:00] [INFO] body="{\"api_key\":\"<redacted>\",\"a\":\"b\"}""#
+        );
+        // Ohne schließendes \" auf derselben Zeile bleibt der Text unverändert
+        assert_eq!(scrub_secrets("{\\\"token\\\":\\\"abc\nnext\\\""), "{\\\"token\\\":\\\"abc\nnext\\\"");
+    }
+
+    #[test]
+    fn scrubs_access_token_argument_at_line_end() {
+        assert_eq!(
+            scrub_secrets("java --username Steve --accessToken\nsecret123 --version 1.20"),
+            "java --username Steve --accessToken\n<redacted> --version 1.20"
+        );
+        assert_eq!(scrub_secrets("--accessToken abc"), "--accessToken <redacted>");
+    }
+
+    #[test]
+    fn scrubs_bearer_and_jwts() {
+        assert_eq!(scrub_secrets("Authorization: Bearer   abc\tnext"), "Authorization: Bearer   <redacted>\tnext");
+        assert_eq!(scrub_secrets("jwt eyJhbGciOiJIUzI1NiJ9.payload.sig end"), "jwt <redacted> end");
+        assert_eq!(scrub_secrets("client_secret=foo other=bar\n"), "client_secret=<redacted> other=bar\n");
+    }
+}
@@ -0,0 +1,56 @@
+//! Abbruch laufender Downloads (z.B. wenn der Nutzer einen Spielstart abbricht).
+//! Ein `CancelToken` wird geklont an alle beteiligten Downloads weitergereicht.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Fehler eines abgebrochenen Vorgangs; per `anyhow::Error::is::<Cancelled>()` erkennbar
+#[derive(Debug, thiserror::Error)]
+#[error("Vorgang abgebrochen")]
+pub struct Cancelled;
+
+#[derive(Default)]
+struct Inner {
+    cancelled: AtomicBool,
+    notify: tokio::sync::Notify,
+}
+
+#[derive(Clone, Default)]
+pub struct CancelToken(Arc<Inner>);
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.cancelled.store(true, Ordering::SeqCst);
+        self.0.notify.notify_waiters();
+    }
+
+    /// Derselbe Token (nicht nur derselbe Zustand)
+    pub fn same(&self, other: &CancelToken) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// `Err(Cancelled)` nach einem Abbruch
+    pub fn check(&self) -> Result<(), Cancelled> {
+        if self.is_cancelled() { Err(Cancelled) } else { Ok(()) }
+    }
+
+    /// Wartet, bis abgebrochen wird
+    pub async fn cancelled(&self) {
+        loop {
+            // Erst registrieren, dann prüfen – sonst geht ein Abbruch dazwischen verloren
+            let notified = self.0.notify.notified();
+            if self.is_cancelled() {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
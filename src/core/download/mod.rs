@@ -1,13 +1,18 @@
 #![allow(dead_code)]
 
+pub mod cancel;
+
 use anyhow::Result;
 use std::path::Path;
 use tokio::io::AsyncWriteExt;
 use futures_util::StreamExt;
 
+pub use cancel::{CancelToken, Cancelled};
+
 #[derive(Clone)]
 pub struct DownloadManager {
     client: reqwest::Client,
+    cancel: Option<CancelToken>,
 }
 
 impl DownloadManager {
@@ -16,7 +21,42 @@ impl DownloadManager {
             .timeout(std::time::Duration::from_secs(300))
             .build()?;
 
-        Ok(Self { client })
+        Ok(Self { client, cancel: None })
+    }
+
+    /// Kopie, deren Downloads mit `token` abgebrochen werden können
+    pub fn with_cancel(&self, token: CancelToken) -> Self {
+        Self { client: self.client.clone(), cancel: Some(token) }
+    }
+
+    /// `Err(Cancelled)`, wenn der zugehörige Vorgang abgebrochen wurde
+    pub fn check_cancelled(&self) -> Result<()> {
+        match &self.cancel {
+            Some(token) => Ok(token.check()?),
+            None => Ok(()),
+        }
+    }
+
+    /// Wartet auf `future`, bricht aber beim Abbruch des Tokens sofort ab
+    /// (`future` wird dabei verworfen, z.B. ein Kindprozess mit `kill_on_drop`)
+    pub async fn cancellable<T>(&self, future: impl std::future::Future<Output = T>) -> Result<T> {
+        match &self.cancel {
+            Some(token) => tokio::select! {
+                value = future => Ok(value),
+                _ = token.cancelled() => Err(Cancelled.into()),
+            },
+            None => Ok(future.await),
+        }
+    }
+
+    /// Lädt eine kleine Textantwort (Metadaten), abbrechbar wie die Downloads
+    pub async fn fetch_text(&self, url: &str) -> Result<String> {
+        self.check_cancelled()?;
+        let response = self.cancellable(self.client.get(url).send()).await??;
+        if !response.status().is_success() {
+            anyhow::bail!("HTTP error {} for URL: {}", response.status().as_u16(), url);
+        }
+        Ok(self.cancellable(response.text()).await??)
     }
 
     pub async fn download_file(
@@ -30,7 +70,8 @@ impl DownloadManager {
             tokio::fs::create_dir_all(parent).await?;
         }
 
-        let response = self.client.get(url).send().await?;
+        self.check_cancelled()?;
+        let response = self.cancellable(self.client.get(url).send()).await??;
 
         // Prüfe HTTP-Status
         if !response.status().is_success() {
@@ -59,7 +100,17 @@ impl DownloadManager {
         let mut downloaded: u64 = 0;
         let mut stream = response.bytes_stream();
 
-        while let Some(chunk) = stream.next().await {
+        loop {
+            let next = match self.cancellable(stream.next()).await {
+                Ok(next) => next,
+                Err(e) => {
+                    // Teildatei nicht liegen lassen
+                    drop(file);
+                    tokio::fs::remove_file(&tmp_dest).await.ok();
+                    return Err(e);
+                }
+            };
+            let Some(chunk) = next else { break };
             let chunk = chunk?;
             file.write_all(&chunk).await?;
             downloaded += chunk.len() as u64;
@@ -108,6 +159,9 @@ impl DownloadManager {
         while retries > 0 {
             // Download
             if let Err(e) = self.download_file(url, dest, None::<fn(u64, u64)>).await {
+                if e.is::<Cancelled>() {
+                    return Err(e);
+                }
                 retries -= 1;
                 tokio::fs::remove_file(dest).await.ok();
                 if retries == 0 {
@@ -158,6 +212,45 @@ impl DownloadManager {
         anyhow::bail!("Hash verification failed after retries for {}", url)
     }
 
+    /// Lädt eine Datei über den Content-Cache (mods_cache_dir, nach SHA-1 abgelegt).
+    /// Liegt die Datei bereits im Cache (z.B. gleiche Mod in einem anderen Profil),
+    /// wird sie von dort kopiert statt erneut heruntergeladen. Gibt `true` bei Cache-Treffer zurück.
+    pub async fn download_cached(
+        &self,
+        url: &str,
+        dest: &Path,
+        expected_sha1: Option<&str>,
+    ) -> Result<bool> {
+        let Some(sha1) = expected_sha1.map(|h| h.to_lowercase()) else {
+            self.download_with_hash(url, dest, None).await?;
+            return Ok(false);
+        };
+
+        let cached = cached_content_path(&sha1);
+        if cached.exists() {
+            if sha1_of_file(&cached).await.ok().as_deref() == Some(sha1.as_str()) {
+                if let Some(parent) = dest.parent() {
+                    tokio::fs::create_dir_all(parent).await?;
+                }
+                tokio::fs::copy(&cached, dest).await?;
+                tracing::info!("Served {} from content cache", dest.display());
+                return Ok(true);
+            }
+            tracing::warn!("Corrupt cache entry {:?}, downloading again", cached);
+            tokio::fs::remove_file(&cached).await.ok();
+        }
+
+        self.download_with_hash(url, dest, Some(&sha1)).await?;
+
+        if let Some(parent) = cached.parent() {
+            tokio::fs::create_dir_all(parent).await.ok();
+        }
+        if let Err(e) = tokio::fs::copy(dest, &cached).await {
+            tracing::warn!("Failed to store {} in content cache: {}", dest.display(), e);
+        }
+        Ok(false)
+    }
+
     pub async fn download_many(
         &self,
         downloads: Vec<(String, std::path::PathBuf)>,
@@ -177,3 +270,20 @@ impl DownloadManager {
         Ok(())
     }
 }
+
+/// Ablageort einer Datei im Content-Cache: mods_cache_dir/<ersten 2 Zeichen>/<sha1>
+pub fn cached_content_path(sha1: &str) -> std::path::PathBuf {
+    let sha1 = sha1.to_lowercase();
+    crate::config::defaults::mods_cache_dir().join(&sha1[..2.min(sha1.len())]).join(&sha1)
+}
+
+/// SHA-1 als Hex-String (Kleinbuchstaben)
+pub fn sha1_hex(data: &[u8]) -> String {
+    use sha1::{Digest, Sha1};
+    hex::encode(Sha1::digest(data))
+}
+
+pub async fn sha1_of_file(path: &Path) -> Result<String> {
+    let content = tokio::fs::read(path).await?;
+    Ok(sha1_hex(&content))
+}
@@ -0,0 +1,131 @@
+//! Umzug des kompletten Launcher-Datenordners (Libraries, Assets, Profile, Konfiguration)
+//! an einen anderen Ort. Pfade in den JSON-Dateien des Datenordners werden angepasst,
+//! der Standardordner verweist danach per `data_location.json` auf den neuen Ort.
+
+use anyhow::{Result, bail};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use super::relocate::MoveReport;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationReport {
+    pub from: PathBuf,
+    pub to: PathBuf,
+    pub moved: MoveReport,
+    /// Angepasste Pfadangaben in profiles.json, config.json & Co.
+    pub rebased_paths: usize,
+    /// Probleme der abschließenden Prüfung (leer = alles in Ordnung)
+    pub problems: Vec<String>,
+}
+
+/// Ersetzt in allen Strings den Präfix `from` durch `to`
+fn rebase_value(value: &mut serde_json::Value, from: &str, to: &str) -> usize {
+    match value {
+        serde_json::Value::String(s) => {
+            let rest = s.strip_prefix(from).filter(|rest| rest.is_empty() || rest.starts_with(['/', '\\']));
+            match rest {
+                Some(rest) => {
+                    *s = format!("{}{}", to, rest);
+                    1
+                }
+                None => 0,
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().map(|v| rebase_value(v, from, to)).sum(),
+        serde_json::Value::Object(map) => map.values_mut().map(|v| rebase_value(v, from, to)).sum(),
+        _ => 0,
+    }
+}
+
+/// Passt die Pfade in den JSON-Dateien direkt im Datenordner an
+fn rebase_json_files(dir: &Path, from: &Path, to: &Path) -> Result<usize> {
+    let (from, to) = (from.to_string_lossy().to_string(), to.to_string_lossy().to_string());
+    let mut total = 0;
+    for entry in std::fs::read_dir(dir)?.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("json") || !path.is_file() {
+            continue;
+        }
+        let Ok(content) = std::fs::read_to_string(&path) else { continue };
+        let Ok(mut value) = serde_json::from_str::<serde_json::Value>(&content) else { continue };
+        let changed = rebase_value(&mut value, &from, &to);
+        if changed > 0 {
+            std::fs::write(&path, serde_json::to_string_pretty(&value)?)?;
+            tracing::info!("Rebased {} paths in {:?}", changed, path);
+            total += changed;
+        }
+    }
+    Ok(total)
+}
+
+/// Prüft den neuen Datenordner: gleiche Einträge wie vorher, lesbare profiles.json,
+/// vorhandene Spielordner
+fn validate(target: &Path, expected_entries: &[String]) -> Vec<String> {
+    let mut problems = Vec::new();
+    for name in expected_entries {
+        if !target.join(name).exists() {
+            problems.push(format!("{} fehlt im neuen Datenordner", name));
+        }
+    }
+
+    let profiles_file = target.join("profiles.json");
+    if profiles_file.exists() {
+        let profiles = std::fs::read_to_string(&profiles_file)
+            .ok()
+            .and_then(|c| serde_json::from_str::<crate::types::profile::ProfileList>(&c).ok());
+        match profiles {
+            Some(profiles) => {
+                for profile in profiles.profiles.iter().filter(|p| p.archive_path.is_none()) {
+                    if !profile.game_dir.is_dir() {
+                        problems.push(format!("Spielordner von {} fehlt: {}", profile.name, profile.game_dir.display()));
+                    }
+                }
+            }
+            None => problems.push("profiles.json ist nicht lesbar".to_string()),
+        }
+    }
+    problems
+}
+
+/// Verschiebt den Datenordner nach `target`. Ist `target` ein nicht leerer Ordner,
+/// wird darin `Lion-Launcher/` angelegt. Blockierend, über `run_blocking` aufrufen.
+pub fn migrate_launcher_data(target: &Path) -> Result<MigrationReport> {
+    let from = crate::config::defaults::launcher_dir();
+    let default_dir = crate::config::defaults::default_launcher_dir();
+
+    let mut target = target.to_path_buf();
+    if target == default_dir {
+        // Zurück an den Standardort: dort liegt nur noch der Verweis
+        let only_pointer = std::fs::read_dir(&target)?
+            .flatten()
+            .all(|e| e.file_name() == "data_location.json");
+        if only_pointer {
+            std::fs::remove_file(target.join("data_location.json")).ok();
+        }
+    } else if target.is_dir() && std::fs::read_dir(&target)?.next().is_some() {
+        target = target.join("Lion-Launcher");
+    }
+    if target == from {
+        bail!("Launcher data already lives in {}", from.display());
+    }
+
+    let expected_entries: Vec<String> = std::fs::read_dir(&from)?
+        .flatten()
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .filter(|name| name != "data_location.json")
+        .collect();
+
+    tracing::info!("Migrating launcher data from {:?} to {:?}", from, target);
+    let moved = super::relocate::move_directory(&from, &target)?;
+
+    let rebased_paths = rebase_json_files(&target, &from, &target)?;
+    crate::config::defaults::set_launcher_dir(&target)?;
+
+    let problems = validate(&target, &expected_entries);
+    for problem in &problems {
+        tracing::warn!("Migration check: {}", problem);
+    }
+
+    Ok(MigrationReport { from, to: target, moved, rebased_paths, problems })
+}
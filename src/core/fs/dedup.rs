@@ -0,0 +1,184 @@
+//! Deduplizierung identischer Dateien (Mods, Resourcepacks, Shaderpacks) über Profile hinweg.
+//!
+//! Jede Datei wird nach ihrem SHA-256 im Content-Store abgelegt und per Hardlink in die
+//! Profile verlinkt. Unterstützt das Dateisystem keine Hardlinks (z.B. anderes Laufwerk,
+//! FAT32), bleibt die Datei einfach unverändert im Profil liegen.
+//!
+//! Verlinkt werden nur Archive (`.jar`, `.zip`), die weder Spiel noch Launcher an Ort und
+//! Stelle ändern. Wer doch in eine solche Datei schreibt, nimmt `write_unshared`, damit
+//! nicht alle Profile mit demselben Inhalt mitgeändert werden.
+
+use anyhow::Result;
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use crate::types::profile::Profile;
+
+/// Ordner im Spielverzeichnis deren Dateien dedupliziert werden
+pub const DEDUP_FOLDERS: [&str; 3] = ["mods", "resourcepacks", "shaderpacks"];
+
+/// Nur Archive teilen; Shader-Einstellungen (`shaderpacks/*.txt`) o.ä. schreibt das Spiel direkt
+fn is_shareable(path: &Path) -> bool {
+    let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("").to_ascii_lowercase();
+    let name = name.strip_suffix(".disabled").unwrap_or(&name);
+    name.ends_with(".jar") || name.ends_with(".zip")
+}
+
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct DedupReport {
+    pub files_scanned: usize,
+    pub files_linked: usize,
+    pub bytes_saved: u64,
+    pub store_entries_removed: usize,
+    pub errors: Vec<String>,
+}
+
+/// Berechnet den SHA-256 einer Datei ohne sie komplett in den Speicher zu laden
+fn sha256_file(path: &Path) -> Result<String> {
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = [0u8; 64 * 1024];
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+fn store_path(hash: &str) -> PathBuf {
+    crate::config::defaults::content_store_dir().join(&hash[..2]).join(hash)
+}
+
+fn same_file(a: &Path, b: &Path) -> bool {
+    same_file::is_same_file(a, b).unwrap_or(false)
+}
+
+/// Schreibt eine Datei über Temp-Datei + Rename. Ein bestehender Hardlink auf den
+/// Content-Store wird dadurch aufgelöst statt überschrieben.
+pub fn write_unshared(path: &Path, content: &[u8]) -> Result<()> {
+    let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(".tmp");
+    let tmp = path.with_file_name(tmp_name);
+    std::fs::write(&tmp, content)?;
+    if let Err(e) = std::fs::rename(&tmp, path) {
+        let _ = std::fs::remove_file(&tmp);
+        return Err(e.into());
+    }
+    Ok(())
+}
+
+/// Legt eine Datei im Content-Store ab bzw. ersetzt sie durch einen Hardlink auf
+/// die bereits gespeicherte Kopie. Gibt die eingesparten Bytes zurück.
+pub fn link_into_store(path: &Path) -> Result<u64> {
+    link_hashed(path, &sha256_file(path)?)
+}
+
+fn link_hashed(path: &Path, hash: &str) -> Result<u64> {
+    if !is_shareable(path) {
+        return Ok(0);
+    }
+    let stored = store_path(hash);
+
+    if !stored.exists() {
+        if let Some(parent) = stored.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        // Erste Kopie: Datei selbst wird zum Store-Eintrag
+        if let Err(e) = std::fs::hard_link(path, &stored) {
+            tracing::debug!("Hardlinks not supported for {:?}: {}", path, e);
+        }
+        return Ok(0);
+    }
+
+    if same_file(path, &stored) {
+        return Ok(0);
+    }
+
+    // Über temporären Link ersetzen, damit die Datei nie fehlt
+    let size = std::fs::metadata(path)?.len();
+    let tmp = path.with_extension("dedup-tmp");
+    let _ = std::fs::remove_file(&tmp);
+    if let Err(e) = std::fs::hard_link(&stored, &tmp) {
+        tracing::debug!("Hardlinks not supported for {:?}: {}", path, e);
+        return Ok(0);
+    }
+    std::fs::rename(&tmp, path)?;
+    Ok(size)
+}
+
+/// Entfernt Store-Einträge die von keinem Profil mehr verwendet werden. `referenced` sind
+/// die Hashes aller Profildateien; unter Unix schützt zusätzlich der Link-Zähler Einträge,
+/// die während des Laufs neu verlinkt wurden.
+fn prune_store(referenced: &HashSet<String>) -> usize {
+    let mut removed = 0;
+    for entry in walkdir::WalkDir::new(crate::config::defaults::content_store_dir())
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+    {
+        if referenced.contains(entry.file_name().to_string_lossy().as_ref()) {
+            continue;
+        }
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            if entry.metadata().map(|m| m.nlink() > 1).unwrap_or(true) {
+                continue;
+            }
+        }
+        if std::fs::remove_file(entry.path()).is_ok() {
+            removed += 1;
+        }
+    }
+    removed
+}
+
+/// Migration: dedupliziert alle bestehenden Dateien der angegebenen Profile
+pub fn dedup_profiles(profiles: &[Profile]) -> DedupReport {
+    let mut report = DedupReport::default();
+    let mut referenced = HashSet::new();
+
+    for profile in profiles {
+        for folder in DEDUP_FOLDERS {
+            let Ok(entries) = std::fs::read_dir(profile.game_dir.join(folder)) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if !path.is_file() || !is_shareable(&path) {
+                    continue;
+                }
+                report.files_scanned += 1;
+                let hash = match sha256_file(&path) {
+                    Ok(hash) => hash,
+                    Err(e) => {
+                        report.errors.push(format!("{}: {}", path.display(), e));
+                        continue;
+                    }
+                };
+                let linked = link_hashed(&path, &hash);
+                referenced.insert(hash);
+                match linked {
+                    Ok(0) => {}
+                    Ok(saved) => {
+                        report.files_linked += 1;
+                        report.bytes_saved += saved;
+                    }
+                    Err(e) => report.errors.push(format!("{}: {}", path.display(), e)),
+                }
+            }
+        }
+    }
+
+    report.store_entries_removed = prune_store(&referenced);
+
+    tracing::info!(
+        "Dedup finished: {} files scanned, {} linked, {} MB saved",
+        report.files_scanned, report.files_linked, report.bytes_saved / 1024 / 1024
+    );
+    report
+}
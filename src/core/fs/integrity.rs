@@ -0,0 +1,115 @@
+//! Prüfung des Datenordners beim Start: fehlende Ordner werden angelegt, abgeschnittene
+//! oder kaputte JSON-Dateien (profiles, config, auth) zur Seite gelegt und aus der
+//! neuesten gültigen Sicherung bzw. mit Standardwerten ersetzt.
+
+use serde::Serialize;
+use std::path::Path;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case", tag = "action")]
+pub enum RepairAction {
+    CreatedDirectory,
+    /// Kaputte Datei ersetzt, Original liegt unter `corrupt_copy`
+    RestoredBackup { backup: String, corrupt_copy: String },
+    ResetToDefault { corrupt_copy: String },
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct IntegrityFix {
+    pub path: String,
+    pub problem: String,
+    #[serde(flatten)]
+    pub action: RepairAction,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct IntegrityReport {
+    pub fixes: Vec<IntegrityFix>,
+    /// Probleme, die nicht behoben werden konnten
+    pub errors: Vec<String>,
+}
+
+fn parses<T: serde::de::DeserializeOwned>(content: &str) -> bool {
+    serde_json::from_str::<T>(content).is_ok()
+}
+
+/// Legt eine kaputte Datei als `<name>.corrupt-<Zeit>` daneben und ersetzt sie
+fn repair_json(path: &Path, is_valid: fn(&str) -> bool, report: &mut IntegrityReport) {
+    let Ok(bytes) = std::fs::read(path) else { return };
+    let content = String::from_utf8_lossy(&bytes);
+    if is_valid(&content) {
+        return;
+    }
+    let file = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let problem = if content.trim().is_empty() {
+        "Datei ist leer".to_string()
+    } else {
+        match serde_json::from_str::<serde_json::Value>(&content) {
+            Ok(_) => "Inhalt passt nicht zum erwarteten Format".to_string(),
+            Err(e) => format!("Ungültiges JSON ({})", e),
+        }
+    };
+    tracing::warn!("{:?} is corrupt: {}", path, problem);
+
+    let corrupt = path.with_file_name(format!("{}.corrupt-{}", file, chrono::Utc::now().format("%Y%m%d-%H%M%S")));
+    if let Err(e) = std::fs::rename(path, &corrupt) {
+        report.errors.push(format!("{} konnte nicht ersetzt werden: {}", file, e));
+        return;
+    }
+    let corrupt_copy = corrupt.to_string_lossy().to_string();
+
+    let restored = crate::config::backup::latest_valid(&file, is_valid)
+        .and_then(|name| crate::config::backup::restore(&name).ok().map(|_| name));
+    let action = match restored {
+        Some(backup) => RepairAction::RestoredBackup { backup, corrupt_copy },
+        // Ohne Datei greifen beim nächsten Laden die Standardwerte
+        None => RepairAction::ResetToDefault { corrupt_copy },
+    };
+    report.fixes.push(IntegrityFix { path: path.to_string_lossy().to_string(), problem, action });
+}
+
+/// Prüft und repariert den Datenordner. Blockierend, vor `ensure_launcher_dirs` aufrufen.
+pub fn check_launcher_data() -> IntegrityReport {
+    use crate::config::defaults;
+
+    let mut report = IntegrityReport::default();
+    let root = defaults::launcher_dir();
+    // Erster Start: fehlende Ordner sind kein Fehler
+    let first_start = !root.join("profiles.json").exists() && !root.join("config.json").exists();
+
+    let dirs = [
+        root.clone(),
+        defaults::profiles_dir(),
+        defaults::libraries_dir(),
+        defaults::assets_dir(),
+        defaults::versions_dir(),
+        defaults::mods_cache_dir(),
+    ];
+    for dir in &dirs {
+        if dir.is_dir() {
+            continue;
+        }
+        if dir.exists() {
+            report.errors.push(format!("{} ist kein Ordner", dir.display()));
+            continue;
+        }
+        match std::fs::create_dir_all(dir) {
+            Ok(()) if !first_start => report.fixes.push(IntegrityFix {
+                path: dir.to_string_lossy().to_string(),
+                problem: "Ordner fehlte".to_string(),
+                action: RepairAction::CreatedDirectory,
+            }),
+            Ok(()) => {}
+            Err(e) => report.errors.push(format!("{} konnte nicht angelegt werden: {}", dir.display(), e)),
+        }
+    }
+
+    repair_json(&root.join("profiles.json"), parses::<crate::types::profile::ProfileList>, &mut report);
+    repair_json(&root.join("config.json"), parses::<crate::config::schema::LauncherConfig>, &mut report);
+    repair_json(&root.join("auth.json"), parses::<crate::core::auth::AuthState>, &mut report);
+
+    if !report.fixes.is_empty() {
+        tracing::info!("Integrity check repaired {} problems", report.fixes.len());
+    }
+    report
+}
@@ -1,5 +1,11 @@
 #![allow(dead_code)]
 
+pub mod data_migration;
+pub mod dedup;
+pub mod integrity;
+pub mod relocate;
+pub mod size;
+
 use anyhow::Result;
 use std::path::Path;
 
@@ -20,20 +26,18 @@ pub async fn ensure_launcher_dirs() -> Result<()> {
     Ok(())
 }
 
+/// Ordnergröße (parallel, mit Cache – siehe `size`)
 pub async fn get_directory_size(path: &Path) -> Result<u64> {
-    let mut total_size = 0;
-    let mut entries = tokio::fs::read_dir(path).await?;
-
-    while let Some(entry) = entries.next_entry().await? {
-        let metadata = entry.metadata().await?;
-        if metadata.is_file() {
-            total_size += metadata.len();
-        } else if metadata.is_dir() {
-            total_size += Box::pin(get_directory_size(&entry.path())).await?;
-        }
-    }
+    get_directory_size_excluding(path, Vec::new()).await
+}
 
-    Ok(total_size)
+/// Wie `get_directory_size`, ohne Einträge die auf eines der Muster passen
+pub async fn get_directory_size_excluding(path: &Path, exclude: Vec<String>) -> Result<u64> {
+    if !path.is_dir() {
+        anyhow::bail!("Not a directory: {}", path.display());
+    }
+    let path = path.to_path_buf();
+    crate::utils::compression::run_blocking(move || Ok(size::directory_size(&path, &exclude))).await
 }
 
 pub async fn cleanup_cache() -> Result<()> {
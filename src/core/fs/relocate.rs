@@ -0,0 +1,128 @@
+//! Verschieben großer Ordner (Spielordner, Launcher-Daten) auf ein anderes Laufwerk.
+//! Auf demselben Dateisystem genügt ein Umbenennen; sonst wird kopiert, jede Datei
+//! per SHA-256 gegen das Original geprüft und erst danach die Quelle entfernt.
+
+use anyhow::{Context, Result, bail};
+use sha2::{Digest, Sha256};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct MoveReport {
+    pub target: PathBuf,
+    /// false wenn der Ordner nur umbenannt werden musste
+    pub copied: bool,
+    pub files: usize,
+    pub bytes: u64,
+    /// Quelle konnte nach dem Kopieren nicht vollständig gelöscht werden
+    pub leftover_source: bool,
+}
+
+fn is_empty_dir(path: &Path) -> bool {
+    std::fs::read_dir(path).map(|mut entries| entries.next().is_none()).unwrap_or(false)
+}
+
+/// Kopiert eine Datei und liefert den SHA-256 des gelesenen Inhalts
+fn copy_hashed(src: &Path, dst: &Path) -> Result<(u64, Vec<u8>)> {
+    let mut reader = std::fs::File::open(src)?;
+    let mut writer = std::io::BufWriter::new(std::fs::File::create(dst)?);
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 1024 * 1024];
+    let mut total = 0;
+    loop {
+        let read = reader.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+        writer.write_all(&buffer[..read])?;
+        total += read as u64;
+    }
+    writer.into_inner().map_err(|e| e.into_error())?.sync_all()?;
+    if let Ok(modified) = std::fs::metadata(src).and_then(|m| m.modified()) {
+        std::fs::File::options().write(true).open(dst)?.set_modified(modified).ok();
+    }
+    Ok((total, hasher.finalize().to_vec()))
+}
+
+fn hash_file(path: &Path) -> Result<Vec<u8>> {
+    let mut reader = std::fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 1024 * 1024];
+    loop {
+        let read = reader.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(hasher.finalize().to_vec())
+}
+
+/// Kopiert `src` nach `dst` und prüft jede Datei. Bei einem Fehler wird die Kopie entfernt.
+fn copy_verified(src: &Path, dst: &Path, report: &mut MoveReport) -> Result<()> {
+    let result = (|| -> Result<()> {
+        for entry in walkdir::WalkDir::new(src) {
+            let entry = entry?;
+            let target = dst.join(entry.path().strip_prefix(src)?);
+            let kind = entry.file_type();
+            if kind.is_dir() {
+                std::fs::create_dir_all(&target)?;
+            } else if kind.is_file() {
+                let (bytes, source_hash) = copy_hashed(entry.path(), &target)
+                    .with_context(|| format!("Copying {}", entry.path().display()))?;
+                if hash_file(&target)? != source_hash {
+                    bail!("Verification failed for {}", target.display());
+                }
+                report.files += 1;
+                report.bytes += bytes;
+            } else {
+                tracing::warn!("Skipping special file {:?}", entry.path());
+            }
+        }
+        Ok(())
+    })();
+
+    if result.is_err() {
+        std::fs::remove_dir_all(dst).ok();
+    }
+    result
+}
+
+/// Verschiebt den Ordner `src` nach `dst` (darf nicht existieren oder muss leer sein).
+/// Blockierend, über `run_blocking` aufrufen.
+pub fn move_directory(src: &Path, dst: &Path) -> Result<MoveReport> {
+    if !src.is_dir() {
+        bail!("Source directory does not exist: {}", src.display());
+    }
+    if dst.exists() && !is_empty_dir(dst) {
+        bail!("Target {} already exists and is not empty", dst.display());
+    }
+    let canonical_src = src.canonicalize()?;
+    let target_parent = dst.parent().context("Invalid target path")?;
+    std::fs::create_dir_all(target_parent)?;
+    if target_parent.canonicalize()?.starts_with(&canonical_src) {
+        bail!("Target {} lies inside the source directory", dst.display());
+    }
+
+    let mut report = MoveReport { target: dst.to_path_buf(), ..Default::default() };
+
+    // Gleiches Dateisystem: sofort fertig
+    if dst.exists() {
+        std::fs::remove_dir(dst)?;
+    }
+    if std::fs::rename(src, dst).is_ok() {
+        tracing::info!("Moved {:?} to {:?} (rename)", src, dst);
+        return Ok(report);
+    }
+
+    tracing::info!("Copying {:?} to {:?} (different drive)", src, dst);
+    report.copied = true;
+    copy_verified(src, dst, &mut report)?;
+
+    if let Err(e) = std::fs::remove_dir_all(src) {
+        tracing::warn!("Copied to {:?}, but could not remove {:?}: {}", dst, src, e);
+        report.leftover_source = true;
+    }
+    Ok(report)
+}
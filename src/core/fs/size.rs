@@ -0,0 +1,140 @@
+//! Ordnergrößen für große Instanzen: Unterordner werden parallel (rayon) durchlaufen
+//! und pro Ordner zwischengespeichert. Ein Eintrag gilt, solange sich die Änderungszeit
+//! des Ordners nicht ändert und er jünger als `RECHECK_AFTER` ist – Dateien, die an Ort
+//! und Stelle wachsen (Region-Dateien), ändern die Ordner-mtime nämlich nicht.
+
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant, SystemTime};
+
+const RECHECK_AFTER: Duration = Duration::from_secs(10 * 60);
+
+/// Obergrenze für Cache-Einträge; darüber werden abgelaufene (bzw. alle) Einträge verworfen
+const MAX_CACHED_DIRS: usize = 50_000;
+
+struct CachedDir {
+    mtime: SystemTime,
+    checked_at: Instant,
+    /// Summe der direkt enthaltenen Dateien
+    files_size: u64,
+    subdirs: Vec<PathBuf>,
+}
+
+/// Schlüssel: Ordner + Ausschlussmuster (andere Muster zählen andere Dateien)
+type CacheKey = (PathBuf, String);
+
+fn cache() -> &'static Mutex<HashMap<CacheKey, CachedDir>> {
+    static CACHE: OnceLock<Mutex<HashMap<CacheKey, CachedDir>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Einfache Glob-Muster mit `*` und `?`. Iterativ mit Rücksprung zum letzten `*`,
+/// damit Muster mit vielen Sternen nicht exponentiell laufen.
+fn glob_match(pattern: &[u8], text: &[u8]) -> bool {
+    let (mut p, mut t) = (0, 0);
+    // Position nach dem letzten `*` und die Textstelle, ab der es gerade passt
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some(b'*') => {
+                star = Some((p + 1, t));
+                p += 1;
+            }
+            Some(b'?') => {
+                p += 1;
+                t += 1;
+            }
+            Some(c) if c.eq_ignore_ascii_case(&text[t]) => {
+                p += 1;
+                t += 1;
+            }
+            _ => match star {
+                // Der Stern schluckt ein Zeichen mehr
+                Some((star_p, star_t)) => {
+                    star = Some((star_p, star_t + 1));
+                    p = star_p;
+                    t = star_t + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == b'*')
+}
+
+/// Ausschlussmuster gelten für den Namen ("logs", "*.log") oder den Pfad relativ
+/// zum Startordner ("saves/*/region")
+struct Exclusions<'a> {
+    root: &'a Path,
+    patterns: &'a [String],
+}
+
+impl Exclusions<'_> {
+    fn excludes(&self, path: &Path) -> bool {
+        if self.patterns.is_empty() {
+            return false;
+        }
+        let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        let relative = path.strip_prefix(self.root).unwrap_or(path).to_string_lossy().replace('\\', "/");
+        self.patterns.iter().any(|p| {
+            let p = p.trim_matches('/').as_bytes();
+            glob_match(p, name.as_bytes()) || glob_match(p, relative.as_bytes())
+        })
+    }
+}
+
+fn dir_size(dir: &Path, exclusions: &Exclusions, key_suffix: &str) -> u64 {
+    let Ok(mtime) = std::fs::metadata(dir).and_then(|m| m.modified()) else { return 0 };
+    let key = (dir.to_path_buf(), key_suffix.to_string());
+
+    let cached = cache().lock().ok().and_then(|cache| {
+        cache.get(&key)
+            .filter(|c| c.mtime == mtime && c.checked_at.elapsed() < RECHECK_AFTER)
+            .map(|c| (c.files_size, c.subdirs.clone()))
+    });
+
+    let (files_size, subdirs) = cached.unwrap_or_else(|| {
+        let mut files_size = 0;
+        let mut subdirs = Vec::new();
+        if let Ok(entries) = std::fs::read_dir(dir) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if exclusions.excludes(&path) {
+                    continue;
+                }
+                // Symlinks nicht folgen (Schleifen, doppelt gezählte Ordner)
+                match entry.file_type() {
+                    Ok(kind) if kind.is_dir() => subdirs.push(path),
+                    Ok(kind) if kind.is_file() => files_size += entry.metadata().map(|m| m.len()).unwrap_or(0),
+                    _ => {}
+                }
+            }
+        }
+        if let Ok(mut cache) = cache().lock() {
+            if cache.len() >= MAX_CACHED_DIRS {
+                cache.retain(|_, c| c.checked_at.elapsed() < RECHECK_AFTER);
+                if cache.len() >= MAX_CACHED_DIRS {
+                    cache.clear();
+                }
+            }
+            cache.insert(key, CachedDir { mtime, checked_at: Instant::now(), files_size, subdirs: subdirs.clone() });
+        }
+        (files_size, subdirs)
+    });
+
+    files_size + subdirs.par_iter().map(|sub| dir_size(sub, exclusions, key_suffix)).sum::<u64>()
+}
+
+/// Größe eines Ordners in Bytes ohne die ausgeschlossenen Einträge. Blockierend.
+pub fn directory_size(path: &Path, exclude: &[String]) -> u64 {
+    let exclusions = Exclusions { root: path, patterns: exclude };
+    // Die Muster hängen vom Startordner ab, daher gehört er mit in den Schlüssel
+    let key_suffix = if exclude.is_empty() {
+        String::new()
+    } else {
+        format!("{}|{}", path.display(), exclude.join("|"))
+    };
+    dir_size(path, &exclusions, &key_suffix)
+}
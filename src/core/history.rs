@@ -0,0 +1,148 @@
+#![allow(dead_code)]
+
+//! Verlauf der Inhaltsänderungen eines Profils (Installation, Update, Entfernen von
+//! Mods, Resource Packs und Shader Packs) in `content-history.json` im Spielordner.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Maximale Anzahl gespeicherter Einträge pro Profil (älteste werden verworfen)
+const MAX_ENTRIES: usize = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ContentAction {
+    Install,
+    Update,
+    Remove,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ContentKind {
+    Mod,
+    ResourcePack,
+    ShaderPack,
+}
+
+impl ContentKind {
+    /// Unterordner des Spielordners
+    pub fn dir_name(&self) -> &'static str {
+        match self {
+            ContentKind::Mod => "mods",
+            ContentKind::ResourcePack => "resourcepacks",
+            ContentKind::ShaderPack => "shaderpacks",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub id: String,
+    pub timestamp: String,
+    pub action: ContentAction,
+    pub kind: ContentKind,
+    pub filename: String,
+    /// Ersetzte Datei bei Updates
+    #[serde(default)]
+    pub previous_filename: Option<String>,
+    /// Projekt-ID bei Modrinth/CurseForge (None bei lokalen Dateien)
+    #[serde(default)]
+    pub project_id: Option<String>,
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub version_id: Option<String>,
+    #[serde(default)]
+    pub source: Option<String>,
+    /// Papierkorb-Eintrag der entfernten Datei
+    #[serde(default)]
+    pub trash_id: Option<String>,
+    #[serde(default)]
+    pub reverted: bool,
+}
+
+impl HistoryEntry {
+    pub fn new(action: ContentAction, kind: ContentKind, filename: impl Into<String>) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            action,
+            kind,
+            filename: filename.into(),
+            previous_filename: None,
+            project_id: None,
+            version: None,
+            version_id: None,
+            source: None,
+            trash_id: None,
+            reverted: false,
+        }
+    }
+}
+
+fn history_file(game_dir: &Path) -> PathBuf {
+    game_dir.join("content-history.json")
+}
+
+/// Alle Einträge eines Profils, älteste zuerst
+pub fn load(game_dir: &Path) -> Vec<HistoryEntry> {
+    std::fs::read_to_string(history_file(game_dir))
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+fn save(game_dir: &Path, entries: &[HistoryEntry]) -> Result<()> {
+    // Über Temp-Datei schreiben, damit ein Absturz keine halbe Historie hinterlässt
+    let path = history_file(game_dir);
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, serde_json::to_string_pretty(entries)?)?;
+    std::fs::rename(&tmp, &path)?;
+    Ok(())
+}
+
+/// Hängt einen Eintrag an; Fehler werden nur geloggt, damit die eigentliche Aktion nicht scheitert
+pub fn record(game_dir: &Path, entry: HistoryEntry) {
+    let mut entries = load(game_dir);
+    tracing::debug!("Content history: {:?} {:?} {}", entry.action, entry.kind, entry.filename);
+    entries.push(entry);
+    let overflow = entries.len().saturating_sub(MAX_ENTRIES);
+    entries.drain(..overflow);
+
+    if let Err(e) = save(game_dir, &entries) {
+        tracing::warn!("Could not save content history: {}", e);
+    }
+}
+
+pub fn find(game_dir: &Path, id: &str) -> Option<HistoryEntry> {
+    load(game_dir).into_iter().find(|e| e.id == id)
+}
+
+pub fn mark_reverted(game_dir: &Path, id: &str) -> Result<()> {
+    let mut entries = load(game_dir);
+    let entry = entries.iter_mut()
+        .find(|e| e.id == id)
+        .context("History entry not found")?;
+    entry.reverted = true;
+    save(game_dir, &entries)
+}
+
+/// Letzte Installation einer Datei mit Projekt-Infos (für das Wiederherstellen entfernter Packs)
+pub fn find_origin(game_dir: &Path, kind: ContentKind, filename: &str) -> Option<HistoryEntry> {
+    load(game_dir).into_iter().rev().find(|e| {
+        e.kind == kind && e.filename == filename && e.action != ContentAction::Remove && e.project_id.is_some()
+    })
+}
+
+/// Übernimmt Projekt-ID, Version und Quelle aus der Inhalts-Datenbank
+pub fn with_mod_metadata(mut entry: HistoryEntry, game_dir: &Path) -> HistoryEntry {
+    if let Some(record) = crate::core::mods::content_db::get(game_dir, &entry.filename) {
+        entry.project_id = record.mod_id;
+        entry.version = record.version;
+        entry.version_id = record.version_id;
+        entry.source = record.source;
+    }
+    entry
+}
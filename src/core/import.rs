@@ -0,0 +1,229 @@
+#![allow(dead_code)]
+
+//! Drag & Drop: erkennt den Typ einer abgelegten Datei (Mod, Resource Pack, Shader Pack,
+//! Modpack, Welt, Skin) und leitet sie an den passenden Import für das Profil weiter.
+
+use anyhow::{Context, Result, bail};
+use serde::Serialize;
+use std::io::Read;
+use std::path::Path;
+
+use super::packs::{copy_dir, unique_target};
+use crate::types::profile::Profile;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportOutcome {
+    /// "mod", "resourcepack", "shaderpack", "modpack", "world" oder "skin"
+    pub kind: String,
+    /// false wenn der Typ erkannt, aber (noch) nicht automatisch importiert werden kann
+    pub imported: bool,
+    /// Dateiname am Ziel (bei Skins relativ zum Skins-Ordner)
+    pub file_name: Option<String>,
+    pub message: String,
+    pub warnings: Vec<String>,
+    /// Ergebnis des jeweiligen Imports (z.B. `ResourcePackImport`)
+    pub details: Option<serde_json::Value>,
+}
+
+impl ImportOutcome {
+    fn new(kind: &str, file_name: Option<String>, message: String) -> Self {
+        Self {
+            kind: kind.to_string(),
+            imported: true,
+            file_name,
+            message,
+            warnings: Vec::new(),
+            details: None,
+        }
+    }
+}
+
+/// Breite und Höhe aus dem IHDR-Chunk einer PNG-Datei
+fn png_dimensions(data: &[u8]) -> Option<(u32, u32)> {
+    const SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+    if data.len() < 24 || !data.starts_with(SIGNATURE) || &data[12..16] != b"IHDR" {
+        return None;
+    }
+    let width = u32::from_be_bytes(data[16..20].try_into().ok()?);
+    let height = u32::from_be_bytes(data[20..24].try_into().ok()?);
+    Some((width, height))
+}
+
+fn file_name_of(path: &Path) -> Result<String> {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .context("Invalid file path")
+}
+
+/// Erkennt den Typ der Datei bzw. des Ordners und importiert sie in das Profil.
+/// Blockierend, über `run_blocking` aufrufen.
+pub fn classify_and_import(source: &Path, profile: &Profile) -> Result<ImportOutcome> {
+    if !source.exists() {
+        bail!("File not found: {}", source.display());
+    }
+
+    if source.is_dir() {
+        if source.join("level.dat").is_file() {
+            return import_world_folder(source, &profile.game_dir);
+        }
+        if source.join("pack.mcmeta").is_file() {
+            return import_resourcepack(source, &profile.game_dir);
+        }
+        bail!("Folder is neither a world (level.dat) nor a resource pack (pack.mcmeta)");
+    }
+
+    let extension = source.extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    match extension.as_str() {
+        "jar" => import_mod(source, profile),
+        "mrpack" => describe_modpack(source),
+        "png" => import_skin(source),
+        "zip" => {
+            let archive = zip::ZipArchive::new(std::fs::File::open(source)?)
+                .context("File is not a valid ZIP archive")?;
+            let names: Vec<String> = archive.file_names().map(|n| n.replace('\\', "/")).collect();
+            let has = |name: &str| names.iter().any(|n| n == name || n.ends_with(&format!("/{}", name)));
+
+            let curseforge_pack = names.iter().any(|n| n == "manifest.json")
+                && names.iter().any(|n| n.starts_with("overrides/"));
+
+            if names.iter().any(|n| n == "modrinth.index.json") || curseforge_pack {
+                describe_modpack(source)
+            } else if has("level.dat") {
+                let folder = crate::core::minecraft::worlds::import_world_zip(&profile.game_dir, source)?;
+                Ok(ImportOutcome::new("world", Some(folder.clone()), format!("Welt {} importiert", folder)))
+            } else if names.iter().any(|n| n.starts_with("shaders/") || n.contains("/shaders/")) {
+                import_shaderpack(source, &profile.game_dir)
+            } else if has("pack.mcmeta") {
+                import_resourcepack(source, &profile.game_dir)
+            } else {
+                bail!("Unrecognized ZIP archive – no resource pack, shader pack, world or modpack")
+            }
+        }
+        other => bail!("Unsupported file type: .{}", other),
+    }
+}
+
+fn import_mod(source: &Path, profile: &Profile) -> Result<ImportOutcome> {
+    let file_name = file_name_of(source)?;
+    zip::ZipArchive::new(std::fs::File::open(source)?).context("File is not a valid JAR archive")?;
+
+    let loader = &profile.loader.loader;
+    let mut warnings = Vec::new();
+    if !loader.supports_mods() {
+        warnings.push("Das Profil hat keinen Mod-Loader – die Mod wird nicht geladen".to_string());
+    }
+    match crate::core::mods::dependencies::read_mod_metadata(source) {
+        Some(meta) => {
+            if loader.supports_mods() && !meta.loaders.is_empty()
+                && !crate::core::mods::migration::loader_accepts(loader, &meta.loaders)
+            {
+                warnings.push(format!("Mod für {}, Profil nutzt {}", meta.loaders.join("/"), loader));
+            }
+            if let Some(req) = &meta.minecraft_requirement {
+                if crate::core::mods::migration::minecraft_requirement_matches(req, &profile.minecraft_version) == Some(false) {
+                    warnings.push(format!("Benötigt Minecraft {}", req));
+                }
+            }
+        }
+        None => warnings.push("Keine Mod-Metadaten gefunden".to_string()),
+    }
+
+    let mods_dir = profile.game_dir.join("mods");
+    std::fs::create_dir_all(&mods_dir)?;
+    let target = unique_target(&mods_dir, &file_name);
+    std::fs::copy(source, &target)?;
+    tracing::info!("Imported mod {:?} to {:?}", source, target);
+
+    let target_name = target.file_name().unwrap_or_default().to_string_lossy().to_string();
+    Ok(ImportOutcome {
+        warnings,
+        ..ImportOutcome::new("mod", Some(target_name.clone()), format!("Mod {} importiert", target_name))
+    })
+}
+
+fn import_resourcepack(source: &Path, game_dir: &Path) -> Result<ImportOutcome> {
+    let pack = super::packs::import_resourcepack(source, &game_dir.join("resourcepacks"))?;
+    Ok(ImportOutcome {
+        details: Some(serde_json::to_value(&pack)?),
+        ..ImportOutcome::new(
+            "resourcepack",
+            Some(pack.file_name.clone()),
+            format!("Resource Pack {} importiert", pack.file_name),
+        )
+    })
+}
+
+fn import_shaderpack(source: &Path, game_dir: &Path) -> Result<ImportOutcome> {
+    let pack = super::packs::import_shaderpack(source, &game_dir.join("shaderpacks"))?;
+    Ok(ImportOutcome {
+        details: Some(serde_json::to_value(&pack)?),
+        ..ImportOutcome::new(
+            "shaderpack",
+            Some(pack.file_name.clone()),
+            format!("Shader Pack {} importiert", pack.file_name),
+        )
+    })
+}
+
+/// Modpacks legen ein eigenes Profil an (`gui::install_modpack_file`) und werden nicht in das gewählte Profil importiert
+fn describe_modpack(source: &Path) -> Result<ImportOutcome> {
+    let mut archive = zip::ZipArchive::new(std::fs::File::open(source)?)
+        .context("File is not a valid ZIP archive")?;
+
+    let details = archive.by_name("modrinth.index.json").ok().and_then(|mut entry| {
+        let mut content = String::new();
+        entry.read_to_string(&mut content).ok()?;
+        let index: serde_json::Value = serde_json::from_str(&content).ok()?;
+        Some(serde_json::json!({
+            "format": "modrinth",
+            "name": index.get("name"),
+            "version": index.get("versionId"),
+            "dependencies": index.get("dependencies"),
+        }))
+    });
+
+    Ok(ImportOutcome {
+        imported: false,
+        details: Some(details.unwrap_or_else(|| serde_json::json!({ "format": "curseforge" }))),
+        ..ImportOutcome::new("modpack", None, "Modpack erkannt – wird als neues Profil installiert".to_string())
+    })
+}
+
+/// Kopiert einen Welt-Ordner nach saves/ (bei gleichem Namen als "Name (2)")
+fn import_world_folder(source: &Path, game_dir: &Path) -> Result<ImportOutcome> {
+    let folder_name = file_name_of(source)?;
+    let saves_dir = game_dir.join("saves");
+    std::fs::create_dir_all(&saves_dir)?;
+
+    let target = crate::core::packs::unique_dir_target(&saves_dir, &folder_name);
+    copy_dir(source, &target)?;
+    // Eine mitkopierte Sperrdatei würde das Öffnen der Welt blockieren
+    std::fs::remove_file(target.join("session.lock")).ok();
+    tracing::info!("Imported world {:?} to {:?}", source, target);
+
+    let target_name = target.file_name().unwrap_or_default().to_string_lossy().to_string();
+    Ok(ImportOutcome::new("world", Some(target_name.clone()), format!("Welt {} importiert", target_name)))
+}
+
+/// Skins (64x64 bzw. altes 64x32-Format) landen bei den gespeicherten Skins
+fn import_skin(source: &Path) -> Result<ImportOutcome> {
+    let data = std::fs::read(source)?;
+    let (width, height) = png_dimensions(&data).context("File is not a valid PNG image")?;
+    if width != 64 || (height != 64 && height != 32) {
+        bail!("Image is {}x{} – skins must be 64x64 or 64x32", width, height);
+    }
+
+    let skins_dir = crate::config::defaults::skins_dir();
+    std::fs::create_dir_all(&skins_dir)?;
+    let target = unique_target(&skins_dir, &file_name_of(source)?);
+    std::fs::write(&target, &data)?;
+    tracing::info!("Imported skin {:?} to {:?}", source, target);
+
+    let target_name = target.file_name().unwrap_or_default().to_string_lossy().to_string();
+    let mut outcome = ImportOutcome::new("skin", Some(target_name), "Skin gespeichert".to_string());
+    outcome.details = Some(serde_json::json!({ "width": width, "height": height, "legacy": height == 32 }));
+    Ok(outcome)
+}
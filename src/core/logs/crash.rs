@@ -0,0 +1,98 @@
+//! Crash-Reports eines Spielordners: `crash-reports/*.txt` von Minecraft sowie
+//! `hs_err_pid*.log` der JVM (Absturz der Java-VM selbst, z.B. Grafiktreiber).
+
+use anyhow::{Result, bail};
+use serde::Serialize;
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CrashKind {
+    Minecraft,
+    Jvm,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CrashReportInfo {
+    /// Dateiname, für `crash_report_path`
+    pub name: String,
+    pub kind: CrashKind,
+    pub created: chrono::DateTime<chrono::Utc>,
+    pub size: u64,
+    /// `Description:`-Zeile des Reports, z.B. "Rendering overlay"
+    pub description: Option<String>,
+}
+
+fn kind_of(name: &str) -> Option<CrashKind> {
+    if name.starts_with("hs_err_pid") && name.ends_with(".log") {
+        Some(CrashKind::Jvm)
+    } else if name.ends_with(".txt") {
+        Some(CrashKind::Minecraft)
+    } else {
+        None
+    }
+}
+
+fn dir_of(game_dir: &Path, kind: CrashKind) -> PathBuf {
+    match kind {
+        CrashKind::Minecraft => game_dir.join("crash-reports"),
+        CrashKind::Jvm => game_dir.to_path_buf(),
+    }
+}
+
+/// Liest die `Description:`-Zeile aus den ersten Zeilen eines Reports
+fn read_description(path: &Path) -> Option<String> {
+    let file = std::fs::File::open(path).ok()?;
+    std::io::BufReader::new(file)
+        .lines()
+        .take(40)
+        .map_while(|line| line.ok())
+        .find_map(|line| line.strip_prefix("Description:").map(|d| d.trim().to_string()))
+}
+
+fn list_dir(dir: &Path, kind: CrashKind, reports: &mut Vec<CrashReportInfo>) {
+    let Ok(entries) = std::fs::read_dir(dir) else { return };
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if kind_of(&name) != Some(kind) {
+            continue;
+        }
+        let Ok(meta) = entry.metadata() else { continue };
+        if !meta.is_file() {
+            continue;
+        }
+        let created = meta.modified().map(chrono::DateTime::from).unwrap_or_default();
+        reports.push(CrashReportInfo {
+            description: if kind == CrashKind::Minecraft { read_description(&entry.path()) } else { None },
+            name,
+            kind,
+            created,
+            size: meta.len(),
+        });
+    }
+}
+
+/// Alle Crash-Reports, neueste zuerst. Blockierend.
+pub fn list_crash_reports(game_dir: &Path) -> Vec<CrashReportInfo> {
+    let mut reports = Vec::new();
+    list_dir(&dir_of(game_dir, CrashKind::Minecraft), CrashKind::Minecraft, &mut reports);
+    list_dir(&dir_of(game_dir, CrashKind::Jvm), CrashKind::Jvm, &mut reports);
+    reports.sort_by_key(|r| std::cmp::Reverse(r.created));
+    reports
+}
+
+/// Pfad eines Reports aus `list_crash_reports` (nur Dateinamen, kein Pfad-Traversal)
+pub fn crash_report_path(game_dir: &Path, name: &str) -> Result<PathBuf> {
+    if name.contains('/') || name.contains('\\') || name.contains("..") {
+        bail!("Ungültiger Dateiname");
+    }
+    let Some(kind) = kind_of(name) else {
+        bail!("{} ist kein Crash-Report", name);
+    };
+    let path = dir_of(game_dir, kind).join(name);
+    if !path.is_file() {
+        bail!("Crash-Report {} nicht gefunden", name);
+    }
+    Ok(path)
+}
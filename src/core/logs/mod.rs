@@ -0,0 +1,232 @@
+//! Lesen von Spiel-Logs in Abschnitten. Große Logs werden zeilenweise gestreamt, im
+//! Speicher landen nur die angeforderten Zeilen – die GUI lädt weitere Abschnitte nach
+//! oder folgt einer wachsenden latest.log ab der zuletzt gelesenen Byte-Position.
+
+#![allow(dead_code)]
+
+pub mod crash;
+pub mod parse;
+pub mod search;
+
+use anyhow::{Result, bail};
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+/// Standardgröße eines Abschnitts
+pub const DEFAULT_CHUNK_LINES: usize = 1000;
+
+/// Obergrenze pro Abschnitt, damit die Webview nicht hängt
+pub const MAX_CHUNK_LINES: usize = 10_000;
+
+/// Welche Zeilen gelesen werden
+#[derive(Debug, Clone, Copy)]
+pub enum LogWindow {
+    /// `limit` Zeilen ab Zeile `offset` (0-basiert)
+    Range { offset: usize, limit: usize },
+    /// Die letzten `lines` Zeilen
+    Tail { lines: usize },
+    /// Neue Zeilen ab Byte `byte`, das ist der Anfang von Zeile `line`
+    Follow { byte: u64, line: usize, limit: usize },
+}
+
+impl LogWindow {
+    /// Aus den Command-Parametern: `follow_from_byte` vor `tail` vor `offset`/`limit`
+    pub fn from_params(offset: Option<usize>, limit: Option<usize>, tail: Option<usize>, follow_from_byte: Option<u64>) -> Self {
+        let limit = limit.unwrap_or(DEFAULT_CHUNK_LINES);
+        match (follow_from_byte, tail) {
+            (Some(byte), _) => Self::Follow { byte, line: offset.unwrap_or(0), limit },
+            (None, Some(lines)) => Self::Tail { lines },
+            (None, None) => Self::Range { offset: offset.unwrap_or(0), limit },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct LogChunk {
+    pub lines: Vec<String>,
+    /// Zeilennummer (0-basiert) der ersten gelieferten Zeile
+    pub first_line: usize,
+    /// Vollständige Zeilen der Datei bis `end_byte`
+    pub total_lines: usize,
+    /// Position hinter der letzten vollständigen gelesenen Zeile – Startpunkt für `Follow`.
+    /// Eine noch unvollständige letzte Zeile wird mitgeliefert, aber nicht mitgezählt.
+    pub end_byte: u64,
+    /// Weitere Zeilen nach diesem Abschnitt vorhanden (nur bei `Range`/`Follow` mit Limit)
+    pub has_more: bool,
+}
+
+/// Pfad der Log-Datei eines Spielordners: "latest", "debug" oder "file:<name>"
+/// (ältere Logs im logs-Ordner, auch .log.gz)
+pub fn log_file_path(game_dir: &Path, log_type: &str) -> Result<PathBuf> {
+    let logs_dir = game_dir.join("logs");
+    match log_type {
+        "latest" => Ok(logs_dir.join("latest.log")),
+        "debug" => Ok(logs_dir.join("debug.log")),
+        lt if lt.starts_with("file:") => {
+            let filename = &lt["file:".len()..];
+            // Kein Pfad-Traversal
+            if filename.is_empty() || filename.contains('/') || filename.contains('\\') || filename.contains("..") {
+                bail!("Ungültiger Dateiname");
+            }
+            Ok(logs_dir.join(filename))
+        }
+        _ => bail!("Unbekannter Log-Typ"),
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LogFileInfo {
+    pub name: String,
+    /// Wert für `log_type` der Log-Commands (`file:<name>`)
+    pub log_type: String,
+    /// Von Minecraft rotiert und mit gzip gepackt (`2024-01-15-1.log.gz`)
+    pub compressed: bool,
+    /// Größe auf der Platte (bei .gz gepackt)
+    pub size: u64,
+    pub modified: chrono::DateTime<chrono::Utc>,
+    /// Datum der Sitzung aus dem Dateinamen
+    pub session_date: Option<chrono::NaiveDate>,
+}
+
+/// Ältere Logs im logs-Ordner (ohne latest.log/debug.log), neueste zuerst. Blockierend.
+pub fn list_log_files(game_dir: &Path) -> Vec<LogFileInfo> {
+    let Ok(entries) = std::fs::read_dir(game_dir.join("logs")) else { return Vec::new() };
+    let mut files: Vec<LogFileInfo> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let compressed = name.ends_with(".log.gz");
+            if !(compressed || name.ends_with(".log")) || name == "latest.log" || name == "debug.log" {
+                return None;
+            }
+            let meta = entry.metadata().ok().filter(|m| m.is_file())?;
+            Some(LogFileInfo {
+                log_type: format!("file:{}", name),
+                compressed,
+                size: meta.len(),
+                modified: meta.modified().map(chrono::DateTime::from).unwrap_or_default(),
+                session_date: name.get(..10).and_then(|d| chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d").ok()),
+                name,
+            })
+        })
+        .collect();
+    files.sort_by_key(|f| std::cmp::Reverse(f.modified));
+    files
+}
+
+/// Öffnet eine Log-Datei, .gz wird transparent entpackt
+pub fn open_log(path: &Path) -> Result<Box<dyn BufRead + Send>> {
+    let file = std::fs::File::open(path)?;
+    if path.extension().is_some_and(|ext| ext == "gz") {
+        Ok(Box::new(BufReader::new(flate2::read::GzDecoder::new(file))))
+    } else {
+        Ok(Box::new(BufReader::new(file)))
+    }
+}
+
+/// Liest eine Zeile; `None` am Dateiende. Das bool gibt an, ob die Zeile vollständig ist.
+fn next_line(reader: &mut impl BufRead, buffer: &mut Vec<u8>) -> std::io::Result<Option<(String, bool)>> {
+    buffer.clear();
+    if reader.read_until(b'\n', buffer)? == 0 {
+        return Ok(None);
+    }
+    let complete = buffer.last() == Some(&b'\n');
+    let text = String::from_utf8_lossy(buffer);
+    Ok(Some((text.trim_end_matches(['\n', '\r']).to_string(), complete)))
+}
+
+/// Liest einen Abschnitt einer Log-Datei. Blockierend.
+pub fn read_chunk(path: &Path, window: LogWindow) -> Result<LogChunk> {
+    let mut buffer = Vec::new();
+    let mut chunk = LogChunk::default();
+
+    if let LogWindow::Follow { byte, line, limit } = window {
+        let is_gz = path.extension().is_some_and(|ext| ext == "gz");
+        // Kürzer als die letzte Position: Datei wurde neu angelegt (neuer Spielstart)
+        let (byte, line) = if !is_gz && byte > std::fs::metadata(path)?.len() { (0, 0) } else { (byte, line) };
+        let mut reader: Box<dyn BufRead + Send> = if is_gz {
+            let mut reader = open_log(path)?;
+            std::io::copy(&mut (&mut reader).take(byte), &mut std::io::sink())?;
+            reader
+        } else {
+            let mut file = std::fs::File::open(path)?;
+            file.seek(SeekFrom::Start(byte))?;
+            Box::new(BufReader::new(file))
+        };
+        chunk.end_byte = byte;
+        return read_follow(&mut reader, &mut buffer, chunk, line, limit);
+    }
+
+    let mut reader = open_log(path)?;
+    let mut line_no = 0;
+    let limit = match window {
+        LogWindow::Range { offset, limit } => Some((offset, limit.min(MAX_CHUNK_LINES))),
+        _ => None,
+    };
+
+    let tail_lines = match window {
+        LogWindow::Tail { lines } => lines.min(MAX_CHUNK_LINES),
+        _ => 0,
+    };
+    let mut tail: VecDeque<String> = VecDeque::with_capacity(tail_lines);
+
+    while let Some((line, complete)) = next_line(&mut reader, &mut buffer)? {
+        match limit {
+            Some((offset, limit)) => {
+                if line_no >= offset && chunk.lines.len() < limit {
+                    chunk.lines.push(line);
+                } else if line_no >= offset {
+                    chunk.has_more = true;
+                }
+            }
+            None => {
+                if tail.len() == tail_lines {
+                    tail.pop_front();
+                }
+                if tail_lines > 0 {
+                    tail.push_back(line);
+                }
+            }
+        }
+        if complete {
+            chunk.end_byte += buffer.len() as u64;
+            chunk.total_lines += 1;
+        }
+        line_no += 1;
+    }
+
+    match limit {
+        Some((offset, _)) => chunk.first_line = offset,
+        None => {
+            chunk.first_line = line_no - tail.len();
+            chunk.lines = tail.into();
+        }
+    }
+    Ok(chunk)
+}
+
+fn read_follow(
+    reader: &mut impl BufRead,
+    buffer: &mut Vec<u8>,
+    mut chunk: LogChunk,
+    line: usize,
+    limit: usize,
+) -> Result<LogChunk> {
+    let limit = limit.min(MAX_CHUNK_LINES);
+    chunk.first_line = line;
+    chunk.total_lines = line;
+    while let Some((text, complete)) = next_line(reader, buffer)? {
+        if chunk.lines.len() == limit {
+            chunk.has_more = true;
+            break;
+        }
+        chunk.lines.push(text);
+        if complete {
+            chunk.end_byte += buffer.len() as u64;
+            chunk.total_lines += 1;
+        }
+    }
+    Ok(chunk)
+}
@@ -0,0 +1,122 @@
+//! Zerlegt Log-Zeilen in Einträge (Zeit, Thread, Level, Logger, Meldung). Zeilen ohne
+//! eigenen Kopf (Stacktraces, mehrzeilige Meldungen) werden an den vorherigen Eintrag
+//! angehängt. Unterstützte Formate:
+//! - Vanilla/Fabric: `[12:34:56] [Render thread/WARN]: msg`, `[12:34:56] [main/INFO] (FabricLoader) msg`
+//! - Forge/NeoForge: `[01Jan2024 12:34:56.789] [main/INFO] [cpw.mods.modlauncher.Launcher/MODLAUNCHER]: msg`
+//! - Alt: `[12:34:56 INFO]: msg`
+
+use serde::Serialize;
+
+use super::search::{LogLevel, is_continuation};
+use super::LogChunk;
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct LogEntry {
+    /// Zeilennummer (0-basiert) der Kopfzeile
+    pub line: usize,
+    /// Anzahl Zeilen inkl. Fortsetzungszeilen
+    pub line_count: usize,
+    pub time: Option<String>,
+    pub thread: Option<String>,
+    pub level: Option<LogLevel>,
+    pub logger: Option<String>,
+    /// Meldung, bei Stacktraces mehrzeilig
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct LogEntries {
+    pub entries: Vec<LogEntry>,
+    pub first_line: usize,
+    pub total_lines: usize,
+    pub end_byte: u64,
+    pub has_more: bool,
+}
+
+/// `[inhalt]rest` → (inhalt, rest)
+fn take_group(text: &str, open: char, close: char) -> Option<(&str, &str)> {
+    let inner = text.strip_prefix(open)?;
+    let end = inner.find(close)?;
+    Some((&inner[..end], &inner[end + close.len_utf8()..]))
+}
+
+/// Letztes Wort einer Gruppe als Level (`main/INFO`, `12:34:56 INFO`)
+fn group_level(group: &str) -> Option<(LogLevel, &str)> {
+    let split = group.rfind(['/', ' '])?;
+    LogLevel::parse(&group[split + 1..]).map(|level| (level, &group[..split]))
+}
+
+/// Zerlegt eine Kopfzeile; `None` wenn die Zeile keinem bekannten Format entspricht
+pub fn parse_line(line: &str) -> Option<LogEntry> {
+    let mut entry = LogEntry { line_count: 1, ..Default::default() };
+    let mut rest = line;
+    let mut groups = Vec::new();
+
+    // Aufeinanderfolgende [..]-Gruppen bis zum ':' vor der Meldung
+    while let Some((group, after)) = take_group(rest, '[', ']') {
+        groups.push(group);
+        rest = after;
+        if groups.len() == 3 || !rest.trim_start().starts_with('[') {
+            break;
+        }
+        rest = rest.trim_start();
+    }
+    let level_index = groups.iter().position(|g| group_level(g).is_some())?;
+    let (level, before_level) = group_level(groups[level_index])?;
+    entry.level = Some(level);
+
+    if level_index == 0 {
+        // Altes Format: Zeit und Level in einer Gruppe
+        entry.time = Some(before_level.to_string());
+    } else {
+        entry.time = Some(groups[0].to_string());
+        entry.thread = Some(before_level.to_string()).filter(|t| !t.is_empty());
+    }
+
+    if let Some(group) = groups.get(level_index + 1) {
+        entry.logger = group.split('/').next().map(str::to_string).filter(|l| !l.is_empty());
+    } else if let Some((logger, after)) = take_group(rest.trim_start(), '(', ')') {
+        // Fabric: Logger in runden Klammern
+        entry.logger = Some(logger.to_string());
+        rest = after;
+    }
+
+    entry.message = rest.strip_prefix(':').unwrap_or(rest).trim_start().to_string();
+    Some(entry)
+}
+
+/// Fasst Zeilen zu Einträgen zusammen; `first_line` ist die Nummer der ersten Zeile
+pub fn parse_lines(lines: &[String], first_line: usize) -> Vec<LogEntry> {
+    let mut entries: Vec<LogEntry> = Vec::new();
+    for (index, line) in lines.iter().enumerate() {
+        let line_no = first_line + index;
+        if is_continuation(line) {
+            if let Some(last) = entries.last_mut() {
+                last.message.push('\n');
+                last.message.push_str(line);
+                last.line_count += 1;
+                continue;
+            }
+        }
+        let mut entry = parse_line(line).unwrap_or_else(|| LogEntry {
+            line_count: 1,
+            message: line.clone(),
+            ..Default::default()
+        });
+        entry.line = line_no;
+        entries.push(entry);
+    }
+    entries
+}
+
+impl From<LogChunk> for LogEntries {
+    fn from(chunk: LogChunk) -> Self {
+        Self {
+            entries: parse_lines(&chunk.lines, chunk.first_line),
+            first_line: chunk.first_line,
+            total_lines: chunk.total_lines,
+            end_byte: chunk.end_byte,
+            has_more: chunk.has_more,
+        }
+    }
+}
@@ -0,0 +1,123 @@
+//! Suche und Level-Filter in Spiel-Logs, serverseitig – die GUI bekommt nur die Treffer
+//! mit Zeilennummer und springt damit in den passenden Abschnitt (`read_chunk`).
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Obergrenze der gelieferten Treffer
+pub const MAX_MATCHES: usize = 5000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+    Fatal,
+}
+
+impl LogLevel {
+    pub fn parse(text: &str) -> Option<Self> {
+        match text.trim() {
+            "TRACE" => Some(Self::Trace),
+            "DEBUG" => Some(Self::Debug),
+            "INFO" => Some(Self::Info),
+            "WARN" | "WARNING" => Some(Self::Warn),
+            "ERROR" | "SEVERE" => Some(Self::Error),
+            "FATAL" => Some(Self::Fatal),
+            _ => None,
+        }
+    }
+}
+
+/// Level aus dem Zeilenkopf, z.B. `[12:34:56] [Render thread/WARN]: ...` oder
+/// `[01Jan2024 12:34:56.789] [main/INFO] [cpw.mods/]: ...` (ältere Versionen: `[12:34:56 INFO]`)
+pub fn detect_level(line: &str) -> Option<LogLevel> {
+    if !line.starts_with('[') {
+        return None;
+    }
+    let head_end = line.char_indices().nth(200).map_or(line.len(), |(i, _)| i);
+    line[..head_end]
+        .split(']')
+        .filter_map(|part| part.rsplit_once('[').map(|(_, group)| group))
+        .find_map(|group| group.rsplit(['/', ' ']).next().and_then(LogLevel::parse))
+}
+
+/// Zeile ohne eigenen Kopf (Stacktrace, mehrzeilige Meldung) gehört zur vorherigen
+pub fn is_continuation(line: &str) -> bool {
+    !line.starts_with('[')
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LogMatch {
+    /// Zeilennummer (0-basiert), passend zu `LogChunk::first_line`
+    pub line: usize,
+    pub level: Option<LogLevel>,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct LogSearchResult {
+    pub matches: Vec<LogMatch>,
+    pub total_matches: usize,
+    /// Mehr Treffer als geliefert
+    pub truncated: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct LogQuery {
+    pub text: Option<String>,
+    /// Leer = alle Level
+    pub levels: Vec<LogLevel>,
+    pub case_sensitive: bool,
+    pub limit: usize,
+}
+
+/// Durchsucht eine Log-Datei. Fortsetzungszeilen erben das Level der Kopfzeile,
+/// damit Stacktraces beim Filtern nach ERROR mitkommen. Blockierend.
+pub fn search(path: &Path, query: &LogQuery) -> Result<LogSearchResult> {
+    use std::io::BufRead;
+
+    let needle = query.text.as_deref().filter(|t| !t.is_empty()).map(|t| {
+        if query.case_sensitive { t.to_string() } else { t.to_lowercase() }
+    });
+    let limit = if query.limit == 0 { MAX_MATCHES } else { query.limit.min(MAX_MATCHES) };
+
+    let mut result = LogSearchResult::default();
+    let mut current_level = None;
+    let mut buffer = Vec::new();
+    let mut reader = super::open_log(path)?;
+    let mut line_no = 0;
+
+    loop {
+        buffer.clear();
+        if reader.read_until(b'\n', &mut buffer)? == 0 {
+            break;
+        }
+        let text = String::from_utf8_lossy(&buffer);
+        let text = text.trim_end_matches(['\n', '\r']);
+        if !is_continuation(text) {
+            current_level = detect_level(text);
+        }
+
+        let level_ok = query.levels.is_empty() || current_level.is_some_and(|l| query.levels.contains(&l));
+        let text_ok = match &needle {
+            Some(needle) if query.case_sensitive => text.contains(needle.as_str()),
+            Some(needle) => text.to_lowercase().contains(needle.as_str()),
+            None => true,
+        };
+        if level_ok && text_ok {
+            result.total_matches += 1;
+            if result.matches.len() < limit {
+                result.matches.push(LogMatch { line: line_no, level: current_level, text: text.to_string() });
+            }
+        }
+        line_no += 1;
+    }
+
+    result.truncated = result.total_matches > result.matches.len();
+    Ok(result)
+}
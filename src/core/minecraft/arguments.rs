@@ -0,0 +1,156 @@
+//! Argument-Templates aus der Version-JSON (`arguments.game` / `arguments.jvm`, ab 1.13).
+//! Ein Eintrag ist entweder ein fester String oder ein Objekt mit `rules` und `value`;
+//! `${platzhalter}` werden über einen `ArgumentContext` ersetzt.
+
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use super::Rule;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub(super) enum ArgumentValue {
+    Single(String),
+    Many(Vec<String>),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub(super) enum Argument {
+    Plain(String),
+    Conditional {
+        #[serde(default)]
+        rules: Vec<Rule>,
+        value: ArgumentValue,
+    },
+    /// Unbekanntes Format – wird ignoriert statt die ganze Version-JSON zu verwerfen
+    Other(serde_json::Value),
+}
+
+impl Argument {
+    pub fn plain(value: &str) -> Self {
+        Self::Plain(value.to_string())
+    }
+
+    pub fn rules(&self) -> &[Rule] {
+        match self {
+            Self::Conditional { rules, .. } => rules,
+            _ => &[],
+        }
+    }
+
+    /// Rohwerte des Eintrags (noch mit Platzhaltern)
+    pub fn values(&self) -> Vec<&str> {
+        match self {
+            Self::Plain(value) | Self::Conditional { value: ArgumentValue::Single(value), .. } => vec![value],
+            Self::Conditional { value: ArgumentValue::Many(values), .. } => values.iter().map(String::as_str).collect(),
+            Self::Other(_) => Vec::new(),
+        }
+    }
+}
+
+/// Werte für `${platzhalter}`; unbekannte Platzhalter bleiben unverändert stehen
+#[derive(Debug, Clone, Default)]
+pub(super) struct ArgumentContext {
+    values: HashMap<&'static str, String>,
+}
+
+impl ArgumentContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set(mut self, key: &'static str, value: impl Into<String>) -> Self {
+        self.values.insert(key, value.into());
+        self
+    }
+
+    pub fn substitute(&self, template: &str) -> String {
+        let mut out = String::with_capacity(template.len());
+        let mut rest = template;
+        while let Some(start) = rest.find("${") {
+            out.push_str(&rest[..start]);
+            let Some(len) = rest[start..].find('}') else { break };
+            let key = &rest[start + 2..start + len];
+            match self.values.get(key) {
+                Some(value) => out.push_str(value),
+                None => {
+                    tracing::debug!("Unknown launch argument placeholder: ${{{}}}", key);
+                    out.push_str(&rest[start..=start + len]);
+                }
+            }
+            rest = &rest[start + len + 1..];
+        }
+        out.push_str(rest);
+        out
+    }
+}
+
+/// Löst Templates auf: nur Einträge, deren Regeln `allowed` erfüllen. Optionen, deren Wert
+/// leer ersetzt wurde (z.B. `--xuid ${auth_xuid}` ohne Xbox-ID), entfallen ganz.
+pub(super) fn resolve(entries: &[Argument], allowed: impl Fn(&[Rule]) -> bool, ctx: &ArgumentContext) -> Vec<String> {
+    let mut args: Vec<String> = Vec::new();
+    for entry in entries {
+        if !entry.rules().is_empty() && !allowed(entry.rules()) {
+            continue;
+        }
+        for value in entry.values() {
+            let arg = ctx.substitute(value);
+            if arg.is_empty() {
+                if args.last().is_some_and(|prev| prev.starts_with("--")) {
+                    args.pop();
+                }
+                continue;
+            }
+            args.push(arg);
+        }
+    }
+    args
+}
+
+/// Hängt Optionen aus `template` an, die in `existing` noch fehlen (jeweils mit ihren Werten).
+/// Für Loader, deren eigene Argumente die Vanilla-Argumente nur teilweise enthalten.
+pub(super) fn missing_options(existing: &[String], template: &[String]) -> Vec<String> {
+    let mut missing = Vec::new();
+    let mut include = false;
+    for arg in template {
+        if arg.starts_with("--") {
+            include = !existing.contains(arg);
+        }
+        if include {
+            missing.push(arg.clone());
+        }
+    }
+    missing
+}
+
+/// JVM-Argumente für Versionen ohne `arguments` (vor 1.13)
+pub(super) fn default_jvm_arguments() -> Vec<Argument> {
+    [
+        "-Djava.library.path=${natives_directory}",
+        "-Dminecraft.launcher.brand=${launcher_name}",
+        "-Dminecraft.launcher.version=${launcher_version}",
+        "-cp",
+        "${classpath}",
+    ]
+    .into_iter()
+    .map(Argument::plain)
+    .collect()
+}
+
+/// Game-Argumente für Versionen ohne `arguments` (vor 1.13)
+pub(super) fn default_game_arguments() -> Vec<Argument> {
+    [
+        "--username", "${auth_player_name}",
+        "--version", "${version_name}",
+        "--gameDir", "${game_directory}",
+        "--assetsDir", "${assets_root}",
+        "--assetIndex", "${assets_index_name}",
+        "--uuid", "${auth_uuid}",
+        "--accessToken", "${auth_access_token}",
+        "--userType", "${user_type}",
+    ]
+    .into_iter()
+    .map(Argument::plain)
+    .collect()
+}
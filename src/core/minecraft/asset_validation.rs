@@ -0,0 +1,97 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+// Validierungs-Index für Assets: merkt sich pro Hash Größe und mtime der zuletzt
+// geprüften Datei. Ist der Index vollständig und frisch, entfällt beim Start das
+// Prüfen tausender Einzeldateien.
+
+/// Nach dieser Zeit wird trotz vollständigem Index wieder jede Datei geprüft
+const MAX_INDEX_AGE_SECS: u64 = 7 * 24 * 60 * 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct VerifiedAsset {
+    pub size: u64,
+    pub mtime: u64,
+}
+
+impl VerifiedAsset {
+    fn from_file(path: &Path) -> Option<Self> {
+        let meta = std::fs::metadata(path).ok()?;
+        let mtime = meta.modified().ok()?.duration_since(UNIX_EPOCH).ok()?.as_secs();
+        Some(Self { size: meta.len(), mtime })
+    }
+}
+
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+pub struct AssetValidationIndex {
+    /// SHA-1 des Asset-Index auf den sich die Einträge beziehen
+    pub index_sha1: String,
+    pub verified_at: u64,
+    pub entries: HashMap<String, VerifiedAsset>,
+}
+
+impl AssetValidationIndex {
+    pub fn path(assets_dir: &Path, index_id: &str) -> PathBuf {
+        assets_dir.join("indexes").join(format!("{}.validated.json", index_id))
+    }
+
+    /// Lädt den Index; gehört er zu einem anderen Asset-Index, wird neu begonnen
+    pub fn load(path: &Path, index_sha1: &str) -> Self {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<Self>(&content).ok())
+            .filter(|index| index.index_sha1 == index_sha1)
+            .unwrap_or_else(|| Self { index_sha1: index_sha1.to_string(), ..Default::default() })
+    }
+
+    pub fn save(&mut self, path: &Path) {
+        self.verified_at = chrono::Utc::now().timestamp().max(0) as u64;
+        match serde_json::to_string(self) {
+            Ok(json) => {
+                if let Err(e) = std::fs::write(path, json) {
+                    tracing::warn!("Failed to save asset validation index: {}", e);
+                }
+            }
+            Err(e) => tracing::warn!("Failed to serialize asset validation index: {}", e),
+        }
+    }
+
+    fn is_stale(&self) -> bool {
+        let now = chrono::Utc::now().timestamp().max(0) as u64;
+        now.saturating_sub(self.verified_at) > MAX_INDEX_AGE_SECS
+    }
+
+    /// true wenn alle Hashes verifiziert sind und der Index nicht veraltet ist
+    pub fn covers<'a>(&self, hashes: impl IntoIterator<Item = &'a String>) -> bool {
+        !self.is_stale() && hashes.into_iter().all(|hash| self.entries.contains_key(hash))
+    }
+
+    /// Prüft eine vorhandene Datei gegen den Index bzw. die erwartete Größe
+    /// und merkt sie sich bei Erfolg. `false` bedeutet: neu herunterladen.
+    pub fn check(&mut self, hash: &str, path: &Path, expected_size: Option<u64>) -> bool {
+        let Some(current) = VerifiedAsset::from_file(path) else {
+            self.entries.remove(hash);
+            return false;
+        };
+
+        let valid = match self.entries.get(hash) {
+            Some(known) => *known == current,
+            None => expected_size.is_none_or(|size| size == current.size),
+        };
+
+        if valid {
+            self.entries.insert(hash.to_string(), current);
+        } else {
+            self.entries.remove(hash);
+        }
+        valid
+    }
+
+    /// Merkt sich eine frisch heruntergeladene (bereits per Hash geprüfte) Datei
+    pub fn record(&mut self, hash: &str, path: &Path) {
+        if let Some(current) = VerifiedAsset::from_file(path) {
+            self.entries.insert(hash.to_string(), current);
+        }
+    }
+}
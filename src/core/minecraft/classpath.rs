@@ -0,0 +1,158 @@
+//! Aufbau des Java-Classpaths für alle Launch-Pfade (Vanilla, Fabric, Quilt, Forge,
+//! NeoForge). Der Trenner hängt vom Betriebssystem ab: `;` unter Windows, sonst `:`.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClasspathPlatform {
+    Windows,
+    Unix,
+}
+
+impl ClasspathPlatform {
+    pub fn current() -> Self {
+        if cfg!(windows) { Self::Windows } else { Self::Unix }
+    }
+
+    pub fn separator(self) -> &'static str {
+        match self {
+            Self::Windows => ";",
+            Self::Unix => ":",
+        }
+    }
+}
+
+/// Sammelt Classpath-Einträge in Reihenfolge, ohne leere Einträge und Duplikate
+#[derive(Debug, Clone)]
+pub struct ClasspathBuilder {
+    platform: ClasspathPlatform,
+    entries: Vec<String>,
+    seen: HashSet<String>,
+}
+
+impl Default for ClasspathBuilder {
+    fn default() -> Self {
+        Self::for_platform(ClasspathPlatform::current())
+    }
+}
+
+impl ClasspathBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn for_platform(platform: ClasspathPlatform) -> Self {
+        Self { platform, entries: Vec::new(), seen: HashSet::new() }
+    }
+
+    pub fn from_entries<T: AsRef<str>>(entries: impl IntoIterator<Item = T>) -> Self {
+        let mut builder = Self::new();
+        builder.extend(entries);
+        builder
+    }
+
+    /// Trenner des aktuellen Betriebssystems (auch für `${classpath_separator}`)
+    pub fn separator() -> &'static str {
+        ClasspathPlatform::current().separator()
+    }
+
+    /// Zerlegt einen Classpath-String des aktuellen Betriebssystems
+    pub fn split(classpath: &str) -> Vec<String> {
+        Self::split_for(ClasspathPlatform::current(), classpath)
+    }
+
+    pub fn split_for(platform: ClasspathPlatform, classpath: &str) -> Vec<String> {
+        classpath
+            .split(platform.separator())
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(String::from)
+            .collect()
+    }
+
+    /// Fügt einen Eintrag hinzu; `false` bei leerem oder bereits vorhandenem Eintrag
+    pub fn push(&mut self, entry: impl AsRef<str>) -> bool {
+        let entry = entry.as_ref().trim();
+        if entry.is_empty() || !self.seen.insert(entry.to_string()) {
+            return false;
+        }
+        self.entries.push(entry.to_string());
+        true
+    }
+
+    pub fn push_path(&mut self, path: &Path) -> bool {
+        self.push(path.display().to_string())
+    }
+
+    pub fn extend<T: AsRef<str>>(&mut self, entries: impl IntoIterator<Item = T>) {
+        for entry in entries {
+            self.push(entry);
+        }
+    }
+
+    /// Übernimmt alle Einträge eines Classpath-Strings
+    pub fn extend_classpath(&mut self, classpath: &str) {
+        self.extend(Self::split_for(self.platform, classpath));
+    }
+
+    pub fn contains(&self, entry: &str) -> bool {
+        self.seen.contains(entry)
+    }
+
+    pub fn entries(&self) -> &[String] {
+        &self.entries
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn build(&self) -> String {
+        self.entries.join(self.platform.separator())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn windows_uses_semicolon() {
+        let mut builder = ClasspathBuilder::for_platform(ClasspathPlatform::Windows);
+        builder.extend(["C:\\libs\\a.jar", "C:\\libs\\b.jar"]);
+        assert_eq!(builder.build(), "C:\\libs\\a.jar;C:\\libs\\b.jar");
+        assert_eq!(
+            ClasspathBuilder::split_for(ClasspathPlatform::Windows, "C:\\libs\\a.jar;C:\\libs\\b.jar"),
+            vec!["C:\\libs\\a.jar", "C:\\libs\\b.jar"]
+        );
+    }
+
+    #[test]
+    fn unix_uses_colon() {
+        let mut builder = ClasspathBuilder::for_platform(ClasspathPlatform::Unix);
+        builder.extend(["/libs/a.jar", "/libs/b.jar"]);
+        assert_eq!(builder.build(), "/libs/a.jar:/libs/b.jar");
+        assert_eq!(
+            ClasspathBuilder::split_for(ClasspathPlatform::Unix, "/libs/a.jar::/libs/b.jar"),
+            vec!["/libs/a.jar", "/libs/b.jar"]
+        );
+    }
+
+    #[test]
+    fn skips_duplicates_and_empty_entries() {
+        let mut builder = ClasspathBuilder::for_platform(ClasspathPlatform::Unix);
+        assert!(builder.push("/libs/a.jar"));
+        assert!(!builder.push("/libs/a.jar"));
+        assert!(!builder.push(" /libs/a.jar "));
+        assert!(!builder.push("   "));
+        builder.extend_classpath("/libs/b.jar:/libs/a.jar:/libs/c.jar");
+        assert_eq!(builder.entries(), ["/libs/a.jar", "/libs/b.jar", "/libs/c.jar"]);
+        assert!(builder.contains("/libs/b.jar"));
+        assert_eq!(builder.len(), 3);
+    }
+}
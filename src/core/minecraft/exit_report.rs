@@ -0,0 +1,128 @@
+//! Auswertung eines unerwarteten Spielendes: Exit-Code, neuer Crash-Report, Laufzeit
+//! und daraus abgeleitete Vorschläge für den Dialog der Oberfläche.
+
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::Mutex;
+
+/// Kürzer gelaufen: das Spiel ist vermutlich gar nicht erst gestartet
+const EARLY_EXIT_SECS: u64 = 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExitAction {
+    Relaunch,
+    OpenCrashReport,
+    OpenLog,
+    RunDiagnostics,
+    IncreaseMemory,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GameExitReport {
+    pub profile_id: String,
+    pub exit_code: Option<i32>,
+    /// Während der Sitzung wurde ein Crash-Report (oder hs_err der JVM) geschrieben
+    pub crash_detected: bool,
+    /// Name für `get_crash_report`
+    pub crash_report: Option<String>,
+    pub uptime_secs: u64,
+    pub suggested_actions: Vec<ExitAction>,
+}
+
+static LAST_REPORTS: Mutex<Option<HashMap<String, GameExitReport>>> = Mutex::new(None);
+
+/// Vom Nutzer beendete Instanzen – deren Ende ist kein Absturz
+static STOPPED_BY_USER: Mutex<Option<HashSet<String>>> = Mutex::new(None);
+
+pub fn mark_stopped_by_user(profile_id: &str) {
+    if let Ok(mut stopped) = STOPPED_BY_USER.lock() {
+        stopped.get_or_insert_with(HashSet::new).insert(profile_id.to_string());
+    }
+}
+
+fn take_stopped_by_user(profile_id: &str) -> bool {
+    STOPPED_BY_USER.lock()
+        .map(|mut stopped| stopped.as_mut().is_some_and(|s| s.remove(profile_id)))
+        .unwrap_or(false)
+}
+
+/// Entfernt die Stopp-Markierung beim Drop, auch wenn `evaluate` nie aufgerufen wurde
+/// (z.B. weil das Speichern der Sitzung fehlschlug) – sonst gälte der nächste Absturz
+/// fälschlich als vom Nutzer beendet.
+pub struct StoppedByUserGuard(String);
+
+impl StoppedByUserGuard {
+    pub fn new(profile_id: &str) -> Self {
+        Self(profile_id.to_string())
+    }
+}
+
+impl Drop for StoppedByUserGuard {
+    fn drop(&mut self) {
+        take_stopped_by_user(&self.0);
+    }
+}
+
+/// Letzter Bericht eines Profils (für die GUI, falls sie das Event verpasst hat)
+pub fn last_report(profile_id: &str) -> Option<GameExitReport> {
+    LAST_REPORTS.lock().ok()?.as_ref()?.get(profile_id).cloned()
+}
+
+/// Prüft die letzten Zeilen der latest.log auf Speichermangel
+fn ran_out_of_memory(game_dir: &Path) -> bool {
+    let path = game_dir.join("logs").join("latest.log");
+    crate::core::logs::read_chunk(&path, crate::core::logs::LogWindow::Tail { lines: 300 })
+        .map(|chunk| chunk.lines.iter().any(|l| l.contains("java.lang.OutOfMemoryError")))
+        .unwrap_or(false)
+}
+
+/// Erstellt den Bericht, wenn das Spiel unerwartet beendet wurde (Exit-Code ≠ 0 oder
+/// neuer Crash-Report). `None` bei normalem Beenden oder Stopp durch den Nutzer.
+pub fn evaluate(
+    profile_id: &str,
+    game_dir: &Path,
+    started_at: chrono::DateTime<chrono::Utc>,
+    uptime_secs: u64,
+    exit_code: Option<i32>,
+) -> Option<GameExitReport> {
+    if take_stopped_by_user(profile_id) {
+        return None;
+    }
+
+    let crash_report = crate::core::logs::crash::list_crash_reports(game_dir)
+        .into_iter()
+        .find(|r| r.created >= started_at)
+        .map(|r| r.name);
+    if exit_code == Some(0) && crash_report.is_none() {
+        return None;
+    }
+
+    let mut actions = vec![ExitAction::Relaunch];
+    if crash_report.is_some() {
+        actions.push(ExitAction::OpenCrashReport);
+    }
+    actions.push(ExitAction::OpenLog);
+    if ran_out_of_memory(game_dir) {
+        actions.push(ExitAction::IncreaseMemory);
+    }
+    if uptime_secs < EARLY_EXIT_SECS || crash_report.is_some() {
+        actions.push(ExitAction::RunDiagnostics);
+    }
+
+    let report = GameExitReport {
+        profile_id: profile_id.to_string(),
+        exit_code,
+        crash_detected: crash_report.is_some(),
+        crash_report,
+        uptime_secs,
+        suggested_actions: actions,
+    };
+    tracing::warn!("Abnormal game exit for {}: code {:?}, crash report {:?}", profile_id, exit_code, report.crash_report);
+
+    if let Ok(mut reports) = LAST_REPORTS.lock() {
+        reports.get_or_insert_with(HashMap::new).insert(profile_id.to_string(), report.clone());
+    }
+    Some(report)
+}
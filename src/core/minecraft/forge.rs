@@ -10,6 +10,9 @@ use anyhow::{Result, bail};
 use std::path::{Path, PathBuf};
 use std::io::Read;
 use crate::core::download::DownloadManager;
+use crate::utils::compression::ExtractionTracker;
+use super::install_cache;
+use super::classpath::ClasspathBuilder;
 
 #[derive(serde::Deserialize, Debug, Clone)]
 struct Processor {
@@ -26,6 +29,7 @@ struct SidedData {
     server: String,
 }
 
+#[derive(serde::Serialize, serde::Deserialize)]
 pub struct ForgeInstallResult {
     pub main_class: String,
     pub bootstrap_classpath: Vec<String>,
@@ -62,6 +66,13 @@ impl ForgeInstaller {
 
         tracing::info!("=== Forge Install: {}-{} ===", mc_version, forge_version);
 
+        // Bereits aufgelöste Installation wiederverwenden (solange alle Dateien noch existieren)
+        let cache_path = install_cache::cache_path(libraries_dir, "forge", mc_version, forge_version);
+        if let Some(cached) = install_cache::load(&cache_path, Self::referenced_files) {
+            tracing::info!("Using cached Forge install result for {}-{}", mc_version, forge_version);
+            return Ok(cached);
+        }
+
         // Alte Forge-Versionen für diese MC-Version aufräumen
         Self::cleanup_old_forge_versions(mc_version, forge_version, libraries_dir).await;
 
@@ -404,7 +415,7 @@ impl ForgeInstaller {
             bootstrap_classpath.insert(0, patched_str);
         }
 
-        Ok(ForgeInstallResult {
+        let result = ForgeInstallResult {
             main_class: version_json.main_class,
             bootstrap_classpath,
             native_jars,
@@ -416,7 +427,19 @@ impl ForgeInstaller {
             is_bootstrap,
             patched_client_jar,
             minecraft_arguments: version_json.minecraft_arguments,
-        })
+        };
+        install_cache::store(&cache_path, &result);
+        Ok(result)
+    }
+
+    /// Dateien auf die ein (gecachtes) Installationsergebnis verweist
+    fn referenced_files(result: &ForgeInstallResult) -> Vec<PathBuf> {
+        result.bootstrap_classpath.iter()
+            .chain(&result.classpath)
+            .chain(&result.native_jars)
+            .map(PathBuf::from)
+            .chain(std::iter::once(result.patched_client_jar.clone()))
+            .collect()
     }
 
     /// Liest version.json und install_profile.json aus dem Installer.
@@ -429,6 +452,32 @@ impl ForgeInstaller {
         installer_data_dir: &Path,
         libraries_dir: &Path,
     ) -> Result<(String, String)> {
+        let (installer_path, installer_data_dir, libraries_dir) = (
+            installer_path.to_path_buf(),
+            installer_data_dir.to_path_buf(),
+            libraries_dir.to_path_buf(),
+        );
+        let (version_json, install_profile, to_extract) = crate::utils::compression::run_blocking(move || {
+            Self::read_installer_archive(&installer_path, &installer_data_dir, &libraries_dir)
+        }).await?;
+
+        for (dest, data) in to_extract {
+            if let Some(parent) = dest.parent() {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+            tokio::fs::write(&dest, &data).await?;
+        }
+
+        Ok((version_json, install_profile))
+    }
+
+    /// Synchroner Teil von `read_installer_contents` (läuft auf einem Blocking-Thread)
+    #[allow(clippy::type_complexity)]
+    fn read_installer_archive(
+        installer_path: &Path,
+        installer_data_dir: &Path,
+        libraries_dir: &Path,
+    ) -> Result<(String, String, Vec<(PathBuf, Vec<u8>)>)> {
         let file = std::fs::File::open(installer_path)?;
         let mut archive = zip::ZipArchive::new(file)?;
 
@@ -469,9 +518,11 @@ impl ForgeInstaller {
 
         // Embedded JARs und Datendateien extrahieren
         let mut to_extract: Vec<(PathBuf, Vec<u8>)> = Vec::new();
+        let mut tracker = ExtractionTracker::for_archive(installer_path, &mut archive);
 
         for i in 0..archive.len() {
             if let Ok(mut entry) = archive.by_index(i) {
+                tracker.advance(entry.size());
                 let name = entry.name().to_string();
                 if name.ends_with('/') { continue; }
 
@@ -497,14 +548,9 @@ impl ForgeInstaller {
             }
         }
 
-        for (dest, data) in to_extract {
-            if let Some(parent) = dest.parent() {
-                tokio::fs::create_dir_all(parent).await?;
-            }
-            tokio::fs::write(&dest, &data).await?;
-        }
-
-        Ok((version_json, install_profile))
+        tracker.finish();
+        tracing::info!("Forge installer: {} embedded files read", to_extract.len());
+        Ok((version_json, install_profile, to_extract))
     }
 
     /// Führt die Forge-Installer-Prozessoren aus.
@@ -732,9 +778,8 @@ impl ForgeInstaller {
             tracing::info!("Prozessor: {} → {}", proc.jar, main_class);
             tracing::info!("Argumente: {:?}", resolved_args);
 
-            let cp_sep = if cfg!(windows) { ";" } else { ":" };
             let out = tokio::process::Command::new(&java)
-                .arg("-cp").arg(proc_cp.join(cp_sep))
+                .arg("-cp").arg(ClasspathBuilder::from_entries(&proc_cp).build())
                 .arg(&main_class)
                 .args(&resolved_args)
                 .output().await;
@@ -1184,7 +1229,7 @@ pub fn resolve_arg_placeholders(
 ) -> String {
     arg
         .replace("${library_directory}", &libraries_dir.display().to_string())
-        .replace("${classpath_separator}", if cfg!(windows) { ";" } else { ":" })
+        .replace("${classpath_separator}", ClasspathBuilder::separator())
         .replace("${version_name}", mc_version)
         .replace("${launcher_name}", "lion-launcher")
         .replace("${launcher_version}", env!("CARGO_PKG_VERSION"))
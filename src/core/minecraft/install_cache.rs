@@ -0,0 +1,78 @@
+use serde::{de::DeserializeOwned, Serialize};
+use std::path::{Path, PathBuf};
+
+// Zwischenspeicher für aufgelöste Forge/NeoForge-Installationen (Classpath, Modulpfad,
+// Argumente) pro Loader-Version. Spart beim Start das erneute Parsen des Installers
+// und das Prüfen jeder einzelnen Library.
+
+fn cache_dir(libraries_dir: &Path) -> PathBuf {
+    libraries_dir.join("install-cache")
+}
+
+pub fn cache_path(libraries_dir: &Path, loader: &str, mc_version: &str, loader_version: &str) -> PathBuf {
+    let key: String = format!("{}-{}-{}", loader, mc_version, loader_version)
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '.' || c == '-' { c } else { '_' })
+        .collect();
+    cache_dir(libraries_dir).join(format!("{}.json", key))
+}
+
+/// Lädt ein gecachtes Ergebnis, sofern alle referenzierten Dateien noch existieren
+pub fn load<T: DeserializeOwned>(path: &Path, referenced_files: impl Fn(&T) -> Vec<PathBuf>) -> Option<T> {
+    let content = std::fs::read_to_string(path).ok()?;
+    let value: T = serde_json::from_str(&content).ok()?;
+
+    if let Some(missing) = referenced_files(&value).into_iter().find(|p| !p.exists()) {
+        tracing::info!("Install cache {:?} is stale ({:?} missing)", path.file_name().unwrap_or_default(), missing);
+        std::fs::remove_file(path).ok();
+        return None;
+    }
+    Some(value)
+}
+
+pub fn store<T: Serialize>(path: &Path, value: &T) {
+    let result = (|| -> anyhow::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        // Über Temp-Datei schreiben, damit parallele Starts nie einen halben Eintrag lesen
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_string_pretty(value)?)?;
+        std::fs::rename(&tmp, path)?;
+        Ok(())
+    })();
+    if let Err(e) = result {
+        tracing::warn!("Could not write install cache {:?}: {}", path, e);
+    }
+}
+
+/// Gecachte Installationen; mit `loader`/`mc_version` nur die passenden Einträge
+pub fn entries(libraries_dir: &Path, loader: Option<&str>, mc_version: Option<&str>) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(cache_dir(libraries_dir)) else {
+        return Vec::new();
+    };
+
+    let prefix = match (loader, mc_version) {
+        (Some(loader), Some(mc)) => format!("{}-{}-", loader, mc),
+        (Some(loader), None) => format!("{}-", loader),
+        _ => String::new(),
+    };
+
+    entries
+        .flatten()
+        .filter(|e| {
+            let name = e.file_name().to_string_lossy().to_string();
+            name.starts_with(&prefix) && name.ends_with(".json")
+        })
+        .map(|e| e.path())
+        .collect()
+}
+
+/// Entfernt gecachte Installationen; mit `loader`/`mc_version` nur die passenden Einträge.
+/// Gibt die Anzahl entfernter Einträge zurück.
+pub fn clear(libraries_dir: &Path, loader: Option<&str>, mc_version: Option<&str>) -> usize {
+    entries(libraries_dir, loader, mc_version)
+        .into_iter()
+        .filter(|path| std::fs::remove_file(path).is_ok())
+        .count()
+}
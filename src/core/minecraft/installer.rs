@@ -4,6 +4,7 @@ use anyhow::{Result, bail};
 use serde::Deserialize;
 use std::path::Path;
 use crate::core::download::DownloadManager;
+use super::classpath::ClasspathBuilder;
 
 /// Forge/NeoForge Installer Handler
 /// Unterstützt beide Loader-Typen mit einheitlicher Schnittstelle
@@ -28,7 +29,8 @@ impl ForgeInstaller {
         tracing::info!("Processing Forge installer: {:?}", installer_jar);
 
         // Entpacke install_profile.json und version.json aus dem Installer
-        let profile = self.extract_install_profile(installer_jar)?;
+        let installer = installer_jar.to_path_buf();
+        let profile = crate::utils::compression::run_blocking(move || Self::extract_install_profile(&installer)).await?;
 
         // Lade alle Libraries aus dem Profil
         let mut classpath_entries = Vec::new();
@@ -72,11 +74,9 @@ impl ForgeInstaller {
             classpath_entries.push(lib_dest.display().to_string());
         }
 
-        let cp_sep = if cfg!(windows) { ";" } else { ":" };
-
         Ok(ForgeInstallation {
             main_class: profile.version_info.main_class,
-            classpath: classpath_entries.join(cp_sep),
+            classpath: ClasspathBuilder::from_entries(classpath_entries).build(),
             minecraft_arguments: profile.version_info.minecraft_arguments,
         })
     }
@@ -94,7 +94,7 @@ impl ForgeInstaller {
         self.install_forge(installer_jar, libraries_dir, mc_version).await
     }
 
-    fn extract_install_profile(&self, installer_jar: &Path) -> Result<ForgeInstallProfile> {
+    fn extract_install_profile(installer_jar: &Path) -> Result<ForgeInstallProfile> {
         let file = std::fs::File::open(installer_jar)?;
         let mut archive = zip::ZipArchive::new(file)?;
 
@@ -0,0 +1,157 @@
+//! Prüfung eigener JVM-Argumente eines Profils. Liefert Warnungen statt Fehler, damit
+//! Nutzer erfahren, warum ein Start scheitern würde, bevor Java mit einer kryptischen
+//! Meldung abbricht.
+
+use crate::types::profile::GarbageCollector;
+
+/// Flags, die in aktuellen Java-Versionen entfernt wurden und den Start abbrechen
+const REMOVED_FLAGS: &[(&str, &str)] = &[
+    ("-XX:PermSize", "seit Java 8 entfernt"),
+    ("-XX:MaxPermSize", "seit Java 8 entfernt"),
+    ("-XX:+AggressiveOpts", "seit Java 11 entfernt"),
+    ("-XX:+UseConcMarkSweepGC", "CMS wurde mit Java 14 entfernt"),
+    ("-XX:+CMSIncrementalMode", "CMS wurde mit Java 14 entfernt"),
+    ("-XX:+CMSClassUnloadingEnabled", "CMS wurde mit Java 14 entfernt"),
+    ("-Xincgc", "seit Java 9 entfernt"),
+    ("-d64", "seit Java 10 entfernt"),
+    ("-d32", "seit Java 10 entfernt"),
+];
+
+/// Argumente, die der Launcher selbst setzt und die nicht überschrieben werden dürfen
+const LAUNCHER_CONTROLLED: &[&str] = &["-cp", "-classpath", "--class-path", "-jar", "-Djava.library.path"];
+
+/// GC-Auswahl über eigene Argumente (kollidiert mit der GC-Einstellung des Profils)
+const GC_FLAGS: &[(&str, Option<GarbageCollector>)] = &[
+    ("-XX:+UseG1GC", Some(GarbageCollector::G1)),
+    ("-XX:+UseZGC", Some(GarbageCollector::Zgc)),
+    ("-XX:+UseShenandoahGC", Some(GarbageCollector::Shenandoah)),
+    ("-XX:+UseParallelGC", None),
+    ("-XX:+UseSerialGC", None),
+];
+
+/// Speichergröße wie bei -Xmx ("4G", "512m", "1048576") in Megabyte
+fn parse_memory_mb(value: &str) -> Option<u64> {
+    let value = value.trim();
+    let (digits, unit) = match value.char_indices().last()? {
+        (i, c) if c.is_ascii_alphabetic() => (&value[..i], c.to_ascii_lowercase()),
+        _ => (value, 'b'),
+    };
+    let amount: u64 = digits.parse().ok()?;
+    match unit {
+        'b' => Some(amount / (1024 * 1024)),
+        'k' => Some(amount / 1024),
+        'm' => Some(amount),
+        'g' => Some(amount * 1024),
+        't' => Some(amount * 1024 * 1024),
+        _ => None,
+    }
+}
+
+/// Prüft die Syntax eines einzelnen Arguments
+fn syntax_warning(arg: &str) -> Option<String> {
+    if arg.trim().is_empty() {
+        return Some("Leeres Argument".to_string());
+    }
+    if arg != arg.trim() {
+        return Some(format!("'{}' enthält führende oder folgende Leerzeichen", arg));
+    }
+    if !arg.starts_with('-') {
+        return Some(format!("'{}' ist kein JVM-Argument (muss mit '-' beginnen)", arg));
+    }
+    if arg.contains(" -") {
+        return Some(format!("'{}' enthält mehrere Argumente – bitte einzeln eintragen", arg));
+    }
+    if let Some(option) = arg.strip_prefix("-XX:") {
+        let valid = match option.chars().next() {
+            Some('+') | Some('-') => option.len() > 1 && !option.contains('='),
+            Some(_) => option.contains('=') && !option.ends_with('='),
+            None => false,
+        };
+        if !valid {
+            return Some(format!("'{}': erwartet -XX:+Name, -XX:-Name oder -XX:Name=Wert", arg));
+        }
+    }
+    if let Some(property) = arg.strip_prefix("-D") {
+        if property.is_empty() || property.starts_with('=') {
+            return Some(format!("'{}': Systemeigenschaft ohne Namen", arg));
+        }
+    }
+    for prefix in ["-Xmx", "-Xms", "-Xss", "-Xmn"] {
+        if let Some(size) = arg.strip_prefix(prefix) {
+            if parse_memory_mb(size).is_none() {
+                return Some(format!("'{}': ungültige Größenangabe (z.B. {}4G)", arg, prefix));
+            }
+        }
+    }
+    None
+}
+
+/// Prüft die eigenen JVM-Argumente eines Profils.
+/// `memory_mb` ist die Speicher-Einstellung des Profils, aus der der Launcher -Xmx/-Xms setzt.
+pub fn lint_java_args(args: &[String], memory_mb: Option<u32>, gc: GarbageCollector) -> Vec<String> {
+    let mut warnings = Vec::new();
+    let mut seen: Vec<&str> = Vec::new();
+    let mut xmx: Option<u64> = None;
+    let mut xms: Option<u64> = None;
+
+    for arg in args {
+        if let Some(warning) = syntax_warning(arg) {
+            warnings.push(warning);
+            continue;
+        }
+
+        if seen.contains(&arg.as_str()) {
+            warnings.push(format!("'{}' ist doppelt angegeben", arg));
+        }
+        seen.push(arg);
+
+        if let Some((_, reason)) = REMOVED_FLAGS.iter().find(|(flag, _)| arg == flag || arg.starts_with(&format!("{}=", flag))) {
+            warnings.push(format!("'{}' wird nicht mehr unterstützt ({}) – Java startet damit nicht", arg, reason));
+        }
+        let name = arg.split('=').next().unwrap_or(arg);
+        if LAUNCHER_CONTROLLED.contains(&name) {
+            warnings.push(format!("'{}' wird vom Launcher gesetzt und darf nicht überschrieben werden", name));
+        }
+        if let Some((_, collector)) = GC_FLAGS.iter().find(|(flag, _)| arg == flag) {
+            match collector {
+                Some(c) if *c == gc => warnings.push(format!("'{}' ist überflüssig – bereits über die GC-Einstellung gesetzt", arg)),
+                _ => warnings.push(format!(
+                    "'{}' kollidiert mit der GC-Einstellung ({}) – Java bricht bei mehreren Collectors ab",
+                    arg, gc.as_str()
+                )),
+            }
+        }
+
+        if let Some(size) = arg.strip_prefix("-Xmx") {
+            if xmx.is_some() {
+                warnings.push("-Xmx ist mehrfach angegeben".to_string());
+            }
+            xmx = parse_memory_mb(size);
+        }
+        if let Some(size) = arg.strip_prefix("-Xms") {
+            if xms.is_some() {
+                warnings.push("-Xms ist mehrfach angegeben".to_string());
+            }
+            xms = parse_memory_mb(size);
+        }
+    }
+
+    // Der Launcher setzt -Xmx/-Xms selbst aus der Speicher-Einstellung (Standard 4096 MB)
+    let configured = memory_mb.unwrap_or(4096) as u64;
+    if let Some(max) = xmx {
+        if max != configured {
+            warnings.push(format!(
+                "-Xmx ({} MB) widerspricht der Speicher-Einstellung ({} MB) – bitte dort ändern",
+                max, configured
+            ));
+        }
+    }
+    if let Some(min) = xms {
+        let max = xmx.unwrap_or(configured);
+        if min > max {
+            warnings.push(format!("-Xms ({} MB) ist größer als der maximale Speicher ({} MB)", min, max));
+        }
+    }
+
+    warnings
+}
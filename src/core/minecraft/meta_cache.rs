@@ -0,0 +1,141 @@
+//! Cache für Mojang-Metadaten (Versions-Manifest und Version-JSONs) unter
+//! `launcher_dir()/meta`. Das Manifest wird nach Ablauf der TTL per ETag neu geprüft,
+//! Version-JSONs sind über ihre SHA-1 aus dem Manifest unveränderlich. Ohne Netz wird
+//! die zuletzt geladene Fassung verwendet.
+
+use anyhow::{Result, bail};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::config::defaults;
+use crate::core::download::sha1_hex;
+
+/// Wie lange das Manifest ohne erneute Prüfung verwendet wird
+pub const MANIFEST_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Version-JSONs ohne SHA-1 im Manifest (ändern sich praktisch nie)
+pub const VERSION_TTL: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// Begleitdatei `<datei>.etag.json`
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheInfo {
+    #[serde(default)]
+    etag: Option<String>,
+    #[serde(default)]
+    fetched_at: Option<String>,
+}
+
+fn info_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".etag.json");
+    path.with_file_name(name)
+}
+
+fn load_info(path: &Path) -> CacheInfo {
+    std::fs::read_to_string(info_path(path))
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+fn save_info(path: &Path, info: &CacheInfo) {
+    if let Ok(content) = serde_json::to_string(info) {
+        std::fs::write(info_path(path), content).ok();
+    }
+}
+
+fn is_fresh(info: &CacheInfo, ttl: Duration) -> bool {
+    info.fetched_at
+        .as_deref()
+        .and_then(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+        .and_then(|t| (chrono::Utc::now() - t.with_timezone(&chrono::Utc)).to_std().ok())
+        .is_some_and(|age| age < ttl)
+}
+
+/// Pfad einer Datei im Cache, z.B. `versions/1.20.1.json`
+pub fn path(name: &str) -> PathBuf {
+    defaults::meta_dir().join(name)
+}
+
+/// Liest eine gecachte Datei ohne Netzzugriff
+pub fn read_cached(name: &str) -> Option<String> {
+    std::fs::read_to_string(path(name)).ok()
+}
+
+/// Lädt `url` über den Cache. Mit `sha1` gilt eine passende Datei als dauerhaft gültig,
+/// sonst wird sie nach `ttl` per `If-None-Match` neu geprüft. Schlägt die Anfrage fehl,
+/// wird die vorhandene (ggf. veraltete) Datei zurückgegeben.
+pub async fn fetch(url: &str, name: &str, ttl: Duration, sha1: Option<&str>) -> Result<String> {
+    let path = path(name);
+    let cached = tokio::fs::read(&path).await.ok();
+    let mut info = load_info(&path);
+
+    if let Some(data) = &cached {
+        let valid = match sha1 {
+            Some(sha1) => sha1_hex(data).eq_ignore_ascii_case(sha1),
+            None => is_fresh(&info, ttl),
+        };
+        if valid {
+            return Ok(String::from_utf8_lossy(data).into_owned());
+        }
+    }
+
+    match request(url, cached.as_ref().and(info.etag.as_deref())).await {
+        Ok(Response::NotModified) => {
+            tracing::debug!("Metadata not modified: {}", name);
+            info.fetched_at = Some(chrono::Utc::now().to_rfc3339());
+            save_info(&path, &info);
+            Ok(String::from_utf8_lossy(cached.as_deref().unwrap_or_default()).into_owned())
+        }
+        Ok(Response::Body { body, etag }) => {
+            if let Some(sha1) = sha1.filter(|s| !sha1_hex(body.as_bytes()).eq_ignore_ascii_case(s)) {
+                bail!("SHA-1 mismatch for {} (expected {})", url, sha1);
+            }
+            if let Some(parent) = path.parent() {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+            let tmp = path.with_extension("json.tmp");
+            tokio::fs::write(&tmp, &body).await?;
+            tokio::fs::rename(&tmp, &path).await?;
+            save_info(&path, &CacheInfo { etag, fetched_at: Some(chrono::Utc::now().to_rfc3339()) });
+            Ok(body)
+        }
+        Err(e) => match cached {
+            Some(data) => {
+                tracing::warn!("Could not refresh {} ({}), using cached copy", name, e);
+                Ok(String::from_utf8_lossy(&data).into_owned())
+            }
+            None => Err(e),
+        },
+    }
+}
+
+enum Response {
+    NotModified,
+    Body { body: String, etag: Option<String> },
+}
+
+async fn request(url: &str, etag: Option<&str>) -> Result<Response> {
+    let client = reqwest::Client::builder()
+        .user_agent("Lion-Launcher/1.0")
+        .timeout(Duration::from_secs(30))
+        .build()?;
+    let mut request = client.get(url);
+    if let Some(etag) = etag {
+        request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+    }
+
+    let response = request.send().await?;
+    if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+        return Ok(Response::NotModified);
+    }
+    if !response.status().is_success() {
+        bail!("HTTP {} for {}", response.status(), url);
+    }
+    let etag = response.headers()
+        .get(reqwest::header::ETAG)
+        .and_then(|v| v.to_str().ok())
+        .map(String::from);
+    Ok(Response::Body { body: response.text().await?, etag })
+}
@@ -3,14 +3,30 @@
 mod installer;
 mod neoforge;
 mod forge;
+mod asset_validation;
+pub mod install_cache;
 pub mod worlds;
+pub mod realms;
+pub mod world_optimize;
+pub mod jvm_lint;
+pub mod exit_report;
+pub mod monitor;
+pub mod process;
+pub mod classpath;
+pub mod verify;
+mod arguments;
+mod meta_cache;
+mod rules;
 
 use anyhow::{Result, bail};
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
-use crate::types::profile::Profile;
-use crate::core::download::DownloadManager;
+use crate::types::profile::{GarbageCollector, Profile};
+use crate::core::download::{CancelToken, Cancelled, DownloadManager};
+use classpath::ClasspathBuilder;
+use arguments::{Argument, ArgumentContext};
 use crate::config::defaults;
+use asset_validation::AssetValidationIndex;
 
 const MOJANG_MANIFEST_URL: &str = "https://piston-meta.mojang.com/mc/game/version_manifest_v2.json";
 const RESOURCES_URL: &str = "https://resources.download.minecraft.net";
@@ -85,62 +101,156 @@ fn get_extra_launch_args() -> Vec<String> {
         .unwrap_or_default()
 }
 
+/// `javaVersion.majorVersion` aus einer bereits geladenen Version-JSON (Metadaten-Cache
+/// oder installierte Version), ohne Netzwerkzugriff
+pub fn cached_java_version(version: &str) -> Option<u32> {
+    let content = meta_cache::read_cached(&format!("versions/{}.json", version)).or_else(|| {
+        std::fs::read_to_string(defaults::versions_dir().join(version).join(format!("{}.json", version))).ok()
+    })?;
+    let info: serde_json::Value = serde_json::from_str(&content).ok()?;
+    info.get("javaVersion")?.get("majorVersion")?.as_u64().map(|v| v as u32)
+}
+
+/// Quick Play (`--quickPlay*`) gibt es erst ab 1.20 bzw. Snapshot 23w14a
+pub fn supports_quick_play(minecraft_version: &str) -> bool {
+    // Snapshots im Format 23w14a
+    if let Some((year, rest)) = minecraft_version.split_once('w') {
+        if let (Ok(year), Ok(week)) = (year.parse::<u32>(), rest.trim_end_matches(|c: char| c.is_ascii_alphabetic()).parse::<u32>()) {
+            return year * 100 + week >= 2314;
+        }
+    }
+    // Pre-Releases/Release Candidates ("1.20-pre1", "1.20.1-rc1") wie die Release behandeln
+    let release = minecraft_version.split(['-', ' ']).next().unwrap_or(minecraft_version);
+    // Unbekannte Formate (April-Fools-Versionen, eigene Namen) bekommen die alten Argumente
+    crate::core::mods::migration::compare_minecraft_versions(release, "1.20")
+        .is_some_and(|o| o != std::cmp::Ordering::Less)
+}
+
+/// Argumente zum direkten Betreten eines Servers: Quick Play auf neuen Versionen,
+/// sonst `--server`/`--port` (IPv6-Adressen in eckigen Klammern)
+pub fn server_join_args(minecraft_version: &str, address: &str) -> Vec<String> {
+    if supports_quick_play(minecraft_version) {
+        return vec!["--quickPlayMultiplayer".to_string(), address.to_string()];
+    }
+
+    let (host, port) = match address.strip_prefix('[').and_then(|a| a.split_once(']')) {
+        Some((host, rest)) => (host, rest.strip_prefix(':')),
+        None => match address.rsplit_once(':') {
+            // Nur ein Doppelpunkt → host:port, mehrere → nackte IPv6-Adresse
+            Some((host, port)) if !host.contains(':') => (host, Some(port)),
+            _ => (address, None),
+        },
+    };
+
+    let mut args = vec!["--server".to_string(), host.to_string()];
+    args.push("--port".to_string());
+    args.push(port.filter(|p| p.parse::<u16>().is_ok()).unwrap_or("25565").to_string());
+    args
+}
+
 /// Globaler Speicher für Launch-Warnungen (thread-sicher via Mutex)
 static LAUNCH_WARNINGS: std::sync::OnceLock<std::sync::Mutex<Vec<String>>> =
     std::sync::OnceLock::new();
 
-/// Globale Map: Profile-ID → PID der laufenden Minecraft-Instanz
-static RUNNING_PROCESSES: std::sync::OnceLock<std::sync::Mutex<std::collections::HashMap<String, u32>>> =
-    std::sync::OnceLock::new();
+/// Start/Ende einer Instanz, für das Fenster-Verhalten der Oberfläche
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum InstanceLifecycle {
+    Started { profile_id: String, pid: u32 },
+    /// `exit_code` fehlt, wenn der Nutzer die Instanz beendet hat
+    Exited { profile_id: String, exit_code: Option<i32>, uptime_secs: u64 },
+    /// Unerwartetes Ende (Exit-Code, Crash-Report) – kommt nach `Exited`
+    Crashed { report: exit_report::GameExitReport },
+}
+
+type LifecycleListener = Box<dyn Fn(&InstanceLifecycle) + Send + Sync>;
 
-fn running_processes() -> &'static std::sync::Mutex<std::collections::HashMap<String, u32>> {
-    RUNNING_PROCESSES.get_or_init(|| std::sync::Mutex::new(std::collections::HashMap::new()))
+static LIFECYCLE_LISTENER: std::sync::OnceLock<LifecycleListener> = std::sync::OnceLock::new();
+
+/// Setzt den Empfänger für Start/Ende von Instanzen (einmalig beim App-Start)
+pub fn set_lifecycle_listener(listener: impl Fn(&InstanceLifecycle) + Send + Sync + 'static) {
+    LIFECYCLE_LISTENER.set(Box::new(listener)).ok();
+}
+
+pub(crate) fn notify_lifecycle(event: InstanceLifecycle) {
+    if let Some(listener) = LIFECYCLE_LISTENER.get() {
+        listener(&event);
+    }
 }
 
 /// Registriert eine laufende Minecraft-Instanz.
 pub fn register_running_process(profile_id: &str, pid: u32) {
-    if let Ok(mut map) = running_processes().lock() {
-        map.insert(profile_id.to_string(), pid);
+    process::ProcessManager::global().register(profile_id, pid);
+}
+
+/// Entfernt eine beendete Minecraft-Instanz (nur wenn `pid` noch die registrierte ist).
+pub fn unregister_running_process(profile_id: &str, pid: u32, exit_code: Option<i32>) {
+    process::ProcessManager::global().unregister(profile_id, pid, exit_code);
+}
+
+static VERIFY_EXISTING: crate::config::flag::ConfigFlag =
+    crate::config::flag::ConfigFlag::new(|c| c.verify_existing);
+
+/// Vorhandene Libraries/Client-JARs beim Start per SHA-1 prüfen (`verify_existing` in der Konfiguration)
+pub fn verify_existing() -> bool {
+    VERIFY_EXISTING.get()
+}
+
+pub fn set_verify_existing(verify: bool) {
+    VERIFY_EXISTING.set(verify);
+}
+
+/// Abbruch-Tokens der gerade vorbereiteten Starts (Profil-ID → Token)
+static PENDING_LAUNCHES: std::sync::Mutex<Option<std::collections::HashMap<String, CancelToken>>> =
+    std::sync::Mutex::new(None);
+
+/// Bricht die Startvorbereitung eines Profils ab (laufende Downloads werden beendet,
+/// Teildateien gelöscht). `false`, wenn für das Profil kein Start vorbereitet wird.
+pub fn cancel_launch(profile_id: &str) -> bool {
+    let token = PENDING_LAUNCHES.lock().ok()
+        .and_then(|mut pending| pending.as_mut()?.remove(profile_id));
+    match token {
+        Some(token) => {
+            token.cancel();
+            tracing::info!("Launch of {} cancelled", profile_id);
+            true
+        }
+        None => false,
+    }
+}
+
+fn begin_launch(profile_id: &str) -> CancelToken {
+    let token = CancelToken::new();
+    if let Ok(mut pending) = PENDING_LAUNCHES.lock() {
+        pending.get_or_insert_with(std::collections::HashMap::new).insert(profile_id.to_string(), token.clone());
     }
+    token
 }
 
-/// Entfernt eine beendete Minecraft-Instanz aus der globalen Map.
-pub fn unregister_running_process(profile_id: &str) {
-    if let Ok(mut map) = running_processes().lock() {
-        map.remove(profile_id);
+fn end_launch(profile_id: &str, token: &CancelToken) {
+    if let Ok(mut pending) = PENDING_LAUNCHES.lock() {
+        if let Some(pending) = pending.as_mut() {
+            // Nur den eigenen Eintrag entfernen, nicht den eines neueren Starts
+            if pending.get(profile_id).is_some_and(|t| t.same(token)) {
+                pending.remove(profile_id);
+            }
+        }
     }
 }
 
 /// Gibt alle aktuell laufenden Profil-IDs zurück.
 pub fn get_running_profile_ids() -> Vec<String> {
-    running_processes().lock()
-        .map(|m| m.keys().cloned().collect())
-        .unwrap_or_default()
+    process::ProcessManager::global().list().into_iter().map(|i| i.profile_id).collect()
+}
+
+/// Laufende Instanzen als (Profil-ID, PID)
+pub fn running_processes_snapshot() -> Vec<(String, u32)> {
+    process::ProcessManager::global().list().into_iter().map(|i| (i.profile_id, i.pid)).collect()
 }
 
 /// Beendet die laufende Minecraft-Instanz eines Profils.
-pub fn kill_running_process(profile_id: &str) -> bool {
-    let pid = {
-        running_processes().lock().ok()
-            .and_then(|m| m.get(profile_id).copied())
-    };
-    if let Some(pid) = pid {
-        tracing::info!("Killing Minecraft process PID {} for profile {}", pid, profile_id);
-        #[cfg(unix)]
-        {
-            unsafe { libc::kill(pid as libc::pid_t, libc::SIGTERM); }
-        }
-        #[cfg(windows)]
-        {
-            std::process::Command::new("taskkill")
-                .args(["/PID", &pid.to_string(), "/F"])
-                .spawn().ok();
-        }
-        unregister_running_process(profile_id);
-        true
-    } else {
-        false
-    }
+pub fn kill_running_process(profile_id: &str, force: bool) -> bool {
+    process::ProcessManager::global().kill(profile_id, force)
 }
 
 fn launch_warnings() -> &'static std::sync::Mutex<Vec<String>> {
@@ -159,10 +269,69 @@ pub fn take_launch_warnings() -> Vec<String> {
     launch_warnings().lock().map(|mut w| std::mem::take(&mut *w)).unwrap_or_default()
 }
 
+/// Feature-Flags für die Regel-Auswertung der Argument-Templates
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LaunchFeatures {
+    /// Account ohne Spielbesitz bzw. Demo explizit gewünscht → `--demo`
+    pub is_demo_user: bool,
+}
+
+impl LaunchFeatures {
+    /// Wert eines Features aus der Version-JSON; unbekannte Features sind nie aktiv
+    fn get(&self, name: &str) -> bool {
+        match name {
+            "is_demo_user" => self.is_demo_user,
+            _ => false,
+        }
+    }
+}
+
 pub struct MinecraftLauncher {
     download_manager: DownloadManager,
 }
 
+/// Platzhalter-Werte für die Argument-Templates eines Starts
+#[allow(clippy::too_many_arguments)]
+fn argument_context(
+    version_info: &VersionInfo,
+    game_dir: &Path,
+    assets_dir: &Path,
+    natives_dir: &Path,
+    classpath: &str,
+    username: &str,
+    uuid: &str,
+    access_token: Option<&str>,
+) -> ArgumentContext {
+    let token = access_token.unwrap_or("0");
+    let user_type = if access_token.is_some() && token != "0" { "msa" } else { "legacy" };
+    // Alte Versionen (bis 1.6) erwarten die Session als "token:<accessToken>:<uuid>"
+    let session = if user_type == "msa" { format!("token:{}:{}", token, uuid) } else { token.to_string() };
+    let game_assets = legacy_assets_dir(&version_info.assetIndex.id, assets_dir, game_dir)
+        .unwrap_or_else(|| assets_dir.to_path_buf());
+
+    ArgumentContext::new()
+        .set("auth_player_name", username)
+        .set("version_name", version_info.id.as_str())
+        .set("version_type", version_info.version_type.as_deref().unwrap_or("release"))
+        .set("game_directory", game_dir.display().to_string())
+        .set("assets_root", assets_dir.display().to_string())
+        .set("assets_index_name", version_info.assetIndex.id.as_str())
+        .set("auth_uuid", uuid)
+        .set("auth_access_token", token)
+        .set("auth_session", session)
+        .set("game_assets", game_assets.display().to_string())
+        .set("user_properties", "{}")
+        .set("user_type", user_type)
+        .set("clientid", "")
+        .set("auth_xuid", "")
+        .set("natives_directory", natives_dir.display().to_string())
+        .set("library_directory", defaults::libraries_dir().display().to_string())
+        .set("classpath", classpath)
+        .set("classpath_separator", ClasspathBuilder::separator())
+        .set("launcher_name", "lion-launcher")
+        .set("launcher_version", "1.0")
+}
+
 #[derive(Debug, serde::Deserialize)]
 struct VersionManifest {
     versions: Vec<VersionEntry>,
@@ -172,6 +341,8 @@ struct VersionManifest {
 struct VersionEntry {
     id: String,
     url: String,
+    #[serde(default)]
+    sha1: Option<String>,
 }
 
 #[derive(Debug, serde::Deserialize)]
@@ -183,6 +354,41 @@ struct VersionInfo {
     downloads: GameDownloads,
     assetIndex: AssetIndexInfo,
     javaVersion: Option<JavaVersionInfo>,
+    /// "release", "snapshot", "old_beta", ...
+    #[serde(rename = "type", default)]
+    version_type: Option<String>,
+    /// Ab 1.13: Argument-Templates mit Regeln (fehlt bei älteren Versionen)
+    #[serde(default)]
+    arguments: Option<VersionArguments>,
+    /// Bis 1.12.2: Game-Argumente als ein String mit `${platzhaltern}`
+    #[serde(rename = "minecraftArguments", default)]
+    minecraft_arguments: Option<String>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct VersionArguments {
+    #[serde(default)]
+    game: Vec<Argument>,
+    #[serde(default)]
+    jvm: Vec<Argument>,
+}
+
+impl VersionInfo {
+    /// Game-Argument-Templates: `arguments.game`, sonst `minecraftArguments` alter Versionen
+    fn game_arguments(&self) -> Vec<Argument> {
+        match (&self.arguments, &self.minecraft_arguments) {
+            (Some(arguments), _) if !arguments.game.is_empty() => arguments.game.clone(),
+            (_, Some(legacy)) => legacy.split_whitespace().map(Argument::plain).collect(),
+            _ => arguments::default_game_arguments(),
+        }
+    }
+
+    fn jvm_arguments(&self) -> Vec<Argument> {
+        match &self.arguments {
+            Some(arguments) if !arguments.jvm.is_empty() => arguments.jvm.clone(),
+            _ => arguments::default_jvm_arguments(),
+        }
+    }
 }
 
 #[derive(Debug, serde::Deserialize)]
@@ -210,28 +416,42 @@ struct Artifact {
     path: String,
     sha1: String,
     url: String,
+    #[serde(default)]
+    size: Option<u64>,
 }
 
-#[derive(Debug, serde::Deserialize)]
+#[derive(Debug, Clone, serde::Deserialize)]
 struct Rule {
     action: String,
     os: Option<OsRule>,
+    #[serde(default)]
+    features: Option<std::collections::HashMap<String, bool>>,
 }
 
-#[derive(Debug, serde::Deserialize)]
+#[derive(Debug, Clone, serde::Deserialize)]
 struct OsRule {
     name: Option<String>,
+    /// z.B. `x86` – vergleicht mit der CPU-Architektur des Launchers
+    #[serde(default)]
+    arch: Option<String>,
+    /// Regex gegen die OS-Version, z.B. `^10\.5\.\d$`
+    #[serde(default)]
+    version: Option<String>,
 }
 
 #[derive(Debug, serde::Deserialize)]
 struct GameDownloads {
     client: DownloadInfo,
+    #[serde(default)]
+    server: Option<DownloadInfo>,
 }
 
 #[derive(Debug, serde::Deserialize)]
 struct DownloadInfo {
     sha1: String,
     url: String,
+    #[serde(default)]
+    size: Option<u64>,
 }
 
 #[derive(Debug, serde::Deserialize)]
@@ -239,16 +459,120 @@ struct AssetIndexInfo {
     id: String,
     sha1: String,
     url: String,
+    #[serde(default)]
+    size: Option<u64>,
 }
 
 #[derive(Debug, serde::Deserialize)]
 struct AssetIndex {
     objects: std::collections::HashMap<String, AssetObject>,
+    /// 1.6–1.7.2 ("legacy"): Assets zusätzlich unter ihrem Namen in `assets/virtual/<index>`
+    #[serde(rename = "virtual", default)]
+    is_virtual: bool,
+    /// Vor 1.6 ("pre-1.6"): Assets unter ihrem Namen in `<game_dir>/resources`
+    #[serde(default)]
+    map_to_resources: bool,
+}
+
+/// Verzeichnis mit den benannten Asset-Kopien alter Versionen (`${game_assets}`).
+/// `None` für Versionen, die direkt `assets/objects` nutzen.
+fn legacy_assets_dir(index_id: &str, assets_dir: &Path, game_dir: &Path) -> Option<PathBuf> {
+    match index_id {
+        "pre-1.6" => Some(game_dir.join("resources")),
+        "legacy" => Some(assets_dir.join("virtual").join("legacy")),
+        _ => None,
+    }
+}
+
+/// Natives pro Minecraft-Version, damit sich die LWJGL-Dateien verschiedener Versionen
+/// im selben Spielverzeichnis nicht überschreiben
+fn natives_dir(game_dir: &Path, version: &str) -> PathBuf {
+    game_dir.join("natives").join(version)
+}
+
+/// Merkt sich pro Natives-Verzeichnis, welche JARs (SHA-1) bereits entpackt wurden
+const NATIVES_MANIFEST: &str = ".natives.json";
+
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+struct NativesManifest {
+    #[serde(default)]
+    jars: std::collections::HashMap<String, ExtractedNatives>,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+struct ExtractedNatives {
+    sha1: String,
+    /// Entpackte Datei → SHA-1. Ein anderes JAR kann dieselbe Datei überschrieben haben,
+    /// daher reicht es nicht, nur das Vorhandensein zu prüfen.
+    #[serde(default)]
+    files: std::collections::HashMap<String, String>,
+}
+
+impl NativesManifest {
+    fn load(dir: &Path) -> Self {
+        std::fs::read_to_string(dir.join(NATIVES_MANIFEST))
+            .ok()
+            .and_then(|c| serde_json::from_str(&c).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self, dir: &Path) {
+        if let Ok(content) = serde_json::to_string_pretty(self) {
+            std::fs::write(dir.join(NATIVES_MANIFEST), content).ok();
+        }
+    }
+
+    /// JAR unverändert und alle daraus entpackten Dateien noch mit demselben Inhalt vorhanden
+    fn is_current(&self, jar: &str, sha1: &str, dir: &Path) -> bool {
+        self.jars.get(jar).is_some_and(|entry| {
+            entry.sha1 == sha1
+                && entry.files.iter().all(|(file, hash)| {
+                    std::fs::read(dir.join(file))
+                        .is_ok_and(|data| crate::core::download::sha1_hex(&data) == *hash)
+                })
+        })
+    }
+}
+
+/// Entfernt lose Dateien aus dem früheren gemeinsamen `natives/` und die Verzeichnisse
+/// anderer Minecraft-Versionen. Noch geladene DLLs (Windows) bleiben einfach liegen.
+fn remove_stale_natives(game_dir: &Path, version: &str) {
+    let Ok(entries) = std::fs::read_dir(game_dir.join("natives")) else { return };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let removed = if path.is_dir() {
+            if entry.file_name() == version {
+                continue;
+            }
+            std::fs::remove_dir_all(&path)
+        } else {
+            std::fs::remove_file(&path)
+        };
+        match removed {
+            Ok(()) => tracing::debug!("Removed stale natives {:?}", path),
+            Err(e) => tracing::debug!("Could not remove stale natives {:?}: {}", path, e),
+        }
+    }
+}
+
+/// Anzahl der entpackten Natives (ohne Manifest)
+fn count_natives(dir: &Path) -> usize {
+    std::fs::read_dir(dir)
+        .map(|d| d.flatten().filter(|e| e.file_name() != NATIVES_MANIFEST).count())
+        .unwrap_or(0)
+}
+
+/// Classifier einer Maven-Koordinate, falls es ein Natives-Artefakt ist
+/// (`org.lwjgl:lwjgl:3.3.3:natives-linux` → `natives-linux`)
+fn native_classifier(maven: &str) -> Option<&str> {
+    maven.split(':').nth(3).filter(|c| c.starts_with("natives-"))
 }
 
 #[derive(Debug, serde::Deserialize)]
 struct AssetObject {
     hash: String,
+    #[serde(default)]
+    size: Option<u64>,
 }
 
 /// Ergebnis einer NeoForge/Forge-Installation
@@ -281,14 +605,10 @@ fn maven_to_path(maven: &str) -> String {
     }
 }
 
-fn classpath_separator() -> &'static str {
-    if cfg!(windows) { ";" } else { ":" }
-}
-
-/// Erzeugt plattform-optimierte JVM Performance-Flags basierend auf OS und Java-Version.
+/// Erzeugt plattform-optimierte JVM Performance-Flags basierend auf OS, Java-Version und GC.
 ///
 /// Folgt dem Ansatz von Prism/Modrinth Launcher mit plattform-konditionalen Flags:
-/// - G1GC mit Tuning-Flags für alle Plattformen (wie Prism)
+/// - G1GC mit Tuning-Flags (wie Prism), alternativ ZGC oder Shenandoah
 /// - `-XX:+AlwaysPreTouch` nur auf Linux/macOS (auf Windows unnötig, erhöht Startzeit)
 /// - `-XX:HeapDumpPath=...` nur auf Windows (Mojang-Konvention)
 /// - `-XX:+UseStringDeduplication` ab Java 17 für bessere Speichernutzung
@@ -297,19 +617,44 @@ fn classpath_separator() -> &'static str {
 /// - `os`: Betriebssystem ("linux", "windows", "macos") via `std::env::consts::OS`
 /// - `java_version`: Java-Major-Version (8, 17, 21, …)
 /// - `memory_mb`: Heap-Größe in Megabyte
-pub(super) fn get_jvm_flags(os: &str, java_version: u32, memory_mb: u32) -> Vec<String> {
+/// - `gc`: gewählter Collector; fällt auf G1 zurück, wenn die Java-Version ihn nicht kennt
+pub(super) fn get_jvm_flags(os: &str, java_version: u32, memory_mb: u32, gc: GarbageCollector) -> Vec<String> {
     let mut flags = vec![
         format!("-Xmx{}M", memory_mb),
         format!("-Xms{}M", memory_mb / 2),
         "-XX:+UnlockExperimentalVMOptions".to_string(),
-        "-XX:+UseG1GC".to_string(),
-        "-XX:G1NewSizePercent=20".to_string(),
-        "-XX:G1ReservePercent=20".to_string(),
-        "-XX:MaxGCPauseMillis=50".to_string(),
-        "-XX:G1HeapRegionSize=32M".to_string(),
-        "-Dfile.encoding=UTF-8".to_string(),
     ];
 
+    let gc = if java_version < gc.min_java_version() {
+        add_launch_warning(format!(
+            "{} benötigt mindestens Java {} – verwende G1GC",
+            gc.as_str(), gc.min_java_version()
+        ));
+        GarbageCollector::G1
+    } else {
+        gc
+    };
+
+    match gc {
+        GarbageCollector::G1 => flags.extend([
+            "-XX:+UseG1GC".to_string(),
+            "-XX:G1NewSizePercent=20".to_string(),
+            "-XX:G1ReservePercent=20".to_string(),
+            "-XX:MaxGCPauseMillis=50".to_string(),
+            "-XX:G1HeapRegionSize=32M".to_string(),
+        ]),
+        GarbageCollector::Zgc => {
+            flags.push("-XX:+UseZGC".to_string());
+            // Java 21/22: generationaler Modus muss explizit aktiviert werden,
+            // ab 23 ist er Standard und ab 24 ist das Flag entfernt
+            if (21..=22).contains(&java_version) {
+                flags.push("-XX:+ZGenerational".to_string());
+            }
+        }
+        GarbageCollector::Shenandoah => flags.push("-XX:+UseShenandoahGC".to_string()),
+    }
+    flags.push("-Dfile.encoding=UTF-8".to_string());
+
     // AlwaysPreTouch: Reserviert physischen RAM beim Start → weniger GC-Jitter im Spiel.
     // Auf Windows unnötig (Windows Page-File-Management ist anders) und erhöht die Startzeit.
     if os != "windows" {
@@ -325,27 +670,48 @@ pub(super) fn get_jvm_flags(os: &str, java_version: u32, memory_mb: u32) -> Vec<
         );
     }
 
-    // String-Deduplizierung ab Java 17: spart Heap-Speicher durch G1-interne Dedup-Threads.
-    // Nur sinnvoll ab Java 17 (stabil) und bei ausreichend RAM.
-    if java_version >= 17 && memory_mb >= 2048 {
+    // String-Deduplizierung ab Java 17: spart Heap-Speicher durch GC-interne Dedup-Threads.
+    // ZGC unterstützt das erst ab Java 18.
+    let dedup_java = if gc == GarbageCollector::Zgc { 18 } else { 17 };
+    if java_version >= dedup_java && memory_mb >= 2048 {
         flags.push("-XX:+UseStringDeduplication".to_string());
     }
 
     flags
 }
 
-fn split_classpath_entries(classpath: &str) -> Vec<String> {
-    std::env::split_paths(std::ffi::OsStr::new(classpath))
-        .map(|p| p.to_string_lossy().to_string())
-        .collect()
-}
+/// Startet das fertige Java-Kommando innerhalb von gamescope, falls im Profil aktiviert.
+/// Programm, Argumente, Umgebung und Arbeitsverzeichnis werden übernommen;
+/// stdout/stderr müssen danach gesetzt werden.
+fn wrap_with_gamescope(cmd: Command, options: &crate::types::profile::GamescopeOptions) -> Result<Command> {
+    if !options.enabled {
+        return Ok(cmd);
+    }
+    if !cfg!(target_os = "linux") {
+        add_launch_warning("gamescope ist nur unter Linux verfügbar – starte ohne".to_string());
+        return Ok(cmd);
+    }
+    let found = std::env::var_os("PATH")
+        .is_some_and(|path| std::env::split_paths(&path).any(|dir| dir.join("gamescope").is_file()));
+    if !found {
+        bail!("gamescope ist aktiviert, aber nicht installiert (nicht im PATH gefunden)");
+    }
 
-fn join_classpath_entries<T: AsRef<str>>(entries: impl IntoIterator<Item = T>) -> String {
-    entries
-        .into_iter()
-        .map(|entry| entry.as_ref().to_string())
-        .collect::<Vec<_>>()
-        .join(classpath_separator())
+    let mut wrapped = Command::new("gamescope");
+    wrapped.args(options.wrapper_args());
+    wrapped.arg(cmd.get_program());
+    wrapped.args(cmd.get_args());
+    for (key, value) in cmd.get_envs() {
+        match value {
+            Some(value) => wrapped.env(key, value),
+            None => wrapped.env_remove(key),
+        };
+    }
+    if let Some(dir) = cmd.get_current_dir() {
+        wrapped.current_dir(dir);
+    }
+    tracing::info!("Launching inside gamescope: {:?}", options.wrapper_args());
+    Ok(wrapped)
 }
 
 impl MinecraftLauncher {
@@ -355,6 +721,30 @@ impl MinecraftLauncher {
         })
     }
 
+    /// `true`, wenn `path` (neu) geladen werden muss: die Datei fehlt oder passt bei
+    /// aktivierter Prüfung nicht zur SHA-1 aus dem Manifest (dann wird sie gelöscht).
+    async fn needs_download(&self, path: &Path, sha1: &str) -> bool {
+        if !path.exists() {
+            return true;
+        }
+        if !verify_existing() {
+            return false;
+        }
+        match crate::core::download::sha1_of_file(path).await {
+            Ok(actual) if actual.eq_ignore_ascii_case(sha1) => false,
+            Ok(actual) => {
+                tracing::warn!("SHA-1 mismatch for {:?} (expected {}, got {}), re-downloading", path, sha1, actual);
+                tokio::fs::remove_file(path).await.ok();
+                true
+            }
+            Err(e) => {
+                tracing::warn!("Could not hash {:?} ({}), re-downloading", path, e);
+                tokio::fs::remove_file(path).await.ok();
+                true
+            }
+        }
+    }
+
     /// Startet Minecraft mit zusätzlichen Argumenten (z.B. für Quick Play)
     pub async fn launch_with_extra_args(
         &self,
@@ -377,8 +767,113 @@ impl MinecraftLauncher {
         result.map(|_| ())
     }
 
+    /// Installiert nur den Mod-Loader eines Profils (inkl. benötigter Vanilla-Dateien),
+    /// ohne das Spiel zu starten. Wird von `reinstall_loader` verwendet.
+    pub async fn install_loader(&self, profile: &Profile) -> Result<()> {
+        use crate::types::version::ModLoader;
+
+        let version = &profile.minecraft_version;
+        let game_dir = Path::new(&profile.game_dir);
+        let version_info = self.get_version_info(version).await?;
+
+        let versions_dir = defaults::versions_dir();
+        let libraries_dir = defaults::libraries_dir();
+        let natives_dir = natives_dir(game_dir, version);
+        tokio::fs::create_dir_all(&libraries_dir).await?;
+        tokio::fs::create_dir_all(&natives_dir).await?;
+
+        tracing::info!("Installing {:?} loader for profile {}", profile.loader.loader, profile.name);
+
+        match profile.loader.loader {
+            ModLoader::Vanilla => {}
+            ModLoader::Fabric => {
+                self.install_fabric(version, &libraries_dir).await?;
+            }
+            ModLoader::Quilt => {
+                self.install_quilt(version, &libraries_dir).await?;
+            }
+            ModLoader::NeoForge => {
+                let loader_version = if profile.loader.version.is_empty() { "latest" } else { &profile.loader.version };
+                let vanilla_classpath = self.download_libraries(&version_info, &libraries_dir, &natives_dir).await?;
+                let required_java = version_info.javaVersion.as_ref().map(|j| j.majorVersion).unwrap_or(21).max(21);
+                let java_path = self.resolve_java(profile, required_java, None).await?;
+                neoforge::install_neoforge(
+                    &self.download_manager, version, loader_version, &libraries_dir, &versions_dir, &java_path, &vanilla_classpath,
+                ).await?;
+            }
+            ModLoader::Forge => {
+                let loader_version = if profile.loader.version == "latest" || profile.loader.version.is_empty() {
+                    self.resolve_latest_forge_version(version).await?
+                } else {
+                    profile.loader.version.clone()
+                };
+
+                let client_jar = versions_dir.join(format!("{}/{}.jar", version, version));
+                if self.needs_download(&client_jar, &version_info.downloads.client.sha1).await {
+                    tokio::fs::create_dir_all(client_jar.parent().unwrap()).await?;
+                    self.download_manager
+                        .download_with_hash(&version_info.downloads.client.url, &client_jar, Some(&version_info.downloads.client.sha1))
+                        .await?;
+                }
+                self.download_libraries(&version_info, &libraries_dir, &natives_dir).await?;
+
+                let required_java = version_info.javaVersion.as_ref().map(|j| j.majorVersion).unwrap_or(8);
+                let max_java = if required_java <= 8 { Some(8) } else { None };
+                let java_path = self.resolve_java(profile, required_java, max_java).await?;
+
+                forge::ForgeInstaller::new(self.download_manager.clone())
+                    .install_forge_complete(version, &loader_version, &libraries_dir, &client_jar, Some(&java_path))
+                    .await?;
+            }
+        }
+
+        tracing::info!("Loader installed for profile {}", profile.name);
+        Ok(())
+    }
+
     /// Startet Minecraft und gibt Warnungen zurück (z.B. Quilt-Fallback-Info).
     pub async fn launch(&self, profile: &Profile, username: &str, uuid: &str, access_token: Option<&str>) -> Result<Vec<String>> {
+        self.launch_with_features(profile, username, uuid, access_token, LaunchFeatures::default()).await
+    }
+
+    /// Wie `launch`, wertet aber feature-abhängige Argumente (z.B. `--demo`) aus
+    pub async fn launch_with_features(
+        &self,
+        profile: &Profile,
+        username: &str,
+        uuid: &str,
+        access_token: Option<&str>,
+        features: LaunchFeatures,
+    ) -> Result<Vec<String>> {
+        // Feature-Argumente landen im Extra-Args-Puffer, den alle Launch-Pfade anhängen;
+        // danach wird der vorherige Inhalt (z.B. Quick Play) wiederhergestellt
+        let previous_extra_args = get_extra_launch_args();
+
+        // Alle Downloads dieses Starts laufen über einen abbrechbaren DownloadManager
+        let token = begin_launch(&profile.id);
+        let launcher = Self {
+            download_manager: self.download_manager.with_cancel(token.clone()),
+        };
+        let result = launcher.launch_inner(profile, username, uuid, access_token, features).await;
+        end_launch(&profile.id, &token);
+
+        set_extra_launch_args(previous_extra_args);
+        match &result {
+            Ok(_) => crate::core::telemetry::record_launch(profile.loader.loader.as_str()),
+            Err(e) if e.is::<Cancelled>() => {}
+            Err(_) => crate::core::telemetry::record_failure(crate::core::telemetry::FailureCategory::LaunchError),
+        }
+        result
+    }
+
+    async fn launch_inner(
+        &self,
+        profile: &Profile,
+        username: &str,
+        uuid: &str,
+        access_token: Option<&str>,
+        features: LaunchFeatures,
+    ) -> Result<Vec<String>> {
         // Warnungs-Puffer leeren (Überrest aus vorherigem Start)
         take_launch_warnings();
 
@@ -392,25 +887,32 @@ impl MinecraftLauncher {
         // Version-Info laden
         let version_info = self.get_version_info(version).await?;
 
+        let feature_args = self.feature_game_args(&version_info, &features);
+        if !feature_args.is_empty() {
+            tracing::info!("Feature-dependent game args: {:?}", feature_args);
+            let mut extra_args = get_extra_launch_args();
+            extra_args.extend(feature_args);
+            set_extra_launch_args(extra_args);
+        }
+
         // Verzeichnisse
         let versions_dir = defaults::versions_dir();
         let libraries_dir = defaults::libraries_dir();
         let assets_dir = defaults::assets_dir();
-        let natives_dir = game_dir.join("natives");
+        let natives_dir = natives_dir(game_dir, version);
 
         tokio::fs::create_dir_all(&versions_dir).await?;
         tokio::fs::create_dir_all(&libraries_dir).await?;
         tokio::fs::create_dir_all(&assets_dir).await?;
-        // IMMER leeren: verhindert LWJGL-Versionskonflikte wenn MC-Version gewechselt wird.
-        if natives_dir.exists() {
-            tokio::fs::remove_dir_all(&natives_dir).await.ok();
-        }
+        // Eigenes Verzeichnis pro Version; unveränderte JARs werden nicht erneut entpackt
+        let (game_dir_owned, version_owned) = (game_dir.to_path_buf(), version.to_string());
+        tokio::task::spawn_blocking(move || remove_stale_natives(&game_dir_owned, &version_owned)).await.ok();
         tokio::fs::create_dir_all(&natives_dir).await?;
         tokio::fs::create_dir_all(game_dir).await?;
 
         // Client-JAR
         let client_jar = versions_dir.join(format!("{}/{}.jar", version, version));
-        if !client_jar.exists() {
+        if self.needs_download(&client_jar, &version_info.downloads.client.sha1).await {
             tracing::info!("Downloading client...");
             send_launch_progress("Lade Minecraft Client-JAR...", 15);
             tokio::fs::create_dir_all(client_jar.parent().unwrap()).await?;
@@ -427,7 +929,7 @@ impl MinecraftLauncher {
         // Assets
         tracing::info!("Checking assets...");
         send_launch_progress("Lade Assets (Sounds, Texturen)... Das kann beim ersten Mal 1-2 Min. dauern.", 50);
-        self.download_assets(&version_info.assetIndex, &assets_dir).await?;
+        self.download_assets(&version_info.assetIndex, &assets_dir, game_dir).await?;
 
         // NeoForge/Forge verwendet einen speziellen Launch-Mechanismus
         if matches!(loader, crate::types::version::ModLoader::NeoForge) {
@@ -459,35 +961,35 @@ impl MinecraftLauncher {
                 send_launch_progress("Installiere Fabric Loader...", 70);
                 let (fabric_classpath, fabric_main_class) = self.install_fabric(version, &libraries_dir).await?;
 
-                let mut cp_entries = split_classpath_entries(&fabric_classpath);
-                cp_entries.extend(
-                    split_classpath_entries(&classpath)
+                let mut cp = ClasspathBuilder::new();
+                cp.extend_classpath(&fabric_classpath);
+                cp.extend(
+                    ClasspathBuilder::split(&classpath)
                         .into_iter()
                         .filter(|path| !path.contains("/org/ow2/asm/") && !path.contains("\\org\\ow2\\asm\\"))
                 );
-                cp_entries.push(client_jar.display().to_string());
-                let cp = join_classpath_entries(cp_entries);
-                (fabric_main_class, cp)
+                cp.push_path(&client_jar);
+                (fabric_main_class, cp.build())
             }
             crate::types::version::ModLoader::Quilt => {
                 tracing::info!("Installing Quilt loader...");
                 let (quilt_classpath, quilt_main_class) = self.install_quilt(version, &libraries_dir).await?;
 
-                let mut cp_entries = split_classpath_entries(&quilt_classpath);
-                cp_entries.extend(
-                    split_classpath_entries(&classpath)
+                let mut cp = ClasspathBuilder::new();
+                cp.extend_classpath(&quilt_classpath);
+                cp.extend(
+                    ClasspathBuilder::split(&classpath)
                         .into_iter()
                         .filter(|path| !path.contains("/org/ow2/asm/") && !path.contains("\\org\\ow2\\asm\\"))
                 );
-                cp_entries.push(client_jar.display().to_string());
-                let cp = join_classpath_entries(cp_entries);
-                (quilt_main_class, cp)
+                cp.push_path(&client_jar);
+                (quilt_main_class, cp.build())
             }
             crate::types::version::ModLoader::Vanilla => {
-                let mut cp_entries = split_classpath_entries(&classpath);
-                cp_entries.push(client_jar.display().to_string());
-                let cp = join_classpath_entries(cp_entries);
-                (version_info.mainClass.clone(), cp)
+                let mut cp = ClasspathBuilder::new();
+                cp.extend_classpath(&classpath);
+                cp.push_path(&client_jar);
+                (version_info.mainClass.clone(), cp.build())
             }
             _ => unreachable!()
         };
@@ -543,10 +1045,11 @@ impl MinecraftLauncher {
         // Finde Java – verwende die von Mojang angegebene Mindestversion (mindestens 21 für NeoForge)
         let required_java = version_info.javaVersion.as_ref().map(|j| j.majorVersion).unwrap_or(21).max(21);
         tracing::info!("Required Java version: {}", required_java);
-        let java_path = self.ensure_java_installed(required_java, None).await?;
+        let java_path = self.resolve_java(profile, required_java, None).await?;
 
         // Installiere NeoForge (mit Vanilla-Libraries)
         let installation = neoforge::install_neoforge(
+            &self.download_manager,
             version,
             loader_version,
             libraries_dir,
@@ -557,22 +1060,24 @@ impl MinecraftLauncher {
 
         // Baue das Launch-Command
         let memory_mb = profile.memory_mb.unwrap_or(4096);
-        let token = access_token.unwrap_or("0");
+        let ctx = argument_context(version_info, game_dir, assets_dir, natives_dir, vanilla_classpath, username, uuid, access_token);
+        let vanilla_game_args = self.resolve_arguments(&version_info.game_arguments(), &ctx);
 
         let mut cmd = neoforge::build_launch_command(
             &installation,
             &java_path,
             memory_mb,
+            profile.garbage_collector,
             required_java,
             game_dir,
-            assets_dir,
             natives_dir,
             libraries_dir,
-            username,
-            uuid,
-            token,
             version,
-            &version_info.assetIndex.id,
+            &vanilla_game_args,
+            &[
+                profile.debug_options.jvm_args(&profile.loader.loader, required_java, game_dir),
+                profile.display_options.jvm_args(),
+            ].concat(),
         );
 
         // Display-Umgebungsvariablen weitergeben (verhindert GBM/EGL-Fallback → SIGABRT)
@@ -592,6 +1097,7 @@ impl MinecraftLauncher {
             // libflite.so: Systemlib → Symlink; kein System-flite → Stub via GCC
             Self::ensure_flite_stub(natives_dir);
         }
+        profile.display_options.apply_env(&mut cmd);
 
         // Extra-Args (Quick Play) — fehlte hier komplett!
         let extra_args = get_extra_launch_args();
@@ -608,6 +1114,8 @@ impl MinecraftLauncher {
         tracing::info!("✅ Starting NeoForge...");
 
         // Starte das Spiel
+        let mut cmd = wrap_with_gamescope(cmd, &profile.gamescope)?;
+        self.download_manager.check_cancelled()?;
         let mut child = cmd.spawn()?;
         let pid = child.id();
         tracing::info!("🎮 Minecraft started with PID: {}", pid);
@@ -615,11 +1123,14 @@ impl MinecraftLauncher {
         // PID in globalem Zustand registrieren
         let profile_id_owned = profile.id.clone();
         register_running_process(&profile.id, pid);
+        let started_at = chrono::Utc::now();
 
         // Warte auf das Spiel im Hintergrund
         tokio::spawn(async move {
+            let mut exit_code = None;
             match child.wait() {
                 Ok(status) => {
+                    exit_code = status.code();
                     if status.success() {
                         tracing::info!("✅ Minecraft (PID {}) exited successfully", pid);
                     } else {
@@ -628,7 +1139,8 @@ impl MinecraftLauncher {
                 }
                 Err(e) => tracing::error!("❌ Error waiting for Minecraft: {}", e),
             }
-            unregister_running_process(&profile_id_owned);
+            unregister_running_process(&profile_id_owned, pid, exit_code);
+            crate::core::sessions::finish_session(&profile_id_owned, started_at, exit_code).await;
         });
 
         Ok(())
@@ -715,7 +1227,7 @@ impl MinecraftLauncher {
         };
 
         tracing::info!("Required Java version for Forge: {} (max: {:?})", required_java, max_java);
-        let java_path = self.ensure_java_installed(required_java, max_java).await?;
+        let java_path = self.resolve_java(profile, required_java, max_java).await?;
 
         // fml.toml schreiben: EarlyDisplay deaktivieren.
         // earlyWindowControl=true + NVIDIA/GLX → "BadValue" bei allen GL-Profilen (3.2–4.6).
@@ -753,10 +1265,7 @@ maxThreads = -1
             version, &loader_version, libraries_dir, client_jar, Some(&java_path)
         ).await?;
 
-        // Natives-Verzeichnis leeren und neu befüllen
-        if natives_dir.exists() {
-            tokio::fs::remove_dir_all(natives_dir).await.ok();
-        }
+        // Natives-Verzeichnis ist versionsspezifisch; bereits entpackte JARs überspringt das Manifest
         tokio::fs::create_dir_all(natives_dir).await?;
         let os = Self::get_os();
 
@@ -790,7 +1299,7 @@ maxThreads = -1
             };
             if is_my_os && path.exists() {
                 tracing::info!("Extracting Forge native: {}", fname);
-                self.extract_native(path, natives_dir)?;
+                self.extract_native(path, natives_dir).await?;
             }
         }
 
@@ -819,19 +1328,21 @@ maxThreads = -1
                         if native_path.exists() {
                             let fname = native_path.file_name().and_then(|n| n.to_str()).unwrap_or("");
                             tracing::info!("Extracting Vanilla native: {}", fname);
-                            self.extract_native(&native_path, natives_dir)?;
+                            self.extract_native(&native_path, natives_dir).await?;
                         }
                     }
                 }
                 // Altes Format (classifiers)
                 if let Some(classifiers) = &dl.classifiers {
-                    let key = format!("natives-{}", os);
+                    let key = lib.natives.as_ref()
+                        .and_then(|natives| Self::native_classifier(natives, &os))
+                        .unwrap_or_else(|| format!("natives-{}", os));
                     if let Some(nat) = classifiers.get(&key) {
                         let native_path = libraries_dir.join(&nat.path);
                         if native_path.exists() {
                             let fname = native_path.file_name().and_then(|n| n.to_str()).unwrap_or("");
                             tracing::info!("Extracting Vanilla native (legacy): {}", fname);
-                            self.extract_native(&native_path, natives_dir)?;
+                            self.extract_native(&native_path, natives_dir).await?;
                         }
                     }
                 }
@@ -840,8 +1351,7 @@ maxThreads = -1
 
         // Quelle 3: Direkte Suche im libraries-Verzeichnis nach natives-linux JARs.
         // Fallback wenn Quellen 1+2 nichts fanden (z.B. frischer Download).
-        let extracted = std::fs::read_dir(natives_dir)
-            .map(|d| d.count()).unwrap_or(0);
+        let extracted = count_natives(natives_dir);
         if extracted == 0 {
             tracing::warn!("No natives extracted from classpath — scanning libraries dir");
             if let Ok(entries) = walkdir_lwjgl_natives(libraries_dir, &os) {
@@ -849,13 +1359,13 @@ maxThreads = -1
                     let fname = native_path.file_name()
                         .and_then(|n| n.to_str()).unwrap_or("");
                     tracing::info!("Fallback: Extracting native {}", fname);
-                    self.extract_native(&native_path, natives_dir)?;
+                    self.extract_native(&native_path, natives_dir).await?;
                 }
             }
         }
 
         tracing::info!("Natives directory populated: {} files",
-            std::fs::read_dir(natives_dir).map(|d| d.count()).unwrap_or(0));
+            count_natives(natives_dir));
 
         // options.txt: fullscreen=false + narrator=0 setzen
         Self::patch_game_options(game_dir).await;
@@ -921,8 +1431,11 @@ maxThreads = -1
             }
             // NVIDIA-Treiber für GLX explizit wählen (nicht Mesa-Fallback)
             cmd.env("__GLX_VENDOR_LIBRARY_NAME", "nvidia");
-            // NVIDIA EGL-Treiber explizit (für LWJGL EGL-Pfad)
-            cmd.env("__EGL_VENDOR_LIBRARY_FILENAMES", "/usr/lib/x86_64-linux-gnu/libEGL_nvidia.so.0");
+            // NVIDIA EGL-Treiber explizit (für LWJGL EGL-Pfad). In der Flatpak-Sandbox
+            // liegt der Treiber in der GL-Erweiterung, der Host-Pfad existiert dort nicht.
+            if !crate::utils::flatpak::is_flatpak() {
+                cmd.env("__EGL_VENDOR_LIBRARY_FILENAMES", "/usr/lib/x86_64-linux-gnu/libEGL_nvidia.so.0");
+            }
             // Threaded Optimizations AUS – verursacht BadValue bei Context-Create
             cmd.env("__GL_THREADED_OPTIMIZATIONS", "0");
             // Kein indirektes Rendering (würde Software-Fallback erzwingen)
@@ -947,7 +1460,7 @@ maxThreads = -1
 
         // === BASIS JVM-ARGUMENTE (plattform-optimiert) ===
         let os_name = std::env::consts::OS; // "linux", "windows", "macos"
-        for flag in get_jvm_flags(os_name, required_java, memory_mb) {
+        for flag in get_jvm_flags(os_name, required_java, memory_mb, profile.garbage_collector) {
             cmd.arg(flag);
         }
         // Beide Properties setzen: LWJGL im Forge SECURE-BOOTSTRAP ModuleLayer
@@ -981,6 +1494,16 @@ maxThreads = -1
         // Verhindert dass Transformationsfehler die Mod-Loading-Pipeline unterbrechen
         cmd.arg("-Dfml.earlywindow.headless=true");
 
+        // Debug-Optionen des Profils (überschreiben z.B. forge.logging.console.level)
+        for arg in profile.debug_options.jvm_args(&profile.loader.loader, required_java, game_dir) {
+            cmd.arg(arg);
+        }
+        // Wayland/X11-Auswahl des Profils
+        for arg in profile.display_options.jvm_args() {
+            cmd.arg(arg);
+        }
+        profile.display_options.apply_env(&mut cmd);
+
         // === MODUL-ARGS: NUR für Java 9+ und nur was zur Forge-Ära passt ===
         // Erkennung: ForgeBootstrap (1.20.2+), BootstrapLauncher (1.18-1.20.1),
         //            ModLauncher (1.13-1.17), LaunchWrapper (≤1.12.2)
@@ -1068,7 +1591,7 @@ maxThreads = -1
                 .collect();
 
             // Vanilla-CP filtern: Konflikte mit Forge-JARs und Natives entfernen
-            let filtered_vanilla: Vec<String> = split_classpath_entries(vanilla_classpath)
+            let filtered_vanilla: Vec<String> = ClasspathBuilder::split(vanilla_classpath)
                 .into_iter()
                 .filter(|e| {
                     if e.is_empty() { return false; }
@@ -1086,20 +1609,16 @@ maxThreads = -1
 
             // Classpath aufbauen:
             // bootstrap_classpath (alle Forge-JARs) + classpath (mcp_config etc.)
-            // + client.jar + gefilterter Vanilla-CP
-            let mut cp_parts: Vec<String> = Vec::new();
-            cp_parts.extend(install_result.bootstrap_classpath.iter().cloned());
-            cp_parts.extend(install_result.classpath.iter().cloned());
-            cp_parts.push(client_jar.display().to_string());
-            cp_parts.extend(filtered_vanilla.iter().cloned());
-
-            // Deduplizieren
-            let mut seen_cp = std::collections::HashSet::new();
-            cp_parts.retain(|p| seen_cp.insert(p.clone()));
-
-            let bootstrap_cp = join_classpath_entries(cp_parts.iter());
+            // + client.jar + gefilterter Vanilla-CP (Duplikate entfernt der Builder)
+            let mut cp = ClasspathBuilder::new();
+            cp.extend(&install_result.bootstrap_classpath);
+            cp.extend(&install_result.classpath);
+            cp.push_path(client_jar);
+            cp.extend(&filtered_vanilla);
+
+            let bootstrap_cp = cp.build();
             tracing::info!("Bootstrap classpath: {} JARs ({} forge + {} vanilla)",
-                cp_parts.len(),
+                cp.len(),
                 install_result.bootstrap_classpath.len() + install_result.classpath.len(),
                 filtered_vanilla.len()
             );
@@ -1123,19 +1642,15 @@ maxThreads = -1
             // liest vom Classpath und nutzt TweakClasses für Forge-Injection.
             //
             // ALLE Forge-JARs müssen auf dem Classpath sein!
-            let mut full_cp_entries: Vec<String> = Vec::new();
-            full_cp_entries.extend(install_result.bootstrap_classpath.iter().cloned());
-            full_cp_entries.extend(install_result.classpath.iter().cloned());
-            full_cp_entries.push(client_jar.display().to_string());
-            full_cp_entries.extend(split_classpath_entries(vanilla_classpath));
-
-            // Deduplizieren
-            let mut seen_cp = std::collections::HashSet::new();
-            full_cp_entries.retain(|p| !p.is_empty() && seen_cp.insert(p.clone()));
+            let mut cp = ClasspathBuilder::new();
+            cp.extend(&install_result.bootstrap_classpath);
+            cp.extend(&install_result.classpath);
+            cp.push_path(client_jar);
+            cp.extend_classpath(vanilla_classpath);
 
-            let full_cp = join_classpath_entries(full_cp_entries.iter());
+            let full_cp = cp.build();
             tracing::info!("Forge classpath: {} JARs ({} forge + vanilla)",
-                full_cp_entries.len(),
+                cp.len(),
                 install_result.bootstrap_classpath.len() + install_result.classpath.len()
             );
             cmd.arg("-cp").arg(&full_cp);
@@ -1148,11 +1663,10 @@ maxThreads = -1
             // Einzelner String mit ${Platzhaltern}, durch Leerzeichen getrennt.
             // Enthält bereits --tweakClass und alle nötigen Minecraft-Args.
             tracing::info!("Legacy Forge: Verwende minecraftArguments");
+            // Gleiche Platzhalter wie Vanilla (inkl. ${game_assets} / ${auth_session})
+            let ctx = argument_context(version_info, game_dir, assets_dir, natives_dir, vanilla_classpath, username, uuid, access_token);
             let legacy_args: Vec<String> = mc_args_str.split_whitespace()
-                .map(|arg| forge::resolve_arg_placeholders(
-                    arg, libraries_dir, natives_dir, game_dir, assets_dir,
-                    &version_info.assetIndex.id, version, uuid, token, user_type, username,
-                ))
+                .map(|arg| ctx.substitute(arg))
                 .collect();
             for arg in &legacy_args {
                 cmd.arg(arg);
@@ -1185,30 +1699,11 @@ maxThreads = -1
             if !has_arg("--fml.mcpVersion") {
                 cmd.arg("--fml.mcpVersion").arg(&install_result.mcp_version);
             }
-            if !has_arg("--username") {
-                cmd.arg("--username").arg(username);
-            }
-            if !has_arg("--version") {
-                cmd.arg("--version").arg(version);
-            }
-            if !has_arg("--gameDir") {
-                cmd.arg("--gameDir").arg(game_dir);
-            }
-            if !has_arg("--assetsDir") {
-                cmd.arg("--assetsDir").arg(assets_dir);
-            }
-            if !has_arg("--assetIndex") {
-                cmd.arg("--assetIndex").arg(&version_info.assetIndex.id);
-            }
-            if !has_arg("--uuid") {
-                cmd.arg("--uuid").arg(uuid);
-            }
-            if !has_arg("--accessToken") {
-                cmd.arg("--accessToken").arg(token);
-            }
-            if !has_arg("--userType") {
-                cmd.arg("--userType").arg(user_type);
-            }
+
+            // Vanilla-Game-Argumente aus der Version-JSON, soweit Forge sie nicht selbst setzt
+            let ctx = argument_context(version_info, game_dir, assets_dir, natives_dir, vanilla_classpath, username, uuid, access_token);
+            let vanilla_args = self.resolve_arguments(&version_info.game_arguments(), &ctx);
+            cmd.args(arguments::missing_options(&resolved_game_args, &vanilla_args));
         }
 
         // Extra-Args (Quick Play)
@@ -1234,6 +1729,7 @@ maxThreads = -1
 
         // Starte den Prozess
         cmd.current_dir(game_dir);
+        let mut cmd = wrap_with_gamescope(cmd, &profile.gamescope)?;
         // Auf Windows: Stdio::null() statt inherit(), da Tauri kein Konsolenfenster hat.
         // Forge schreibt Logs ohnehin in latest.log / debug.log im GameDir.
         #[cfg(windows)]
@@ -1249,16 +1745,20 @@ maxThreads = -1
 
         tracing::info!("Launching Forge {} for MC {}...", loader_version, version);
 
+        self.download_manager.check_cancelled()?;
         let mut child = cmd.spawn()?;
         let pid = child.id();
         tracing::info!("Forge started with PID: {}", pid);
 
         let profile_id_owned = profile.id.clone();
         register_running_process(&profile.id, pid);
+        let started_at = chrono::Utc::now();
 
         tokio::spawn(async move {
+            let mut exit_code = None;
             match child.wait() {
                 Ok(status) => {
+                    exit_code = status.code();
                     if status.success() {
                         tracing::info!("Forge (PID {}) exited successfully", pid);
                     } else {
@@ -1267,7 +1767,8 @@ maxThreads = -1
                 }
                 Err(e) => tracing::error!("Error waiting for Forge: {}", e),
             }
-            unregister_running_process(&profile_id_owned);
+            unregister_running_process(&profile_id_owned, pid, exit_code);
+            crate::core::sessions::finish_session(&profile_id_owned, started_at, exit_code).await;
         });
 
         Ok(())
@@ -1294,7 +1795,7 @@ maxThreads = -1
         // aber benötigen Java 8. Mit 21 als Fallback würde Forge ≤1.16.5 (Nashorn) crashen.
         let required_java = version_info.javaVersion.as_ref().map(|j| j.majorVersion).unwrap_or(8);
         tracing::info!("Required Java version: {}", required_java);
-        let java_path = self.ensure_java_installed(required_java, None).await?;
+        let java_path = self.resolve_java(profile, required_java, None).await?;
 
         // Auf Windows javaw.exe nutzen (kein Konsolenfenster).
         // Robuste Variante: nur den Dateinamen ersetzen, nicht per String-Replace
@@ -1341,11 +1842,16 @@ maxThreads = -1
 
         // Plattform-optimierte JVM-Flags (Xmx/Xms + G1GC-Tuning + OS-spezifische Flags)
         let os_name = std::env::consts::OS; // "linux", "windows", "macos"
-        for flag in get_jvm_flags(os_name, required_java, memory_mb) {
+        for flag in get_jvm_flags(os_name, required_java, memory_mb, profile.garbage_collector) {
             cmd.arg(flag);
         }
-        // java.library.path: Standard-JVM-Pfad für native Bibliotheken (alle Versionen)
-        cmd.arg(format!("-Djava.library.path={}", natives_dir.display()));
+        // JVM-Argumente aus der Version-JSON (java.library.path, Launcher-Brand, -cp, ...)
+        let ctx = argument_context(version_info, game_dir, assets_dir, natives_dir, classpath, username, uuid, access_token);
+        let jvm_args = self.resolve_arguments(&version_info.jvm_arguments(), &ctx);
+        if !jvm_args.iter().any(|a| a == "-cp" || a == "-classpath") {
+            cmd.arg("-cp").arg(classpath);
+        }
+        cmd.args(&jvm_args);
         // org.lwjgl.librarypath: LWJGL 3.3.2+ bevorzugt diese Property gegenüber java.library.path.
         // Ohne diese Property findet LWJGL auf Windows keine lwjgl.dll (auch wenn java.library.path gesetzt ist).
         // Forge setzt beide Properties – Fabric/Quilt/Vanilla muss das ebenfalls tun.
@@ -1353,8 +1859,6 @@ maxThreads = -1
         // JNA-Bibliothekspfad: damit text2speech/libflite.so im natives-Dir gefunden wird.
         #[cfg(target_os = "linux")]
         cmd.arg(format!("-Djna.library.path={}", natives_dir.display()));
-        cmd.arg("-Dminecraft.launcher.brand=lion-launcher");
-        cmd.arg("-Dminecraft.launcher.version=1.0");
 
         // Notwendige --add-opens für Java 17+ (Minecraft 1.17+)
         if required_java >= 17 {
@@ -1383,20 +1887,20 @@ maxThreads = -1
             _ => {}
         }
 
-        cmd.arg("-cp").arg(classpath);
-        cmd.arg(main_class);
+        // Debug-Optionen des Profils
+        for arg in profile.debug_options.jvm_args(loader, required_java, game_dir) {
+            cmd.arg(arg);
+        }
+        // Wayland/X11-Auswahl des Profils
+        for arg in profile.display_options.jvm_args() {
+            cmd.arg(arg);
+        }
+        profile.display_options.apply_env(&mut cmd);
 
-        let token = access_token.unwrap_or("0");
-        let user_type = if access_token.is_some() && token != "0" { "msa" } else { "legacy" };
+        cmd.arg(main_class);
 
-        cmd.arg("--username").arg(username);
-        cmd.arg("--version").arg(&profile.minecraft_version);
-        cmd.arg("--gameDir").arg(game_dir);
-        cmd.arg("--assetsDir").arg(assets_dir);
-        cmd.arg("--assetIndex").arg(&version_info.assetIndex.id);
-        cmd.arg("--uuid").arg(uuid);
-        cmd.arg("--accessToken").arg(token);
-        cmd.arg("--userType").arg(user_type);
+        // Game-Argumente aus der Version-JSON
+        cmd.args(self.resolve_arguments(&version_info.game_arguments(), &ctx));
 
         // Extra args (z.B. für Quick Play)
         let extra_args = get_extra_launch_args();
@@ -1408,12 +1912,14 @@ maxThreads = -1
         Self::patch_game_options(game_dir).await;
 
         cmd.current_dir(game_dir);
+        let mut cmd = wrap_with_gamescope(cmd, &profile.gamescope)?;
         // stdout/stderr pipen und via tracing loggen (funktioniert auch ohne Terminal)
         cmd.stdout(Stdio::piped());
         cmd.stderr(Stdio::piped());
 
         tracing::info!("Launching Minecraft ({})...", loader.as_str());
         tracing::info!("Java: {}", java_bin);
+        self.download_manager.check_cancelled()?;
         let mut child = cmd.spawn()
             .map_err(|e| anyhow::anyhow!("Konnte Minecraft nicht starten ({}): {}", java_bin, e))?;
         let pid = child.id();
@@ -1421,6 +1927,7 @@ maxThreads = -1
 
         let profile_id_owned = profile.id.clone();
         register_running_process(&profile.id, pid);
+        let started_at = chrono::Utc::now();
 
         // stdout/stderr im Hintergrund lesen und loggen
         if let Some(stdout) = child.stdout.take() {
@@ -1443,8 +1950,10 @@ maxThreads = -1
         }
 
         tokio::spawn(async move {
+            let mut exit_code = None;
             match child.wait() {
                 Ok(status) => {
+                    exit_code = status.code();
                     if status.success() {
                         tracing::info!("✅ Minecraft (PID {}) erfolgreich beendet", pid);
                     } else {
@@ -1453,7 +1962,8 @@ maxThreads = -1
                 }
                 Err(e) => tracing::error!("❌ Fehler beim Warten auf Minecraft: {}", e),
             }
-            unregister_running_process(&profile_id_owned);
+            unregister_running_process(&profile_id_owned, pid, exit_code);
+            crate::core::sessions::finish_session(&profile_id_owned, started_at, exit_code).await;
         });
 
         Ok(())
@@ -1626,7 +2136,7 @@ void* flite_voice_load(const char* p)                    { return (void*)0; }
         // z.B. "guava-33.3.1-jre.jar" -> "guava"
         // z.B. "asm-9.6.jar" -> "asm"
         let extract_library_name = |path: &str| -> Option<String> {
-            let filename = path.split('/').next_back()?;
+            let filename = Path::new(path).file_name()?.to_str()?;
             // Entferne .jar Endung
             let name = filename.strip_suffix(".jar")?;
             // Entferne Version und Klassifier (z.B. -33.3.1-jre, -9.6, -natives-linux)
@@ -1652,7 +2162,7 @@ void* flite_voice_load(const char* p)                    { return (void*)0; }
             }
         };
 
-        for entry in split_classpath_entries(classpath) {
+        for entry in ClasspathBuilder::split(classpath) {
             let entry = entry.trim();
             if entry.is_empty() {
                 continue;
@@ -1688,9 +2198,9 @@ void* flite_voice_load(const char* p)                    { return (void*)0; }
             unique_entries.push(entry.to_string());
         }
 
-        let result = join_classpath_entries(unique_entries.iter());
+        let result = ClasspathBuilder::from_entries(&unique_entries).build();
         tracing::info!("Classpath deduplicated: {} -> {} entries",
-            split_classpath_entries(classpath).len(),
+            ClasspathBuilder::split(classpath).len(),
             unique_entries.len()
         );
 
@@ -1834,7 +2344,7 @@ void* flite_voice_load(const char* p)                    { return (void*)0; }
         }
 
         tracing::info!("Fabric installed with {} libraries", classpath_entries.len());
-        Ok((join_classpath_entries(classpath_entries), main_class))
+        Ok((ClasspathBuilder::from_entries(classpath_entries).build(), main_class))
     }
 
     /// Quilt Loader installieren und (Classpath, MainClass) zurückgeben.
@@ -1897,7 +2407,7 @@ void* flite_voice_load(const char* p)                    { return (void*)0; }
         }
 
         tracing::info!("Quilt installiert mit {} Libraries (Loader {})", classpath_entries.len(), loader_version);
-        Ok((join_classpath_entries(classpath_entries), profile.main_class))
+        Ok((ClasspathBuilder::from_entries(classpath_entries).build(), profile.main_class))
     }
 
 
@@ -1941,8 +2451,10 @@ void* flite_voice_load(const char* p)                    { return (void*)0; }
     async fn extract_neoforge_libraries(&self, installer_jar: &Path, libraries_dir: &Path) -> Result<(Vec<String>, String)> {
         use std::io::Read;
 
-        // Alle ZIP-Operationen synchron ausführen und Daten sammeln
-        let (version_json, jars_data) = {
+        // Alle ZIP-Operationen auf einem Blocking-Thread ausführen und Daten sammeln
+        let (installer_jar_owned, libraries_dir_owned) = (installer_jar.to_path_buf(), libraries_dir.to_path_buf());
+        let (version_json, jars_data) = crate::utils::compression::run_blocking(move || {
+            let (installer_jar, libraries_dir) = (installer_jar_owned.as_path(), libraries_dir_owned.as_path());
             let file = std::fs::File::open(installer_jar)?;
             let mut archive = zip::ZipArchive::new(file)?;
 
@@ -1974,16 +2486,23 @@ void* flite_voice_load(const char* p)                    { return (void*)0; }
             }
 
             // Dann die Daten extrahieren
+            let bytes_total = jar_names.iter()
+                .filter_map(|(name, _)| archive.by_name(name).ok().map(|e| e.size()))
+                .sum();
+            let mut tracker = crate::utils::compression::ExtractionTracker::new(installer_jar, jar_names.len(), bytes_total);
             for (name, dest) in jar_names {
                 if let Ok(mut entry) = archive.by_name(&name) {
                     let mut data = Vec::new();
                     entry.read_to_end(&mut data)?;
+                    tracker.advance(data.len() as u64);
                     jars_data.push((dest, data));
                 }
             }
+            tracker.finish();
 
-            (version_json, jars_data)
-        };
+            tracing::info!("NeoForge installer: {} embedded libraries read", jars_data.len());
+            Ok((version_json, jars_data))
+        }).await?;
 
         #[derive(serde::Deserialize)]
         #[allow(non_snake_case)]
@@ -2099,11 +2618,30 @@ void* flite_voice_load(const char* p)                    { return (void*)0; }
         }
     }
 
+    /// Version-JSON über den Metadaten-Cache; offline reicht eine bereits geladene Fassung
     async fn get_version_info(&self, version: &str) -> Result<VersionInfo> {
-        let manifest: VersionManifest = reqwest::get(MOJANG_MANIFEST_URL).await?.json().await?;
-        let entry = manifest.versions.iter().find(|v| v.id == version)
-            .ok_or_else(|| anyhow::anyhow!("Version not found: {}", version))?;
-        Ok(reqwest::get(&entry.url).await?.json().await?)
+        let cache_name = format!("versions/{}.json", version);
+        let content = match self.find_version_entry(version).await {
+            Ok(entry) => {
+                let ttl = meta_cache::VERSION_TTL;
+                meta_cache::fetch(&entry.url, &cache_name, ttl, entry.sha1.as_deref()).await?
+            }
+            Err(e) => meta_cache::read_cached(&cache_name).ok_or(e)?,
+        };
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Manifest-Eintrag einer Version. Fehlt sie im gecachten Manifest (z.B. neuer Snapshot),
+    /// wird das Manifest einmal sofort neu geprüft.
+    async fn find_version_entry(&self, version: &str) -> Result<VersionEntry> {
+        for ttl in [meta_cache::MANIFEST_TTL, std::time::Duration::ZERO] {
+            let manifest = meta_cache::fetch(MOJANG_MANIFEST_URL, "version_manifest_v2.json", ttl, None).await?;
+            let manifest: VersionManifest = serde_json::from_str(&manifest)?;
+            if let Some(entry) = manifest.versions.into_iter().find(|v| v.id == version) {
+                return Ok(entry);
+            }
+        }
+        bail!("Version not found: {}", version)
     }
 
     async fn download_libraries(&self, info: &VersionInfo, lib_dir: &Path, natives_dir: &Path) -> Result<String> {
@@ -2130,7 +2668,7 @@ void* flite_voice_load(const char* p)                    { return (void*)0; }
                         tokio::fs::remove_file(&dest).await.ok();
                     }
 
-                    if !dest.exists() {
+                    if self.needs_download(&dest, &art.sha1).await {
                         tracing::info!("Downloading: {}", lib.name);
                         tokio::fs::create_dir_all(dest.parent().unwrap()).await?;
                         self.download_manager.download_with_hash(&art.url, &dest, Some(&art.sha1)).await?;
@@ -2142,14 +2680,19 @@ void* flite_voice_load(const char* p)                    { return (void*)0; }
                     //   natives-windows.jar (x64), natives-windows-arm64.jar, natives-windows-x86.jar
                     //   natives-linux.jar (x64), natives-linux-arm64.jar, natives-linux-aarch64.jar
                     //   natives-osx.jar (x64), natives-macos-arm64.jar
-                    let is_native_jar = art.path.contains("natives-linux")
+                    // Das Betriebssystem wählen dabei die `rules` der Library aus, erkannt wird
+                    // das Artefakt am Classifier der Maven-Koordinate.
+                    let classifier = native_classifier(&lib.name);
+                    let is_native_jar = classifier.is_some()
+                        || art.path.contains("natives-linux")
                         || art.path.contains("natives-windows")
                         || art.path.contains("natives-osx")
                         || art.path.contains("natives-macos");
 
                     if is_native_jar {
                         // Architektur- und OS-bewusste Extraktion:
-                        if Self::should_extract_native_for_platform(&art.path, &os) {
+                        let platform = classifier.unwrap_or(&art.path);
+                        if Self::should_extract_native_for_platform(platform, &os) {
                             if !Self::is_valid_zip(&dest) {
                                 tracing::warn!("Corrupt native archive detected, re-downloading: {:?}", dest);
                                 tokio::fs::remove_file(&dest).await.ok();
@@ -2159,7 +2702,7 @@ void* flite_voice_load(const char* p)                    { return (void*)0; }
                                 }
                             }
                             tracing::debug!("Extracting native: {}", lib.name);
-                            self.extract_native(&dest, natives_dir)?;
+                            self.extract_native(&dest, natives_dir).await?;
                         }
                         // Natives kommen NICHT in den Classpath
                     } else {
@@ -2171,15 +2714,15 @@ void* flite_voice_load(const char* p)                    { return (void*)0; }
 
                 // Altes Format (pre-1.19): classifiers mit "natives-linux" key
                 if let Some(natives_map) = &lib.natives {
-                    if let Some(key) = natives_map.get(&os) {
+                    if let Some(key) = Self::native_classifier(natives_map, &os) {
                         if let Some(cls) = &dl.classifiers {
-                            if let Some(nat) = cls.get(key) {
+                            if let Some(nat) = cls.get(&key) {
                                 let dest = lib_dir.join(&nat.path);
                                 if dest.exists() && !Self::is_valid_zip(&dest) {
                                     tracing::warn!("Corrupt legacy native archive detected, re-downloading: {:?}", dest);
                                     tokio::fs::remove_file(&dest).await.ok();
                                 }
-                                if !dest.exists() {
+                                if self.needs_download(&dest, &nat.sha1).await {
                                     tracing::info!("Downloading native (legacy): {}", lib.name);
                                     tokio::fs::create_dir_all(dest.parent().unwrap()).await?;
                                     self.download_manager.download_with_hash(&nat.url, &dest, Some(&nat.sha1)).await?;
@@ -2187,7 +2730,7 @@ void* flite_voice_load(const char* p)                    { return (void*)0; }
                                 if !Self::is_valid_zip(&dest) {
                                     bail!("Legacy native archive is corrupt: {}", dest.display());
                                 }
-                                self.extract_native(&dest, natives_dir)?;
+                                self.extract_native(&dest, natives_dir).await?;
                             }
                         }
                     }
@@ -2198,10 +2741,12 @@ void* flite_voice_load(const char* p)                    { return (void*)0; }
         }
 
         tracing::info!("Vanilla libraries: {} entries in classpath", cp.len());
-        Ok(join_classpath_entries(cp))
+        Ok(ClasspathBuilder::from_entries(cp).build())
     }
 
-    async fn download_assets(&self, info: &AssetIndexInfo, assets_dir: &Path) -> Result<()> {
+    /// Lädt Index und Objekte; alte Indizes (`virtual` / `map_to_resources`) werden zusätzlich
+    /// unter ihren Namen nach `legacy_assets_dir` kopiert.
+    async fn download_assets(&self, info: &AssetIndexInfo, assets_dir: &Path, game_dir: &Path) -> Result<()> {
         let idx_dir = assets_dir.join("indexes");
         let obj_dir = assets_dir.join("objects");
         tokio::fs::create_dir_all(&idx_dir).await?;
@@ -2216,25 +2761,102 @@ void* flite_voice_load(const char* p)                    { return (void*)0; }
         let total = idx.objects.len();
         let mut done = 0;
 
+        // Schneller Pfad: alle Assets wurden bereits verifiziert → keine Einzelprüfung
+        let validation_path = AssetValidationIndex::path(assets_dir, &info.id);
+        let mut validation = AssetValidationIndex::load(&validation_path, &info.sha1);
+        if validation.covers(idx.objects.values().map(|a| &a.hash)) {
+            tracing::info!("Assets: {} objects verified by validation index", total);
+            return self.copy_legacy_assets(&idx, info, assets_dir, game_dir).await;
+        }
+
         for asset in idx.objects.values() {
             let pre = &asset.hash[..2];
             let dest = obj_dir.join(pre).join(&asset.hash);
-            if !dest.exists() {
+            if !validation.check(&asset.hash, &dest, asset.size) {
                 tokio::fs::create_dir_all(dest.parent().unwrap()).await?;
                 let url = format!("{}/{}/{}", RESOURCES_URL, pre, asset.hash);
-                self.download_manager.download_with_hash(&url, &dest, Some(&asset.hash)).await?;
+                if let Err(e) = self.download_manager.download_with_hash(&url, &dest, Some(&asset.hash)).await {
+                    // Bisherigen Fortschritt behalten, damit der nächste Versuch nicht von vorn prüft
+                    validation.save(&validation_path);
+                    return Err(e);
+                }
+                validation.record(&asset.hash, &dest);
                 done += 1;
                 if done % 200 == 0 { tracing::info!("Assets: {}/{}", done, total); }
             }
         }
+
+        validation.save(&validation_path);
+        self.copy_legacy_assets(&idx, info, assets_dir, game_dir).await
+    }
+
+    /// Kopiert die Objekte alter Asset-Indizes unter ihren Namen (z.B. `sound/step/grass1.ogg`)
+    async fn copy_legacy_assets(&self, idx: &AssetIndex, info: &AssetIndexInfo, assets_dir: &Path, game_dir: &Path) -> Result<()> {
+        if !idx.is_virtual && !idx.map_to_resources {
+            return Ok(());
+        }
+        let target = legacy_assets_dir(&info.id, assets_dir, game_dir).unwrap_or_else(|| {
+            if idx.map_to_resources { game_dir.join("resources") } else { assets_dir.join("virtual").join(&info.id) }
+        });
+        let objects: Vec<(String, String, Option<u64>)> = idx.objects.iter()
+            .map(|(name, asset)| (name.clone(), asset.hash.clone(), asset.size))
+            .collect();
+        let obj_dir = assets_dir.join("objects");
+
+        let copied = crate::utils::compression::run_blocking(move || {
+            let mut copied = 0;
+            for (name, hash, size) in objects {
+                // Namen aus dem Index dürfen nicht aus dem Zielordner herausführen
+                let relative = Path::new(&name);
+                let safe_name = relative.components().all(|c| matches!(c, std::path::Component::Normal(_)));
+                let prefix = hash.get(..2).filter(|_| hash.chars().all(|c| c.is_ascii_hexdigit()));
+                let (true, Some(prefix)) = (safe_name, prefix) else {
+                    tracing::warn!("Skipping invalid legacy asset entry {:?} ({})", name, hash);
+                    continue;
+                };
+                let dest = target.join(relative);
+                // Unveränderte Kopien überspringen
+                let existing = std::fs::metadata(&dest).ok().map(|m| m.len());
+                if existing.is_some() && (size.is_none() || existing == size) {
+                    continue;
+                }
+                if let Some(parent) = dest.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::fs::copy(obj_dir.join(prefix).join(&hash), &dest)?;
+                copied += 1;
+            }
+            Ok(copied)
+        })
+        .await?;
+        if copied > 0 {
+            tracing::info!("Assets: copied {} legacy assets to {}", copied, info.id);
+        }
         Ok(())
     }
 
-    fn extract_native(&self, jar: &Path, dir: &Path) -> Result<()> {
-        let file = std::fs::File::open(jar)
+    /// Entpackt ein Natives-JAR auf einem Blocking-Thread
+    async fn extract_native(&self, jar: &Path, dir: &Path) -> Result<()> {
+        let (jar, dir) = (jar.to_path_buf(), dir.to_path_buf());
+        crate::utils::compression::run_blocking(move || Self::extract_native_blocking(&jar, &dir)).await
+    }
+
+    /// Überspringt JARs, die laut `NATIVES_MANIFEST` mit gleicher SHA-1 bereits entpackt wurden
+    fn extract_native_blocking(jar: &Path, dir: &Path) -> Result<()> {
+        let data = std::fs::read(jar)
             .map_err(|e| anyhow::anyhow!("Cannot open native JAR {:?}: {}", jar, e))?;
-        let mut archive = zip::ZipArchive::new(file)
+        let key = jar.file_name().unwrap_or_default().to_string_lossy().to_string();
+        let sha1 = crate::core::download::sha1_hex(&data);
+
+        let mut manifest = NativesManifest::load(dir);
+        if manifest.is_current(&key, &sha1, dir) {
+            tracing::debug!("Natives from {} unchanged, skipping extraction", key);
+            return Ok(());
+        }
+
+        let mut archive = zip::ZipArchive::new(std::io::Cursor::new(data))
             .map_err(|e| anyhow::anyhow!("Cannot read native JAR {:?}: {}", jar, e))?;
+        let mut files = std::collections::HashMap::new();
 
         for i in 0..archive.len() {
             let mut f = archive.by_index(i)?;
@@ -2274,12 +2896,17 @@ void* flite_voice_load(const char* p)                    { return (void*)0; }
                 .unwrap_or(&name);
 
             let dest = dir.join(file_name);
-            // Immer überschreiben – stellt sicher dass die Natives zur aktuellen LWJGL-Version passen
+            // Überschreiben – stellt sicher dass die Natives zum aktuellen JAR passen
             tracing::debug!("Extracting native: {} -> {:?}", name, dest);
-            if let Ok(mut out) = std::fs::File::create(&dest) {
-                std::io::copy(&mut f, &mut out)?;
+            let mut content = Vec::new();
+            std::io::Read::read_to_end(&mut f, &mut content)?;
+            if std::fs::write(&dest, &content).is_ok() {
+                files.insert(file_name.to_string(), crate::core::download::sha1_hex(&content));
             }
         }
+        tracing::debug!("{} natives extracted from {:?}", files.len(), jar);
+        manifest.jars.insert(key, ExtractedNatives { sha1, files });
+        manifest.save(dir);
         Ok(())
     }
 
@@ -2321,6 +2948,35 @@ void* flite_voice_load(const char* p)                    { return (void*)0; }
         bail!("Java not found! Install Java 17+")
     }
 
+    /// Java für ein Profil: fester `java_path` des Profils, sonst die im Profil gewählte
+    /// Hauptversion (`java_version`), sonst die von der Minecraft-Version geforderte.
+    async fn resolve_java(&self, profile: &Profile, required_major: u32, max_major: Option<u32>) -> Result<String> {
+        if let Some(java_path) = &profile.java_path {
+            if !java_path.exists() {
+                bail!("Java-Pfad des Profils existiert nicht: {}", java_path.display());
+            }
+            let java_path = java_path.display().to_string();
+            let installed = Self::java_major_version(&java_path).await;
+            if installed != 0 && (installed < required_major || max_major.is_some_and(|max| installed > max)) {
+                tracing::warn!(
+                    "Profile Java {} ({}) does not match required Java {} – using it anyway",
+                    installed, java_path, required_major
+                );
+            }
+            tracing::info!("Using profile Java override: {}", java_path);
+            return Ok(java_path);
+        }
+
+        if let Some(version) = profile.java_version {
+            if version < required_major || max_major.is_some_and(|max| version > max) {
+                tracing::warn!("Profile requests Java {}, Minecraft requires Java {}", version, required_major);
+            }
+            return self.ensure_java_installed(version, Some(version)).await;
+        }
+
+        self.ensure_java_installed(required_major, max_major).await
+    }
+
     /// Findet oder installiert Java mit der passenden Version.
     /// `max_major`: Wenn gesetzt, wird NUR Java im Bereich [required_major, max_major] akzeptiert.
     ///              Wichtig für alte Forge-Versionen die Nashorn brauchen (Java ≤ 14).
@@ -2412,6 +3068,18 @@ void* flite_voice_load(const char* p)                    { return (void*)0; }
             }
         }
 
+        // Flatpak: Host-Java ist in der Sandbox nicht sichtbar, nur SDK-Erweiterungen bzw. gebündeltes JRE
+        if crate::utils::flatpak::is_flatpak() {
+            for candidate in crate::utils::flatpak::sandbox_java_candidates() {
+                let p = candidate.display().to_string();
+                let v = Self::java_major_version(&p).await;
+                if version_ok(v) {
+                    tracing::info!("Using Flatpak sandbox Java {}: {}", v, p);
+                    return Ok(p);
+                }
+            }
+        }
+
         // 3. System paths — Reihenfolge: bei max_major von niedrig nach hoch suchen
         let system_paths_low_first: &[&str] = if cfg!(target_os = "linux") {
             &[
@@ -2638,6 +3306,13 @@ void* flite_voice_load(const char* p)                    { return (void*)0; }
         else { "linux" }.to_string()
     }
 
+    /// Classifier der Legacy-Natives für `os`; `${arch}` steht wie in Java
+    /// (`sun.arch.data.model`) für die Bitbreite, z.B. `natives-windows-${arch}` → `natives-windows-64`
+    fn native_classifier(natives: &std::collections::HashMap<String, String>, os: &str) -> Option<String> {
+        let bits = if cfg!(target_pointer_width = "64") { "64" } else { "32" };
+        natives.get(os).map(|key| key.replace("${arch}", bits))
+    }
+
     /// Prüft ob ein natives-JAR für das aktuelle Betriebssystem UND die aktuelle CPU-Architektur
     /// extrahiert werden soll.
     ///
@@ -2715,17 +3390,60 @@ void* flite_voice_load(const char* p)                    { return (void*)0; }
         }
     }
 
+    /// Wie `check_rules`, berücksichtigt zusätzlich `features` (z.B. `is_demo_user`)
+    fn check_rules_with_features(&self, rules: &[Rule], features: &LaunchFeatures) -> bool {
+        Self::evaluate_rules(rules, Some(features))
+    }
+
+    /// Game-Argumente aus der Version-JSON, die nur über Feature-Regeln aktiv werden.
+    /// Alte Versionen ohne `arguments` kennen `--demo` trotzdem.
+    fn feature_game_args(&self, info: &VersionInfo, features: &LaunchFeatures) -> Vec<String> {
+        let Some(arguments) = &info.arguments else {
+            return if features.is_demo_user { vec!["--demo".to_string()] } else { Vec::new() };
+        };
+
+        let mut args = Vec::new();
+        for entry in &arguments.game {
+            let rules = entry.rules();
+            if !rules.iter().any(|r| r.features.is_some()) || !self.check_rules_with_features(rules, features) {
+                continue;
+            }
+            args.extend(entry.values().into_iter().map(String::from));
+        }
+        args
+    }
+
+    /// Löst Argument-Templates der Version-JSON auf. Feature-abhängige Einträge bleiben
+    /// außen vor – die kommen über `feature_game_args` in den Extra-Args-Puffer.
+    fn resolve_arguments(&self, entries: &[Argument], ctx: &ArgumentContext) -> Vec<String> {
+        let no_features = LaunchFeatures::default();
+        arguments::resolve(entries, |rules| self.check_rules_with_features(rules, &no_features), ctx)
+    }
+
     fn check_rules(&self, rules: &[Rule]) -> bool {
+        Self::evaluate_rules(rules, None)
+    }
+
+    /// Auswertung wie im Vanilla-Launcher: die letzte zutreffende Regel entscheidet, trifft
+    /// keine zu, ist der Eintrag nicht erlaubt. Ohne `features` werden Feature-Bedingungen
+    /// als erfüllt betrachtet.
+    fn evaluate_rules(rules: &[Rule], features: Option<&LaunchFeatures>) -> bool {
+        if rules.is_empty() {
+            return true;
+        }
         let os = Self::get_os();
+        let mut allowed = false;
         for r in rules {
-            if let Some(o) = &r.os {
-                if let Some(n) = &o.name {
-                    if r.action == "allow" && n != &os { return false; }
-                    if r.action == "disallow" && n == &os { return false; }
-                }
+            let os_matches = r.os.as_ref().is_none_or(|o| rules::os_matches(o, &os));
+            let features_match = match (&r.features, features) {
+                (Some(required), Some(features)) => required.iter().all(|(name, value)| features.get(name) == *value),
+                _ => true,
+            };
+            if os_matches && features_match {
+                allowed = r.action == "allow";
             }
         }
-        true
+        allowed
     }
 }
 
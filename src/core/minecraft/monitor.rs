@@ -0,0 +1,100 @@
+//! Ressourcen-Überwachung laufender Instanzen: Speicher (RSS) und CPU-Last werden alle
+//! `SAMPLE_INTERVAL` per sysinfo gemessen und an einen Listener (GUI-Event) gemeldet.
+//! Gezählt wird der ganze Prozessbaum – mit Gamescope oder Wrapper-Skripten ist die
+//! registrierte PID nicht die JVM selbst.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct InstanceMetrics {
+    pub profile_id: String,
+    pub pid: u32,
+    /// Belegter Arbeitsspeicher (RSS) in Bytes
+    pub memory_bytes: u64,
+    /// CPU-Last über alle Kerne, 0–100 %
+    pub cpu_percent: f32,
+    pub uptime_secs: u64,
+}
+
+static LATEST: Mutex<Vec<InstanceMetrics>> = Mutex::new(Vec::new());
+
+/// Letzte Messung aller laufenden Instanzen
+pub fn latest_metrics() -> Vec<InstanceMetrics> {
+    LATEST.lock().map(|m| m.clone()).unwrap_or_default()
+}
+
+/// Summiert Speicher und CPU eines Prozesses samt aller Kindprozesse
+fn sample_tree(sys: &sysinfo::System, children: &HashMap<sysinfo::Pid, Vec<sysinfo::Pid>>, root: sysinfo::Pid) -> (u64, f32) {
+    let mut memory = 0;
+    let mut cpu = 0.0;
+    let mut stack = vec![root];
+    while let Some(pid) = stack.pop() {
+        if let Some(process) = sys.process(pid) {
+            memory += process.memory();
+            cpu += process.cpu_usage();
+        }
+        if let Some(kids) = children.get(&pid) {
+            stack.extend(kids);
+        }
+    }
+    (memory, cpu)
+}
+
+/// Startet die Überwachung in einem eigenen Thread. `on_sample` wird bei jeder Messung
+/// mit laufenden Instanzen aufgerufen und einmal mit leerer Liste, wenn die letzte endet.
+pub fn start(on_sample: impl Fn(&[InstanceMetrics]) + Send + 'static) {
+    std::thread::spawn(move || {
+        let mut sys = sysinfo::System::new();
+        let cores = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1) as f32;
+        let mut was_running = false;
+
+        loop {
+            std::thread::sleep(SAMPLE_INTERVAL);
+            let running = super::running_processes_snapshot();
+            if running.is_empty() {
+                if was_running {
+                    if let Ok(mut latest) = LATEST.lock() {
+                        latest.clear();
+                    }
+                    on_sample(&[]);
+                    was_running = false;
+                }
+                continue;
+            }
+            was_running = true;
+
+            sys.refresh_processes();
+            let mut children: HashMap<sysinfo::Pid, Vec<sysinfo::Pid>> = HashMap::new();
+            for (pid, process) in sys.processes() {
+                if let Some(parent) = process.parent() {
+                    children.entry(parent).or_default().push(*pid);
+                }
+            }
+
+            let metrics: Vec<InstanceMetrics> = running
+                .into_iter()
+                .map(|(profile_id, pid)| {
+                    let root = sysinfo::Pid::from_u32(pid);
+                    let (memory_bytes, cpu) = sample_tree(&sys, &children, root);
+                    InstanceMetrics {
+                        profile_id,
+                        pid,
+                        memory_bytes,
+                        cpu_percent: (cpu / cores).min(100.0),
+                        uptime_secs: sys.process(root).map(|p| p.run_time()).unwrap_or(0),
+                    }
+                })
+                .collect();
+
+            if let Ok(mut latest) = LATEST.lock() {
+                *latest = metrics.clone();
+            }
+            on_sample(&metrics);
+        }
+    });
+}
@@ -2,29 +2,25 @@ use anyhow::{Result, bail};
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 use serde::Deserialize;
+use super::install_cache;
+use super::classpath::ClasspathBuilder;
+use crate::core::download::DownloadManager;
 
 // NeoForge Installation und Launch-Logik
 // Basierend auf PandoraLauncher und PrismLauncher Best Practices
 
 /// Ermittelt die neueste NeoForge-Version für eine Minecraft-Version dynamisch von der API
-async fn get_latest_neoforge_version(mc_version: &str) -> Result<String> {
+async fn get_latest_neoforge_version(download_manager: &DownloadManager, mc_version: &str) -> Result<String> {
     tracing::info!("🔍 Searching for NeoForge versions for Minecraft {}...", mc_version);
 
     // Verwende die NeoForge Maven-Metadata API
     let maven_metadata_url = "https://maven.neoforged.net/releases/net/neoforged/neoforge/maven-metadata.xml";
 
-    let response = match reqwest::get(maven_metadata_url).await {
-        Ok(r) => r,
-        Err(e) => {
-            tracing::warn!("⚠️  Failed to fetch NeoForge versions: {}", e);
-            return get_fallback_version(mc_version);
-        }
-    };
-
-    let xml = match response.text().await {
+    let xml = match download_manager.fetch_text(maven_metadata_url).await {
         Ok(text) => text,
+        Err(e) if e.is::<crate::core::download::Cancelled>() => return Err(e),
         Err(e) => {
-            tracing::warn!("⚠️  Failed to read metadata: {}", e);
+            tracing::warn!("⚠️  Failed to fetch NeoForge versions: {}", e);
             return get_fallback_version(mc_version);
         }
     };
@@ -153,6 +149,7 @@ fn get_fallback_version(mc_version: &str) -> Result<String> {
 }
 
 #[derive(Debug)]
+#[derive(serde::Serialize, serde::Deserialize)]
 pub struct NeoForgeInstallation {
     pub main_class: String,
     pub classpath: Vec<String>,
@@ -197,8 +194,10 @@ struct NeoForgeArguments {
     game: Option<Vec<serde_json::Value>>,
 }
 
-/// Installiert NeoForge und bereitet die Launch-Konfiguration vor
+/// Installiert NeoForge und bereitet die Launch-Konfiguration vor. Alle Downloads und der
+/// Installer laufen über `download_manager` und lassen sich mit dessen Token abbrechen.
 pub async fn install_neoforge(
+    download_manager: &DownloadManager,
     mc_version: &str,
     neoforge_version: &str,
     libraries_dir: &Path,
@@ -208,24 +207,38 @@ pub async fn install_neoforge(
 ) -> Result<NeoForgeInstallation> {
     // Wenn "latest" angegeben wurde, ermittle die tatsächliche Version
     let actual_version = if neoforge_version == "latest" || neoforge_version.is_empty() {
-        let latest = get_latest_neoforge_version(mc_version).await?;
+        let latest = get_latest_neoforge_version(download_manager, mc_version).await?;
         tracing::info!("🔍 Resolved 'latest' to NeoForge version: {}", latest);
         latest
     } else {
         neoforge_version.to_string()
     };
 
+    // Bereits aufgelöste Installation wiederverwenden (solange alle Dateien noch existieren)
+    let cache_path = install_cache::cache_path(libraries_dir, "neoforge", mc_version, &actual_version);
+    if let Some(cached) = install_cache::load(&cache_path, |inst: &NeoForgeInstallation| {
+        inst.classpath.iter()
+            .chain(&inst.module_path)
+            .chain(std::iter::once(&inst.minecraft_jar))
+            .map(PathBuf::from)
+            .collect()
+    }) {
+        tracing::info!("Using cached NeoForge install result for {}", actual_version);
+        return Ok(cached);
+    }
+
     tracing::info!("🔨 Installing NeoForge {} for Minecraft {}", actual_version, mc_version);
 
     // 1. Lade den NeoForge-Installer
-    let installer_path = download_neoforge_installer(&actual_version, libraries_dir).await?;
+    let installer_path = download_neoforge_installer(download_manager, &actual_version, libraries_dir).await?;
 
     // 2. Führe den Installer aus um die PATCHED-Client-JAR zu erstellen
     let launcher_dir = libraries_dir.parent().unwrap();
-    run_neoforge_installer(&installer_path, launcher_dir, java_path, mc_version, &actual_version).await?;
+    run_neoforge_installer(download_manager, &installer_path, launcher_dir, java_path, mc_version, &actual_version).await?;
 
     // 3. Extrahiere die version.json aus dem Installer
-    let version_json = extract_version_json(&installer_path)?;
+    let installer_jar = installer_path.to_path_buf();
+    let version_json = crate::utils::compression::run_blocking(move || extract_version_json(&installer_jar)).await?;
     let version: NeoForgeVersion = serde_json::from_str(&version_json)?;
 
     tracing::info!("✅ NeoForge main class: {}", version.main_class);
@@ -271,11 +284,10 @@ pub async fn install_neoforge(
                 if let Some(artifact_info) = &downloads.artifact {
                     tracing::info!("📥 Downloading: {}", lib.name);
 
-                    tokio::fs::create_dir_all(lib_path.parent().unwrap()).await.ok();
-
-                    let response = reqwest::get(&artifact_info.url).await?;
-                    let bytes = response.bytes().await?;
-                    tokio::fs::write(&lib_path, &bytes).await?;
+                    // Über eine .part-Datei: ein Abbruch hinterlässt keine halbe Library
+                    download_manager
+                        .download_with_hash(&artifact_info.url, &lib_path, artifact_info.sha1.as_deref())
+                        .await?;
                 }
             } else {
                 tracing::warn!("⚠️  No download info for: {}", lib.name);
@@ -345,7 +357,7 @@ pub async fn install_neoforge(
                 if let Some(s) = arg.as_str() {
                     let processed = s
                         .replace("${library_directory}", &libraries_dir.display().to_string())
-                        .replace("${classpath_separator}", if cfg!(windows) { ";" } else { ":" })
+                        .replace("${classpath_separator}", ClasspathBuilder::separator())
                         .replace("${version_name}", &actual_version);
                     jvm_args.push(processed);
                 }
@@ -381,18 +393,21 @@ pub async fn install_neoforge(
     tracing::info!("   Module path: {} entries", module_path.len());
     tracing::info!("   JVM args: {} entries", jvm_args.len());
 
-    Ok(NeoForgeInstallation {
+    let installation = NeoForgeInstallation {
         main_class: version.main_class,
         classpath,
         module_path,
         jvm_args,
         game_args,
         minecraft_jar: minecraft_jar.display().to_string(),
-    })
+    };
+    install_cache::store(&cache_path, &installation);
+    Ok(installation)
 }
 
 /// Lädt den NeoForge-Installer herunter
 async fn download_neoforge_installer(
+    download_manager: &DownloadManager,
     neoforge_version: &str,
     libraries_dir: &Path,
 ) -> Result<PathBuf> {
@@ -415,11 +430,7 @@ async fn download_neoforge_installer(
 
     tracing::info!("📥 Downloading NeoForge installer from: {}", url);
 
-    let response = reqwest::get(&url).await?;
-    let bytes = response.bytes().await?;
-
-    tokio::fs::create_dir_all(installer_path.parent().unwrap()).await?;
-    tokio::fs::write(&installer_path, &bytes).await?;
+    download_manager.download_file(&url, &installer_path, None::<fn(u64, u64)>).await?;
 
     tracing::info!("✅ NeoForge installer downloaded");
     Ok(installer_path)
@@ -427,6 +438,7 @@ async fn download_neoforge_installer(
 
 /// Führt den NeoForge-Installer aus
 async fn run_neoforge_installer(
+    download_manager: &DownloadManager,
     installer_path: &Path,
     launcher_dir: &Path,
     java_path: &str,
@@ -459,7 +471,8 @@ async fn run_neoforge_installer(
 
     tracing::info!("🔨 Running NeoForge installer (this may take 1-2 minutes)...");
 
-    let mut cmd = Command::new(java_path);
+    download_manager.check_cancelled()?;
+    let mut cmd = tokio::process::Command::new(java_path);
     cmd.arg("-jar");
     cmd.arg(installer_path);
     cmd.arg("--installClient");
@@ -467,8 +480,12 @@ async fn run_neoforge_installer(
     cmd.current_dir(launcher_dir);
     cmd.stdout(Stdio::piped());
     cmd.stderr(Stdio::piped());
+    // Bei einem Abbruch wird das Warten verworfen und der Installer damit beendet
+    cmd.kill_on_drop(true);
 
-    let output = cmd.output()?;
+    let child = cmd.spawn()?;
+    let output = download_manager.cancellable(child.wait_with_output()).await??;
+    download_manager.check_cancelled()?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -608,16 +625,14 @@ pub fn build_launch_command(
     installation: &NeoForgeInstallation,
     java_path: &str,
     memory_mb: u32,
+    garbage_collector: crate::types::profile::GarbageCollector,
     java_version: u32,
     game_dir: &Path,
-    assets_dir: &Path,
     natives_dir: &Path,
     libraries_dir: &Path,
-    username: &str,
-    uuid: &str,
-    access_token: &str,
     version: &str,
-    asset_index: &str,
+    vanilla_game_args: &[String],
+    debug_args: &[String],
 ) -> Command {
     // Auf Windows javaw.exe nutzen um kein CMD-Fenster zu öffnen.
     // Tauri-Apps sind windowless (windows_subsystem = "windows"), daher würde java.exe
@@ -654,7 +669,7 @@ pub fn build_launch_command(
 
     // Plattform-optimierte JVM-Flags (Xmx/Xms + G1GC-Tuning + OS-spezifische Flags)
     let os_name = std::env::consts::OS; // "linux", "windows", "macos"
-    for flag in super::get_jvm_flags(os_name, java_version, memory_mb) {
+    for flag in super::get_jvm_flags(os_name, java_version, memory_mb, garbage_collector) {
         cmd.arg(flag);
     }
     // java.library.path: Standard-JVM-Pfad für native Bibliotheken (alle Versionen)
@@ -707,25 +722,29 @@ pub fn build_launch_command(
     cmd.arg("-Djava.net.preferIPv6Addresses=system");
     cmd.arg(format!("-DignoreList={}.jar,client-extra", version));
     cmd.arg(format!("-DlibraryDirectory={}", libraries_dir.display()));
-    let cp_sep = if cfg!(windows) { ";" } else { ":" };
-    cmd.arg(format!("-DlegacyClassPath={}", installation.classpath.join(cp_sep)));
+    cmd.arg(format!("-DlegacyClassPath={}", ClasspathBuilder::from_entries(&installation.classpath).build()));
 
     // NeoForge JVM-Args
     for arg in &installation.jvm_args {
         cmd.arg(arg);
     }
 
+    // Debug-Optionen des Profils (nach den Standard-Properties, damit sie diese überschreiben)
+    for arg in debug_args {
+        cmd.arg(arg);
+    }
+
     // Module Path (falls vorhanden)
     if !installation.module_path.is_empty() {
         cmd.arg("-p");
-        cmd.arg(installation.module_path.join(cp_sep));
+        cmd.arg(ClasspathBuilder::from_entries(&installation.module_path).build());
         cmd.arg("--add-modules");
         cmd.arg("ALL-MODULE-PATH");
     }
 
     // Classpath - KRITISCH!
     cmd.arg("-cp");
-    cmd.arg(installation.classpath.join(cp_sep));
+    cmd.arg(ClasspathBuilder::from_entries(&installation.classpath).build());
 
     // Main Class
     cmd.arg(&installation.main_class);
@@ -735,23 +754,8 @@ pub fn build_launch_command(
         cmd.arg(arg);
     }
 
-    // userType: "msa" für Microsoft-Accounts, "legacy" für Offline-Accounts.
-    // NICHT hardcoden – bei Offline-Accounts führt "msa" zu Auth-Fehlern.
-    let user_type = if !access_token.is_empty() && access_token != "0" {
-        "msa"
-    } else {
-        "legacy"
-    };
-
-    // Vanilla Game Args
-    cmd.arg("--username").arg(username);
-    cmd.arg("--version").arg(version);
-    cmd.arg("--gameDir").arg(game_dir);
-    cmd.arg("--assetsDir").arg(assets_dir);
-    cmd.arg("--assetIndex").arg(asset_index);
-    cmd.arg("--uuid").arg(uuid);
-    cmd.arg("--accessToken").arg(access_token);
-    cmd.arg("--userType").arg(user_type);
+    // Vanilla Game Args (aus der Version-JSON), soweit NeoForge sie nicht selbst setzt
+    cmd.args(super::arguments::missing_options(&installation.game_args, vanilla_game_args));
 
     cmd.current_dir(game_dir);
 
@@ -0,0 +1,117 @@
+//! Verwaltung laufender Spielinstanzen: PID, Profil und Startzeit jeder Instanz.
+//! Start und Ende werden über den Lifecycle-Listener gemeldet, damit die Oberfläche
+//! Play/Stop-Buttons korrekt anzeigen kann.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+use super::{InstanceLifecycle, exit_report, notify_lifecycle};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RunningInstance {
+    pub profile_id: String,
+    pub pid: u32,
+    pub started_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl RunningInstance {
+    pub fn uptime_secs(&self) -> u64 {
+        (chrono::Utc::now() - self.started_at).num_seconds().max(0) as u64
+    }
+}
+
+/// Eine Instanz pro Profil
+pub struct ProcessManager {
+    instances: Mutex<HashMap<String, RunningInstance>>,
+}
+
+static MANAGER: OnceLock<ProcessManager> = OnceLock::new();
+
+impl ProcessManager {
+    pub fn global() -> &'static ProcessManager {
+        MANAGER.get_or_init(|| ProcessManager { instances: Mutex::new(HashMap::new()) })
+    }
+
+    /// Registriert einen gerade gestarteten Spielprozess
+    pub fn register(&self, profile_id: &str, pid: u32) -> RunningInstance {
+        let instance = RunningInstance {
+            profile_id: profile_id.to_string(),
+            pid,
+            started_at: chrono::Utc::now(),
+        };
+        if let Ok(mut instances) = self.instances.lock() {
+            instances.insert(profile_id.to_string(), instance.clone());
+        }
+        notify_lifecycle(InstanceLifecycle::Started { profile_id: profile_id.to_string(), pid });
+        instance
+    }
+
+    /// Entfernt eine beendete Instanz. Nur wenn sie mit dieser PID noch registriert war,
+    /// wird das Ende gemeldet – nach `kill` (oder einem schnellen Neustart des Profils)
+    /// entfernt der Warte-Task der alten Instanz so nicht die neue.
+    pub fn unregister(&self, profile_id: &str, pid: u32, exit_code: Option<i32>) -> Option<RunningInstance> {
+        let removed = {
+            let mut instances = self.instances.lock().ok()?;
+            if instances.get(profile_id)?.pid != pid {
+                return None;
+            }
+            instances.remove(profile_id)?
+        };
+        notify_lifecycle(InstanceLifecycle::Exited {
+            profile_id: profile_id.to_string(),
+            exit_code,
+            uptime_secs: removed.uptime_secs(),
+        });
+        Some(removed)
+    }
+
+    pub fn get(&self, profile_id: &str) -> Option<RunningInstance> {
+        self.instances.lock().ok()?.get(profile_id).cloned()
+    }
+
+    pub fn is_running(&self, profile_id: &str) -> bool {
+        self.instances.lock().is_ok_and(|i| i.contains_key(profile_id))
+    }
+
+    /// Alle laufenden Instanzen, zuerst gestartete zuerst
+    pub fn list(&self) -> Vec<RunningInstance> {
+        let mut instances: Vec<RunningInstance> = self.instances.lock()
+            .map(|i| i.values().cloned().collect())
+            .unwrap_or_default();
+        instances.sort_by_key(|i| i.started_at);
+        instances
+    }
+
+    /// Beendet die Instanz eines Profils. Ohne `force` wird das Spiel gebeten, sich zu
+    /// beenden (SIGTERM / Fenster schließen), mit `force` sofort abgeschossen (SIGKILL /
+    /// `taskkill /F`). `false`, wenn keine läuft. Abgemeldet wird die Instanz erst vom
+    /// Warte-Task, wenn der Prozess tatsächlich beendet ist (das Spiel speichert evtl. noch).
+    pub fn kill(&self, profile_id: &str, force: bool) -> bool {
+        let Some(instance) = self.get(profile_id) else { return false };
+        let pid = instance.pid;
+        tracing::info!("Stopping Minecraft process PID {} for profile {} (force: {})", pid, profile_id, force);
+        exit_report::mark_stopped_by_user(profile_id);
+        #[cfg(unix)]
+        {
+            let signal = if force { libc::SIGKILL } else { libc::SIGTERM };
+            unsafe { libc::kill(pid as libc::pid_t, signal); }
+        }
+        #[cfg(windows)]
+        {
+            let mut cmd = std::process::Command::new("taskkill");
+            cmd.args(["/PID", &pid.to_string()]);
+            if force {
+                cmd.arg("/F");
+            }
+            match cmd.status() {
+                Ok(status) if !status.success() => {
+                    tracing::warn!("taskkill for PID {} exited with {}", pid, status);
+                }
+                Ok(_) => {}
+                Err(e) => tracing::warn!("taskkill for PID {} could not be started: {}", pid, e),
+            }
+        }
+        true
+    }
+}
@@ -0,0 +1,67 @@
+use anyhow::{Result, bail};
+use serde::{Deserialize, Serialize};
+
+// Realms-API: Liste der Realms auf die der Account Zugriff hat (eigene und eingeladene).
+// Beitreten läuft über Quick Play (`--quickPlayRealms <id>`, ab 1.20).
+
+const REALMS_API_URL: &str = "https://pc.realms.minecraft.net";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RealmInfo {
+    pub id: i64,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub motd: Option<String>,
+    #[serde(default)]
+    pub owner: Option<String>,
+    #[serde(default, rename(deserialize = "ownerUUID"))]
+    pub owner_uuid: Option<String>,
+    /// "OPEN", "CLOSED" oder "UNINITIALIZED"
+    #[serde(default)]
+    pub state: String,
+    #[serde(default)]
+    pub expired: bool,
+    #[serde(default)]
+    pub max_players: Option<u32>,
+}
+
+impl RealmInfo {
+    /// Nur offene, nicht abgelaufene Realms können betreten werden
+    pub fn is_joinable(&self) -> bool {
+        self.state == "OPEN" && !self.expired
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct RealmsResponse {
+    #[serde(default)]
+    servers: Vec<RealmInfo>,
+}
+
+/// Lädt alle Realms des Accounts. Die Realms-API erwartet die Session als Cookie
+/// und prüft die Spielversion, daher wird die Version des Profils mitgeschickt.
+pub async fn list_realms(access_token: &str, uuid: &str, username: &str, minecraft_version: &str) -> Result<Vec<RealmInfo>> {
+    let client = reqwest::Client::builder()
+        .user_agent("Lion-Launcher/1.0")
+        .build()?;
+
+    let response = client
+        .get(format!("{}/worlds", REALMS_API_URL))
+        .header(
+            "Cookie",
+            format!("sid=token:{}:{};user={};version={}", access_token, uuid, username, minecraft_version),
+        )
+        .send()
+        .await?;
+
+    match response.status().as_u16() {
+        401 => bail!("Realms: Sitzung ungültig – bitte erneut anmelden"),
+        403 => bail!("Realms: Kein Zugriff (Account besitzt das Spiel nicht oder Version {} wird nicht unterstützt)", minecraft_version),
+        _ => {}
+    }
+
+    let realms: RealmsResponse = response.error_for_status()?.json().await?;
+    Ok(realms.servers)
+}
@@ -0,0 +1,99 @@
+//! `os`-Bedingungen in Library- und Argument-Regeln: Name, Architektur und Versions-Regex
+//! werden wie im Vanilla-Launcher gegen das laufende System geprüft.
+
+use std::sync::OnceLock;
+
+use super::OsRule;
+
+/// Schreibweisen der aktuellen Architektur, wie sie in Version-JSONs vorkommen
+fn arch_aliases() -> &'static [&'static str] {
+    match std::env::consts::ARCH {
+        "x86_64" => &["x86_64", "amd64", "x64"],
+        "x86" => &["x86", "i386", "i686"],
+        "aarch64" => &["aarch64", "arm64"],
+        "arm" => &["arm", "arm32"],
+        _ => &[],
+    }
+}
+
+/// sysinfo meldet unter Windows `"10 (19045)"` bzw. `"11 (22631)"`, Java dagegen `"10.0"`
+/// für Windows 10 und 11. Ältere Versionen (ohne Major-Version in der Registry) bleiben leer.
+fn java_windows_version(sysinfo_version: &str) -> String {
+    let major = sysinfo_version.split_whitespace().next().and_then(|m| m.parse::<u32>().ok());
+    match major {
+        Some(major) if major >= 10 => "10.0".to_string(),
+        _ => String::new(),
+    }
+}
+
+/// Entspricht Javas `os.version`: Kernel-Version unter Linux, `10.0` unter Windows 10/11,
+/// sonst die OS-Version (macOS z.B. `14.2.1`)
+fn os_version() -> &'static str {
+    static VERSION: OnceLock<String> = OnceLock::new();
+    VERSION.get_or_init(|| {
+        if cfg!(target_os = "linux") {
+            sysinfo::System::kernel_version().unwrap_or_default()
+        } else if cfg!(windows) {
+            java_windows_version(&sysinfo::System::os_version().unwrap_or_default())
+        } else {
+            sysinfo::System::os_version().unwrap_or_default()
+        }
+    })
+}
+
+/// Prüft eine `os`-Bedingung gegen das aktuelle System (`os` wie von `get_os`)
+pub(super) fn os_matches(rule: &OsRule, os: &str) -> bool {
+    if let Some(name) = &rule.name {
+        let name = if name == "macos" { "osx" } else { name.as_str() };
+        if name != os {
+            return false;
+        }
+    }
+    if let Some(arch) = &rule.arch {
+        let current = std::env::consts::ARCH;
+        if !arch.eq_ignore_ascii_case(current) && !arch_aliases().iter().any(|a| arch.eq_ignore_ascii_case(a)) {
+            return false;
+        }
+    }
+    if let Some(pattern) = &rule.version {
+        if !version_matches(pattern, os_version()) {
+            return false;
+        }
+    }
+    true
+}
+
+/// Versionsmuster aus der Version-JSON (z.B. `^10\.5\.\d$`), wie in Java per `find()`
+fn version_matches(pattern: &str, version: &str) -> bool {
+    match regex::Regex::new(pattern) {
+        Ok(re) => re.is_match(version),
+        Err(e) => {
+            tracing::debug!("Invalid os.version pattern {:?}: {}", pattern, e);
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn windows_version_matches_java() {
+        assert_eq!(java_windows_version("10 (19045)"), "10.0");
+        assert_eq!(java_windows_version("11 (22631)"), "10.0");
+        assert_eq!(java_windows_version("0 ()"), "");
+    }
+
+    #[test]
+    fn mojang_version_patterns() {
+        assert!(version_matches(r"^10\.", "10.0"));
+        assert!(version_matches(r"^10\.", "10.15.7"));
+        assert!(!version_matches(r"^10\.", "6.1"));
+        assert!(!version_matches(r"^10\.", "11.0"));
+        assert!(version_matches(r"^10\.5\.\d$", "10.5.8"));
+        assert!(!version_matches(r"^10\.5\.\d$", "10.5.10"));
+        assert!(!version_matches(r"^10\.5\.\d$", "10.6.1"));
+        assert!(!version_matches("(", "10.0"));
+    }
+}
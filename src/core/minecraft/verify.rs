@@ -0,0 +1,348 @@
+//! Gezielte Prüfung der Spieldateien eines Profils: Version-JSON, Client-JAR, Libraries,
+//! Natives, Assets und Loader-Dateien. Fehlende oder beschädigte Dateien werden mit ihrer
+//! Größe gemeldet und auf Wunsch einzeln neu geladen – statt wie `repair_profile` alles
+//! zu löschen.
+//!
+//! Client, Libraries und Assets werden per SHA-1 aus dem Manifest geprüft. Für
+//! Loader-Dateien gibt es keine Hashes; sie gelten als beschädigt, wenn das JAR kein
+//! gültiges ZIP ist.
+
+use anyhow::Result;
+use rayon::prelude::*;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use crate::core::download::sha1_hex;
+use crate::types::profile::Profile;
+use crate::types::version::ModLoader;
+use super::{defaults, install_cache, meta_cache, AssetIndex, MinecraftLauncher, RESOURCES_URL};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileKind {
+    VersionJson,
+    Client,
+    Library,
+    Native,
+    AssetIndex,
+    Asset,
+    Loader,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileProblem {
+    Missing,
+    Corrupt,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FileIssue {
+    pub kind: FileKind,
+    pub path: String,
+    pub problem: FileProblem,
+    /// Größe laut Manifest, falls bekannt
+    pub expected_size: Option<u64>,
+    /// Größe der vorhandenen Datei
+    pub actual_size: Option<u64>,
+    pub repaired: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct VerificationReport {
+    pub checked: usize,
+    pub issues: Vec<FileIssue>,
+    /// Summe der erwarteten Größen aller fehlerhaften Dateien (soweit bekannt)
+    pub issue_bytes: u64,
+    pub repaired: usize,
+}
+
+impl VerificationReport {
+    fn add(&mut self, issue: FileIssue) {
+        self.issue_bytes += issue.expected_size.unwrap_or(0);
+        if issue.repaired {
+            self.repaired += 1;
+        }
+        self.issues.push(issue);
+    }
+}
+
+/// Eine zu prüfende Datei; ohne `url` kann sie nicht einzeln neu geladen werden
+struct ExpectedFile {
+    kind: FileKind,
+    path: PathBuf,
+    sha1: Option<String>,
+    size: Option<u64>,
+    url: Option<String>,
+}
+
+/// Blockierend: `None`, wenn die Datei in Ordnung ist
+fn check_file(file: &ExpectedFile) -> Option<FileIssue> {
+    let issue = |problem, actual_size| FileIssue {
+        kind: file.kind,
+        path: file.path.display().to_string(),
+        problem,
+        expected_size: file.size,
+        actual_size,
+        repaired: false,
+    };
+
+    let Ok(meta) = std::fs::metadata(&file.path) else {
+        return Some(issue(FileProblem::Missing, None));
+    };
+    let actual_size = Some(meta.len());
+    let valid = if file.size.is_some_and(|size| size != meta.len()) {
+        false
+    } else if let Some(sha1) = &file.sha1 {
+        std::fs::read(&file.path)
+            .is_ok_and(|data| sha1_hex(&data).eq_ignore_ascii_case(sha1))
+    } else {
+        MinecraftLauncher::is_valid_zip(&file.path)
+    };
+    (!valid).then(|| issue(FileProblem::Corrupt, actual_size))
+}
+
+async fn check_files(files: Vec<ExpectedFile>) -> Result<Vec<(ExpectedFile, FileIssue)>> {
+    crate::utils::compression::run_blocking(move || {
+        Ok(files
+            .into_par_iter()
+            .filter_map(|file| check_file(&file).map(|issue| (file, issue)))
+            .collect())
+    })
+    .await
+}
+
+/// Alle JAR-Pfade in einem gecachten Forge/NeoForge-Installationsergebnis
+fn collect_jar_paths(value: &serde_json::Value, out: &mut Vec<PathBuf>) {
+    match value {
+        serde_json::Value::String(s) if s.ends_with(".jar") && Path::new(s).is_absolute() => out.push(PathBuf::from(s)),
+        serde_json::Value::Array(values) => values.iter().for_each(|v| collect_jar_paths(v, out)),
+        serde_json::Value::Object(map) => map.values().for_each(|v| collect_jar_paths(v, out)),
+        _ => {}
+    }
+}
+
+/// Loader-Dateien: Forge/NeoForge laut Installations-Cache, Fabric/Quilt alle vorhandenen
+/// JARs im Maven-Verzeichnis des Loaders (fehlende lassen sich dort nicht erkennen)
+fn loader_files(loader: &ModLoader, mc_version: &str, libraries_dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    match loader {
+        ModLoader::Forge | ModLoader::NeoForge => {
+            for cache in install_cache::entries(libraries_dir, Some(loader.as_str()), Some(mc_version)) {
+                let value = std::fs::read_to_string(&cache)
+                    .ok()
+                    .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok());
+                if let Some(value) = value {
+                    collect_jar_paths(&value, &mut files);
+                }
+            }
+        }
+        ModLoader::Fabric | ModLoader::Quilt => {
+            let dir = match loader {
+                ModLoader::Fabric => libraries_dir.join("net").join("fabricmc"),
+                _ => libraries_dir.join("org").join("quiltmc"),
+            };
+            files.extend(
+                walkdir::WalkDir::new(dir)
+                    .into_iter()
+                    .flatten()
+                    .filter(|e| e.file_type().is_file() && e.path().extension().is_some_and(|x| x == "jar"))
+                    .map(|e| e.into_path()),
+            );
+        }
+        ModLoader::Vanilla => {}
+    }
+    files.sort();
+    files.dedup();
+    files
+}
+
+impl MinecraftLauncher {
+    /// Prüft die Dateien eines Profils; mit `repair` werden fehlerhafte Dateien einzeln neu
+    /// geladen und der Loader bei Bedarf neu installiert. Fehlt die Version-JSON, ist ohne
+    /// `repair` keine weitere Prüfung möglich und nur das wird gemeldet.
+    pub async fn verify_profile_files(&self, profile: &Profile, repair: bool) -> Result<VerificationReport> {
+        let version = &profile.minecraft_version;
+        let libraries_dir = defaults::libraries_dir();
+        let assets_dir = defaults::assets_dir();
+        let mut report = VerificationReport::default();
+
+        // 1. Version-JSON (der Metadaten-Cache prüft sie beim Laden per SHA-1)
+        let json_name = format!("versions/{}.json", version);
+        let json_cached = meta_cache::read_cached(&json_name).is_some();
+        if !json_cached && !repair {
+            // Ohne Reparatur nichts herunterladen: fehlende Version-JSON nur melden
+            report.checked += 1;
+            report.add(FileIssue {
+                kind: FileKind::VersionJson,
+                path: meta_cache::path(&json_name).display().to_string(),
+                problem: FileProblem::Missing,
+                expected_size: None,
+                actual_size: None,
+                repaired: false,
+            });
+            return Ok(report);
+        }
+        let version_info = self.get_version_info(version).await?;
+        report.checked += 1;
+        if !json_cached {
+            report.add(FileIssue {
+                kind: FileKind::VersionJson,
+                path: meta_cache::path(&json_name).display().to_string(),
+                problem: FileProblem::Missing,
+                expected_size: None,
+                actual_size: None,
+                repaired: true,
+            });
+        }
+
+        // 2. Client, Libraries und Natives
+        let client = &version_info.downloads.client;
+        let mut files = vec![ExpectedFile {
+            kind: FileKind::Client,
+            path: defaults::versions_dir().join(version).join(format!("{}.jar", version)),
+            sha1: Some(client.sha1.clone()),
+            size: client.size,
+            url: Some(client.url.clone()),
+        }];
+        let os = Self::get_os();
+        for lib in &version_info.libraries {
+            if lib.rules.as_ref().is_some_and(|rules| !self.check_rules(rules)) {
+                continue;
+            }
+            let Some(downloads) = &lib.downloads else { continue };
+            if let Some(artifact) = &downloads.artifact {
+                let kind = if artifact.path.contains("natives-") { FileKind::Native } else { FileKind::Library };
+                files.push(ExpectedFile {
+                    kind,
+                    path: libraries_dir.join(&artifact.path),
+                    sha1: Some(artifact.sha1.clone()),
+                    size: artifact.size,
+                    url: Some(artifact.url.clone()),
+                });
+            }
+            let classifier = lib.natives.as_ref()
+                .and_then(|natives| Self::native_classifier(natives, &os))
+                .and_then(|key| downloads.classifiers.as_ref()?.get(&key));
+            if let Some(native) = classifier {
+                files.push(ExpectedFile {
+                    kind: FileKind::Native,
+                    path: libraries_dir.join(&native.path),
+                    sha1: Some(native.sha1.clone()),
+                    size: native.size,
+                    url: Some(native.url.clone()),
+                });
+            }
+        }
+
+        // 3. Asset-Index – vor den Objekten, da diese erst aus dem Index bekannt sind
+        let index_info = &version_info.assetIndex;
+        let index_file = ExpectedFile {
+            kind: FileKind::AssetIndex,
+            path: assets_dir.join("indexes").join(format!("{}.json", index_info.id)),
+            sha1: Some(index_info.sha1.clone()),
+            size: index_info.size,
+            url: Some(index_info.url.clone()),
+        };
+        report.checked += 1;
+        let index_issue = check_files(vec![index_file]).await?.pop();
+        let index_usable = match index_issue {
+            None => true,
+            Some((file, issue)) => {
+                let repaired = repair && self.redownload(&file).await;
+                report.add(FileIssue { repaired, ..issue });
+                repaired
+            }
+        };
+        if index_usable {
+            let index_path = assets_dir.join("indexes").join(format!("{}.json", index_info.id));
+            let index: AssetIndex = serde_json::from_str(&tokio::fs::read_to_string(&index_path).await?)?;
+            files.extend(index.objects.values().filter_map(|asset| {
+                let Some(prefix) = asset.hash.get(..2) else {
+                    tracing::warn!("Skipping asset with invalid hash {:?}", asset.hash);
+                    return None;
+                };
+                Some(ExpectedFile {
+                    kind: FileKind::Asset,
+                    path: assets_dir.join("objects").join(prefix).join(&asset.hash),
+                    sha1: Some(asset.hash.clone()),
+                    size: asset.size,
+                    url: Some(format!("{}/{}/{}", RESOURCES_URL, prefix, asset.hash)),
+                })
+            }));
+        }
+
+        // 4. Loader-Dateien
+        let loader = &profile.loader.loader;
+        files.extend(loader_files(loader, version, &libraries_dir).into_iter().map(|path| ExpectedFile {
+            kind: FileKind::Loader,
+            path,
+            sha1: None,
+            size: None,
+            url: None,
+        }));
+
+        report.checked += files.len();
+        let broken = check_files(files).await?;
+        tracing::info!("Verified {} files of {}: {} problems", report.checked, profile.name, broken.len());
+
+        // Einzeln neu laden (bis zu 8 parallel)
+        let mut repaired = vec![false; broken.len()];
+        if repair {
+            for (chunk, results) in broken.chunks(8).zip(repaired.chunks_mut(8)) {
+                let downloads = chunk.iter().map(|(file, _)| self.redownload(file));
+                for (result, ok) in results.iter_mut().zip(futures_util::future::join_all(downloads).await) {
+                    *result = ok;
+                }
+            }
+        }
+
+        // Loader-Dateien ohne Quelle: löschen und den Loader neu installieren lassen
+        let mut loader_broken = Vec::new();
+        for ((file, issue), repaired) in broken.into_iter().zip(repaired) {
+            if file.kind == FileKind::Loader {
+                loader_broken.push(issue);
+            } else {
+                report.add(FileIssue { repaired, ..issue });
+            }
+        }
+        if !loader_broken.is_empty() {
+            let reinstalled = repair && self.reinstall_loader(profile, &loader_broken, &libraries_dir).await;
+            for issue in loader_broken {
+                report.add(FileIssue { repaired: reinstalled, ..issue });
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Lädt eine Datei neu; `false` bei Fehler oder fehlender Quelle
+    async fn redownload(&self, file: &ExpectedFile) -> bool {
+        let Some(url) = &file.url else { return false };
+        tokio::fs::remove_file(&file.path).await.ok();
+        if let Some(parent) = file.path.parent() {
+            tokio::fs::create_dir_all(parent).await.ok();
+        }
+        match self.download_manager.download_with_hash(url, &file.path, file.sha1.as_deref()).await {
+            Ok(()) => true,
+            Err(e) => {
+                tracing::warn!("Could not repair {:?}: {}", file.path, e);
+                false
+            }
+        }
+    }
+
+    async fn reinstall_loader(&self, profile: &Profile, broken: &[FileIssue], libraries_dir: &Path) -> bool {
+        for issue in broken {
+            tokio::fs::remove_file(&issue.path).await.ok();
+        }
+        install_cache::clear(libraries_dir, Some(profile.loader.loader.as_str()), Some(&profile.minecraft_version));
+        match self.install_loader(profile).await {
+            Ok(()) => true,
+            Err(e) => {
+                tracing::warn!("Could not reinstall {} loader: {}", profile.loader.loader, e);
+                false
+            }
+        }
+    }
+}
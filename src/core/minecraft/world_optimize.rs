@@ -0,0 +1,227 @@
+use anyhow::{Context, Result, bail};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use super::{defaults, MinecraftLauncher};
+use crate::types::version::ModLoader;
+
+// "Welt optimieren": startet den Vanilla-Server der Profil-Version mit --forceUpgrade
+// auf einer Kopie der Welt. Erst wenn das Upgrade erfolgreich war, wird das Original
+// gesichert (ZIP) und durch die aktualisierte Kopie ersetzt.
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct WorldOptimizeProgress {
+    pub world: String,
+    /// "preparing", "upgrading", "replacing" oder "done"
+    pub stage: String,
+    pub percent: u8,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct WorldOptimizeResult {
+    pub world: String,
+    pub backup_path: String,
+    pub duration_secs: u64,
+}
+
+/// Staging-Ordner eines Profils (wird nach dem Lauf entfernt)
+fn staging_dir(game_dir: &Path) -> PathBuf {
+    game_dir.join(".world-optimize")
+}
+
+fn copy_world(src: &Path, dst: &Path) -> Result<()> {
+    for entry in walkdir::WalkDir::new(src) {
+        let entry = entry?;
+        let target = dst.join(entry.path().strip_prefix(src)?);
+        if entry.file_type().is_dir() {
+            std::fs::create_dir_all(&target)?;
+        } else if entry.file_name() != "session.lock" {
+            std::fs::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
+
+/// Fortschritt aus einer Server-Zeile wie "... 42% completed (1234 / 2900 chunks)..."
+fn parse_upgrade_percent(line: &str) -> Option<u8> {
+    let idx = line.find("% completed")?;
+    let digits: String = line[..idx].chars().rev().take_while(|c| c.is_ascii_digit()).collect();
+    digits.chars().rev().collect::<String>().parse::<u8>().ok().map(|p| p.min(100))
+}
+
+impl MinecraftLauncher {
+    /// Lädt den Server der Version herunter (falls nötig) und gibt den JAR-Pfad zurück
+    async fn ensure_server_jar(&self, version: &str) -> Result<(PathBuf, u32)> {
+        let version_info = self.get_version_info(version).await?;
+        let server = version_info.downloads.server.as_ref()
+            .with_context(|| format!("Minecraft {} has no server download", version))?;
+
+        let server_jar = defaults::versions_dir().join(version).join(format!("{}-server.jar", version));
+        if !server_jar.exists() {
+            tokio::fs::create_dir_all(server_jar.parent().unwrap()).await?;
+            self.download_manager
+                .download_with_hash(&server.url, &server_jar, Some(&server.sha1))
+                .await?;
+        }
+
+        let required_java = version_info.javaVersion.as_ref().map(|j| j.majorVersion).unwrap_or(8);
+        Ok((server_jar, required_java))
+    }
+
+    /// Aktualisiert alle Chunks einer Welt auf die Version des Profils.
+    /// Der Server startet nur, wenn der Nutzer der Minecraft-EULA ausdrücklich zugestimmt hat.
+    /// Nur für Vanilla-Profile: der Vanilla-Server würde modded Blöcke, Items und Entities entfernen.
+    pub async fn optimize_world(
+        &self,
+        game_dir: &Path,
+        loader: &ModLoader,
+        minecraft_version: &str,
+        folder_name: &str,
+        eula_accepted: bool,
+        on_progress: impl Fn(WorldOptimizeProgress) + Send + Sync + 'static,
+    ) -> Result<WorldOptimizeResult> {
+        if !eula_accepted {
+            bail!("The Minecraft EULA (https://aka.ms/MinecraftEULA) must be accepted to run the server");
+        }
+        if loader.supports_mods() {
+            bail!("World optimization is only supported for vanilla profiles (profile uses {})", loader);
+        }
+        super::worlds::check_folder_name(folder_name)?;
+        let world_dir = game_dir.join("saves").join(folder_name);
+        if !world_dir.join("level.dat").exists() {
+            bail!("World not found: {}", folder_name);
+        }
+        // --forceUpgrade gibt es erst ab 1.13
+        if crate::core::mods::migration::compare_minecraft_versions(minecraft_version, "1.13")
+            .is_some_and(|o| o == std::cmp::Ordering::Less)
+        {
+            bail!("World optimization requires Minecraft 1.13 or newer");
+        }
+
+        let started = std::time::Instant::now();
+        let world = folder_name.to_string();
+        let on_progress = std::sync::Arc::new(on_progress);
+        let progress = on_progress.clone();
+        let report = move |stage: &str, percent: u8, message: String| {
+            progress(WorldOptimizeProgress { world: world.clone(), stage: stage.to_string(), percent, message });
+        };
+
+        report("preparing", 0, format!("Lade Server für Minecraft {}...", minecraft_version));
+        let (server_jar, required_java) = self.ensure_server_jar(minecraft_version).await?;
+        let max_java = if required_java <= 8 { Some(8) } else { None };
+        let java_path = self.ensure_java_installed(required_java, max_java).await?;
+
+        let staging = staging_dir(game_dir);
+        if staging.exists() {
+            tokio::fs::remove_dir_all(&staging).await.ok();
+        }
+
+        report("preparing", 0, "Kopiere Welt...".to_string());
+        let (src, dst) = (world_dir.clone(), staging.join(folder_name));
+        crate::utils::compression::run_blocking(move || copy_world(&src, &dst)).await?;
+        // Die Kopie läuft in einem eigenen Server-Verzeichnis; die Zustimmung hat der Nutzer oben gegeben
+        std::fs::write(staging.join("eula.txt"), "eula=true\n")?;
+
+        let folder = folder_name.to_string();
+        let staging_for_run = staging.clone();
+        let report_for_run = report.clone();
+        let upgrade = crate::utils::compression::run_blocking(move || {
+            let mut child = Command::new(&java_path)
+                .current_dir(&staging_for_run)
+                .arg("-jar")
+                .arg(&server_jar)
+                .args(["--nogui", "--forceUpgrade", "--universe"])
+                .arg(&staging_for_run)
+                .arg("--world")
+                .arg(&folder)
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .spawn()
+                .context("Could not start Minecraft server")?;
+
+            let stdout = child.stdout.take().context("No server output")?;
+            // stderr parallel lesen (sonst kann die Pipe volllaufen) und für Fehlermeldungen behalten
+            let stderr = child.stderr.take().map(|stderr| {
+                std::thread::spawn(move || {
+                    let mut tail: Vec<String> = Vec::new();
+                    for line in BufReader::new(stderr).lines().map_while(|l| l.ok()) {
+                        tail.push(line);
+                        if tail.len() > 20 {
+                            tail.remove(0);
+                        }
+                    }
+                    tail
+                })
+            });
+            let mut stdin = child.stdin.take();
+            let mut finished = false;
+            let mut last_lines = Vec::new();
+            // Nach dem Upgrade fährt der Server hoch; "stop" wird von der Konsole gelesen,
+            // sobald er bereit ist, und beendet ihn sauber (Ausgabe bis EOF weiterlesen)
+            for line in BufReader::new(stdout).lines().map_while(|l| l.ok()) {
+                if let Some(percent) = parse_upgrade_percent(&line) {
+                    report_for_run("upgrading", percent, line.clone());
+                }
+                if line.contains("World optimization finished") {
+                    finished = true;
+                }
+                if finished || line.contains("Done (") {
+                    if let Some(mut stdin) = stdin.take() {
+                        stdin.write_all(b"stop\n").ok();
+                    }
+                }
+                last_lines.push(line);
+                if last_lines.len() > 20 {
+                    last_lines.remove(0);
+                }
+            }
+            let status = child.wait().context("Minecraft server did not exit")?;
+            last_lines.extend(stderr.and_then(|h| h.join().ok()).unwrap_or_default());
+
+            if !status.success() {
+                bail!("Minecraft server exited with {}:\n{}", status, last_lines.join("\n"));
+            }
+            if !finished {
+                bail!("World upgrade did not finish:\n{}", last_lines.join("\n"));
+            }
+            Ok(())
+        })
+        .await;
+
+        if let Err(e) = upgrade {
+            tokio::fs::remove_dir_all(&staging).await.ok();
+            return Err(e);
+        }
+
+        report("replacing", 100, "Sichere Original und übernehme optimierte Welt...".to_string());
+        let (game_dir_owned, folder) = (game_dir.to_path_buf(), folder_name.to_string());
+        let backup_path = crate::utils::compression::run_blocking(move || {
+            let backup = super::worlds::backup_world(&game_dir_owned, &folder)?;
+            let world_dir = game_dir_owned.join("saves").join(&folder);
+            let staging = staging_dir(&game_dir_owned);
+            // Original erst beiseite legen, damit bei einem Fehler nichts verloren geht
+            let old_dir = staging.join(format!("{}.old", folder));
+            std::fs::rename(&world_dir, &old_dir)?;
+            if let Err(e) = std::fs::rename(staging.join(&folder), &world_dir) {
+                std::fs::rename(&old_dir, &world_dir).ok();
+                return Err(e.into());
+            }
+            std::fs::remove_dir_all(&staging).ok();
+            Ok(backup)
+        })
+        .await?;
+
+        report("done", 100, "Welt optimiert".to_string());
+        let duration_secs = started.elapsed().as_secs();
+        tracing::info!("Optimized world {} for {} in {}s", folder_name, minecraft_version, duration_secs);
+
+        Ok(WorldOptimizeResult {
+            world: folder_name.to_string(),
+            backup_path: backup_path.display().to_string(),
+            duration_secs,
+        })
+    }
+}
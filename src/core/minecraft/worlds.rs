@@ -12,6 +12,16 @@ pub struct WorldInfo {
     pub game_mode: String,
     pub difficulty: String,
     pub size_bytes: u64,
+    /// Als Text, da JavaScript 64-Bit-Seeds nicht exakt darstellen kann
+    #[serde(default)]
+    pub seed: Option<String>,
+    /// Minecraft-Version mit der die Welt zuletzt gespeichert wurde (ab 1.9)
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub cheats: bool,
+    #[serde(default)]
+    pub hardcore: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -66,6 +76,10 @@ pub async fn get_worlds(game_dir: &Path) -> Result<Vec<WorldInfo>> {
                 game_mode: "Unknown".to_string(),
                 difficulty: "Unknown".to_string(),
                 size_bytes: 0,
+                seed: None,
+                version: None,
+                cheats: false,
+                hardcore: false,
             });
 
         worlds.push(world_info);
@@ -79,25 +93,25 @@ pub async fn get_worlds(game_dir: &Path) -> Result<Vec<WorldInfo>> {
 
 /// Liest World-Info aus level.dat
 async fn read_world_info(world_path: &Path, folder_name: &str) -> Result<WorldInfo> {
-    use std::io::Read;
-    use flate2::read::GzDecoder;
+    use crate::utils::nbt::Tag;
 
     let level_dat_path = world_path.join("level.dat");
     let data = fs::read(&level_dat_path).await?;
 
-    // level.dat ist gzip-komprimiert
-    let mut decoder = GzDecoder::new(&data[..]);
-    let mut decompressed = Vec::new();
-    decoder.read_to_end(&mut decompressed)?;
+    // level.dat ist gzip-komprimiert, alle Werte liegen unter "Data"
+    let (_, root) = crate::utils::nbt::read(&data)?;
+    let level = root.get("Data").context("level.dat has no Data compound")?;
 
-    // Parse NBT (vereinfacht - wir suchen nach bekannten Strings)
-    let name = extract_nbt_string(&decompressed, "LevelName")
+    let name = level.get("LevelName")
+        .and_then(Tag::as_str)
+        .map(String::from)
         .unwrap_or_else(|| folder_name.to_string());
 
-    let last_played = extract_nbt_long(&decompressed, "LastPlayed")
+    let last_played = level.get("LastPlayed")
+        .and_then(Tag::as_i64)
         .unwrap_or(0);
 
-    let game_mode = match extract_nbt_int(&decompressed, "GameType") {
+    let game_mode = match level.get("GameType").and_then(Tag::as_i64) {
         Some(0) => "Survival",
         Some(1) => "Creative",
         Some(2) => "Adventure",
@@ -105,13 +119,32 @@ async fn read_world_info(world_path: &Path, folder_name: &str) -> Result<WorldIn
         _ => "Unknown",
     }.to_string();
 
-    let difficulty = match extract_nbt_int(&decompressed, "Difficulty") {
-        Some(0) => "Peaceful",
-        Some(1) => "Easy",
-        Some(2) => "Normal",
-        Some(3) => "Hard",
-        _ => "Normal",
-    }.to_string();
+    // Neuere Versionen speichern die Schwierigkeit als Text in "difficulty_settings"
+    let difficulty = match level.get_path(&["difficulty_settings", "difficulty"]).and_then(Tag::as_str) {
+        Some(name) => {
+            let mut chars = name.chars();
+            chars.next().map(|c| c.to_uppercase().chain(chars).collect()).unwrap_or_default()
+        }
+        None => match level.get("Difficulty").and_then(Tag::as_i64) {
+            Some(0) => "Peaceful",
+            Some(1) => "Easy",
+            Some(2) => "Normal",
+            Some(3) => "Hard",
+            _ => "Normal",
+        }.to_string(),
+    };
+
+    let seed = seed_from_level(level).map(|seed| seed.to_string());
+
+    let version = level.get_path(&["Version", "Name"])
+        .and_then(Tag::as_str)
+        .map(String::from);
+
+    let cheats = level.get("allowCommands").and_then(Tag::as_bool).unwrap_or(false);
+    let hardcore = level.get_path(&["difficulty_settings", "hardcore"])
+        .or_else(|| level.get("hardcore"))
+        .and_then(Tag::as_bool)
+        .unwrap_or(false);
 
     // Versuche Icon zu laden
     let icon_path = world_path.join("icon.png");
@@ -135,59 +168,42 @@ async fn read_world_info(world_path: &Path, folder_name: &str) -> Result<WorldIn
         game_mode,
         difficulty,
         size_bytes,
+        seed,
+        version,
+        cheats,
+        hardcore,
     })
 }
 
-/// Extrahiert einen String aus NBT-Daten (vereinfachte Methode)
-fn extract_nbt_string(data: &[u8], key: &str) -> Option<String> {
-    let key_bytes = key.as_bytes();
-
-    // Suche nach dem Key im NBT
-    for i in 0..data.len().saturating_sub(key_bytes.len() + 4) {
-        if &data[i..i + key_bytes.len()] == key_bytes {
-            // Nach dem Key kommt die String-Länge (2 bytes, big-endian) und dann der String
-            let offset = i + key_bytes.len();
-            if offset + 2 < data.len() {
-                let len = ((data[offset] as usize) << 8) | (data[offset + 1] as usize);
-                if offset + 2 + len <= data.len() {
-                    return String::from_utf8(data[offset + 2..offset + 2 + len].to_vec()).ok();
-                }
-            }
-        }
-    }
-    None
-}
-
-/// Extrahiert einen Long aus NBT-Daten (vereinfachte Methode)
-fn extract_nbt_long(data: &[u8], key: &str) -> Option<i64> {
-    let key_bytes = key.as_bytes();
+/// Seed aus dem "Data"-Compound: ab 1.16 in WorldGenSettings, davor RandomSeed
+fn seed_from_level(level: &crate::utils::nbt::Tag) -> Option<i64> {
+    use crate::utils::nbt::Tag;
 
-    for i in 0..data.len().saturating_sub(key_bytes.len() + 8) {
-        if &data[i..i + key_bytes.len()] == key_bytes {
-            let offset = i + key_bytes.len();
-            if offset + 8 <= data.len() {
-                let bytes: [u8; 8] = data[offset..offset + 8].try_into().ok()?;
-                return Some(i64::from_be_bytes(bytes));
-            }
-        }
-    }
-    None
+    level.get_path(&["WorldGenSettings", "seed"])
+        .or_else(|| level.get("RandomSeed"))
+        .and_then(Tag::as_i64)
 }
 
-/// Extrahiert einen Int aus NBT-Daten (vereinfachte Methode)
-fn extract_nbt_int(data: &[u8], key: &str) -> Option<i32> {
-    let key_bytes = key.as_bytes();
+/// Liest nur den Seed einer Welt aus level.dat
+pub async fn get_world_seed(game_dir: &Path, folder_name: &str) -> Result<i64> {
+    check_folder_name(folder_name)?;
+    let level_dat = game_dir.join("saves").join(folder_name).join("level.dat");
+    let data = fs::read(&level_dat).await
+        .with_context(|| format!("World not found: {}", folder_name))?;
+
+    let (_, root) = crate::utils::nbt::read(&data)?;
+    root.get("Data")
+        .and_then(seed_from_level)
+        .context("level.dat contains no seed")
+}
 
-    for i in 0..data.len().saturating_sub(key_bytes.len() + 4) {
-        if &data[i..i + key_bytes.len()] == key_bytes {
-            let offset = i + key_bytes.len();
-            if offset + 4 <= data.len() {
-                let bytes: [u8; 4] = data[offset..offset + 4].try_into().ok()?;
-                return Some(i32::from_be_bytes(bytes));
-            }
-        }
+/// Liest die Infos einer einzelnen Welt
+pub async fn get_world_info(game_dir: &Path, folder_name: &str) -> Result<WorldInfo> {
+    let world_path = game_dir.join("saves").join(folder_name);
+    if !world_path.join("level.dat").exists() {
+        anyhow::bail!("World not found: {}", folder_name);
     }
-    None
+    read_world_info(&world_path, folder_name).await
 }
 
 /// Berechnet die Größe eines Verzeichnisses
@@ -545,6 +561,257 @@ fn find_sequence(data: &[u8], start: usize, seq: &[u8]) -> Option<usize> {
     None
 }
 
+/// Ordner für Welt-Backups eines Profils
+pub fn world_backups_dir(game_dir: &Path) -> std::path::PathBuf {
+    game_dir.join("backups").join("worlds")
+}
+
+/// Nur ein einzelner Ordnername in saves/ – "..", Trennzeichen oder absolute Pfade würden ausbrechen
+pub fn check_folder_name(folder_name: &str) -> Result<()> {
+    let mut components = Path::new(folder_name).components();
+    if !matches!((components.next(), components.next()), (Some(std::path::Component::Normal(_)), None))
+        || folder_name.contains(['/', '\\'])
+    {
+        anyhow::bail!("Invalid world folder name: {}", folder_name);
+    }
+    Ok(())
+}
+
+/// Sichert eine Welt als ZIP nach `backups/worlds/<ordner>-<zeitstempel>.zip`
+/// (blockierend, über `run_blocking` aufrufen)
+pub fn backup_world(game_dir: &Path, folder_name: &str) -> Result<std::path::PathBuf> {
+    check_folder_name(folder_name)?;
+    let world_dir = game_dir.join("saves").join(folder_name);
+    if !world_dir.is_dir() {
+        anyhow::bail!("World not found: {}", folder_name);
+    }
+
+    let backups_dir = world_backups_dir(game_dir);
+    std::fs::create_dir_all(&backups_dir)?;
+    let timestamp = chrono::Local::now().format("%Y-%m-%d_%H-%M-%S");
+    let output = backups_dir.join(format!("{}-{}.zip", folder_name, timestamp));
+
+    crate::utils::compression::compress_directory(&world_dir, &output)
+        .with_context(|| format!("Backup of world {} failed", folder_name))?;
+    tracing::info!("World backup created: {:?}", output);
+    Ok(output)
+}
+
+/// Entpackt eine gezippte Welt nach saves/ und gibt den Ordnernamen zurück.
+/// level.dat darf im Wurzelverzeichnis oder in einem Unterordner des Archivs liegen.
+/// Blockierend, über `run_blocking` aufrufen.
+pub fn import_world_zip(game_dir: &Path, zip_path: &Path) -> Result<String> {
+    let mut archive = zip::ZipArchive::new(std::fs::File::open(zip_path)?)
+        .context("File is not a valid ZIP archive")?;
+
+    // Flachste level.dat bestimmt den Welt-Ordner (macOS-Metadaten ignorieren)
+    let level_dat = archive.file_names()
+        .map(|name| name.replace('\\', "/"))
+        .filter(|name| !name.starts_with("__MACOSX/"))
+        .filter(|name| name == "level.dat" || name.ends_with("/level.dat"))
+        .min_by_key(|name| name.matches('/').count())
+        .context("ZIP contains no level.dat – not a Minecraft world")?;
+    let prefix = level_dat.trim_end_matches("level.dat").to_string();
+
+    let folder_name = match prefix.trim_end_matches('/').rsplit('/').next() {
+        Some(name) if !name.is_empty() => name.to_string(),
+        _ => zip_path.file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| "World".to_string()),
+    };
+    if folder_name.starts_with('.') || folder_name.contains(['/', '\\']) {
+        anyhow::bail!("Invalid world folder name: {}", folder_name);
+    }
+
+    let saves_dir = game_dir.join("saves");
+    std::fs::create_dir_all(&saves_dir)?;
+    let target = crate::core::packs::unique_dir_target(&saves_dir, &folder_name);
+
+    let extract = |archive: &mut zip::ZipArchive<std::fs::File>| -> Result<()> {
+        for i in 0..archive.len() {
+            let mut entry = archive.by_index(i)?;
+            let name = entry.name().replace('\\', "/");
+            let Some(relative) = name.strip_prefix(&prefix) else { continue };
+            if relative.is_empty() || relative.ends_with("session.lock") {
+                continue;
+            }
+            // Einträge mit ".." oder absoluten Pfaden dürfen den Welt-Ordner nicht verlassen
+            let Some(safe) = entry.enclosed_name().map(|p| p.to_path_buf()) else {
+                tracing::warn!("Skipping unsafe path in world archive: {}", name);
+                continue;
+            };
+            let Ok(relative) = safe.strip_prefix(prefix.trim_end_matches('/')) else { continue };
+            let out = target.join(relative);
+
+            if entry.is_dir() {
+                std::fs::create_dir_all(&out)?;
+            } else {
+                if let Some(parent) = out.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::io::copy(&mut entry, &mut std::fs::File::create(&out)?)?;
+            }
+        }
+        Ok(())
+    };
+
+    std::fs::create_dir_all(&target)?;
+    if let Err(e) = extract(&mut archive) {
+        std::fs::remove_dir_all(&target).ok();
+        return Err(e.context("Extracting world failed"));
+    }
+
+    let folder = target.file_name().unwrap_or_default().to_string_lossy().to_string();
+    tracing::info!("Imported world archive {:?} to {:?}", zip_path, target);
+    Ok(folder)
+}
+
+/// Fortschritt beim Export einer Welt (wird als "world-export-progress" an die GUI gesendet)
+#[derive(Debug, Clone, Serialize)]
+pub struct WorldExportProgress {
+    pub world: String,
+    pub files_done: usize,
+    pub files_total: usize,
+    pub bytes_done: u64,
+    pub bytes_total: u64,
+}
+
+/// Packt eine Welt als ZIP (mit dem Welt-Ordner als Wurzel, ohne session.lock) nach `output`
+/// und gibt die Größe des Archivs zurück. Blockierend, über `run_blocking` aufrufen.
+pub fn export_world(
+    game_dir: &Path,
+    folder_name: &str,
+    output: &Path,
+    mut on_progress: impl FnMut(&WorldExportProgress),
+) -> Result<u64> {
+    check_folder_name(folder_name)?;
+    let world_dir = game_dir.join("saves").join(folder_name);
+    if !world_dir.join("level.dat").exists() {
+        anyhow::bail!("World not found: {}", folder_name);
+    }
+    if output.starts_with(&world_dir) {
+        anyhow::bail!("The archive cannot be saved inside the world folder");
+    }
+
+    let files: Vec<(std::path::PathBuf, u64)> = walkdir::WalkDir::new(&world_dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && e.file_name() != "session.lock")
+        .map(|e| {
+            let size = e.metadata().map(|m| m.len()).unwrap_or(0);
+            (e.into_path(), size)
+        })
+        .collect();
+
+    let mut progress = WorldExportProgress {
+        world: folder_name.to_string(),
+        files_done: 0,
+        files_total: files.len(),
+        bytes_done: 0,
+        bytes_total: files.iter().map(|(_, size)| size).sum(),
+    };
+    on_progress(&progress);
+
+    // Erst in eine Temp-Datei schreiben, damit ein abgebrochener Export kein halbes ZIP hinterlässt
+    let tmp = output.with_extension("zip.part");
+    let write = |progress: &mut WorldExportProgress, on_progress: &mut dyn FnMut(&WorldExportProgress)| -> Result<()> {
+        let mut zip = zip::ZipWriter::new(std::fs::File::create(&tmp)?);
+        let options = zip::write::FileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated);
+        let mut last_report = std::time::Instant::now();
+
+        for (path, size) in &files {
+            let relative = path.strip_prefix(&world_dir)?.to_string_lossy().replace('\\', "/");
+            zip.start_file(format!("{}/{}", folder_name, relative), options)?;
+            std::io::copy(&mut std::fs::File::open(path)?, &mut zip)?;
+
+            progress.files_done += 1;
+            progress.bytes_done += size;
+            if last_report.elapsed() >= std::time::Duration::from_millis(150) {
+                on_progress(progress);
+                last_report = std::time::Instant::now();
+            }
+        }
+        zip.finish()?;
+        Ok(())
+    };
+
+    if let Err(e) = write(&mut progress, &mut on_progress) {
+        std::fs::remove_file(&tmp).ok();
+        return Err(e.context(format!("Export of world {} failed", folder_name)));
+    }
+    std::fs::rename(&tmp, output)?;
+    on_progress(&progress);
+
+    let size = std::fs::metadata(output)?.len();
+    tracing::info!("Exported world {} to {:?} ({})", folder_name, output, format_size(size));
+    Ok(size)
+}
+
+/// Änderbare Welt-Einstellungen (None = unverändert)
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct WorldSettingsUpdate {
+    pub name: Option<String>,
+    pub cheats: Option<bool>,
+    /// "peaceful", "easy", "normal" oder "hard"
+    pub difficulty: Option<String>,
+}
+
+/// Schreibt geänderte Einstellungen in level.dat. Vorher wird eine Kopie unter
+/// `backups/worlds/<ordner>-level-<zeitstempel>.dat` angelegt, geschrieben wird
+/// über eine Temp-Datei. Blockierend, über `run_blocking` aufrufen.
+pub fn update_world_settings(game_dir: &Path, folder_name: &str, update: &WorldSettingsUpdate) -> Result<()> {
+    use crate::utils::nbt::{self, Tag};
+
+    check_folder_name(folder_name)?;
+    let level_dat = game_dir.join("saves").join(folder_name).join("level.dat");
+    let data = std::fs::read(&level_dat)
+        .with_context(|| format!("World not found: {}", folder_name))?;
+    let (root_name, mut root) = nbt::read(&data)?;
+    let level = root.get_mut("Data").context("level.dat has no Data compound")?;
+
+    if let Some(name) = &update.name {
+        let name = name.trim();
+        if name.is_empty() {
+            anyhow::bail!("World name must not be empty");
+        }
+        level.set("LevelName", Tag::String(name.to_string()));
+    }
+
+    if let Some(cheats) = update.cheats {
+        level.set("allowCommands", Tag::Byte(cheats as i8));
+    }
+
+    if let Some(difficulty) = &update.difficulty {
+        let difficulty = difficulty.to_lowercase();
+        let id = match difficulty.as_str() {
+            "peaceful" => 0,
+            "easy" => 1,
+            "normal" => 2,
+            "hard" => 3,
+            other => anyhow::bail!("Unknown difficulty: {}", other),
+        };
+        // Neuere Versionen speichern die Schwierigkeit zusätzlich in "difficulty_settings"
+        match level.get_mut("difficulty_settings") {
+            Some(settings) => settings.set("difficulty", Tag::String(difficulty)),
+            None => level.set("Difficulty", Tag::Byte(id)),
+        }
+    }
+
+    let backups_dir = world_backups_dir(game_dir);
+    std::fs::create_dir_all(&backups_dir)?;
+    let timestamp = chrono::Local::now().format("%Y-%m-%d_%H-%M-%S");
+    let backup = backups_dir.join(format!("{}-level-{}.dat", folder_name, timestamp));
+    std::fs::copy(&level_dat, &backup).context("Could not back up level.dat")?;
+
+    let tmp = level_dat.with_extension("dat.tmp");
+    std::fs::write(&tmp, nbt::write_compressed(&root_name, &root)?)?;
+    std::fs::rename(&tmp, &level_dat)?;
+
+    tracing::info!("Updated world settings of {} (backup: {:?})", folder_name, backup);
+    Ok(())
+}
+
 /// Formatiert Bytes in lesbare Größe
 pub fn format_size(bytes: u64) -> String {
     const KB: u64 = 1024;
@@ -4,3 +4,15 @@ pub mod download;
 pub mod profiles;
 pub mod fs;
 pub mod auth;
+pub mod diagnostics;
+pub mod cloud;
+pub mod artwork;
+pub mod sessions;
+pub mod uri;
+pub mod packs;
+pub mod import;
+pub mod history;
+pub mod trash;
+pub mod undo;
+pub mod logs;
+pub mod telemetry;
@@ -0,0 +1,329 @@
+//! Lokale Metadaten-Datenbank der installierten Mods eines Profils (SQLite, `content.db`
+//! im Spielordner). Ein Eintrag pro JAR mit Quelle, Projekt/Version, SHA-1, Aktiv-Status und
+//! der zuletzt gefundenen Update-Version. Ersetzt die früheren Einzeldateien unter
+//! `modinfos/` (und die noch älteren `mods/*.jar.meta.json`) sowie `content.json`; sie werden
+//! beim ersten Öffnen einmalig übernommen und erst danach gelöscht.
+//!
+//! Einträge bleiben erhalten, wenn das JAR fehlt (z.B. im Papierkorb) – `installed`
+//! liefert nur Einträge, deren Datei tatsächlich im mods-Ordner liegt.
+
+use anyhow::Result;
+use rusqlite::{Connection, OptionalExtension, Row, TransactionBehavior, params};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+const DB_FILE: &str = "content.db";
+/// `PRAGMA user_version` nach Anlegen der Tabelle und Übernahme der alten Dateien
+const SCHEMA_VERSION: u32 = 1;
+/// Vorgänger-Format (eine JSON-Datei für alle Einträge)
+const LEGACY_DB_FILE: &str = "content.json";
+
+const COLUMNS: &str = "filename, mod_id, mod_name, icon_url, version, version_id, source, sha1, \
+    enabled, installed_at, latest_version, update_checked_at";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ContentRecord {
+    /// JAR-Name ohne `.disabled` – Schlüssel des Eintrags
+    #[serde(default)]
+    pub filename: String,
+    #[serde(default)]
+    pub mod_id: Option<String>,
+    #[serde(default)]
+    pub mod_name: Option<String>,
+    #[serde(default)]
+    pub icon_url: Option<String>,
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub version_id: Option<String>,
+    /// "modrinth" oder "curseforge"
+    #[serde(default)]
+    pub source: Option<String>,
+    #[serde(default)]
+    pub sha1: Option<String>,
+    /// Wird beim Lesen mit dem Dateinamen abgeglichen (`.jar.disabled` = deaktiviert)
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub installed_at: Option<String>,
+    /// Neueste Version laut letzter Update-Prüfung
+    #[serde(default)]
+    pub latest_version: Option<String>,
+    #[serde(default)]
+    pub update_checked_at: Option<String>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl ContentRecord {
+    pub fn has_update(&self) -> bool {
+        match (&self.version, &self.latest_version) {
+            (Some(current), Some(latest)) => current != latest,
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct LegacyDb {
+    #[serde(default)]
+    records: BTreeMap<String, ContentRecord>,
+}
+
+/// `sodium.jar.disabled` → `sodium.jar`
+pub fn key(filename: &str) -> &str {
+    filename.trim_end_matches(".disabled")
+}
+
+fn db_path(game_dir: &Path) -> PathBuf {
+    game_dir.join(DB_FILE)
+}
+
+/// Aktiv-Status laut mods-Ordner, `None` wenn das JAR nicht (mehr) vorhanden ist
+fn state_on_disk(game_dir: &Path, filename: &str) -> Option<bool> {
+    let mods_dir = game_dir.join("mods");
+    if mods_dir.join(filename).exists() {
+        Some(true)
+    } else if mods_dir.join(format!("{}.disabled", filename)).exists() {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+/// Liest eine alte Metadaten-Datei; fehlt `filename`, wird er aus dem Namen abgeleitet
+fn read_sidecar(path: &Path, fallback_filename: String) -> Option<ContentRecord> {
+    let content = std::fs::read_to_string(path).ok()?;
+    let mut record: ContentRecord = serde_json::from_str(&content).ok()?;
+    if record.filename.is_empty() {
+        record.filename = fallback_filename;
+    }
+    record.filename = key(&record.filename).to_string();
+    Some(record)
+}
+
+/// Sammelt `content.json`, `modinfos/*.json` und `mods/*.jar.meta.json` ein
+fn legacy_records(game_dir: &Path) -> (Vec<ContentRecord>, Vec<PathBuf>) {
+    let mut records = Vec::new();
+    let mut files = Vec::new();
+
+    let legacy_db = game_dir.join(LEGACY_DB_FILE);
+    if let Ok(content) = std::fs::read_to_string(&legacy_db) {
+        match serde_json::from_str::<LegacyDb>(&content) {
+            Ok(db) => records.extend(db.records.into_values()),
+            Err(e) => tracing::warn!("Ignoring unreadable {:?}: {}", legacy_db, e),
+        }
+        files.push(legacy_db);
+    }
+
+    let sources = [(game_dir.join("mods"), ".jar.meta.json"), (game_dir.join("modinfos"), ".json")];
+    for (dir, suffix) in sources {
+        let Ok(entries) = std::fs::read_dir(&dir) else { continue };
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let Some(stem) = name.strip_suffix(suffix) else { continue };
+            let Some(mut record) = read_sidecar(&entry.path(), format!("{}.jar", stem)) else { continue };
+            record.enabled = state_on_disk(game_dir, &record.filename).unwrap_or(true);
+            records.push(record);
+            files.push(entry.path());
+        }
+    }
+    (records, files)
+}
+
+fn insert(conn: &Connection, record: &ContentRecord) -> rusqlite::Result<()> {
+    conn.execute(
+        &format!("INSERT OR REPLACE INTO content ({}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)", COLUMNS),
+        params![
+            record.filename,
+            record.mod_id,
+            record.mod_name,
+            record.icon_url,
+            record.version,
+            record.version_id,
+            record.source,
+            record.sha1,
+            record.enabled,
+            record.installed_at,
+            record.latest_version,
+            record.update_checked_at,
+        ],
+    )?;
+    Ok(())
+}
+
+fn from_row(row: &Row) -> rusqlite::Result<ContentRecord> {
+    Ok(ContentRecord {
+        filename: row.get(0)?,
+        mod_id: row.get(1)?,
+        mod_name: row.get(2)?,
+        icon_url: row.get(3)?,
+        version: row.get(4)?,
+        version_id: row.get(5)?,
+        source: row.get(6)?,
+        sha1: row.get(7)?,
+        enabled: row.get(8)?,
+        installed_at: row.get(9)?,
+        latest_version: row.get(10)?,
+        update_checked_at: row.get(11)?,
+    })
+}
+
+/// Legt die Tabelle an und übernimmt die alten Dateien in einer Transaktion. Gelöscht
+/// werden sie erst nach dem Commit; `user_version` verhindert eine erneute Übernahme.
+fn migrate(conn: &mut Connection, game_dir: &Path) -> Result<()> {
+    let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+    // Eine zweite Verbindung kann zwischenzeitlich migriert haben
+    let version: u32 = tx.pragma_query_value(None, "user_version", |row| row.get(0))?;
+    if version >= SCHEMA_VERSION {
+        return Ok(());
+    }
+
+    tx.execute_batch(
+        "CREATE TABLE IF NOT EXISTS content (
+            filename TEXT PRIMARY KEY,
+            mod_id TEXT,
+            mod_name TEXT,
+            icon_url TEXT,
+            version TEXT,
+            version_id TEXT,
+            source TEXT,
+            sha1 TEXT,
+            enabled INTEGER NOT NULL DEFAULT 1,
+            installed_at TEXT,
+            latest_version TEXT,
+            update_checked_at TEXT
+        );
+        CREATE INDEX IF NOT EXISTS content_mod_id ON content (mod_id);",
+    )?;
+    let (records, files) = legacy_records(game_dir);
+    for record in &records {
+        insert(&tx, record)?;
+    }
+    tx.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+    tx.commit()?;
+
+    if !files.is_empty() {
+        for file in &files {
+            std::fs::remove_file(file).ok();
+        }
+        std::fs::remove_dir(game_dir.join("modinfos")).ok();
+        tracing::info!("Migrated {} metadata files into {:?}", files.len(), db_path(game_dir));
+    }
+    Ok(())
+}
+
+fn open(game_dir: &Path) -> Result<Connection> {
+    std::fs::create_dir_all(game_dir)?;
+    let mut conn = Connection::open(db_path(game_dir))?;
+    conn.busy_timeout(std::time::Duration::from_secs(5))?;
+    let version: u32 = conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+    if version < SCHEMA_VERSION {
+        migrate(&mut conn, game_dir)?;
+    }
+    Ok(conn)
+}
+
+/// Lesender Zugriff; ohne Spielordner gibt es nichts zu lesen (und nichts anzulegen)
+fn read<T: Default>(game_dir: &Path, f: impl FnOnce(&Connection) -> rusqlite::Result<T>) -> T {
+    if !game_dir.is_dir() {
+        return T::default();
+    }
+    let result = open(game_dir).and_then(|conn| Ok(f(&conn)?));
+    result.unwrap_or_else(|e| {
+        tracing::warn!("Could not read content database in {:?}: {}", game_dir, e);
+        T::default()
+    })
+}
+
+/// Eintrag einer Datei (mit oder ohne `.disabled`)
+pub fn get(game_dir: &Path, filename: &str) -> Option<ContentRecord> {
+    let filename = key(filename);
+    let mut record = read(game_dir, |conn| {
+        conn.query_row(&format!("SELECT {} FROM content WHERE filename = ?1", COLUMNS), [filename], from_row)
+            .optional()
+    })?;
+    if let Some(enabled) = state_on_disk(game_dir, filename) {
+        record.enabled = enabled;
+    }
+    Some(record)
+}
+
+fn query_all(game_dir: &Path, filter: Option<&str>) -> Vec<ContentRecord> {
+    read(game_dir, |conn| match filter {
+        Some(mod_id) => conn
+            .prepare(&format!("SELECT {} FROM content WHERE mod_id = ?1 ORDER BY filename", COLUMNS))?
+            .query_map([mod_id], from_row)?
+            .collect(),
+        None => conn
+            .prepare(&format!("SELECT {} FROM content ORDER BY filename", COLUMNS))?
+            .query_map([], from_row)?
+            .collect(),
+    })
+}
+
+/// Nur Einträge, deren JAR im mods-Ordner liegt – mit aktuellem Aktiv-Status
+fn on_disk(game_dir: &Path, records: Vec<ContentRecord>) -> Vec<ContentRecord> {
+    records
+        .into_iter()
+        .filter_map(|mut record| {
+            record.enabled = state_on_disk(game_dir, &record.filename)?;
+            Some(record)
+        })
+        .collect()
+}
+
+/// Alle Einträge, deren JAR im mods-Ordner liegt
+pub fn installed(game_dir: &Path) -> Vec<ContentRecord> {
+    on_disk(game_dir, query_all(game_dir, None))
+}
+
+/// Installierte Dateien eines Projekts
+pub fn installed_by_project(game_dir: &Path, mod_id: &str) -> Vec<ContentRecord> {
+    on_disk(game_dir, query_all(game_dir, Some(mod_id)))
+}
+
+/// Legt einen Eintrag an oder ersetzt ihn
+pub fn upsert(game_dir: &Path, mut record: ContentRecord) -> Result<()> {
+    record.filename = key(&record.filename).to_string();
+    if record.installed_at.is_none() {
+        record.installed_at = Some(chrono::Utc::now().to_rfc3339());
+    }
+    insert(&open(game_dir)?, &record)?;
+    Ok(())
+}
+
+/// Entfernt einen Eintrag und gibt ihn zurück
+pub fn remove(game_dir: &Path, filename: &str) -> Result<Option<ContentRecord>> {
+    if !game_dir.is_dir() {
+        return Ok(None);
+    }
+    let mut conn = open(game_dir)?;
+    let tx = conn.transaction()?;
+    let record = tx
+        .query_row(&format!("SELECT {} FROM content WHERE filename = ?1", COLUMNS), [key(filename)], from_row)
+        .optional()?;
+    tx.execute("DELETE FROM content WHERE filename = ?1", [key(filename)])?;
+    tx.commit()?;
+    Ok(record)
+}
+
+pub fn set_enabled(game_dir: &Path, filename: &str, enabled: bool) -> Result<()> {
+    open(game_dir)?.execute(
+        "UPDATE content SET enabled = ?2 WHERE filename = ?1",
+        params![key(filename), enabled],
+    )?;
+    Ok(())
+}
+
+/// Speichert das Ergebnis einer Update-Prüfung
+pub fn set_update_info(game_dir: &Path, filename: &str, latest_version: Option<String>) -> Result<()> {
+    open(game_dir)?.execute(
+        "UPDATE content SET latest_version = ?2, update_checked_at = ?3 WHERE filename = ?1",
+        params![key(filename), latest_version, chrono::Utc::now().to_rfc3339()],
+    )?;
+    Ok(())
+}
@@ -0,0 +1,248 @@
+//! Liest die Abhängigkeits-Deklarationen installierter Mods
+//! (fabric.mod.json, quilt.mod.json, META-INF/mods.toml, META-INF/neoforge.mods.toml)
+//! und ermittelt fehlende Pflicht-Abhängigkeiten vor dem Start.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::{Cursor, Read, Seek};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+/// IDs die vom Loader/Spiel selbst bereitgestellt werden
+const BUILTIN_IDS: [&str; 10] = [
+    "minecraft", "java", "fabricloader", "fabric-loader", "quilt_loader",
+    "quilt_base", "forge", "neoforge", "javafml", "lowcodefml",
+];
+
+#[derive(Debug, Clone, Default)]
+pub struct ModMetadata {
+    /// Mod-IDs die dieses JAR bereitstellt (inkl. `provides` und verschachtelter JARs)
+    pub provides: Vec<String>,
+    /// Pflicht-Abhängigkeiten
+    pub requires: Vec<String>,
+    /// Loader für die das JAR Metadaten mitbringt ("fabric", "quilt", "forge", "neoforge")
+    pub loaders: Vec<String>,
+    /// Angegebene Minecraft-Version bzw. -Bereich (fabric.mod.json / mods.toml)
+    pub minecraft_requirement: Option<String>,
+}
+
+/// Ergebnis der letzten Prüfung pro mods-Ordner; gilt solange sich dessen Änderungszeit nicht ändert
+/// (Hinzufügen, Entfernen und Umbenennen von JARs ändern sie)
+static MISSING_CACHE: Mutex<Option<HashMap<PathBuf, CachedScan>>> = Mutex::new(None);
+
+struct CachedScan {
+    modified: SystemTime,
+    missing: Vec<MissingDependency>,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct MissingDependency {
+    pub mod_id: String,
+    /// Dateinamen der Mods, die diese Abhängigkeit benötigen
+    pub required_by: Vec<String>,
+}
+
+fn read_entry<R: Read + Seek>(archive: &mut zip::ZipArchive<R>, name: &str) -> Option<String> {
+    let mut file = archive.by_name(name).ok()?;
+    let mut content = String::new();
+    file.read_to_string(&mut content).ok()?;
+    Some(content)
+}
+
+fn parse_metadata<R: Read + Seek>(archive: &mut zip::ZipArchive<R>, depth: u8) -> ModMetadata {
+    let mut meta = ModMetadata::default();
+
+    if let Some(json) = read_entry(archive, "fabric.mod.json")
+        .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok())
+    {
+        if let Some(id) = json.get("id").and_then(|v| v.as_str()) {
+            meta.provides.push(id.to_string());
+        }
+        if let Some(provides) = json.get("provides").and_then(|v| v.as_array()) {
+            meta.provides.extend(provides.iter().filter_map(|p| p.as_str()).map(String::from));
+        }
+        if let Some(depends) = json.get("depends").and_then(|v| v.as_object()) {
+            meta.requires.extend(depends.keys().cloned());
+            // Ein Eintrag ist ein Prädikat oder eine Liste davon (erfüllt, wenn eines passt)
+            meta.minecraft_requirement = match depends.get("minecraft") {
+                Some(serde_json::Value::String(predicate)) => Some(predicate.clone()),
+                Some(serde_json::Value::Array(predicates)) => {
+                    let predicates: Vec<&str> = predicates.iter().filter_map(|p| p.as_str()).collect();
+                    (!predicates.is_empty()).then(|| predicates.join(" || "))
+                }
+                _ => None,
+            };
+        }
+        meta.loaders.push("fabric".to_string());
+    }
+
+    if let Some(loader) = read_entry(archive, "quilt.mod.json")
+        .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok())
+        .and_then(|json| json.get("quilt_loader").cloned())
+    {
+        meta.loaders.push("quilt".to_string());
+        if let Some(id) = loader.get("id").and_then(|v| v.as_str()) {
+            meta.provides.push(id.to_string());
+        }
+        for provided in loader.get("provides").and_then(|v| v.as_array()).into_iter().flatten() {
+            let id = provided.as_str().or_else(|| provided.get("id").and_then(|v| v.as_str()));
+            meta.provides.extend(id.map(String::from));
+        }
+        for dep in loader.get("depends").and_then(|v| v.as_array()).into_iter().flatten() {
+            match dep {
+                serde_json::Value::String(id) => meta.requires.push(id.clone()),
+                serde_json::Value::Object(obj) => {
+                    let optional = obj.get("optional").and_then(|v| v.as_bool()).unwrap_or(false);
+                    if let Some(id) = obj.get("id").and_then(|v| v.as_str()).filter(|_| !optional) {
+                        meta.requires.push(id.to_string());
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    for (toml_name, loader) in [("META-INF/mods.toml", "forge"), ("META-INF/neoforge.mods.toml", "neoforge")] {
+        if let Some(content) = read_entry(archive, toml_name) {
+            parse_mods_toml(&content, &mut meta);
+            meta.loaders.push(loader.to_string());
+        }
+    }
+
+    // Verschachtelte JARs (Jar-in-Jar) stellen ebenfalls Mods bereit
+    if depth == 0 {
+        let nested: Vec<String> = archive.file_names()
+            .filter(|n| n.starts_with("META-INF/jars/") && n.ends_with(".jar"))
+            .map(String::from)
+            .collect();
+        for name in nested {
+            let mut bytes = Vec::new();
+            let Ok(mut file) = archive.by_name(&name) else { continue };
+            if file.read_to_end(&mut bytes).is_err() {
+                continue;
+            }
+            drop(file);
+            if let Ok(mut inner) = zip::ZipArchive::new(Cursor::new(bytes)) {
+                meta.provides.extend(parse_metadata(&mut inner, depth + 1).provides);
+            }
+        }
+    }
+
+    meta
+}
+
+/// Minimaler Parser für mods.toml: `[[mods]]` liefert bereitgestellte IDs,
+/// `[[dependencies.x]]` mit `mandatory=true` bzw. `type="required"` die Pflicht-Abhängigkeiten
+fn parse_mods_toml(content: &str, meta: &mut ModMetadata) {
+    enum Section { Mods, Dependency, Other }
+
+    fn flush(dep_id: &mut Option<String>, dep_required: &mut bool, meta: &mut ModMetadata) {
+        if let Some(id) = dep_id.take() {
+            if *dep_required {
+                meta.requires.push(id);
+            }
+        }
+        *dep_required = false;
+    }
+
+    let mut section = Section::Other;
+    let mut dep_id: Option<String> = None;
+    let mut dep_required = false;
+    let mut dep_range: Option<String> = None;
+
+    for line in content.lines() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        if line.starts_with('[') {
+            if dep_id.as_deref() == Some("minecraft") {
+                meta.minecraft_requirement = dep_range.take();
+            }
+            dep_range = None;
+            flush(&mut dep_id, &mut dep_required, meta);
+            section = if line == "[[mods]]" {
+                Section::Mods
+            } else if line.starts_with("[[dependencies") {
+                Section::Dependency
+            } else {
+                Section::Other
+            };
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once('=') else { continue };
+        let key = key.trim();
+        let value = value.trim().trim_matches('"').trim_matches('\'');
+
+        match (&section, key) {
+            (Section::Mods, "modId") => meta.provides.push(value.to_string()),
+            (Section::Dependency, "modId") => dep_id = Some(value.to_string()),
+            (Section::Dependency, "versionRange") => dep_range = Some(value.to_string()),
+            (Section::Dependency, "mandatory") => dep_required = value == "true",
+            (Section::Dependency, "type") => dep_required = value.eq_ignore_ascii_case("required"),
+            _ => {}
+        }
+    }
+    if dep_id.as_deref() == Some("minecraft") {
+        meta.minecraft_requirement = dep_range;
+    }
+    flush(&mut dep_id, &mut dep_required, meta);
+}
+
+/// Liest die Metadaten eines Mod-JARs
+pub fn read_mod_metadata(jar: &Path) -> Option<ModMetadata> {
+    let file = std::fs::File::open(jar).ok()?;
+    let mut archive = zip::ZipArchive::new(file).ok()?;
+    Some(parse_metadata(&mut archive, 0))
+}
+
+/// Ermittelt alle Pflicht-Abhängigkeiten der aktiven Mods, die von keinem installierten JAR erfüllt werden.
+/// Die JARs werden nur neu gelesen, wenn sich der mods-Ordner seit der letzten Prüfung geändert hat.
+pub fn find_missing_dependencies(mods_dir: &Path) -> Vec<MissingDependency> {
+    let modified = std::fs::metadata(mods_dir).and_then(|m| m.modified()).ok();
+    if let Some(modified) = modified {
+        let cache = MISSING_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(scan) = cache.as_ref().and_then(|c| c.get(mods_dir)).filter(|s| s.modified == modified) {
+            return scan.missing.clone();
+        }
+    }
+
+    let missing = scan_missing_dependencies(mods_dir);
+    if let Some(modified) = modified {
+        MISSING_CACHE.lock().unwrap_or_else(|e| e.into_inner())
+            .get_or_insert_with(HashMap::new)
+            .insert(mods_dir.to_path_buf(), CachedScan { modified, missing: missing.clone() });
+    }
+    missing
+}
+
+fn scan_missing_dependencies(mods_dir: &Path) -> Vec<MissingDependency> {
+    let Ok(entries) = std::fs::read_dir(mods_dir) else {
+        return Vec::new();
+    };
+
+    let mut provided: HashSet<String> = BUILTIN_IDS.iter().map(|s| s.to_string()).collect();
+    let mut required: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let filename = entry.file_name().to_string_lossy().to_string();
+        if !filename.ends_with(".jar") {
+            continue;
+        }
+        let Some(meta) = read_mod_metadata(&path) else { continue };
+        provided.extend(meta.provides);
+        for dep in meta.requires {
+            required.entry(dep).or_default().push(filename.clone());
+        }
+    }
+
+    // Alte Fabric-API-Versionen deklarieren die ID "fabric"
+    if provided.contains("fabric-api") {
+        provided.insert("fabric".to_string());
+    }
+
+    required
+        .into_iter()
+        .filter(|(id, _)| !provided.contains(id))
+        .map(|(mod_id, required_by)| MissingDependency { mod_id, required_by })
+        .collect()
+}
@@ -0,0 +1,169 @@
+//! Erkennung von Mods, die nicht über den Launcher installiert wurden, per
+//! CurseForge-Fingerprint. Erkannte Mods bekommen einen Eintrag in der Inhalts-Datenbank
+//! mit `source: "curseforge"` und können damit auf Updates geprüft werden.
+
+use anyhow::Result;
+use rayon::prelude::*;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use crate::api::curseforge::{CurseForgeClient, CurseForgeFile};
+use super::content_db::{self, ContentRecord};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct IdentifiedMod {
+    pub filename: String,
+    /// CurseForge-Projekt-ID
+    pub mod_id: String,
+    pub file_id: i32,
+    pub name: String,
+    pub version: String,
+    pub icon_url: Option<String>,
+    /// Neueste Datei für Minecraft-Version und Loader des Profils
+    pub latest_file_id: Option<i32>,
+    pub latest_version: Option<String>,
+    pub has_update: bool,
+}
+
+/// JARs im mods-Ordner ohne Eintrag in der Inhalts-Datenbank (bzw. ohne Quelle)
+pub fn unidentified_jars(game_dir: &Path, include_curseforge: bool) -> Vec<(String, PathBuf)> {
+    let Ok(entries) = std::fs::read_dir(game_dir.join("mods")) else { return Vec::new() };
+
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let filename = entry.file_name().to_string_lossy().to_string();
+            if !(filename.ends_with(".jar") || filename.ends_with(".jar.disabled")) {
+                return None;
+            }
+            let source = content_db::get(game_dir, &filename).and_then(|record| record.source);
+            let unidentified = match source.as_deref() {
+                None => true,
+                Some("curseforge") => include_curseforge,
+                Some(_) => false,
+            };
+            unidentified.then(|| (filename, entry.path()))
+        })
+        .collect()
+}
+
+/// Bereits berechneter Fingerprint, gültig solange Größe und Änderungszeit gleich bleiben
+#[derive(Debug, Clone, Copy)]
+struct CachedFingerprint {
+    size: u64,
+    modified: SystemTime,
+    fingerprint: u32,
+}
+
+/// Update-Checks müssen so nicht bei jedem Aufruf alle JARs neu einlesen
+static FINGERPRINT_CACHE: Mutex<Option<HashMap<PathBuf, CachedFingerprint>>> = Mutex::new(None);
+
+/// Fingerprints der Dateien (parallel, unveränderte Dateien aus dem Cache). Blockierend.
+pub fn fingerprint_files(files: &[(String, PathBuf)]) -> HashMap<u32, String> {
+    let cached = FINGERPRINT_CACHE.lock().ok().and_then(|cache| cache.clone()).unwrap_or_default();
+
+    let computed: Vec<(PathBuf, CachedFingerprint, String)> = files
+        .par_iter()
+        .filter_map(|(filename, path)| {
+            let meta = std::fs::metadata(path).ok()?;
+            let (size, modified) = (meta.len(), meta.modified().ok()?);
+            let fingerprint = match cached.get(path) {
+                Some(entry) if entry.size == size && entry.modified == modified => entry.fingerprint,
+                _ => crate::utils::murmur2::curseforge_fingerprint(&std::fs::read(path).ok()?),
+            };
+            Some((path.clone(), CachedFingerprint { size, modified, fingerprint }, filename.clone()))
+        })
+        .collect();
+
+    if let Ok(mut cache) = FINGERPRINT_CACHE.lock() {
+        let cache = cache.get_or_insert_with(HashMap::new);
+        // Einträge entfernter Dateien desselben Ordners verwerfen
+        let dirs: HashSet<&Path> = files.iter().filter_map(|(_, path)| path.parent()).collect();
+        let current: HashSet<&PathBuf> = computed.iter().map(|(path, ..)| path).collect();
+        cache.retain(|path, _| current.contains(path) || !path.parent().is_some_and(|p| dirs.contains(p)));
+        for (path, entry, _) in &computed {
+            cache.insert(path.clone(), *entry);
+        }
+    }
+
+    computed.into_iter().map(|(_, entry, filename)| (entry.fingerprint, filename)).collect()
+}
+
+/// Neueste passende Datei (höchste Datei-ID) für Version und Loader
+fn latest_matching<'a>(files: &'a [CurseForgeFile], minecraft_version: &str, loader: &str) -> Option<&'a CurseForgeFile> {
+    files.iter().filter(|f| f.supports(minecraft_version, loader)).max_by_key(|f| f.id)
+}
+
+/// Identifiziert die Mods eines Spielordners über die Fingerprint-API und speichert
+/// die Metadaten in der Inhalts-Datenbank. `include_known` prüft auch bereits erkannte
+/// CurseForge-Mods erneut (für Update-Checks).
+pub async fn identify_mods(
+    client: &CurseForgeClient,
+    game_dir: &Path,
+    minecraft_version: &str,
+    loader: &str,
+    include_known: bool,
+) -> Result<Vec<IdentifiedMod>> {
+    let dir = game_dir.to_path_buf();
+    let by_fingerprint = crate::utils::compression::run_blocking(move || {
+        let files = unidentified_jars(&dir, include_known);
+        Ok(fingerprint_files(&files))
+    })
+    .await?;
+    if by_fingerprint.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let fingerprints: Vec<u32> = by_fingerprint.keys().copied().collect();
+    let matches = client.match_fingerprints(&fingerprints).await?;
+
+    let mod_ids: Vec<i32> = matches.iter().map(|m| m.id).collect();
+    let names: HashMap<i32, (String, Option<String>)> = match client.get_mod_names(&mod_ids).await {
+        Ok(names) => names.into_iter().map(|(id, name, logo)| (id, (name, logo))).collect(),
+        Err(e) => {
+            tracing::warn!("Could not load CurseForge project names: {}", e);
+            HashMap::new()
+        }
+    };
+
+    let mut identified = Vec::new();
+    for m in matches {
+        let Some(filename) = by_fingerprint.get(&m.file.file_fingerprint) else { continue };
+        let (name, icon_url) = names.get(&m.id).cloned().unwrap_or_else(|| (m.file.display_name.clone(), None));
+        let latest = latest_matching(&m.latest_files, minecraft_version, loader);
+
+        let existing = content_db::get(game_dir, filename).unwrap_or_default();
+        let record = ContentRecord {
+            filename: filename.clone(),
+            mod_id: Some(m.id.to_string()),
+            mod_name: Some(name.clone()),
+            icon_url: icon_url.clone(),
+            version: Some(m.file.display_name.clone()),
+            version_id: Some(m.file.id.to_string()),
+            source: Some("curseforge".to_string()),
+            enabled: !filename.ends_with(".disabled"),
+            ..existing
+        };
+        if let Err(e) = content_db::upsert(game_dir, record) {
+            tracing::warn!("Failed to save metadata for {}: {}", filename, e);
+        }
+
+        identified.push(IdentifiedMod {
+            filename: filename.clone(),
+            mod_id: m.id.to_string(),
+            file_id: m.file.id,
+            name,
+            version: m.file.display_name.clone(),
+            icon_url,
+            has_update: latest.is_some_and(|l| l.id > m.file.id),
+            latest_file_id: latest.map(|l| l.id),
+            latest_version: latest.map(|l| l.display_name.clone()),
+        });
+    }
+
+    tracing::info!("Identified {} of {} mods via CurseForge fingerprints", identified.len(), by_fingerprint.len());
+    Ok(identified)
+}
@@ -0,0 +1,215 @@
+//! Migration eines Profils beim Wechsel von Mod-Loader oder Minecraft-Version:
+//! inkompatible Mods werden deaktiviert (`.disabled`), Natives geleert und das
+//! Ergebnis als Zusammenfassung zurückgegeben.
+
+use std::cmp::Ordering;
+use std::path::Path;
+use crate::types::version::ModLoader;
+use super::dependencies::read_mod_metadata;
+
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct LoaderMigrationSummary {
+    pub from_loader: String,
+    pub to_loader: String,
+    pub from_minecraft: String,
+    pub to_minecraft: String,
+    /// Deaktivierte Mods mit Grund
+    pub disabled_mods: Vec<DisabledMod>,
+    /// Mods deren Kompatibilität nicht sicher bestimmt werden konnte
+    pub needs_review: Vec<String>,
+    pub removed_loader_files: bool,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct DisabledMod {
+    pub filename: String,
+    pub reason: String,
+}
+
+/// Welche Metadaten-Formate ein Loader laden kann
+pub(crate) fn loader_accepts(loader: &ModLoader, mod_loaders: &[String]) -> bool {
+    let accepted: &[&str] = match loader {
+        ModLoader::Vanilla => &[],
+        ModLoader::Fabric => &["fabric"],
+        // Quilt lädt auch Fabric-Mods
+        ModLoader::Quilt => &["quilt", "fabric"],
+        ModLoader::Forge => &["forge"],
+        // NeoForge (1.20.1 bzw. vor 20.5) liest noch mods.toml
+        ModLoader::NeoForge => &["neoforge", "forge"],
+    };
+    mod_loaders.iter().any(|l| accepted.contains(&l.as_str()))
+}
+
+fn parse_version(v: &str) -> Option<Vec<u64>> {
+    v.trim().split('.').map(|p| p.parse().ok()).collect()
+}
+
+fn compare_versions(a: &[u64], b: &[u64]) -> Ordering {
+    let len = a.len().max(b.len());
+    (0..len)
+        .map(|i| a.get(i).unwrap_or(&0).cmp(b.get(i).unwrap_or(&0)))
+        .find(|o| *o != Ordering::Equal)
+        .unwrap_or(Ordering::Equal)
+}
+
+/// Prüft einen Maven-Bereich wie `[1.20.1,1.21)` oder `[1.20,)`
+fn matches_maven_range(range: &str, version: &[u64]) -> Option<bool> {
+    let range = range.trim();
+    let (open, close) = (range.chars().next()?, range.chars().last()?);
+    if !matches!(open, '[' | '(') || !matches!(close, ']' | ')') {
+        // Einzelne Version ohne Klammern = Mindestversion
+        return Some(compare_versions(version, &parse_version(range)?) != Ordering::Less);
+    }
+    let inner = &range[1..range.len() - 1];
+    let (low, high) = match inner.split_once(',') {
+        Some((low, high)) => (low.trim(), high.trim()),
+        None => (inner.trim(), inner.trim()),
+    };
+
+    if !low.is_empty() {
+        let ord = compare_versions(version, &parse_version(low)?);
+        if ord == Ordering::Less || (open == '(' && ord == Ordering::Equal) {
+            return Some(false);
+        }
+    }
+    if !high.is_empty() {
+        let ord = compare_versions(version, &parse_version(high)?);
+        if ord == Ordering::Greater || (close == ')' && ord == Ordering::Equal) {
+            return Some(false);
+        }
+    }
+    Some(true)
+}
+
+/// Prüft ein Fabric-Versionsprädikat (`1.20.1`, `1.20.x`, `>=1.20 <1.21`, `~1.20`, `a || b`)
+fn matches_fabric_predicate(predicate: &str, version: &[u64]) -> Option<bool> {
+    let mut any_alternative = false;
+    for alternative in predicate.split("||") {
+        let mut all = true;
+        for term in alternative.split_whitespace() {
+            let (op, rest) = ["~", "^", ">=", "<=", ">", "<", "="]
+                .iter()
+                .find_map(|op| term.strip_prefix(op).map(|rest| (*op, rest)))
+                .unwrap_or(("=", term));
+
+            if rest == "*" || rest.is_empty() {
+                continue;
+            }
+            let wildcard = rest.ends_with(".x") || rest.ends_with(".*");
+            let target = parse_version(rest.trim_end_matches(".x").trim_end_matches(".*"))?;
+            let prefix_matches = version.len() >= target.len() && version[..target.len()] == target[..];
+
+            let ok = match op {
+                ">=" => compare_versions(version, &target) != Ordering::Less,
+                "<=" => compare_versions(version, &target) != Ordering::Greater,
+                ">" => compare_versions(version, &target) == Ordering::Greater,
+                "<" => compare_versions(version, &target) == Ordering::Less,
+                // ~1.20.1: gleiche Minor-Version, ^1.20: gleiche Major-Version
+                "~" => {
+                    let n = target.len().min(2);
+                    compare_versions(version, &target) != Ordering::Less
+                        && version.len() >= n && version[..n] == target[..n]
+                }
+                "^" => compare_versions(version, &target) != Ordering::Less
+                    && version.first() == target.first(),
+                _ if wildcard => prefix_matches,
+                _ => compare_versions(version, &target) == Ordering::Equal,
+            };
+            all &= ok;
+        }
+        any_alternative |= all;
+    }
+    Some(any_alternative)
+}
+
+/// Vergleicht zwei Release-Versionen (`None` bei Snapshots o.ä.)
+pub fn compare_minecraft_versions(a: &str, b: &str) -> Option<Ordering> {
+    Some(compare_versions(&parse_version(a)?, &parse_version(b)?))
+}
+
+/// `Some(false)` wenn die Anforderung die Version sicher ausschließt, `None` wenn unklar
+pub fn minecraft_requirement_matches(requirement: &str, minecraft_version: &str) -> Option<bool> {
+    let version = parse_version(minecraft_version)?;
+    if requirement.trim_start().starts_with(['[', '(']) {
+        matches_maven_range(requirement, &version)
+    } else {
+        matches_fabric_predicate(requirement, &version)
+    }
+}
+
+/// Deaktiviert alle Mods im Spielordner, die mit dem neuen Loader bzw. der neuen
+/// Minecraft-Version nicht laufen, und leert die Natives des Profils
+pub fn migrate_profile_mods(
+    game_dir: &Path,
+    from: (&ModLoader, &str),
+    to: (&ModLoader, &str),
+) -> LoaderMigrationSummary {
+    let mut summary = LoaderMigrationSummary {
+        from_loader: from.0.as_str().to_string(),
+        to_loader: to.0.as_str().to_string(),
+        from_minecraft: from.1.to_string(),
+        to_minecraft: to.1.to_string(),
+        ..Default::default()
+    };
+
+    let loader_changed = from.0 != to.0;
+    let version_changed = from.1 != to.1;
+
+    let natives_dir = game_dir.join("natives");
+    if natives_dir.exists() {
+        std::fs::remove_dir_all(&natives_dir).ok();
+    }
+
+    let Ok(entries) = std::fs::read_dir(game_dir.join("mods")) else {
+        return summary;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let filename = entry.file_name().to_string_lossy().to_string();
+        if !filename.ends_with(".jar") {
+            continue;
+        }
+
+        let reason = match read_mod_metadata(&path) {
+            Some(meta) if loader_changed && !loader_accepts(to.0, &meta.loaders) => {
+                if meta.loaders.is_empty() {
+                    summary.needs_review.push(filename.clone());
+                    None
+                } else {
+                    Some(format!("Nicht kompatibel mit {} (Mod für {})", to.0, meta.loaders.join("/")))
+                }
+            }
+            Some(meta) if version_changed => {
+                match meta.minecraft_requirement.as_deref().map(|req| (req, minecraft_requirement_matches(req, to.1))) {
+                    Some((req, Some(false))) => Some(format!("Benötigt Minecraft {}", req)),
+                    Some((_, None)) => {
+                        summary.needs_review.push(filename.clone());
+                        None
+                    }
+                    _ => None,
+                }
+            }
+            Some(_) => None,
+            None => {
+                summary.needs_review.push(filename.clone());
+                None
+            }
+        };
+
+        if let Some(reason) = reason {
+            let disabled = path.with_file_name(format!("{}.disabled", filename));
+            match std::fs::rename(&path, &disabled) {
+                Ok(_) => {
+                    tracing::info!("Migration: disabled {} ({})", filename, reason);
+                    summary.disabled_mods.push(DisabledMod { filename, reason });
+                }
+                Err(e) => tracing::warn!("Migration: could not disable {}: {}", filename, e),
+            }
+        }
+    }
+
+    summary.disabled_mods.sort_by(|a, b| a.filename.cmp(&b.filename));
+    summary.needs_review.sort();
+    summary
+}
@@ -1,5 +1,13 @@
 #![allow(dead_code)]
 
+pub mod content_db;
+pub mod dependencies;
+pub mod fingerprint;
+pub mod modpack_state;
+pub mod migration;
+pub mod search_merge;
+pub mod version_archive;
+
 use anyhow::Result;
 use std::path::Path;
 use crate::types::mod_info::{ModInfo, ModVersion, ModSearchQuery};
@@ -38,6 +46,10 @@ impl ModManager {
             }
         }
 
+        if use_modrinth && use_curseforge {
+            all_mods = search_merge::merge_duplicates(all_mods);
+        }
+
         Ok(all_mods)
     }
 
@@ -80,7 +92,7 @@ impl ModManager {
             tracing::info!("Download URL: {}", file.url);
 
             self.download_manager
-                .download_with_hash(&file.url, &dest, file.hashes.sha1.as_deref())
+                .download_cached(&file.url, &dest, file.hashes.sha1.as_deref())
                 .await?;
 
             tracing::info!("✅ Mod file downloaded successfully: {:?}", dest);
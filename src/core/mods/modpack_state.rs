@@ -0,0 +1,114 @@
+//! Fortschritt laufender Modpack-Installationen. Wird eine Installation unterbrochen,
+//! kann sie mit demselben Pack und derselben Version fortgesetzt werden: bereits
+//! verifizierte Dateien werden übersprungen, das Profil wird wiederverwendet.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModpackInstallState {
+    pub pack_id: String,
+    pub version_id: String,
+    pub pack_name: String,
+    pub profile_id: String,
+    /// Pfade (relativ zum Spielordner) die heruntergeladen und geprüft wurden
+    pub completed_files: HashSet<String>,
+    pub total_files: usize,
+    pub overrides_done: bool,
+    pub updated_at: String,
+}
+
+/// Ordner für Zustände und heruntergeladene .mrpack-Dateien
+pub fn installs_dir() -> PathBuf {
+    crate::config::defaults::launcher_dir().join("modpack_installs")
+}
+
+fn operation_key(pack_id: &str, version_id: &str) -> String {
+    format!("{}_{}", pack_id, version_id)
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect()
+}
+
+impl ModpackInstallState {
+    pub fn new(pack_id: &str, version_id: &str, pack_name: &str, profile_id: &str, total_files: usize) -> Self {
+        Self {
+            pack_id: pack_id.to_string(),
+            version_id: version_id.to_string(),
+            pack_name: pack_name.to_string(),
+            profile_id: profile_id.to_string(),
+            completed_files: HashSet::new(),
+            total_files,
+            overrides_done: false,
+            updated_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+
+    /// Arbeitsordner dieser Installation (enthält das .mrpack)
+    pub fn work_dir(pack_id: &str, version_id: &str) -> PathBuf {
+        installs_dir().join(operation_key(pack_id, version_id))
+    }
+
+    fn state_file(pack_id: &str, version_id: &str) -> PathBuf {
+        Self::work_dir(pack_id, version_id).join("state.json")
+    }
+
+    pub fn load(pack_id: &str, version_id: &str) -> Option<Self> {
+        let content = std::fs::read_to_string(Self::state_file(pack_id, version_id)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    pub fn save(&mut self) -> Result<()> {
+        self.updated_at = chrono::Utc::now().to_rfc3339();
+        let path = Self::state_file(&self.pack_id, &self.version_id);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        // Über Temp-Datei schreiben, damit ein Abbruch keinen halben Zustand hinterlässt
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_string_pretty(self)?)?;
+        std::fs::rename(&tmp, &path)?;
+        Ok(())
+    }
+
+    /// Ist die Datei bereits vollständig vorhanden? Prüft zusätzlich den SHA-1,
+    /// falls der Zustand vor dem Abbruch nicht mehr gespeichert wurde.
+    pub fn is_file_complete(&self, rel_path: &str, target: &Path, expected_sha1: Option<&str>) -> bool {
+        if !target.is_file() {
+            return false;
+        }
+        if self.completed_files.contains(rel_path) {
+            return true;
+        }
+        let Some(expected) = expected_sha1 else { return false };
+        std::fs::read(target)
+            .map(|content| crate::core::download::sha1_hex(&content).eq_ignore_ascii_case(expected))
+            .unwrap_or(false)
+    }
+
+    /// Entfernt Zustand und heruntergeladenes .mrpack (nach Abschluss oder Verwerfen)
+    pub fn remove(pack_id: &str, version_id: &str) {
+        let dir = Self::work_dir(pack_id, version_id);
+        if dir.exists() {
+            if let Err(e) = std::fs::remove_dir_all(&dir) {
+                tracing::warn!("Could not remove modpack install state {:?}: {}", dir, e);
+            }
+        }
+    }
+}
+
+/// Alle unterbrochenen Modpack-Installationen
+pub fn pending_installs() -> Vec<ModpackInstallState> {
+    let Ok(entries) = std::fs::read_dir(installs_dir()) else {
+        return Vec::new();
+    };
+    let mut states: Vec<ModpackInstallState> = entries
+        .flatten()
+        .filter_map(|e| std::fs::read_to_string(e.path().join("state.json")).ok())
+        .filter_map(|c| serde_json::from_str(&c).ok())
+        .collect();
+    states.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+    states
+}
@@ -0,0 +1,93 @@
+//! Zusammenführen der kombinierten Modrinth- und CurseForge-Suche: Projekte, die auf
+//! beiden Plattformen gelistet sind, erscheinen nur einmal. Behalten wird der Eintrag
+//! mit den besseren Metadaten, die übrigen landen in `also_on`.
+
+use crate::types::mod_info::{ModInfo, ModListing, ModSource};
+
+/// Nur Buchstaben und Ziffern, klein ("Sodium Extra" == "sodium-extra")
+fn normalize(value: &str) -> String {
+    value.chars().filter(|c| c.is_alphanumeric()).flat_map(char::to_lowercase).collect()
+}
+
+/// Verweist eines der Projekte über seine Links auf das andere?
+fn cross_listed(a: &ModInfo, b: &ModInfo) -> bool {
+    let normalized = |url: &String| url.trim_end_matches('/').to_lowercase();
+    let links = |m: &ModInfo| -> Vec<String> {
+        [&m.source_url, &m.issues_url, &m.wiki_url].into_iter().flatten().map(normalized).collect()
+    };
+    // Gemeinsames Repository ist ein sicheres Zeichen für dasselbe Projekt
+    let shared_repo = matches!((&a.source_url, &b.source_url), (Some(x), Some(y)) if normalized(x) == normalized(y));
+    shared_repo || links(a).contains(&normalized(&b.project_url)) || links(b).contains(&normalized(&a.project_url))
+}
+
+fn same_project(a: &ModInfo, b: &ModInfo) -> bool {
+    if a.source == b.source {
+        return false;
+    }
+    if cross_listed(a, b) {
+        return true;
+    }
+    let slug_match = !a.slug.is_empty() && normalize(&a.slug) == normalize(&b.slug);
+    let name_match = normalize(&a.name) == normalize(&b.name);
+    let author_match = normalize(&a.author) == normalize(&b.author);
+    // Gleicher Name allein reicht nicht ("Backpacks" gibt es mehrfach)
+    (slug_match && (name_match || author_match)) || (name_match && author_match)
+}
+
+/// Wie vollständig die Metadaten eines Eintrags sind
+fn metadata_score(m: &ModInfo) -> u32 {
+    [
+        m.body.is_some(),
+        m.icon_url.is_some(),
+        m.source_url.is_some(),
+        m.issues_url.is_some(),
+        m.wiki_url.is_some(),
+        m.client_side.is_some() || m.server_side.is_some(),
+        !m.loaders.is_empty(),
+        !m.game_versions.is_empty(),
+        !m.gallery.is_empty(),
+    ]
+    .into_iter()
+    .filter(|present| *present)
+    .count() as u32
+}
+
+fn listing(m: &ModInfo) -> ModListing {
+    ModListing {
+        source: m.source,
+        id: m.id.clone(),
+        slug: m.slug.clone(),
+        project_url: m.project_url.clone(),
+        downloads: m.downloads,
+    }
+}
+
+/// Führt Duplikate zusammen; die Reihenfolge folgt dem ersten Auftreten eines Projekts
+pub fn merge_duplicates(mods: Vec<ModInfo>) -> Vec<ModInfo> {
+    let mut merged: Vec<ModInfo> = Vec::with_capacity(mods.len());
+
+    for candidate in mods {
+        let existing = merged.iter_mut().find(|m| {
+            same_project(m, &candidate) && !m.also_on.iter().any(|l| l.source == candidate.source)
+        });
+        let Some(existing) = existing else {
+            merged.push(candidate);
+            continue;
+        };
+
+        // Bei Gleichstand gewinnt Modrinth (Seiten-Angaben, Loader, Versionen)
+        let candidate_wins = metadata_score(&candidate) > metadata_score(existing)
+            || (metadata_score(&candidate) == metadata_score(existing) && candidate.source == ModSource::Modrinth);
+        tracing::debug!("Merging duplicate search result {} ({:?} / {:?})", candidate.name, existing.source, candidate.source);
+
+        if candidate_wins {
+            let mut previous = std::mem::replace(existing, candidate);
+            existing.also_on = std::mem::take(&mut previous.also_on);
+            existing.also_on.push(listing(&previous));
+        } else {
+            existing.also_on.push(listing(&candidate));
+        }
+    }
+
+    merged
+}
@@ -0,0 +1,151 @@
+//! Archiv ersetzter Mod-Versionen pro Profil (`mod-versions/<mod_id>/`). Bei einem Update
+//! wandert das alte JAR hierher, sein Eintrag aus der Inhalts-Datenbank liegt als JSON
+//! daneben. `rollback` stellt beides wieder her.
+
+use anyhow::{Context, Result, bail};
+use std::path::{Path, PathBuf};
+
+use super::content_db;
+
+/// Pro Mod werden nur die letzten Versionen aufbewahrt
+const MAX_ARCHIVED_VERSIONS: usize = 3;
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ArchivedModVersion {
+    pub filename: String,
+    pub version: Option<String>,
+    /// Zeitpunkt der ursprünglichen Installation (Unix-Sekunden)
+    pub installed_at: u64,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ModRollbackResult {
+    pub mod_id: String,
+    pub restored_filename: String,
+    pub restored_version: Option<String>,
+    /// Entfernte (fehlerhafte) Version
+    pub removed_filename: Option<String>,
+}
+
+fn mod_archive_dir(game_dir: &Path, mod_id: &str) -> PathBuf {
+    let safe_id: String = mod_id
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    game_dir.join("mod-versions").join(safe_id)
+}
+
+fn meta_name(jar_filename: &str) -> String {
+    jar_filename.replace(".jar", ".json")
+}
+
+fn read_version(meta_path: &Path) -> Option<String> {
+    let content = std::fs::read_to_string(meta_path).ok()?;
+    let meta: serde_json::Value = serde_json::from_str(&content).ok()?;
+    meta.get("version").and_then(|v| v.as_str()).map(String::from)
+}
+
+/// Archivierte Versionen einer Mod, neueste zuerst
+pub fn list_archived(game_dir: &Path, mod_id: &str) -> Vec<ArchivedModVersion> {
+    let dir = mod_archive_dir(game_dir, mod_id);
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+        return Vec::new();
+    };
+
+    let mut versions: Vec<ArchivedModVersion> = entries
+        .flatten()
+        .filter(|e| e.file_name().to_string_lossy().ends_with(".jar"))
+        .map(|e| {
+            let filename = e.file_name().to_string_lossy().to_string();
+            let installed_at = e.metadata()
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                .map(|d| d.as_secs())
+                .unwrap_or(0);
+            ArchivedModVersion {
+                version: read_version(&dir.join(meta_name(&filename))),
+                filename,
+                installed_at,
+            }
+        })
+        .collect();
+    versions.sort_by_key(|v| std::cmp::Reverse(v.installed_at));
+    versions
+}
+
+/// Verschiebt ein installiertes JAR und seine Metadaten ins Archiv der Mod
+/// und entfernt dort ältere Versionen über `MAX_ARCHIVED_VERSIONS` hinaus
+pub fn archive_mod(game_dir: &Path, mod_id: &str, jar_filename: &str) -> Result<PathBuf> {
+    let dir = mod_archive_dir(game_dir, mod_id);
+    std::fs::create_dir_all(&dir)?;
+
+    let target = dir.join(jar_filename);
+    std::fs::rename(game_dir.join("mods").join(jar_filename), &target)
+        .with_context(|| format!("Could not archive {}", jar_filename))?;
+    if let Some(record) = content_db::remove(game_dir, jar_filename)? {
+        std::fs::write(dir.join(meta_name(jar_filename)), serde_json::to_string_pretty(&record)?)?;
+    }
+    tracing::info!("Archived previous version of {}: {}", mod_id, jar_filename);
+
+    for old in list_archived(game_dir, mod_id).into_iter().skip(MAX_ARCHIVED_VERSIONS) {
+        std::fs::remove_file(dir.join(&old.filename)).ok();
+        std::fs::remove_file(dir.join(meta_name(&old.filename))).ok();
+    }
+    Ok(target)
+}
+
+/// Aktive JARs (Dateiname) einer Mod laut Inhalts-Datenbank
+pub fn installed_files(game_dir: &Path, mod_id: &str) -> Vec<String> {
+    content_db::installed_by_project(game_dir, mod_id)
+        .into_iter()
+        .filter(|record| record.enabled)
+        .map(|record| record.filename)
+        .collect()
+}
+
+/// Ersetzt die installierte Version durch die zuletzt archivierte.
+/// Die ersetzte Version wird gelöscht, ein erneutes Update lädt sie wieder herunter.
+pub fn rollback(game_dir: &Path, mod_id: &str) -> Result<ModRollbackResult> {
+    let Some(previous) = list_archived(game_dir, mod_id).into_iter().next() else {
+        bail!("No previous version of {} archived", mod_id);
+    };
+    restore(game_dir, mod_id, &previous.filename)
+}
+
+/// Stellt eine bestimmte archivierte Version wieder her (wie `rollback`)
+pub fn restore(game_dir: &Path, mod_id: &str, filename: &str) -> Result<ModRollbackResult> {
+    let dir = mod_archive_dir(game_dir, mod_id);
+    let Some(previous) = list_archived(game_dir, mod_id).into_iter().find(|v| v.filename == filename) else {
+        bail!("Version {} of {} is no longer archived", filename, mod_id);
+    };
+
+    let mods_dir = game_dir.join("mods");
+
+    let current: Vec<String> = installed_files(game_dir, mod_id)
+        .into_iter()
+        .filter(|filename| filename != &previous.filename)
+        .collect();
+    for filename in &current {
+        std::fs::remove_file(mods_dir.join(filename))?;
+        content_db::remove(game_dir, filename)?;
+    }
+
+    std::fs::rename(dir.join(&previous.filename), mods_dir.join(&previous.filename))?;
+    let archived_meta = dir.join(meta_name(&previous.filename));
+    let record = std::fs::read_to_string(&archived_meta)
+        .ok()
+        .and_then(|c| serde_json::from_str::<content_db::ContentRecord>(&c).ok());
+    if let Some(record) = record {
+        content_db::upsert(game_dir, content_db::ContentRecord { enabled: true, ..record })?;
+        std::fs::remove_file(&archived_meta).ok();
+    }
+    tracing::info!("Rolled back {} to {}", mod_id, previous.filename);
+
+    Ok(ModRollbackResult {
+        mod_id: mod_id.to_string(),
+        restored_filename: previous.filename,
+        restored_version: previous.version,
+        removed_filename: current.into_iter().next(),
+    })
+}
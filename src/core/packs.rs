@@ -0,0 +1,215 @@
+#![allow(dead_code)]
+
+//! Import lokaler Resource Packs (ZIP oder Ordner) und Shader Packs (ZIP) in ein
+//! Profil – Gegenstück zur Installation über Modrinth.
+
+use anyhow::{Context, Result, bail};
+use serde::Serialize;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ResourcePackImport {
+    pub file_name: String,
+    pub description: Option<String>,
+    pub pack_format: Option<u32>,
+    pub is_folder: bool,
+    pub size: u64,
+    pub icon_base64: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ShaderPackImport {
+    pub file_name: String,
+    pub size: u64,
+    /// Anzahl der Shader-Programme (.fsh/.vsh/.gsh/.csh)
+    pub program_count: usize,
+    /// shaders.properties vorhanden (Optionen/Profile des Packs)
+    pub has_properties: bool,
+    /// Dimensions-Ordner wie "world-1" (Nether) oder "world1" (End)
+    pub dimensions: Vec<String>,
+}
+
+/// Wandelt eine Beschreibung aus pack.mcmeta (Text oder Text-Komponente) in reinen Text um
+fn text_component_to_string(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Array(parts) => parts.iter().map(text_component_to_string).collect(),
+        serde_json::Value::Object(obj) => {
+            let mut text = obj.get("text").and_then(|t| t.as_str()).unwrap_or_default().to_string();
+            if let Some(extra) = obj.get("extra") {
+                text.push_str(&text_component_to_string(extra));
+            }
+            text
+        }
+        _ => String::new(),
+    }
+}
+
+/// Liest pack_format und Beschreibung aus dem Inhalt einer pack.mcmeta
+fn parse_pack_mcmeta(content: &str) -> Result<(Option<u32>, Option<String>)> {
+    let json: serde_json::Value = serde_json::from_str(content.trim_start_matches('\u{feff}'))
+        .context("pack.mcmeta is not valid JSON")?;
+    let pack = json.get("pack").context("pack.mcmeta has no \"pack\" section")?;
+
+    let pack_format = pack.get("pack_format").and_then(|f| f.as_u64()).map(|f| f as u32);
+    let description = pack.get("description")
+        .map(text_component_to_string)
+        .filter(|d| !d.is_empty());
+    Ok((pack_format, description))
+}
+
+fn encode_icon(data: &[u8]) -> String {
+    use base64::{Engine as _, engine::general_purpose};
+    format!("data:image/png;base64,{}", general_purpose::STANDARD.encode(data))
+}
+
+/// Freier Zielname: "Name.zip" → "Name (2).zip", falls schon vorhanden
+pub(crate) fn unique_target(dir: &Path, file_name: &str) -> PathBuf {
+    match file_name.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() => unique_path(dir, stem, &format!(".{}", ext)),
+        _ => unique_path(dir, file_name, ""),
+    }
+}
+
+/// Wie `unique_target`, aber für Ordner: Punkte im Namen ("Welt 1.20") sind keine Endung
+pub(crate) fn unique_dir_target(dir: &Path, folder_name: &str) -> PathBuf {
+    unique_path(dir, folder_name, "")
+}
+
+fn unique_path(dir: &Path, stem: &str, ext: &str) -> PathBuf {
+    let target = dir.join(format!("{}{}", stem, ext));
+    if !target.exists() {
+        return target;
+    }
+    (2..)
+        .map(|n| dir.join(format!("{} ({}){}", stem, n, ext)))
+        .find(|candidate| !candidate.exists())
+        .unwrap()
+}
+
+pub(crate) fn copy_dir(src: &Path, dst: &Path) -> Result<u64> {
+    let mut size = 0;
+    for entry in walkdir::WalkDir::new(src) {
+        let entry = entry?;
+        let target = dst.join(entry.path().strip_prefix(src)?);
+        if entry.file_type().is_dir() {
+            std::fs::create_dir_all(&target)?;
+        } else {
+            size += std::fs::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(size)
+}
+
+/// Prüft ein Resource Pack (ZIP oder Ordner mit pack.mcmeta im Wurzelverzeichnis)
+/// und kopiert es nach `resourcepacks_dir`. Blockierend, über `run_blocking` aufrufen.
+pub fn import_resourcepack(source: &Path, resourcepacks_dir: &Path) -> Result<ResourcePackImport> {
+    let file_name = source.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .context("Invalid resource pack path")?;
+    std::fs::create_dir_all(resourcepacks_dir)?;
+
+    if source.is_dir() {
+        let mcmeta = std::fs::read_to_string(source.join("pack.mcmeta"))
+            .context("Folder contains no pack.mcmeta – not a resource pack")?;
+        let (pack_format, description) = parse_pack_mcmeta(&mcmeta)?;
+        let icon_base64 = std::fs::read(source.join("pack.png")).ok().map(|d| encode_icon(&d));
+
+        let target = unique_target(resourcepacks_dir, &file_name);
+        let size = copy_dir(source, &target)?;
+        tracing::info!("Imported resource pack folder {:?} to {:?}", source, target);
+
+        return Ok(ResourcePackImport {
+            file_name: target.file_name().unwrap_or_default().to_string_lossy().to_string(),
+            description,
+            pack_format,
+            is_folder: true,
+            size,
+            icon_base64,
+        });
+    }
+
+    let mut archive = zip::ZipArchive::new(std::fs::File::open(source)?)
+        .context("File is not a valid ZIP archive")?;
+
+    if archive.file_names().all(|name| name != "pack.mcmeta") {
+        // Häufiger Fehler: Ordner statt Inhalt gezippt
+        if archive.file_names().any(|name| name.ends_with("/pack.mcmeta")) {
+            bail!("pack.mcmeta is inside a subfolder – the ZIP must contain the pack contents directly");
+        }
+        bail!("ZIP contains no pack.mcmeta – not a resource pack");
+    }
+    let mut mcmeta = String::new();
+    archive.by_name("pack.mcmeta")?.read_to_string(&mut mcmeta)?;
+    let (pack_format, description) = parse_pack_mcmeta(&mcmeta)?;
+
+    let icon_base64 = archive.by_name("pack.png").ok().and_then(|mut entry| {
+        let mut data = Vec::new();
+        entry.read_to_end(&mut data).ok()?;
+        Some(encode_icon(&data))
+    });
+
+    let target = unique_target(resourcepacks_dir, &file_name);
+    let size = std::fs::copy(source, &target)?;
+    tracing::info!("Imported resource pack {:?} to {:?}", source, target);
+
+    Ok(ResourcePackImport {
+        file_name: target.file_name().unwrap_or_default().to_string_lossy().to_string(),
+        description,
+        pack_format,
+        is_folder: false,
+        size,
+        icon_base64,
+    })
+}
+
+/// Prüft ob eine ZIP wie ein Shader Pack aussieht (`shaders/` im Wurzelverzeichnis)
+/// und kopiert sie nach `shaderpacks_dir`. Blockierend, über `run_blocking` aufrufen.
+pub fn import_shaderpack(source: &Path, shaderpacks_dir: &Path) -> Result<ShaderPackImport> {
+    let file_name = source.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .context("Invalid shader pack path")?;
+    if source.is_dir() {
+        bail!("Shader packs must be imported as ZIP file");
+    }
+
+    let archive = zip::ZipArchive::new(std::fs::File::open(source)?)
+        .context("File is not a valid ZIP archive")?;
+    let names: Vec<&str> = archive.file_names().collect();
+
+    let shader_files: Vec<&str> = names.iter()
+        .filter_map(|name| name.strip_prefix("shaders/"))
+        .collect();
+    if shader_files.is_empty() {
+        if names.iter().any(|name| name.contains("/shaders/")) {
+            bail!("shaders/ is inside a subfolder – the ZIP must contain the shaders folder directly");
+        }
+        bail!("ZIP contains no shaders/ folder – not a shader pack");
+    }
+
+    let program_count = shader_files.iter()
+        .filter(|name| [".fsh", ".vsh", ".gsh", ".csh"].iter().any(|ext| name.ends_with(ext)))
+        .count();
+    let has_properties = shader_files.contains(&"shaders.properties");
+    let mut dimensions: Vec<String> = shader_files.iter()
+        .filter_map(|name| name.split_once('/').map(|(dir, _)| dir))
+        .filter(|dir| dir.starts_with("world"))
+        .map(String::from)
+        .collect();
+    dimensions.sort();
+    dimensions.dedup();
+
+    std::fs::create_dir_all(shaderpacks_dir)?;
+    let target = unique_target(shaderpacks_dir, &file_name);
+    let size = std::fs::copy(source, &target)?;
+    tracing::info!("Imported shader pack {:?} to {:?} ({} programs)", source, target, program_count);
+
+    Ok(ShaderPackImport {
+        file_name: target.file_name().unwrap_or_default().to_string_lossy().to_string(),
+        size,
+        program_count,
+        has_properties,
+        dimensions,
+    })
+}
@@ -0,0 +1,84 @@
+//! Übersicht über die Inhalte eines Spielordners (Mods, Packs, Welten, Screenshots)
+//! in einem Durchlauf – für die Profilkarten.
+
+use serde::Serialize;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct ContentCount {
+    pub count: usize,
+    pub size_bytes: u64,
+}
+
+impl ContentCount {
+    fn add(&mut self, size: u64) {
+        self.count += 1;
+        self.size_bytes += size;
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProfileContentSummary {
+    pub mods_enabled: ContentCount,
+    pub mods_disabled: ContentCount,
+    pub resourcepacks: ContentCount,
+    pub shaderpacks: ContentCount,
+    pub worlds: ContentCount,
+    pub screenshots: ContentCount,
+}
+
+/// Größe einer Datei bzw. eines Ordners (rekursiv)
+fn entry_size(path: &Path) -> u64 {
+    if path.is_file() {
+        return std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+    }
+    crate::core::fs::size::directory_size(path, &[])
+}
+
+/// Ruft `visit` für jeden direkten Eintrag eines Ordners auf (fehlender Ordner = leer)
+fn for_each_entry(dir: &Path, mut visit: impl FnMut(&Path, &str)) {
+    let Ok(entries) = std::fs::read_dir(dir) else { return };
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_lowercase();
+        visit(&entry.path(), &name);
+    }
+}
+
+/// Zählt die Inhalte eines Spielordners. Blockierend, über `run_blocking` aufrufen.
+pub fn summarize(game_dir: &Path) -> ProfileContentSummary {
+    let mut summary = ProfileContentSummary::default();
+
+    for_each_entry(&game_dir.join("mods"), |path, name| {
+        if name.ends_with(".jar") {
+            summary.mods_enabled.add(entry_size(path));
+        } else if name.ends_with(".disabled") {
+            summary.mods_disabled.add(entry_size(path));
+        }
+    });
+
+    for_each_entry(&game_dir.join("resourcepacks"), |path, name| {
+        if path.is_dir() || name.ends_with(".zip") {
+            summary.resourcepacks.add(entry_size(path));
+        }
+    });
+
+    for_each_entry(&game_dir.join("shaderpacks"), |path, name| {
+        if path.is_dir() || name.ends_with(".zip") {
+            summary.shaderpacks.add(entry_size(path));
+        }
+    });
+
+    for_each_entry(&game_dir.join("saves"), |path, _| {
+        if path.join("level.dat").is_file() {
+            summary.worlds.add(entry_size(path));
+        }
+    });
+
+    for_each_entry(&game_dir.join("screenshots"), |path, name| {
+        if name.ends_with(".png") || name.ends_with(".jpg") {
+            summary.screenshots.add(entry_size(path));
+        }
+    });
+
+    summary
+}
@@ -1,40 +1,131 @@
 #![allow(dead_code)]
 
+pub mod content;
+
 use anyhow::Result;
 use std::path::PathBuf;
 use crate::types::profile::{Profile, ProfileList};
+use crate::utils::error::LauncherError;
+
+/// Im Speicher gehaltene profiles.json – von allen ProfileManager-Instanzen geteilt,
+/// damit Commands nicht bei jedem Aufruf die Datei neu lesen.
+static PROFILES_CACHE: std::sync::OnceLock<tokio::sync::RwLock<Option<ProfileList>>> = std::sync::OnceLock::new();
+
+fn profiles_cache() -> &'static tokio::sync::RwLock<Option<ProfileList>> {
+    PROFILES_CACHE.get_or_init(|| tokio::sync::RwLock::new(None))
+}
+
+/// Änderungszeit und Größe von profiles.json nach dem letzten eigenen Lesen/Schreiben.
+/// Weicht die Datei davon ab, wurde sie von außen geändert. Die Größe fängt Änderungen ab,
+/// die auf Dateisystemen mit grober Zeitauflösung dieselbe Änderungszeit bekommen.
+static KNOWN_MTIME: std::sync::Mutex<Option<(std::time::SystemTime, u64)>> = std::sync::Mutex::new(None);
 
-pub struct ProfileManager {
-    profiles_path: PathBuf,
+fn file_mtime(path: &std::path::Path) -> Option<(std::time::SystemTime, u64)> {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
 }
 
+fn remember_mtime(path: &std::path::Path) {
+    if let Ok(mut known) = KNOWN_MTIME.lock() {
+        *known = file_mtime(path);
+    }
+}
+
+/// Überwacht profiles.json auf externe Änderungen (Editor, Cloud-Sync, zweite Instanz),
+/// verwirft dann den Cache und ruft `on_change` auf. Läuft in einem eigenen Thread.
+///
+/// Bewusst per Polling statt über Dateisystem-Events: ein `stat` alle zwei Sekunden kostet
+/// praktisch nichts, funktioniert auch auf Netzlaufwerken und mit Cloud-Ordnern (wo Events
+/// oft fehlen), übersteht das Ersetzen der Datei per Rename und folgt einem zur Laufzeit
+/// verschobenen Datenordner ohne Neu-Registrierung.
+pub fn watch_profiles_file(on_change: impl Fn() + Send + 'static) {
+    if let Ok(mut known) = KNOWN_MTIME.lock() {
+        if known.is_none() {
+            *known = file_mtime(&profiles_file());
+        }
+    }
+    std::thread::spawn(move || loop {
+        std::thread::sleep(std::time::Duration::from_secs(2));
+
+        // Pfad jedes Mal neu auflösen – der Datenordner kann zur Laufzeit umziehen
+        let current = file_mtime(&profiles_file());
+        let changed = KNOWN_MTIME.lock()
+            .map(|mut known| {
+                let changed = *known != current;
+                *known = current;
+                changed
+            })
+            .unwrap_or(false);
+
+        if changed {
+            tracing::info!("profiles.json changed on disk, reloading");
+            if let Ok(mut cache) = profiles_cache().try_write() {
+                *cache = None;
+            } else {
+                // Cache gerade in Benutzung – beim nächsten Durchlauf erneut versuchen
+                if let Ok(mut known) = KNOWN_MTIME.lock() {
+                    *known = None;
+                }
+                continue;
+            }
+            on_change();
+        }
+    });
+}
+
+/// profiles.json im aktuellen Datenordner
+fn profiles_file() -> PathBuf {
+    crate::config::defaults::launcher_dir().join("profiles.json")
+}
+
+/// Zustandslos: der Pfad wird bei jedem Zugriff aus `launcher_dir()` bestimmt, damit auch
+/// langlebige Instanzen (AppState) nach `migrate_launcher_data` den neuen Ort verwenden.
+pub struct ProfileManager;
+
 impl ProfileManager {
     pub fn new() -> Result<Self> {
-        let profiles_path = crate::config::defaults::launcher_dir().join("profiles.json");
-        Ok(Self { profiles_path })
+        Ok(Self)
     }
 
     pub async fn load_profiles(&self) -> Result<ProfileList> {
-        if !self.profiles_path.exists() {
+        if let Some(cached) = profiles_cache().read().await.as_ref() {
+            return Ok(cached.clone());
+        }
+
+        let mut cache = profiles_cache().write().await;
+        if let Some(cached) = cache.as_ref() {
+            return Ok(cached.clone());
+        }
+        let profiles_path = profiles_file();
+        if !profiles_path.exists() {
             return Ok(ProfileList::default());
         }
 
-        let content = tokio::fs::read_to_string(&self.profiles_path).await?;
+        let content = tokio::fs::read_to_string(&profiles_path).await?;
         let profiles: ProfileList = serde_json::from_str(&content)?;
+        remember_mtime(&profiles_path);
+        *cache = Some(profiles.clone());
         Ok(profiles)
     }
 
     pub async fn save_profiles(&self, profiles: &ProfileList) -> Result<()> {
         let content = serde_json::to_string_pretty(profiles)?;
-        
-        if let Some(parent) = self.profiles_path.parent() {
-            tokio::fs::create_dir_all(parent).await?;
-        }
-        
-        tokio::fs::write(&self.profiles_path, content).await?;
+
+        let profiles_path = profiles_file();
+        // Sichern vor dem Sperren, damit Leser des Caches nicht auf das Kopieren warten
+        crate::config::backup::backup_async(&profiles_path).await;
+        let mut cache = profiles_cache().write().await;
+        crate::config::backup::write_atomic(&profiles_path, &content).await?;
+        remember_mtime(&profiles_path);
+        *cache = Some(profiles.clone());
         Ok(())
     }
 
+    /// Verwirft den Cache, z.B. nachdem profiles.json von außen geändert wurde
+    pub async fn invalidate_cache() {
+        *profiles_cache().write().await = None;
+    }
+
     pub async fn create_profile(&self, profile: Profile) -> Result<ProfileList> {
         let mut profiles = self.load_profiles().await?;
         
@@ -51,11 +142,13 @@ impl ProfileManager {
     pub async fn delete_profile(&self, profile_id: &str) -> Result<ProfileList> {
         let mut profiles = self.load_profiles().await?;
         
-        if let Some(profile) = profiles.get_profile(profile_id) {
-            // Optionally delete the game directory
-            if profile.game_dir.exists() {
-                tokio::fs::remove_dir_all(&profile.game_dir).await.ok();
-            }
+        if let Some(profile) = profiles.get_profile(profile_id).cloned() {
+            // Spielordner und Artwork landen im Papierkorb und können wiederhergestellt werden
+            let paths = vec![profile.game_dir.clone(), crate::core::artwork::artwork_dir(profile_id)];
+            crate::utils::compression::run_blocking(move || {
+                crate::core::trash::move_to_trash("profile", &profile.name.clone(), Some(&profile.id.clone()), &paths, Some(profile))
+            })
+            .await?;
         }
         
         profiles.remove_profile(profile_id);
@@ -64,6 +157,88 @@ impl ProfileManager {
         Ok(profiles)
     }
 
+    /// Archiviert ein Profil; mit `compress` wird der Spielordner als ZIP abgelegt und entfernt
+    pub async fn archive_profile(&self, profile_id: &str, compress: bool) -> Result<ProfileList> {
+        let mut profiles = self.load_profiles().await?;
+        let profile = profiles.get_profile_mut(profile_id)
+            .ok_or_else(|| LauncherError::ProfileNotFound(profile_id.to_string()))?;
+
+        if compress && profile.archive_path.is_none() && profile.game_dir.exists() {
+            let snapshot = profile.clone();
+            let archive = crate::utils::compression::run_blocking(move || compress_game_dir(&snapshot)).await?;
+            profile.archive_path = Some(archive);
+        }
+        profile.archived = true;
+        tracing::info!("Archived profile {} (compressed: {})", profile.name, profile.archive_path.is_some());
+
+        self.save_profiles(&profiles).await?;
+        Ok(profiles)
+    }
+
+    /// Holt ein Profil aus dem Archiv zurück und entpackt ggf. den Spielordner
+    pub async fn unarchive_profile(&self, profile_id: &str) -> Result<ProfileList> {
+        let mut profiles = self.load_profiles().await?;
+        let profile = profiles.get_profile_mut(profile_id)
+            .ok_or_else(|| LauncherError::ProfileNotFound(profile_id.to_string()))?;
+
+        if let Some(archive) = profile.archive_path.clone() {
+            let game_dir = profile.game_dir.clone();
+            crate::utils::compression::run_blocking(move || {
+                if game_dir.exists() && std::fs::read_dir(&game_dir)?.next().is_some() {
+                    anyhow::bail!("Game directory {:?} already exists and is not empty", game_dir);
+                }
+                crate::utils::compression::extract_zip(&archive, &game_dir)?;
+                std::fs::remove_file(&archive)?;
+                Ok(())
+            })
+            .await?;
+            profile.archive_path = None;
+        }
+        profile.archived = false;
+        tokio::fs::create_dir_all(profile.game_dir.join("mods")).await?;
+        tracing::info!("Unarchived profile {}", profile.name);
+
+        self.save_profiles(&profiles).await?;
+        Ok(profiles)
+    }
+
+    /// Verschiebt den Spielordner nach `target` (z.B. auf ein anderes Laufwerk). Ist `target`
+    /// ein nicht leerer Ordner, landet der Spielordner darin unter seinem bisherigen Namen.
+    pub async fn move_game_dir(&self, profile_id: &str, target: PathBuf) -> Result<crate::core::fs::relocate::MoveReport> {
+        let profile = self.load_profiles().await?
+            .get_profile(profile_id)
+            .cloned()
+            .ok_or_else(|| LauncherError::ProfileNotFound(profile_id.to_string()))?;
+        if profile.archive_path.is_some() {
+            anyhow::bail!("Profile {} is archived as ZIP, unarchive it first", profile.name);
+        }
+
+        let source = profile.game_dir.clone();
+        let target = if target.is_dir() && std::fs::read_dir(&target)?.next().is_some() {
+            target.join(source.file_name().unwrap_or(std::ffi::OsStr::new(profile_id)))
+        } else {
+            target
+        };
+        if target == source {
+            anyhow::bail!("Profile already uses {}", target.display());
+        }
+
+        let destination = target.clone();
+        let report = crate::utils::compression::run_blocking(move || {
+            crate::core::fs::relocate::move_directory(&source, &destination)
+        })
+        .await?;
+
+        tracing::info!("Moved game directory of {} to {:?}", profile.name, target);
+        // Neu laden: das Kopieren kann dauern, andere Änderungen sollen erhalten bleiben
+        let mut profiles = self.load_profiles().await?;
+        if let Some(profile) = profiles.get_profile_mut(profile_id) {
+            profile.game_dir = target;
+        }
+        self.save_profiles(&profiles).await?;
+        Ok(report)
+    }
+
     pub async fn update_profile(&self, profile: Profile) -> Result<ProfileList> {
         let mut profiles = self.load_profiles().await?;
         
@@ -75,3 +250,85 @@ impl ProfileManager {
         Ok(profiles)
     }
 }
+
+/// Ablage der komprimierten Spielordner archivierter Profile
+pub fn archives_dir() -> PathBuf {
+    crate::config::defaults::launcher_dir().join("archives")
+}
+
+/// Packt den Spielordner als `archives/<id>.zip` und entfernt ihn danach.
+/// Blockierend, über `run_blocking` aufrufen.
+fn compress_game_dir(profile: &Profile) -> Result<PathBuf> {
+    let dir = archives_dir();
+    std::fs::create_dir_all(&dir)?;
+    let archive = dir.join(format!("{}.zip", profile.id));
+
+    crate::utils::compression::compress_directory(&profile.game_dir, &archive)?;
+    // Nur löschen wenn das Archiv lesbar ist
+    if !crate::utils::compression::is_valid_zip(&archive) {
+        std::fs::remove_file(&archive).ok();
+        anyhow::bail!("Compressed archive of {} is not readable", profile.name);
+    }
+    std::fs::remove_dir_all(&profile.game_dir)?;
+    Ok(archive)
+}
+
+/// Ordner im Spielverzeichnis die beim Export nie mitgenommen werden
+const EXPORT_SKIP_DIRS: [&str; 5] = ["logs", "crash-reports", "natives", ".cache", "screenshots"];
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ExportSummary {
+    pub path: PathBuf,
+    pub files: usize,
+    pub bytes: u64,
+}
+
+/// Exportiert ein Profil (profile.json + Spielordner) als ZIP.
+/// Welten (saves/) werden nur mit `include_saves` exportiert.
+pub fn export_profile_archive(profile: &Profile, output: &std::path::Path, include_saves: bool) -> Result<ExportSummary> {
+    use std::io::Write;
+
+    if let Some(parent) = output.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let file = std::fs::File::create(output)?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::FileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+
+    zip.start_file("profile.json", options)?;
+    zip.write_all(serde_json::to_string_pretty(profile)?.as_bytes())?;
+
+    let mut files = 1;
+    let mut bytes = 0;
+    for entry in walkdir::WalkDir::new(&profile.game_dir)
+        .into_iter()
+        .filter_entry(|e| {
+            let rel = e.path().strip_prefix(&profile.game_dir).unwrap_or(e.path());
+            let top = rel.components().next().map(|c| c.as_os_str().to_string_lossy().to_string());
+            match top.as_deref() {
+                Some("saves") => include_saves,
+                Some(dir) => !EXPORT_SKIP_DIRS.contains(&dir),
+                None => true,
+            }
+        })
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+    {
+        // Ziel-ZIP im exportierten Ordner nicht in sich selbst packen
+        if Some(entry.file_name()) == output.file_name()
+            && same_file::is_same_file(entry.path(), output).unwrap_or(false)
+        {
+            continue;
+        }
+        let rel = entry.path().strip_prefix(&profile.game_dir)?;
+        let name = format!("minecraft/{}", rel.to_string_lossy().replace('\\', "/"));
+        zip.start_file(name, options)?;
+        let mut source = std::fs::File::open(entry.path())?;
+        bytes += std::io::copy(&mut source, &mut zip)?;
+        files += 1;
+    }
+    zip.finish()?;
+
+    Ok(ExportSummary { path: output.to_path_buf(), files, bytes })
+}
@@ -0,0 +1,115 @@
+#![allow(dead_code)]
+
+//! Aufzeichnung der Spielsitzungen (Start, Ende, Exit-Code) als Grundlage
+//! für Spielzeit- und Launcher-Statistiken.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// Maximale Anzahl gespeicherter Sitzungen (älteste werden verworfen)
+const MAX_SESSIONS: usize = 5000;
+
+/// Serialisiert Lesen und Schreiben von sessions.json – mehrere Instanzen können gleichzeitig enden
+static SESSIONS_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionRecord {
+    pub profile_id: String,
+    pub started_at: String,
+    pub ended_at: String,
+    pub duration_secs: u64,
+    pub exit_code: Option<i32>,
+}
+
+fn sessions_file() -> PathBuf {
+    crate::config::defaults::launcher_dir().join("sessions.json")
+}
+
+pub fn load_sessions() -> Vec<SessionRecord> {
+    let _guard = SESSIONS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    read_sessions()
+}
+
+/// Aufrufer halten `SESSIONS_LOCK`.
+fn read_sessions() -> Vec<SessionRecord> {
+    std::fs::read_to_string(sessions_file())
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+/// Aufrufer halten `SESSIONS_LOCK`.
+fn save_sessions(sessions: &[SessionRecord]) -> Result<()> {
+    let path = sessions_file();
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, serde_json::to_string(sessions)?)?;
+    std::fs::rename(&tmp, &path)?;
+    Ok(())
+}
+
+/// Speichert eine beendete Sitzung und aktualisiert die Profil-Statistiken
+/// (launch_count, total_playtime_secs, last_exit_status). Wird vom Warte-Task
+/// der Spielinstanz aufgerufen.
+pub async fn finish_session(profile_id: &str, started_at: chrono::DateTime<chrono::Utc>, exit_code: Option<i32>) {
+    let _stopped_guard = crate::core::minecraft::exit_report::StoppedByUserGuard::new(profile_id);
+    let duration_secs = record_session(profile_id, started_at, exit_code);
+
+    let result: Result<Option<PathBuf>> = async {
+        let manager = crate::core::profiles::ProfileManager::new()?;
+        let mut profiles = manager.load_profiles().await?;
+        let Some(profile) = profiles.get_profile_mut(profile_id) else { return Ok(None) };
+        profile.record_session(duration_secs, exit_code);
+        let game_dir = profile.game_dir.clone();
+        manager.save_profiles(&profiles).await?;
+        Ok(Some(game_dir))
+    }.await;
+
+    let game_dir = match result {
+        Ok(game_dir) => game_dir,
+        Err(e) => {
+            tracing::warn!("Could not update profile stats for {}: {}", profile_id, e);
+            None
+        }
+    };
+
+    // Unerwartetes Ende an die Oberfläche melden (Dialog mit Neustart, Log, Diagnose)
+    if let Some(game_dir) = game_dir {
+        let profile_id = profile_id.to_string();
+        let report = crate::utils::compression::run_blocking(move || {
+            Ok(crate::core::minecraft::exit_report::evaluate(&profile_id, &game_dir, started_at, duration_secs, exit_code))
+        })
+        .await
+        .ok()
+        .flatten();
+        if let Some(report) = report {
+            crate::core::telemetry::record_failure(crate::core::telemetry::FailureCategory::from_exit_report(&report));
+            crate::core::minecraft::notify_lifecycle(crate::core::minecraft::InstanceLifecycle::Crashed { report });
+        }
+    }
+}
+
+/// Hängt eine beendete Sitzung an sessions.json an und gibt ihre Dauer zurück
+fn record_session(profile_id: &str, started_at: chrono::DateTime<chrono::Utc>, exit_code: Option<i32>) -> u64 {
+    let ended_at = chrono::Utc::now();
+    let duration_secs = (ended_at - started_at).num_seconds().max(0) as u64;
+    let record = SessionRecord {
+        profile_id: profile_id.to_string(),
+        started_at: started_at.to_rfc3339(),
+        ended_at: ended_at.to_rfc3339(),
+        duration_secs,
+        exit_code,
+    };
+
+    let _guard = SESSIONS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut sessions = read_sessions();
+    sessions.push(record);
+    let overflow = sessions.len().saturating_sub(MAX_SESSIONS);
+    sessions.drain(..overflow);
+
+    if let Err(e) = save_sessions(&sessions) {
+        tracing::warn!("Could not save session record: {}", e);
+    }
+    duration_secs
+}
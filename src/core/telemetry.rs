@@ -0,0 +1,207 @@
+//! Opt-in-Telemetrie: anonyme Nutzungszahlen (Starts, Loader-Verteilung, Fehlerkategorien).
+//! Gezählt wird nur bei aktivierter Telemetrie (`telemetry.enabled`, standardmäßig aus).
+//! Gesendet wird genau der Bericht aus `preview` – keine Profilnamen, Pfade, Accounts oder
+//! Mod-Listen. Die Installations-ID ist zufällig und wird beim Abschalten verworfen.
+
+use anyhow::{Result, bail};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use crate::config::flag::ConfigFlag;
+use crate::config::schema::TelemetrySettings;
+use crate::core::minecraft::exit_report::{ExitAction, GameExitReport};
+
+const SCHEMA_VERSION: u32 = 1;
+
+/// Gesammelte Zahlen werden höchstens einmal pro Woche gesendet
+const SEND_INTERVAL_DAYS: i64 = 7;
+
+/// Serialisiert Zugriffe auf telemetry.json
+static STATS_LOCK: Mutex<()> = Mutex::new(());
+
+static ENABLED: ConfigFlag = ConfigFlag::new(|c| c.telemetry.enabled);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailureCategory {
+    /// Vorbereitung oder Start des Prozesses fehlgeschlagen
+    LaunchError,
+    Crash,
+    /// Absturz der JVM selbst (hs_err_pid*.log)
+    JvmCrash,
+    OutOfMemory,
+    /// Exit-Code ≠ 0 ohne Crash-Report
+    AbnormalExit,
+}
+
+impl FailureCategory {
+    pub fn from_exit_report(report: &GameExitReport) -> Self {
+        if report.suggested_actions.contains(&ExitAction::IncreaseMemory) {
+            Self::OutOfMemory
+        } else if report.crash_report.as_deref().is_some_and(|r| r.starts_with("hs_err_pid")) {
+            Self::JvmCrash
+        } else if report.crash_detected {
+            Self::Crash
+        } else {
+            Self::AbnormalExit
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct TelemetryStats {
+    install_id: String,
+    period_start: String,
+    launches: u64,
+    loaders: BTreeMap<String, u64>,
+    failures: BTreeMap<FailureCategory, u64>,
+}
+
+/// Der Bericht, wie er gesendet würde
+#[derive(Debug, Clone, Serialize)]
+pub struct TelemetryReport {
+    pub schema_version: u32,
+    pub install_id: String,
+    pub launcher_version: String,
+    pub os: String,
+    pub arch: String,
+    pub period_start: String,
+    pub period_end: String,
+    pub launches: u64,
+    /// Starts pro Mod-Loader ("vanilla", "fabric", ...)
+    pub loaders: BTreeMap<String, u64>,
+    pub failures: BTreeMap<FailureCategory, u64>,
+}
+
+fn stats_file() -> PathBuf {
+    crate::config::defaults::launcher_dir().join("telemetry.json")
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.get()
+}
+
+/// Schalter aus der Konfiguration. Beim Abschalten werden alle gesammelten Daten gelöscht.
+pub fn set_enabled(enabled: bool) {
+    ENABLED.set(enabled);
+    if !enabled {
+        let _guard = STATS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        if std::fs::remove_file(stats_file()).is_ok() {
+            tracing::info!("Telemetry disabled, collected data removed");
+        }
+    }
+}
+
+fn new_stats() -> TelemetryStats {
+    TelemetryStats {
+        install_id: uuid::Uuid::new_v4().to_string(),
+        period_start: chrono::Utc::now().to_rfc3339(),
+        ..Default::default()
+    }
+}
+
+/// Liest telemetry.json; fehlt sie, wird sie einmalig angelegt, damit Vorschau und
+/// späterer Bericht dieselbe Installations-ID und denselben Zeitraum zeigen.
+/// Aufrufer halten `STATS_LOCK`.
+fn load_stats() -> TelemetryStats {
+    if let Some(stats) = std::fs::read_to_string(stats_file())
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+    {
+        return stats;
+    }
+    let stats = new_stats();
+    if let Err(e) = save_stats(&stats) {
+        tracing::debug!("Could not save telemetry stats: {}", e);
+    }
+    stats
+}
+
+fn save_stats(stats: &TelemetryStats) -> Result<()> {
+    std::fs::write(stats_file(), serde_json::to_string_pretty(stats)?)?;
+    Ok(())
+}
+
+/// Zählt nur bei aktivierter Telemetrie
+fn update(f: impl FnOnce(&mut TelemetryStats)) {
+    if !is_enabled() {
+        return;
+    }
+    let _guard = STATS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut stats = load_stats();
+    f(&mut stats);
+    if let Err(e) = save_stats(&stats) {
+        tracing::debug!("Could not save telemetry stats: {}", e);
+    }
+}
+
+pub fn record_launch(loader: &str) {
+    update(|stats| {
+        stats.launches += 1;
+        *stats.loaders.entry(loader.to_lowercase()).or_default() += 1;
+    });
+}
+
+pub fn record_failure(category: FailureCategory) {
+    update(|stats| *stats.failures.entry(category).or_default() += 1);
+}
+
+fn build_report(stats: TelemetryStats) -> TelemetryReport {
+    TelemetryReport {
+        schema_version: SCHEMA_VERSION,
+        install_id: stats.install_id,
+        launcher_version: env!("CARGO_PKG_VERSION").to_string(),
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        period_start: stats.period_start,
+        period_end: chrono::Utc::now().to_rfc3339(),
+        launches: stats.launches,
+        loaders: stats.loaders,
+        failures: stats.failures,
+    }
+}
+
+/// Zeigt genau das, was beim nächsten Senden übertragen würde (auch bei deaktivierter Telemetrie)
+pub fn preview() -> TelemetryReport {
+    let _guard = STATS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    build_report(load_stats())
+}
+
+/// Sendet den Bericht, wenn die Telemetrie aktiv, ein Endpunkt eingestellt und der
+/// Zeitraum abgelaufen ist. Danach beginnt ein neuer Zeitraum. Gibt `true` zurück, wenn gesendet wurde.
+pub async fn send_if_due(settings: &TelemetrySettings) -> Result<bool> {
+    let Some(endpoint) = settings.endpoint.as_deref().filter(|e| !e.is_empty()) else { return Ok(false) };
+    if !settings.enabled {
+        return Ok(false);
+    }
+
+    let stats = {
+        let _guard = STATS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        load_stats()
+    };
+    let due = chrono::DateTime::parse_from_rfc3339(&stats.period_start)
+        .map(|start| chrono::Utc::now() - start.with_timezone(&chrono::Utc) >= chrono::Duration::days(SEND_INTERVAL_DAYS))
+        .unwrap_or(true);
+    if !due || stats.launches == 0 && stats.failures.is_empty() {
+        return Ok(false);
+    }
+
+    let report = build_report(stats.clone());
+    let response = reqwest::Client::new()
+        .post(endpoint)
+        .timeout(std::time::Duration::from_secs(15))
+        .json(&report)
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        bail!("Telemetry endpoint returned {}", response.status());
+    }
+
+    // Neuer Zeitraum mit derselben Installations-ID
+    let _guard = STATS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    save_stats(&TelemetryStats { install_id: stats.install_id, ..new_stats() })?;
+    tracing::info!("Sent telemetry report ({} launches)", report.launches);
+    Ok(true)
+}
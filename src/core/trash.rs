@@ -0,0 +1,215 @@
+#![allow(dead_code)]
+
+//! Papierkorb des Launchers: gelöschte Mods, Packs und Profile werden unter `trash/`
+//! aufbewahrt statt sofort entfernt und können wiederhergestellt werden.
+//! Einträge älter als `RETENTION_DAYS` werden automatisch endgültig gelöscht.
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use crate::types::profile::Profile;
+
+const RETENTION_DAYS: i64 = 30;
+
+/// Serialisiert Zugriffe auf index.json
+static TRASH_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashedFile {
+    pub original_path: PathBuf,
+    /// Name innerhalb des Eintrag-Ordners
+    pub stored_name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashItem {
+    pub id: String,
+    /// "mod", "resourcepack", "shaderpack" oder "profile"
+    pub kind: String,
+    pub name: String,
+    pub profile_id: Option<String>,
+    pub deleted_at: String,
+    pub size: u64,
+    pub files: Vec<TrashedFile>,
+    /// Gelöschtes Profil (wird beim Wiederherstellen wieder angelegt)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<Profile>,
+}
+
+pub fn trash_dir() -> PathBuf {
+    crate::config::defaults::launcher_dir().join("trash")
+}
+
+fn index_path() -> PathBuf {
+    trash_dir().join("index.json")
+}
+
+fn load_index() -> Vec<TrashItem> {
+    std::fs::read_to_string(index_path())
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+fn save_index(items: &[TrashItem]) -> Result<()> {
+    std::fs::create_dir_all(trash_dir())?;
+    // Über Temp-Datei schreiben – ein halber Index würde den ganzen Papierkorb unsichtbar machen
+    let path = index_path();
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, serde_json::to_string_pretty(items)?)?;
+    std::fs::rename(&tmp, &path)?;
+    Ok(())
+}
+
+fn path_size(path: &Path) -> u64 {
+    walkdir::WalkDir::new(path)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter_map(|e| e.metadata().ok())
+        .filter(|m| m.is_file())
+        .map(|m| m.len())
+        .sum()
+}
+
+/// Verschiebt eine Datei oder einen Ordner; über Laufwerksgrenzen hinweg per Kopie
+fn move_path(from: &Path, to: &Path) -> Result<()> {
+    if std::fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    if from.is_dir() {
+        crate::core::packs::copy_dir(from, to)?;
+        std::fs::remove_dir_all(from)?;
+    } else {
+        std::fs::copy(from, to)?;
+        std::fs::remove_file(from)?;
+    }
+    Ok(())
+}
+
+/// Verschiebt Dateien/Ordner als einen Eintrag in den Papierkorb.
+/// Pfade die nicht existieren werden übersprungen. Blockierend, über `run_blocking` aufrufen.
+pub fn move_to_trash(
+    kind: &str,
+    name: &str,
+    profile_id: Option<&str>,
+    paths: &[PathBuf],
+    profile: Option<Profile>,
+) -> Result<TrashItem> {
+    let _guard = TRASH_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    purge_expired_locked();
+
+    let id = uuid::Uuid::new_v4().to_string();
+    let item_dir = trash_dir().join(&id);
+    std::fs::create_dir_all(&item_dir)?;
+
+    let mut item = TrashItem {
+        id,
+        kind: kind.to_string(),
+        name: name.to_string(),
+        profile_id: profile_id.map(String::from),
+        deleted_at: chrono::Utc::now().to_rfc3339(),
+        size: 0,
+        files: Vec::new(),
+        profile,
+    };
+
+    for (i, path) in paths.iter().filter(|p| p.exists()).enumerate() {
+        let file_name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        let stored_name = format!("{}-{}", i, file_name);
+        item.size += path_size(path);
+        if let Err(e) = move_path(path, &item_dir.join(&stored_name)) {
+            // Bereits verschobene Dateien zurücklegen, damit nichts halb gelöscht bleibt
+            for moved in &item.files {
+                move_path(&item_dir.join(&moved.stored_name), &moved.original_path).ok();
+            }
+            std::fs::remove_dir_all(&item_dir).ok();
+            return Err(e.context(format!("Could not move {:?} to trash", path)));
+        }
+        item.files.push(TrashedFile { original_path: path.clone(), stored_name });
+    }
+
+    let mut items = load_index();
+    items.push(item.clone());
+    save_index(&items)?;
+    tracing::info!("Moved {} '{}' to trash ({})", kind, name, item.id);
+    Ok(item)
+}
+
+/// Inhalt des Papierkorbs, zuletzt gelöschte zuerst
+pub fn list() -> Vec<TrashItem> {
+    let _guard = TRASH_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut items = load_index();
+    items.reverse();
+    items
+}
+
+pub fn contains(id: &str) -> bool {
+    let _guard = TRASH_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    load_index().iter().any(|i| i.id == id)
+}
+
+/// Legt die Dateien eines Eintrags zurück an ihren ursprünglichen Ort.
+/// Schlägt fehl, wenn dort inzwischen etwas anderes liegt.
+pub fn restore(id: &str) -> Result<TrashItem> {
+    let _guard = TRASH_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut items = load_index();
+    let index = items.iter().position(|i| i.id == id).context("Item not found in trash")?;
+    let item = items[index].clone();
+    let item_dir = trash_dir().join(&item.id);
+
+    if let Some(existing) = item.files.iter().find(|f| f.original_path.exists()) {
+        bail!("{} already exists – remove it first", existing.original_path.display());
+    }
+    for file in &item.files {
+        if let Some(parent) = file.original_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        move_path(&item_dir.join(&file.stored_name), &file.original_path)?;
+    }
+
+    std::fs::remove_dir_all(&item_dir).ok();
+    items.remove(index);
+    save_index(&items)?;
+    tracing::info!("Restored {} '{}' from trash", item.kind, item.name);
+    Ok(item)
+}
+
+/// Löscht einen Eintrag endgültig
+pub fn delete_permanently(id: &str) -> Result<()> {
+    let _guard = TRASH_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut items = load_index();
+    let index = items.iter().position(|i| i.id == id).context("Item not found in trash")?;
+    std::fs::remove_dir_all(trash_dir().join(&items[index].id)).ok();
+    items.remove(index);
+    save_index(&items)
+}
+
+/// Leert den Papierkorb und gibt die Anzahl der entfernten Einträge zurück
+pub fn empty() -> Result<usize> {
+    let _guard = TRASH_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let items = load_index();
+    for item in &items {
+        std::fs::remove_dir_all(trash_dir().join(&item.id)).ok();
+    }
+    save_index(&[])?;
+    tracing::info!("Emptied trash ({} items)", items.len());
+    Ok(items.len())
+}
+
+fn purge_expired_locked() {
+    let cutoff = chrono::Utc::now() - chrono::Duration::days(RETENTION_DAYS);
+    let items = load_index();
+    let (expired, kept): (Vec<_>, Vec<_>) = items.into_iter().partition(|item| {
+        chrono::DateTime::parse_from_rfc3339(&item.deleted_at).is_ok_and(|t| t < cutoff)
+    });
+    if expired.is_empty() {
+        return;
+    }
+    for item in &expired {
+        std::fs::remove_dir_all(trash_dir().join(&item.id)).ok();
+    }
+    if let Err(e) = save_index(&kept) {
+        tracing::warn!("Could not update trash index: {}", e);
+    }
+    tracing::info!("Purged {} expired trash items", expired.len());
+}
@@ -0,0 +1,53 @@
+//! Rückgängig machen von Sammel-Aktionen. Jede Aktion bekommt ein Token; gemerkt werden
+//! nur die letzten `MAX_OPERATIONS` und nur bis zum Neustart des Launchers.
+
+use std::collections::VecDeque;
+use std::sync::{Mutex, OnceLock};
+
+const MAX_OPERATIONS: usize = 20;
+
+#[derive(Debug, Clone)]
+pub enum UndoOperation {
+    /// Umbenennungen (ursprünglicher Name, aktueller Name) in mods/
+    Toggle { profile_id: String, renames: Vec<(String, String)> },
+    /// Papierkorb-Einträge der gelöschten Mods
+    Delete { profile_id: String, trash_ids: Vec<String> },
+}
+
+impl UndoOperation {
+    pub fn profile_id(&self) -> &str {
+        match self {
+            UndoOperation::Toggle { profile_id, .. } | UndoOperation::Delete { profile_id, .. } => profile_id,
+        }
+    }
+}
+
+static OPERATIONS: OnceLock<Mutex<VecDeque<(String, UndoOperation)>>> = OnceLock::new();
+
+fn operations() -> &'static Mutex<VecDeque<(String, UndoOperation)>> {
+    OPERATIONS.get_or_init(|| Mutex::new(VecDeque::new()))
+}
+
+/// Merkt sich eine Aktion und gibt ihr Token zurück
+pub fn register(operation: UndoOperation) -> String {
+    let token = uuid::Uuid::new_v4().to_string();
+    let mut ops = operations().lock().unwrap_or_else(|e| e.into_inner());
+    ops.push_back((token.clone(), operation));
+    while ops.len() > MAX_OPERATIONS {
+        ops.pop_front();
+    }
+    token
+}
+
+/// Profil einer noch offenen Aktion
+pub fn profile_of(token: &str) -> Option<String> {
+    let ops = operations().lock().unwrap_or_else(|e| e.into_inner());
+    ops.iter().find(|(t, _)| t == token).map(|(_, op)| op.profile_id().to_string())
+}
+
+/// Entnimmt eine Aktion (kann nur einmal rückgängig gemacht werden)
+pub fn take(token: &str) -> Option<UndoOperation> {
+    let mut ops = operations().lock().unwrap_or_else(|e| e.into_inner());
+    let index = ops.iter().position(|(t, _)| t == token)?;
+    ops.remove(index).map(|(_, op)| op)
+}
@@ -0,0 +1,237 @@
+#![allow(dead_code)]
+
+//! Parser für `lionlauncher://`-Links, über die Webseiten Aktionen im Launcher auslösen.
+//!
+//! Unterstützte Links:
+//! - `lionlauncher://modpack/install?id=<projekt>[&version=<version>][&name=<name>]`
+//! - `lionlauncher://mod/install?id=<projekt>&profile=<profil-id>`
+//! - `lionlauncher://server/join?address=<host[:port]>[&profile=<profil-id>]`
+//! - `lionlauncher://profile/launch?id=<profil-id>`
+//!
+//! Aktionen werden nie direkt ausgeführt – die GUI zeigt sie zur Bestätigung an.
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+pub const URI_SCHEME: &str = "lionlauncher";
+
+/// Beim Start übergebener Link, bis die GUI ihn abholt
+static PENDING_URI: std::sync::OnceLock<std::sync::Mutex<Option<String>>> = std::sync::OnceLock::new();
+
+fn pending_uri() -> &'static std::sync::Mutex<Option<String>> {
+    PENDING_URI.get_or_init(|| std::sync::Mutex::new(None))
+}
+
+pub fn set_pending_uri(uri: String) {
+    if let Ok(mut pending) = pending_uri().lock() {
+        *pending = Some(uri);
+    }
+}
+
+pub fn take_pending_uri() -> Option<String> {
+    pending_uri().lock().ok().and_then(|mut p| p.take())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum UriAction {
+    InstallModpack { project_id: String, version_id: Option<String>, name: Option<String> },
+    InstallMod { project_id: String, profile_id: String },
+    JoinServer { address: String, profile_id: Option<String> },
+    LaunchProfile { profile_id: String },
+}
+
+/// Modrinth-IDs/Slugs, Versions- und Profil-IDs: nur harmlose Zeichen
+fn valid_id(value: &str) -> bool {
+    !value.is_empty()
+        && value.len() <= 64
+        && value.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+}
+
+/// Hostname oder IP mit optionalem Port
+fn valid_server_address(address: &str) -> bool {
+    let (host, port) = match address.rsplit_once(':') {
+        Some((host, port)) => (host, Some(port)),
+        None => (address, None),
+    };
+    let host_ok = !host.is_empty()
+        && host.len() <= 253
+        && host.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.');
+    let port_ok = port.is_none_or(|p| p.parse::<u16>().is_ok_and(|p| p > 0));
+    host_ok && port_ok
+}
+
+fn required<'a>(query: &'a HashMap<String, String>, key: &str) -> Result<&'a str> {
+    let value = query.get(key).map(|v| v.as_str()).unwrap_or_default();
+    if !valid_id(value) {
+        bail!("Missing or invalid parameter '{}'", key);
+    }
+    Ok(value)
+}
+
+fn optional_id(query: &HashMap<String, String>, key: &str) -> Result<Option<String>> {
+    match query.get(key) {
+        Some(value) if !valid_id(value) => bail!("Invalid parameter '{}'", key),
+        value => Ok(value.cloned()),
+    }
+}
+
+/// Parst und validiert einen `lionlauncher://`-Link
+pub fn parse_uri(uri: &str) -> Result<UriAction> {
+    let url = url::Url::parse(uri.trim())?;
+    if url.scheme() != URI_SCHEME {
+        bail!("Unsupported scheme: {}", url.scheme());
+    }
+
+    let target = url.host_str().unwrap_or_default();
+    let action = url.path().trim_matches('/');
+    let query: HashMap<String, String> = url.query_pairs().into_owned().collect();
+
+    match (target, action) {
+        ("modpack", "install") => Ok(UriAction::InstallModpack {
+            project_id: required(&query, "id")?.to_string(),
+            version_id: optional_id(&query, "version")?,
+            name: query.get("name")
+                .map(|n| n.chars().filter(|c| !c.is_control()).take(100).collect()),
+        }),
+        ("mod", "install") => Ok(UriAction::InstallMod {
+            project_id: required(&query, "id")?.to_string(),
+            profile_id: required(&query, "profile")?.to_string(),
+        }),
+        ("server", "join") => {
+            let address = query.get("address").cloned().unwrap_or_default();
+            if !valid_server_address(&address) {
+                bail!("Invalid server address: {}", address);
+            }
+            Ok(UriAction::JoinServer { address, profile_id: optional_id(&query, "profile")? })
+        }
+        ("profile", "launch") => Ok(UriAction::LaunchProfile {
+            profile_id: required(&query, "id")?.to_string(),
+        }),
+        _ => bail!("Unknown action: {}/{}", target, action),
+    }
+}
+
+/// Sucht einen `lionlauncher://`-Link in den Kommandozeilenargumenten
+/// (so übergeben Betriebssysteme registrierte URI-Schemes an die Anwendung)
+pub fn find_uri_in_args(args: impl IntoIterator<Item = String>) -> Option<String> {
+    let prefix = format!("{}://", URI_SCHEME);
+    args.into_iter().find(|a| a.starts_with(&prefix))
+}
+
+/// Port der laufenden Instanz, über den weitere Starts ihren Link weiterreichen
+fn instance_port_file() -> std::path::PathBuf {
+    crate::config::defaults::launcher_dir().join("instance.port")
+}
+
+const FORWARD_ACK: &str = "ok";
+
+/// Reicht einen Link (oder nur die Aufforderung, das Fenster zu zeigen) an eine bereits
+/// laufende Instanz weiter. `true` wenn diese ihn angenommen hat – dann beendet sich der neue Prozess.
+pub fn forward_to_running_instance(uri: Option<&str>) -> bool {
+    use std::io::{BufRead, BufReader, Write};
+    use std::time::Duration;
+
+    let Some(port) = std::fs::read_to_string(instance_port_file())
+        .ok()
+        .and_then(|p| p.trim().parse::<u16>().ok())
+    else {
+        return false;
+    };
+    let address = std::net::SocketAddr::from(([127, 0, 0, 1], port));
+    let Ok(mut stream) = std::net::TcpStream::connect_timeout(&address, Duration::from_millis(500)) else {
+        return false;
+    };
+    stream.set_read_timeout(Some(Duration::from_secs(2))).ok();
+    if writeln!(stream, "{}", uri.unwrap_or_default()).is_err() {
+        return false;
+    }
+    // Antwort prüfen, damit ein fremder Prozess auf einem veralteten Port nicht als Instanz gilt
+    let mut reply = String::new();
+    BufReader::new(stream).read_line(&mut reply).is_ok() && reply.trim() == FORWARD_ACK
+}
+
+/// Nimmt Links weiterer Starts entgegen (Single-Instance). `on_forward` bekommt den Link,
+/// `None` wenn der Launcher nur erneut geöffnet wurde.
+pub fn listen_for_forwarded_uris(on_forward: impl Fn(Option<String>) + Send + 'static) {
+    use std::io::{BufRead, BufReader, Read, Write};
+
+    let listener = match std::net::TcpListener::bind(("127.0.0.1", 0)) {
+        Ok(listener) => listener,
+        Err(e) => {
+            tracing::warn!("Could not start single-instance listener: {}", e);
+            return;
+        }
+    };
+    let port = match listener.local_addr() {
+        Ok(address) => address.port(),
+        Err(_) => return,
+    };
+    if let Err(e) = std::fs::write(instance_port_file(), port.to_string()) {
+        tracing::warn!("Could not write instance port file: {}", e);
+        return;
+    }
+
+    std::thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            stream.set_read_timeout(Some(std::time::Duration::from_secs(2))).ok();
+            let mut line = String::new();
+            if BufReader::new((&stream).take(4096)).read_line(&mut line).is_err() {
+                continue;
+            }
+            writeln!(&stream, "{}", FORWARD_ACK).ok();
+            let line = line.trim();
+            let uri = find_uri_in_args([line.to_string()]);
+            if uri.is_none() && !line.is_empty() {
+                tracing::warn!("Ignoring forwarded argument: {}", line);
+                continue;
+            }
+            on_forward(uri);
+        }
+    });
+}
+
+/// Registriert das URI-Scheme für den aktuellen Benutzer.
+/// Linux nutzt die `MimeType`-Zeile der .desktop-Datei, unter Windows wird die Registry gesetzt
+/// (nur wenn der Eintrag fehlt oder auf eine andere Exe zeigt).
+/// macOS wird nicht unterstützt: dort kommen Links als Apple Event statt als Argument an.
+pub fn register_uri_scheme() {
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+        let Ok(exe) = std::env::current_exe() else { return };
+        let key = format!("HKCU\\Software\\Classes\\{}", URI_SCHEME);
+        let command_key = format!("{}\\shell\\open\\command", key);
+        let command = format!("\"{}\" \"%1\"", exe.display());
+
+        let registered = std::process::Command::new("reg")
+            .args(["query", &command_key, "/ve"])
+            .creation_flags(CREATE_NO_WINDOW)
+            .output()
+            .is_ok_and(|o| o.status.success() && String::from_utf8_lossy(&o.stdout).contains(&command));
+        if registered {
+            return;
+        }
+
+        let entries: [(&str, Option<&str>, &str); 3] = [
+            (&key, None, "URL:Lion Launcher"),
+            (&key, Some("URL Protocol"), ""),
+            (&command_key, None, &command),
+        ];
+        for (path, name, value) in entries {
+            let mut cmd = std::process::Command::new("reg");
+            cmd.args(["add", path, "/f", "/d", value]).creation_flags(CREATE_NO_WINDOW);
+            match name {
+                Some(name) => { cmd.args(["/v", name]); }
+                None => { cmd.arg("/ve"); }
+            }
+            if let Err(e) = cmd.output() {
+                tracing::warn!("Could not register URI scheme: {}", e);
+                return;
+            }
+        }
+    }
+}
@@ -1,6 +1,9 @@
 #![allow(dead_code)]
 
-use crate::core::auth::{MinecraftAuth, AuthState, DeviceCodeFlow, get_head_url};
+use crate::core::auth::{MinecraftAuth, MinecraftAccount, AuthState, DeviceCodeFlow, TokenPoll, avatars, get_head_url};
+use crate::core::auth::device_flow::PollRejection;
+use crate::config::transfer::{ExportedAccount, SettingsImportResult};
+use crate::gui::state::AppState;
 use tokio::sync::Mutex;
 use once_cell::sync::Lazy;
 
@@ -17,20 +20,41 @@ fn load_auth_state() -> Option<AuthState> {
     let path = get_auth_file_path();
     if path.exists() {
         let content = std::fs::read_to_string(&path).ok()?;
-        serde_json::from_str(&content).ok()
+        let mut state: AuthState = serde_json::from_str(&content).ok()?;
+        migrate_offline_accounts(&mut state);
+        Some(state)
     } else {
         None
     }
 }
 
-fn save_auth_state(state: &AuthState) -> Result<(), String> {
-    let path = get_auth_file_path();
-    if let Some(parent) = path.parent() {
-        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+/// Alte Offline-UUIDs (UUIDv5) auf die Vanilla-UUIDs umstellen, inkl. Spielerdaten in den Welten
+fn migrate_offline_accounts(state: &mut AuthState) {
+    let migrated = crate::core::auth::migrate_offline_uuids(state);
+    if migrated.is_empty() {
+        return;
     }
+    if let Err(e) = save_auth_state(state) {
+        tracing::warn!("Could not save migrated accounts: {}", e);
+    }
+
+    // Profil-Liste direkt lesen – der ProfileManager ist hier (synchron, beim ersten Zugriff) nicht verfügbar
+    let game_dirs: Vec<std::path::PathBuf> = std::fs::read_to_string(crate::config::defaults::launcher_dir().join("profiles.json"))
+        .ok()
+        .and_then(|c| serde_json::from_str::<crate::types::profile::ProfileList>(&c).ok())
+        .map(|list| list.profiles.into_iter().map(|p| p.game_dir).collect())
+        .unwrap_or_default();
+    for (old, new) in migrated {
+        crate::core::auth::migrate_world_player_files(&game_dirs, old, new);
+        avatars::remove(&old.simple().to_string());
+    }
+}
+
+fn save_auth_state(state: &AuthState) -> Result<(), String> {
     let json = serde_json::to_string_pretty(state).map_err(|e| e.to_string())?;
-    std::fs::write(&path, json).map_err(|e| e.to_string())?;
-    Ok(())
+    // Mit Sicherung: eine kaputte auth.json kann so beim Start wiederhergestellt werden
+    crate::config::backup::write_with_backup_blocking(&get_auth_file_path(), &json)
+        .map_err(|e| e.to_string())
 }
 
 #[derive(serde::Serialize)]
@@ -38,23 +62,56 @@ pub struct AccountInfo {
     pub uuid: String,
     pub username: String,
     pub head_url: String,
+    /// Lokal gecachter Kopf als data:-URL (None solange noch nicht heruntergeladen)
+    pub head_data_url: Option<String>,
+    /// Lokal gecachter Skin als data:-URL
+    pub skin_data_url: Option<String>,
     pub is_microsoft: bool,
     pub is_active: bool,
 }
 
+impl AccountInfo {
+    fn new(account: &MinecraftAccount, is_active: bool) -> Self {
+        // Der Webview kann lokale Pfade nicht laden, daher als data:-URL
+        let local = |path: std::path::PathBuf| avatars::cached(path).and_then(|p| crate::core::artwork::file_data_url(&p));
+        Self {
+            uuid: account.uuid.clone(),
+            username: account.username.clone(),
+            head_url: get_head_url(&account.uuid, 64),
+            head_data_url: local(avatars::head_path(&account.uuid)),
+            skin_data_url: local(avatars::skin_path(&account.uuid)),
+            is_microsoft: account.is_microsoft,
+            is_active,
+        }
+    }
+}
+
+/// Mindestabstand zwischen zwei Avatar-Prüfungen aus `get_accounts`
+const AVATAR_REFRESH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
+static LAST_AVATAR_REFRESH: std::sync::Mutex<Option<std::time::Instant>> = std::sync::Mutex::new(None);
+
+/// Lädt fehlende/veraltete Avatare im Hintergrund (blockiert die Account-Liste nicht).
+/// `get_accounts` wird oft aufgerufen, daher höchstens ein Durchlauf pro Minute.
+fn refresh_avatars_in_background(uuids: Vec<String>) {
+    {
+        let mut last = LAST_AVATAR_REFRESH.lock().unwrap_or_else(|e| e.into_inner());
+        if last.is_some_and(|at| at.elapsed() < AVATAR_REFRESH_INTERVAL) {
+            return;
+        }
+        *last = Some(std::time::Instant::now());
+    }
+    tokio::spawn(avatars::refresh_stale(uuids));
+}
+
 #[tauri::command]
 pub async fn get_accounts() -> Result<Vec<AccountInfo>, String> {
     let state = AUTH_STATE.lock().await;
 
-    let accounts: Vec<AccountInfo> = state.accounts.iter().map(|acc| {
-        AccountInfo {
-            uuid: acc.uuid.clone(),
-            username: acc.username.clone(),
-            head_url: get_head_url(&acc.uuid, 64),
-            is_microsoft: acc.is_microsoft,
-            is_active: state.active_account.as_ref() == Some(&acc.uuid),
-        }
-    }).collect();
+    let accounts: Vec<AccountInfo> = state.accounts.iter()
+        .map(|acc| AccountInfo::new(acc, state.active_account.as_ref() == Some(&acc.uuid)))
+        .collect();
+    refresh_avatars_in_background(state.accounts.iter().map(|a| a.uuid.clone()).collect());
 
     Ok(accounts)
 }
@@ -65,13 +122,7 @@ pub async fn get_active_account() -> Result<Option<AccountInfo>, String> {
 
     if let Some(active_uuid) = &state.active_account {
         if let Some(acc) = state.accounts.iter().find(|a| &a.uuid == active_uuid) {
-            return Ok(Some(AccountInfo {
-                uuid: acc.uuid.clone(),
-                username: acc.username.clone(),
-                head_url: get_head_url(&acc.uuid, 64),
-                is_microsoft: acc.is_microsoft,
-                is_active: true,
-            }));
+            return Ok(Some(AccountInfo::new(acc, true)));
         }
     }
 
@@ -91,29 +142,122 @@ pub async fn set_active_account(uuid: String) -> Result<(), String> {
     }
 }
 
-/// Startet den Device Code Flow für Microsoft Login
+/// Rückgabe von `begin_microsoft_login`
+#[derive(serde::Serialize)]
+pub struct MicrosoftLoginStart {
+    #[serde(flatten)]
+    pub flow: DeviceCodeFlow,
+    pub code_copied: bool,
+    pub browser_opened: bool,
+    /// true wenn ein noch gültiger, bereits laufender Login zurückgegeben wurde
+    pub reused: bool,
+}
+
+/// Startet den Device Code Flow für Microsoft Login.
+/// Läuft bereits ein gültiger Flow, wird dieser zurückgegeben (außer bei `restart`).
+/// `copy_code`/`open_browser` überschreiben die Login-Einstellungen.
 #[tauri::command]
-pub async fn begin_microsoft_login() -> Result<DeviceCodeFlow, String> {
-    let auth = MinecraftAuth::new();
-    auth.begin_device_code_flow()
-        .await
-        .map_err(|e| format!("Fehler beim Starten des Logins: {}", e))
+pub async fn begin_microsoft_login(
+    state: tauri::State<'_, AppState>,
+    copy_code: Option<bool>,
+    open_browser: Option<bool>,
+    restart: Option<bool>,
+) -> Result<MicrosoftLoginStart, String> {
+    let settings = crate::gui::settings::get_config().await.map(|c| c.login).unwrap_or_default();
+
+    // Lock über die Anfrage halten, damit parallele Aufrufe nicht zwei Flows starten
+    let mut login = state.login.lock().await;
+    let (flow, reused) = match login.reusable().filter(|_| !restart.unwrap_or(false)) {
+        Some(flow) => (flow, true),
+        None => {
+            let flow = MinecraftAuth::new().begin_device_code_flow()
+                .await
+                .map_err(|e| format!("Fehler beim Starten des Logins: {}", e))?;
+            login.start(flow.clone());
+            (flow, false)
+        }
+    };
+    drop(login);
+
+    let code_copied = if copy_code.unwrap_or(settings.copy_code) {
+        copy_code_to_clipboard(&flow.user_code).await
+    } else {
+        false
+    };
+    let browser_opened = if open_browser.unwrap_or(settings.auto_open_browser) {
+        match open_auth_url(flow.verification_uri.clone()).await {
+            Ok(()) => true,
+            Err(e) => {
+                tracing::warn!("{}", e);
+                false
+            }
+        }
+    } else {
+        false
+    };
+
+    Ok(MicrosoftLoginStart { flow, code_copied, browser_opened, reused })
 }
 
-/// Pollt für Token nachdem User den Code eingegeben hat
+async fn copy_code_to_clipboard(code: &str) -> bool {
+    let code = code.to_string();
+    match crate::utils::compression::run_blocking(move || crate::utils::clipboard::copy_text(&code)).await {
+        Ok(()) => true,
+        Err(e) => {
+            tracing::warn!("Could not copy login code: {}", e);
+            false
+        }
+    }
+}
+
+/// Kopiert den Code des laufenden Logins (erneut) in die Zwischenablage
 #[tauri::command]
-pub async fn poll_microsoft_login(device_code: String) -> Result<Option<AccountInfo>, String> {
-    let auth = MinecraftAuth::new();
+pub async fn copy_microsoft_login_code(state: tauri::State<'_, AppState>) -> Result<(), String> {
+    let code = state.login.lock().await
+        .current()
+        .map(|flow| flow.user_code.clone())
+        .ok_or("Kein Login aktiv")?;
+    let copy = crate::utils::compression::run_blocking(move || crate::utils::clipboard::copy_text(&code));
+    copy.await.map_err(|e| e.to_string())
+}
+
+/// Bricht den laufenden Login ab; weitere Polls mit dessen Device Code schlagen fehl
+#[tauri::command]
+pub async fn cancel_microsoft_login(state: tauri::State<'_, AppState>) -> Result<(), String> {
+    if state.login.lock().await.clear() {
+        tracing::info!("Microsoft login cancelled");
+    }
+    Ok(())
+}
+
+/// Pollt für Token nachdem User den Code eingegeben hat.
+/// Zu frühe oder parallele Polls gehen nicht an Microsoft und liefern `None`.
+#[tauri::command]
+pub async fn poll_microsoft_login(
+    state: tauri::State<'_, AppState>,
+    device_code: String,
+) -> Result<Option<AccountInfo>, String> {
+    match state.login.lock().await.begin_poll(&device_code) {
+        Ok(()) => {}
+        Err(PollRejection::TooEarly { retry_after_ms }) => {
+            tracing::debug!("Login poll throttled, retry in {} ms", retry_after_ms);
+            return Ok(None);
+        }
+        Err(PollRejection::NotActive) => {
+            return Err("Login wurde abgebrochen oder durch einen neuen ersetzt".to_string());
+        }
+        Err(PollRejection::Expired) => return Err("Device code abgelaufen".to_string()),
+    }
 
-    match auth.poll_for_token(&device_code).await {
-        Ok(Some(account)) => {
-            let account_info = AccountInfo {
-                uuid: account.uuid.clone(),
-                username: account.username.clone(),
-                head_url: get_head_url(&account.uuid, 64),
-                is_microsoft: account.is_microsoft,
-                is_active: true,
-            };
+    let result = MinecraftAuth::new().poll_for_token(&device_code).await;
+
+    let mut login = state.login.lock().await;
+    match result {
+        Ok(TokenPoll::Complete(account)) => {
+            login.finish(&device_code);
+            drop(login);
+
+            let account_info = AccountInfo::new(&account, true);
 
             // Zum State hinzufügen
             let mut state = AUTH_STATE.lock().await;
@@ -129,8 +273,25 @@ pub async fn poll_microsoft_login(device_code: String) -> Result<Option<AccountI
 
             Ok(Some(account_info))
         }
-        Ok(None) => Ok(None), // Noch nicht autorisiert
-        Err(e) => Err(e.to_string()),
+        Ok(TokenPoll::Pending) => {
+            login.end_poll(&device_code, false);
+            Ok(None) // Noch nicht autorisiert
+        }
+        Ok(TokenPoll::SlowDown) => {
+            login.end_poll(&device_code, true);
+            Ok(None)
+        }
+        Ok(TokenPoll::Failed(message)) => {
+            // Abgelaufen, verweigert oder Anmeldung fehlgeschlagen – dieser Flow ist beendet
+            login.finish(&device_code);
+            Err(message)
+        }
+        Err(e) => {
+            // Vorübergehend (z.B. Netzwerk): bis zum Ablauf des Codes weiter pollen
+            tracing::warn!("Login poll failed, retrying: {}", e);
+            login.end_poll(&device_code, false);
+            Ok(None)
+        }
     }
 }
 
@@ -142,13 +303,7 @@ pub async fn add_offline_account(username: String) -> Result<AccountInfo, String
 
     let account = MinecraftAuth::create_offline_account(&username);
 
-    let account_info = AccountInfo {
-        uuid: account.uuid.clone(),
-        username: account.username.clone(),
-        head_url: get_head_url(&account.uuid, 64),
-        is_microsoft: account.is_microsoft,
-        is_active: true,
-    };
+    let account_info = AccountInfo::new(&account, true);
 
     let mut state = AUTH_STATE.lock().await;
 
@@ -169,6 +324,7 @@ pub async fn remove_account(uuid: String) -> Result<(), String> {
     let mut state = AUTH_STATE.lock().await;
 
     state.accounts.retain(|a| a.uuid != uuid);
+    avatars::remove(&uuid);
 
     if state.active_account.as_ref() == Some(&uuid) {
         state.active_account = state.accounts.first().map(|a| a.uuid.clone());
@@ -200,13 +356,7 @@ pub async fn refresh_account(uuid: String) -> Result<AccountInfo, String> {
         .await
         .map_err(|e| format!("Refresh fehlgeschlagen: {}", e))?;
 
-    let account_info = AccountInfo {
-        uuid: new_account.uuid.clone(),
-        username: new_account.username.clone(),
-        head_url: get_head_url(&new_account.uuid, 64),
-        is_microsoft: new_account.is_microsoft,
-        is_active: true,
-    };
+    let account_info = AccountInfo::new(&new_account, true);
 
     let mut state = AUTH_STATE.lock().await;
 
@@ -219,6 +369,41 @@ pub async fn refresh_account(uuid: String) -> Result<AccountInfo, String> {
     Ok(account_info)
 }
 
+/// Lädt Name, Skin und Cape eines Microsoft-Accounts neu (z.B. nach Namensänderung)
+#[tauri::command]
+pub async fn refresh_account_profile(uuid: String) -> Result<AccountInfo, String> {
+    let account = {
+        let state = AUTH_STATE.lock().await;
+        state.accounts.iter()
+            .find(|a| a.uuid == uuid)
+            .ok_or_else(|| "Account nicht gefunden".to_string())?
+            .clone()
+    };
+
+    if !account.is_microsoft {
+        return Err("Offline-Accounts haben kein Online-Profil".to_string());
+    }
+
+    let updated = MinecraftAuth::new()
+        .refresh_profile(&account)
+        .await
+        .map_err(|e| format!("Profil konnte nicht aktualisiert werden: {}", e))?;
+
+    let mut state = AUTH_STATE.lock().await;
+    let is_active = state.active_account.as_ref() == Some(&updated.uuid);
+    if let Some(existing) = state.accounts.iter_mut().find(|a| a.uuid == updated.uuid) {
+        *existing = updated.clone();
+    }
+    save_auth_state(&state)?;
+    drop(state);
+
+    // Skin kann sich geändert haben – Avatar neu laden
+    avatars::remove(&updated.uuid);
+    tokio::spawn(avatars::refresh_stale(vec![updated.uuid.clone()]));
+
+    Ok(AccountInfo::new(&updated, is_active))
+}
+
 #[tauri::command]
 pub async fn upload_skin_file(skin_data: String, variant: String) -> Result<(), String> {
     use base64::{Engine as _, engine::general_purpose};
@@ -270,9 +455,17 @@ pub async fn upload_skin_file(skin_data: String, variant: String) -> Result<(),
     }
 
     tracing::info!("Skin erfolgreich hochgeladen!");
+    invalidate_active_avatar().await;
     Ok(())
 }
 
+/// Verwirft den gecachten Kopf/Skin des aktiven Accounts nach einem Skin-Wechsel
+async fn invalidate_active_avatar() {
+    if let Some(uuid) = AUTH_STATE.lock().await.active_account.clone() {
+        avatars::remove(&uuid);
+    }
+}
+
 /// Skin von URL übernehmen (z.B. von einem anderen Spieler)
 /// Lädt den Skin erst herunter und sendet ihn dann als Multipart-Upload,
 /// da die Mojang-API nur URLs von textures.minecraft.net akzeptiert.
@@ -340,6 +533,7 @@ pub async fn apply_skin_from_url(skin_url: String, variant: String) -> Result<()
     }
 
     tracing::info!("Skin erfolgreich von URL übernommen!");
+    invalidate_active_avatar().await;
     Ok(())
 }
 
@@ -364,11 +558,14 @@ pub async fn get_skin_texture(uuid: String) -> Result<String, String> {
         clean_uuid
     );
 
-    let profile_response = client
-        .get(&profile_url)
-        .send()
-        .await
-        .map_err(|e| format!("Fehler beim Laden des Profils: {}", e))?;
+    let profile_response = match client.get(&profile_url).send().await {
+        Ok(response) => response,
+        // Offline: zuletzt gecachten Skin anzeigen
+        Err(e) => {
+            return cached_skin_data_url(&uuid)
+                .ok_or_else(|| format!("Fehler beim Laden des Profils: {}", e));
+        }
+    };
 
     if !profile_response.status().is_success() {
         // Fallback auf mc-heads.net für Offline-/unbekannte UUIDs
@@ -415,6 +612,13 @@ pub async fn get_skin_texture(uuid: String) -> Result<String, String> {
     Ok(format!("data:image/png;base64,{}", encoded))
 }
 
+fn cached_skin_data_url(uuid: &str) -> Option<String> {
+    use base64::{Engine as _, engine::general_purpose};
+
+    let bytes = std::fs::read(avatars::cached(avatars::skin_path(uuid))?).ok()?;
+    Some(format!("data:image/png;base64,{}", general_purpose::STANDARD.encode(&bytes)))
+}
+
 /// Fallback: Skin von mc-heads.net holen (für Offline-Accounts oder wenn Mojang API fehlschlägt)
 async fn get_skin_texture_fallback(client: &reqwest::Client, uuid: &str) -> Result<String, String> {
     use base64::{Engine as _, engine::general_purpose};
@@ -572,10 +776,15 @@ pub fn get_active_access_token() -> Option<(String, String, String)> {
 
 /// Gibt das Access-Token zurück und refreshed es automatisch wenn es abgelaufen ist
 pub async fn get_active_access_token_refreshed() -> Option<(String, String, String)> {
+    let active_uuid = AUTH_STATE.try_lock().ok()?.active_account.clone()?;
+    get_access_token_refreshed(&active_uuid).await
+}
+
+/// Wie `get_active_access_token_refreshed`, aber für einen bestimmten Account
+pub async fn get_access_token_refreshed(account_uuid: &str) -> Option<(String, String, String)> {
     let account_data = {
         let state = AUTH_STATE.try_lock().ok()?;
-        let active_uuid = state.active_account.as_ref()?;
-        let account = state.accounts.iter().find(|a| &a.uuid == active_uuid)?;
+        let account = state.accounts.iter().find(|a| a.uuid == account_uuid)?;
         
         (account.uuid.clone(), 
          account.username.clone(), 
@@ -631,3 +840,38 @@ pub async fn get_active_access_token_refreshed() -> Option<(String, String, Stri
     Some((uuid, username, access_token))
 }
 
+/// Accounts ohne Tokens für den Einstellungs-Export
+pub(crate) async fn export_accounts() -> Vec<ExportedAccount> {
+    let state = AUTH_STATE.lock().await;
+    state.accounts.iter()
+        .map(|acc| ExportedAccount::new(acc, state.active_account.as_ref() == Some(&acc.uuid)))
+        .collect()
+}
+
+/// Übernimmt exportierte Accounts: Offline-Accounts werden angelegt, Microsoft-Accounts
+/// ohne gültige Anmeldung nur gemeldet
+pub(crate) async fn import_accounts(accounts: &[ExportedAccount]) -> Result<SettingsImportResult, String> {
+    let mut result = SettingsImportResult::default();
+    let mut state = AUTH_STATE.lock().await;
+
+    for imported in accounts {
+        if state.accounts.iter().any(|a| a.uuid == imported.uuid) {
+            continue;
+        }
+        if imported.is_microsoft {
+            result.needs_login.push(imported.username.clone());
+            continue;
+        }
+        let account = MinecraftAuth::create_offline_account(&imported.username);
+        if imported.active && state.active_account.is_none() {
+            state.active_account = Some(account.uuid.clone());
+        }
+        state.accounts.push(account);
+        result.accounts_added += 1;
+    }
+
+    if result.accounts_added > 0 {
+        save_auth_state(&state)?;
+    }
+    Ok(result)
+}
@@ -4,6 +4,9 @@ pub mod settings;
 pub mod components;
 pub mod themes;
 pub mod auth;
+pub mod state;
+pub mod startup;
+pub mod tray;
 
 #[tauri::command]
 pub fn greet(name: &str) -> String {
@@ -19,16 +22,69 @@ pub fn get_embedded_logo_data_url() -> String {
     format!("data:image/png;base64,{}", encoded)
 }
 
+/// Holt einen beim Start übergebenen `lionlauncher://`-Link ab (einmalig)
 #[tauri::command]
-pub async fn get_profile_logs(profile_id: String, log_type: String) -> Result<String, String> {
-    use crate::core::profiles::ProfileManager;
+pub async fn take_pending_uri_action() -> Result<Option<crate::core::uri::UriAction>, String> {
+    match crate::core::uri::take_pending_uri() {
+        Some(uri) => crate::core::uri::parse_uri(&uri).map(Some).map_err(|e| e.to_string()),
+        None => Ok(None),
+    }
+}
+
+#[tauri::command]
+pub async fn parse_launcher_uri(uri: String) -> Result<crate::core::uri::UriAction, String> {
+    crate::core::uri::parse_uri(&uri).map_err(|e| e.to_string())
+}
 
+/// Führt eine (vom Nutzer bestätigte) Link-Aktion über den passenden Command aus
+#[tauri::command]
+pub async fn execute_uri_action(
+    app_handle: tauri::AppHandle,
+    action: crate::core::uri::UriAction,
+    state: tauri::State<'_, crate::gui::state::AppState>,
+) -> Result<serde_json::Value, String> {
+    use crate::core::uri::UriAction;
+
+    tracing::info!("Executing URI action: {:?}", action);
+    match action {
+        UriAction::InstallModpack { project_id, version_id, name } => {
+            let name = name.unwrap_or_else(|| project_id.clone());
+            install_modpack(project_id, name, version_id).await
+        }
+        UriAction::InstallMod { project_id, profile_id } => {
+            install_mod(profile_id, project_id, None, "modrinth".to_string()).await?;
+            Ok(serde_json::Value::Null)
+        }
+        UriAction::JoinServer { address, profile_id } => {
+            let profile_id = match profile_id {
+                Some(id) => id,
+                None => {
+                    let profiles = state.profile_manager
+                        .load_profiles()
+                        .await
+                        .map_err(|e| e.to_string())?;
+                    most_recent_profile(&profiles)
+                        .map(|p| p.id.clone())
+                        .ok_or_else(|| "Kein Profil zum Beitreten gefunden".to_string())?
+                }
+            };
+            launch_server(profile_id, address).await?;
+            Ok(serde_json::Value::Null)
+        }
+        UriAction::LaunchProfile { profile_id } => {
+            let account = crate::gui::auth::get_active_account().await?
+                .ok_or_else(|| "Kein aktiver Account".to_string())?;
+            launch_profile(app_handle, profile_id, account.username, None).await?;
+            Ok(serde_json::Value::Null)
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn get_profile_logs(profile_id: String, log_type: String, state: tauri::State<'_, crate::gui::state::AppState>) -> Result<String, String> {
     tracing::info!("get_profile_logs called: profile_id={}, log_type={}", profile_id, log_type);
 
-    let profile_manager = ProfileManager::new().map_err(|e| {
-        tracing::error!("Failed to create ProfileManager: {}", e);
-        e.to_string()
-    })?;
+    let profile_manager = &state.profile_manager;
 
     let profiles = profile_manager.load_profiles().await.map_err(|e| {
         tracing::error!("Failed to load profiles: {}", e);
@@ -38,38 +94,10 @@ pub async fn get_profile_logs(profile_id: String, log_type: String) -> Result<St
     let profile = profiles.get_profile(&profile_id)
         .ok_or_else(|| {
             tracing::error!("Profile not found: {}", profile_id);
-            "Profile not found".to_string()
+            crate::utils::error::profile_not_found(&profile_id)
         })?;
 
-    let logs_dir = profile.game_dir.join("logs");
-    tracing::info!("Looking for logs in: {:?}", logs_dir);
-
     let log_file = match log_type.as_str() {
-        "latest" => logs_dir.join("latest.log"),
-        "debug" => logs_dir.join("debug.log"),
-        lt if lt.starts_with("file:") => {
-            // Ältere Log-Datei nach Namen laden (nur innerhalb des logs-Ordners)
-            let filename = &lt["file:".len()..];
-            // Sicherheitscheck: kein Pfad-Traversal erlaubt
-            if filename.contains('/') || filename.contains('\\') || filename.contains("..") {
-                return Err("Ungültiger Dateiname".to_string());
-            }
-            let path = logs_dir.join(filename);
-            // .log.gz entpacken
-            if filename.ends_with(".log.gz") {
-                use std::io::Read;
-                if let Ok(f) = std::fs::File::open(&path) {
-                    let mut gz = flate2::read::GzDecoder::new(f);
-                    let mut content = String::new();
-                    let _ = gz.read_to_string(&mut content);
-                    let lines: Vec<&str> = content.lines().collect();
-                    let start = if lines.len() > 10000 { lines.len() - 10000 } else { 0 };
-                    return Ok(lines[start..].join("\n"));
-                }
-                return Ok("⚠️ Konnte Log-Datei nicht lesen".to_string());
-            }
-            path
-        }
         "crash" => {
             // Finde neuesten Crash-Report
             let crash_dir = profile.game_dir.join("crash-reports");
@@ -87,7 +115,7 @@ pub async fn get_profile_logs(profile_id: String, log_type: String) -> Result<St
                 return Ok("📋 Keine Crash-Reports vorhanden\n\nDer crash-reports Ordner existiert nicht.".to_string());
             }
         }
-        _ => return Err("Unbekannter Log-Typ".to_string()),
+        other => crate::core::logs::log_file_path(&profile.game_dir, other).map_err(|e| e.to_string())?,
     };
 
     tracing::info!("Log file path: {:?}, exists: {}", log_file, log_file.exists());
@@ -106,12 +134,14 @@ pub async fn get_profile_logs(profile_id: String, log_type: String) -> Result<St
         ));
     }
 
-    // Lese Log-Datei asynchron
-    let content = match tokio::fs::read_to_string(&log_file).await {
-        Ok(c) => {
-            tracing::info!("Read log file: {} bytes", c.len());
-            c
-        }
+    // Nur die letzten 10000 Zeilen – größere Logs über get_profile_log_chunk
+    let path = log_file.clone();
+    let chunk = match crate::utils::compression::run_blocking(move || {
+        crate::core::logs::read_chunk(&path, crate::core::logs::LogWindow::Tail { lines: 10_000 })
+    })
+    .await
+    {
+        Ok(chunk) => chunk,
         Err(e) => {
             tracing::error!("Failed to read log file: {}", e);
             return Ok(format!(
@@ -124,17 +154,118 @@ pub async fn get_profile_logs(profile_id: String, log_type: String) -> Result<St
     };
 
     // Falls leer
-    if content.is_empty() {
+    if chunk.lines.is_empty() {
         return Ok("📄 Log-Datei ist leer\n\nDie Datei existiert, enthält aber keine Daten.".to_string());
     }
 
-    // Nur letzte 10000 Zeilen für Performance
-    let lines: Vec<&str> = content.lines().collect();
-    let start = if lines.len() > 10000 { lines.len() - 10000 } else { 0 };
-    let truncated: String = lines[start..].join("\n");
+    tracing::info!("Returning {} lines of logs", chunk.lines.len());
+    Ok(chunk.lines.join("\n"))
+}
+
+/// Liest einen Abschnitt eines Logs, damit große Dateien die GUI nicht blockieren.
+/// `tail` = letzte N Zeilen; `follow_from_byte` = neue Zeilen ab `end_byte` eines
+/// vorherigen Abschnitts (dann ist `offset` dessen `total_lines`); sonst `limit` Zeilen ab `offset`.
+#[tauri::command]
+pub async fn get_profile_log_chunk(
+    profile_id: String,
+    log_type: String,
+    offset: Option<usize>,
+    limit: Option<usize>,
+    tail: Option<usize>,
+    follow_from_byte: Option<u64>,
+) -> Result<crate::core::logs::LogChunk, String> {
+    let path = profile_log_path(&profile_id, &log_type).await?;
+    if !path.exists() {
+        return Ok(crate::core::logs::LogChunk::default());
+    }
+
+    let window = crate::core::logs::LogWindow::from_params(offset, limit, tail, follow_from_byte);
+    crate::utils::compression::run_blocking(move || crate::core::logs::read_chunk(&path, window))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Wie `get_profile_log_chunk`, aber in Einträge (Zeit, Thread, Level, Logger, Meldung)
+/// zerlegt; Stacktraces hängen am Eintrag davor
+#[tauri::command]
+pub async fn get_profile_log_entries(
+    profile_id: String,
+    log_type: String,
+    offset: Option<usize>,
+    limit: Option<usize>,
+    tail: Option<usize>,
+    follow_from_byte: Option<u64>,
+) -> Result<crate::core::logs::parse::LogEntries, String> {
+    let path = profile_log_path(&profile_id, &log_type).await?;
+    if !path.exists() {
+        return Ok(crate::core::logs::parse::LogEntries::default());
+    }
+
+    let window = crate::core::logs::LogWindow::from_params(offset, limit, tail, follow_from_byte);
+    crate::utils::compression::run_blocking(move || crate::core::logs::read_chunk(&path, window).map(Into::into))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Alle Crash-Reports des Profils (Minecraft und JVM), neueste zuerst
+#[tauri::command]
+pub async fn list_crash_reports(profile_id: String) -> Result<Vec<crate::core::logs::crash::CrashReportInfo>, String> {
+    let game_dir = profile_game_dir(&profile_id).await?;
+    crate::utils::compression::run_blocking(move || Ok(crate::core::logs::crash::list_crash_reports(&game_dir)))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Inhalt eines Crash-Reports aus `list_crash_reports`
+#[tauri::command]
+pub async fn get_crash_report(profile_id: String, name: String) -> Result<String, String> {
+    let game_dir = profile_game_dir(&profile_id).await?;
+    let path = crate::core::logs::crash::crash_report_path(&game_dir, &name).map_err(|e| e.to_string())?;
+    let bytes = tokio::fs::read(&path).await.map_err(|e| e.to_string())?;
+    Ok(String::from_utf8_lossy(&bytes).to_string())
+}
 
-    tracing::info!("Returning {} lines of logs", lines.len() - start);
-    Ok(truncated)
+async fn profile_game_dir(profile_id: &str) -> Result<std::path::PathBuf, String> {
+    use crate::core::profiles::ProfileManager;
+
+    let profiles = ProfileManager::new().map_err(|e| e.to_string())?
+        .load_profiles().await.map_err(|e| e.to_string())?;
+    profiles.get_profile(profile_id)
+        .map(|p| p.game_dir.clone())
+        .ok_or_else(|| crate::utils::error::profile_not_found(profile_id))
+}
+
+/// Pfad einer Log-Datei des Profils ("latest", "debug", "file:<name>")
+async fn profile_log_path(profile_id: &str, log_type: &str) -> Result<std::path::PathBuf, String> {
+    let game_dir = profile_game_dir(profile_id).await?;
+    crate::core::logs::log_file_path(&game_dir, log_type).map_err(|e| e.to_string())
+}
+
+/// Durchsucht ein Log nach Text und/oder Level (ERROR/WARN/INFO ...) und liefert die
+/// Treffer mit Zeilennummer zum Hinspringen
+#[tauri::command]
+pub async fn search_profile_logs(
+    profile_id: String,
+    log_type: String,
+    query: Option<String>,
+    levels: Option<Vec<crate::core::logs::search::LogLevel>>,
+    case_sensitive: Option<bool>,
+    limit: Option<usize>,
+) -> Result<crate::core::logs::search::LogSearchResult, String> {
+    let path = profile_log_path(&profile_id, &log_type).await?;
+    if !path.exists() {
+        return Ok(crate::core::logs::search::LogSearchResult::default());
+    }
+
+    let query = crate::core::logs::search::LogQuery {
+        text: query,
+        levels: levels.unwrap_or_default(),
+        case_sensitive: case_sensitive.unwrap_or(false),
+        limit: limit.unwrap_or(0),
+    };
+    crate::utils::compression::run_blocking(move || crate::core::logs::search::search(&path, &query))
+        .await
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -143,15 +274,67 @@ pub async fn get_live_launcher_logs(limit: Option<usize>) -> Result<String, Stri
     Ok(crate::utils::logging::get_recent_live_logs(max_lines))
 }
 
+/// Erstellt ein Support-Bundle (ZIP) für Bug-Reports und gibt den Pfad zurück.
+/// Tokens und API-Keys werden vor dem Schreiben entfernt.
 #[tauri::command]
-pub async fn open_profile_folder(profile_id: String, subfolder: Option<String>) -> Result<(), String> {
-    use crate::core::profiles::ProfileManager;
+pub async fn export_support_bundle(profile_id: Option<String>, target_path: Option<String>, state: tauri::State<'_, crate::gui::state::AppState>) -> Result<String, String> {
+    let profile = match profile_id {
+        Some(id) => {
+            let profile_manager = &state.profile_manager;
+            let profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
+            Some(profiles.get_profile(&id)
+                .ok_or_else(|| crate::utils::error::profile_not_found(&id))?
+                .clone())
+        }
+        None => None,
+    };
 
-    let profile_manager = ProfileManager::new().map_err(|e| e.to_string())?;
+    let output = match target_path {
+        Some(path) => std::path::PathBuf::from(path),
+        None => crate::config::defaults::launcher_dir()
+            .join("support")
+            .join(format!("lion-support-{}.zip", chrono::Local::now().format("%Y%m%d-%H%M%S"))),
+    };
+
+    let launcher_logs = crate::utils::logging::get_recent_live_logs(6000);
+
+    let path = tokio::task::spawn_blocking(move || {
+        crate::core::diagnostics::create_support_bundle(profile.as_ref(), &launcher_logs, &output)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())?;
+
+    Ok(path.display().to_string())
+}
+
+/// GPU-/Treiber-Infos mit Hinweisen; mit `profile_id` auch profilbezogene Prüfungen (Shader)
+#[tauri::command]
+pub async fn get_gpu_info(profile_id: Option<String>, state: tauri::State<'_, crate::gui::state::AppState>) -> Result<crate::core::diagnostics::gpu::GpuReport, String> {
+    let game_dir = match profile_id {
+        Some(id) => {
+            let profile_manager = &state.profile_manager;
+            let profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
+            Some(profiles.get_profile(&id)
+                .ok_or_else(|| crate::utils::error::profile_not_found(&id))?
+                .game_dir
+                .clone())
+        }
+        None => None,
+    };
+
+    crate::utils::compression::run_blocking(move || Ok(crate::core::diagnostics::gpu::detect(game_dir.as_deref())))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn open_profile_folder(profile_id: String, subfolder: Option<String>, state: tauri::State<'_, crate::gui::state::AppState>) -> Result<(), String> {
+    let profile_manager = &state.profile_manager;
     let profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
 
     let profile = profiles.get_profile(&profile_id)
-        .ok_or_else(|| "Profile not found".to_string())?;
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?;
 
     let path = if let Some(sub) = subfolder {
         profile.game_dir.join(sub)
@@ -162,13 +345,10 @@ pub async fn open_profile_folder(profile_id: String, subfolder: Option<String>)
     // Erstelle Ordner falls nicht vorhanden
     tokio::fs::create_dir_all(&path).await.map_err(|e| e.to_string())?;
 
-    // Öffne Ordner
+    // Öffne Ordner (in der Flatpak-Sandbox über Host bzw. Portal)
     #[cfg(target_os = "linux")]
     {
-        std::process::Command::new("xdg-open")
-            .arg(&path)
-            .spawn()
-            .map_err(|e| e.to_string())?;
+        crate::utils::flatpak::open_folder(&path).await.map_err(|e| e.to_string())?;
     }
 
     #[cfg(target_os = "windows")]
@@ -193,7 +373,7 @@ pub async fn open_profile_folder(profile_id: String, subfolder: Option<String>)
 /// Repariert ein Profil, indem Minecraft und Loader-Dateien neu heruntergeladen werden
 #[tauri::command]
 pub async fn stop_profile(profile_id: String) -> Result<bool, String> {
-    let stopped = crate::core::minecraft::kill_running_process(&profile_id);
+    let stopped = crate::core::minecraft::kill_running_process(&profile_id, false);
     if stopped {
         tracing::info!("Stopped Minecraft instance for profile: {}", profile_id);
     } else {
@@ -207,54 +387,182 @@ pub async fn get_running_profiles() -> Result<Vec<String>, String> {
     Ok(crate::core::minecraft::get_running_profile_ids())
 }
 
+/// Laufende Instanzen mit PID und Startzeit
 #[tauri::command]
-pub async fn get_log_files(profile_id: String) -> Result<Vec<String>, String> {
-    use crate::core::profiles::ProfileManager;
+pub async fn get_running_instances() -> Result<Vec<crate::core::minecraft::process::RunningInstance>, String> {
+    Ok(crate::core::minecraft::process::ProcessManager::global().list())
+}
 
-    let profile_manager = ProfileManager::new().map_err(|e| e.to_string())?;
+/// Beendet die laufende Instanz eines Profils sofort, z.B. wenn sie nach `stop_profile`
+/// hängt; `false`, wenn keine läuft
+#[tauri::command]
+pub async fn kill_instance(profile_id: String) -> Result<bool, String> {
+    let killed = crate::core::minecraft::kill_running_process(&profile_id, true);
+    if killed {
+        tracing::info!("Force-killed Minecraft instance for profile: {}", profile_id);
+    }
+    Ok(killed)
+}
+
+/// Setzt das eingestellte Fenster-Verhalten beim Start/Ende einer Instanz um
+pub async fn apply_launch_behavior(app_handle: &tauri::AppHandle, event: &crate::core::minecraft::InstanceLifecycle) {
+    use crate::config::schema::LaunchBehavior;
+    use crate::core::minecraft::InstanceLifecycle;
+    use tauri::Manager;
+
+    let behavior = get_config().await.map(|c| c.launch_behavior).unwrap_or_default();
+    let Some(window) = app_handle.get_webview_window("main") else { return };
+
+    match (event, behavior) {
+        (InstanceLifecycle::Started { .. } | InstanceLifecycle::Exited { .. }, LaunchBehavior::KeepOpen) => {}
+        (InstanceLifecycle::Started { .. }, LaunchBehavior::Minimize) => {
+            window.minimize().ok();
+        }
+        (InstanceLifecycle::Started { .. }, LaunchBehavior::Hide) => {
+            // Ohne Tray-Icon käme man nicht mehr an das Fenster – dann nur minimieren
+            if tray::is_available() {
+                window.hide().ok();
+            } else {
+                window.minimize().ok();
+            }
+        }
+        (InstanceLifecycle::Started { profile_id, .. }, LaunchBehavior::Close) => {
+            tracing::info!("Closing launcher after starting profile {}", profile_id);
+            app_handle.exit(0);
+        }
+        (InstanceLifecycle::Crashed { .. }, _) => {
+            // Absturz-Dialog muss sichtbar sein, egal welches Verhalten eingestellt ist
+            window.show().ok();
+            window.unminimize().ok();
+            window.set_focus().ok();
+        }
+        (InstanceLifecycle::Exited { .. }, _) => {
+            // Erst wieder anzeigen, wenn keine Instanz mehr läuft
+            if crate::core::minecraft::get_running_profile_ids().is_empty() {
+                window.show().ok();
+                window.unminimize().ok();
+                window.set_focus().ok();
+            }
+        }
+    }
+}
+
+/// Letzter Bericht über ein unerwartetes Spielende (auch als "instance-lifecycle"-Event `crashed`)
+#[tauri::command]
+pub async fn get_last_exit_report(profile_id: String) -> Result<Option<crate::core::minecraft::exit_report::GameExitReport>, String> {
+    Ok(crate::core::minecraft::exit_report::last_report(&profile_id))
+}
+
+/// Letzte Speicher-/CPU-Messung der laufenden Instanzen (laufend per "instance-metrics"-Event)
+#[tauri::command]
+pub async fn get_instance_metrics() -> Result<Vec<crate::core::minecraft::monitor::InstanceMetrics>, String> {
+    Ok(crate::core::minecraft::monitor::latest_metrics())
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ProfilePlaytime {
+    pub profile_id: String,
+    pub name: String,
+    pub playtime_secs: u64,
+    pub launches: u64,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct LauncherStats {
+    pub total_playtime_secs: u64,
+    pub total_launches: u64,
+    pub profile_count: usize,
+    pub installed_mods: usize,
+    pub disk_usage_bytes: u64,
+    pub most_played_profile: Option<ProfilePlaytime>,
+    pub profiles: Vec<ProfilePlaytime>,
+}
+
+/// Aggregierte Statistiken für das Dashboard (aus Sitzungsdaten und Profil-Scans)
+#[tauri::command]
+pub async fn get_launcher_stats(state: tauri::State<'_, crate::gui::state::AppState>) -> Result<LauncherStats, String> {
+    let profile_manager = &state.profile_manager;
     let profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
-    let profile = profiles.get_profile(&profile_id)
-        .ok_or_else(|| "Profile not found".to_string())?;
-
-    let logs_dir = profile.game_dir.join("logs");
-    if !logs_dir.exists() {
-        return Ok(vec![]);
-    }
-
-    let mut files: Vec<(String, std::time::SystemTime)> = std::fs::read_dir(&logs_dir)
-        .map_err(|e| e.to_string())?
-        .filter_map(|e| e.ok())
-        .filter(|e| {
-            let name = e.file_name().to_string_lossy().to_string();
-            // Nur ältere, datierte Logs (nicht latest.log / debug.log)
-            (name.ends_with(".log") || name.ends_with(".log.gz"))
-                && name != "latest.log"
-                && name != "debug.log"
-        })
-        .map(|e| {
-            let name = e.file_name().to_string_lossy().to_string();
-            let modified = e.metadata().and_then(|m| m.modified()).unwrap_or(std::time::SystemTime::UNIX_EPOCH);
-            (name, modified)
+    let sessions = tokio::task::spawn_blocking(crate::core::sessions::load_sessions)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut per_profile: Vec<ProfilePlaytime> = profiles.profiles.iter()
+        .map(|p| {
+            let own = sessions.iter().filter(|s| s.profile_id == p.id);
+            ProfilePlaytime {
+                profile_id: p.id.clone(),
+                name: p.name.clone(),
+                playtime_secs: own.clone().map(|s| s.duration_secs).sum(),
+                launches: own.count() as u64,
+            }
         })
         .collect();
+    per_profile.sort_by_key(|p| std::cmp::Reverse(p.playtime_secs));
+
+    let mut installed_mods = 0;
+    for profile in &profiles.profiles {
+        if let Ok(mut entries) = tokio::fs::read_dir(profile.game_dir.join("mods")).await {
+            while let Ok(Some(entry)) = entries.next_entry().await {
+                if entry.file_name().to_string_lossy().ends_with(".jar") {
+                    installed_mods += 1;
+                }
+            }
+        }
+    }
 
-    // Neueste zuerst
-    files.sort_by(|a, b| b.1.cmp(&a.1));
-    Ok(files.into_iter().map(|(name, _)| name).collect())
+    // Launcher-Ordner plus Profile mit eigenem Spielordner außerhalb davon
+    let launcher_dir = crate::config::defaults::launcher_dir();
+    let mut disk_usage_bytes = crate::core::fs::get_directory_size(&launcher_dir).await.unwrap_or(0);
+    for profile in profiles.profiles.iter().filter(|p| !p.game_dir.starts_with(&launcher_dir)) {
+        disk_usage_bytes += crate::core::fs::get_directory_size(&profile.game_dir).await.unwrap_or(0);
+    }
+
+    Ok(LauncherStats {
+        total_playtime_secs: sessions.iter().map(|s| s.duration_secs).sum(),
+        total_launches: sessions.len() as u64,
+        profile_count: profiles.profiles.len(),
+        installed_mods,
+        disk_usage_bytes,
+        most_played_profile: per_profile.first().filter(|p| p.playtime_secs > 0).cloned(),
+        profiles: per_profile,
+    })
 }
 
 #[tauri::command]
-pub async fn repair_profile(profile_id: String) -> Result<(), String> {
-    use crate::core::profiles::ProfileManager;
+pub async fn get_log_files(profile_id: String, state: tauri::State<'_, crate::gui::state::AppState>) -> Result<Vec<String>, String> {
+    let profile_manager = &state.profile_manager;
+    let profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
+    let profile = profiles.get_profile(&profile_id)
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?;
+
+    let game_dir = profile.game_dir.clone();
+    let files = crate::utils::compression::run_blocking(move || Ok(crate::core::logs::list_log_files(&game_dir)))
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(files.into_iter().map(|f| f.name).collect())
+}
+
+/// Ältere Logs mit Größe, Datum und ob sie gepackt sind (.log.gz werden beim Lesen entpackt)
+#[tauri::command]
+pub async fn list_profile_log_files(profile_id: String) -> Result<Vec<crate::core::logs::LogFileInfo>, String> {
+    let game_dir = profile_game_dir(&profile_id).await?;
+    crate::utils::compression::run_blocking(move || Ok(crate::core::logs::list_log_files(&game_dir)))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn repair_profile(profile_id: String, state: tauri::State<'_, crate::gui::state::AppState>) -> Result<(), String> {
     use crate::config::defaults;
 
     tracing::info!("Repairing profile: {}", profile_id);
 
-    let profile_manager = ProfileManager::new().map_err(|e| e.to_string())?;
+    let profile_manager = &state.profile_manager;
     let profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
 
     let profile = profiles.get_profile(&profile_id)
-        .ok_or_else(|| "Profile not found".to_string())?;
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?;
 
     let mc_version = &profile.minecraft_version;
     let loader = &profile.loader.loader;
@@ -271,12 +579,41 @@ pub async fn repair_profile(profile_id: String) -> Result<(), String> {
         tokio::fs::remove_dir_all(&versions_dir).await.ok();
     }
 
-    // Lösche Loader-spezifische Installer
+    // Lösche Loader-spezifische Installer und Libraries
+    remove_loader_files(loader, mc_version, &libraries_dir).await;
+
+    tracing::info!("Profile repair completed. Next launch will re-download everything.");
+    Ok(())
+}
+
+/// Prüft Version-JSON, Client, Libraries, Assets und Loader-Dateien eines Profils.
+/// Mit `repair` werden nur die fehlerhaften Dateien neu geladen.
+#[tauri::command]
+pub async fn verify_profile_files(
+    profile_id: String,
+    repair: Option<bool>,
+    state: tauri::State<'_, crate::gui::state::AppState>,
+) -> Result<crate::core::minecraft::verify::VerificationReport, String> {
+    use crate::core::minecraft::MinecraftLauncher;
+    let profile_manager = &state.profile_manager;
+    let profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
+    let profile = profiles.get_profile(&profile_id)
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?;
+
+    let launcher = MinecraftLauncher::new().map_err(|e| e.to_string())?;
+    launcher.verify_profile_files(profile, repair.unwrap_or(false))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Entfernt Installer und Libraries des angegebenen Mod-Loaders sowie
+/// gecachte Installationsergebnisse (Mods, Welten und Vanilla-Dateien bleiben erhalten)
+pub(crate) async fn remove_loader_files(loader: &crate::types::version::ModLoader, mc_version: &str, libraries_dir: &std::path::Path) {
     match loader {
         crate::types::version::ModLoader::NeoForge => {
             // Lösche NeoForge Installer
             let pattern = "neoforge-".to_string();
-            if let Ok(entries) = std::fs::read_dir(&libraries_dir) {
+            if let Ok(entries) = std::fs::read_dir(libraries_dir) {
                 for entry in entries.filter_map(|e| e.ok()) {
                     let name = entry.file_name().to_string_lossy().to_string();
                     if name.contains(&pattern) && name.contains("installer") {
@@ -295,7 +632,7 @@ pub async fn repair_profile(profile_id: String) -> Result<(), String> {
         crate::types::version::ModLoader::Forge => {
             // Lösche Forge Installer
             let pattern = format!("forge-{}", mc_version);
-            if let Ok(entries) = std::fs::read_dir(&libraries_dir) {
+            if let Ok(entries) = std::fs::read_dir(libraries_dir) {
                 for entry in entries.filter_map(|e| e.ok()) {
                     let name = entry.file_name().to_string_lossy().to_string();
                     if name.contains(&pattern) && name.contains("installer") {
@@ -304,6 +641,19 @@ pub async fn repair_profile(profile_id: String) -> Result<(), String> {
                     }
                 }
             }
+            // Lösche heruntergeladene Installer und Installer-Daten dieser MC-Version
+            let installer_dir = libraries_dir.join("forge-installer").join(mc_version);
+            if installer_dir.exists() {
+                tracing::info!("Removing Forge installer dir: {:?}", installer_dir);
+                tokio::fs::remove_dir_all(&installer_dir).await.ok();
+            }
+            if let Ok(entries) = std::fs::read_dir(libraries_dir.join("forge-installer-data")) {
+                for entry in entries.filter_map(|e| e.ok()) {
+                    if entry.file_name().to_string_lossy().starts_with(&format!("{}-", mc_version)) {
+                        tokio::fs::remove_dir_all(entry.path()).await.ok();
+                    }
+                }
+            }
             // Lösche Forge Libraries
             let forge_libs = libraries_dir.join("net").join("minecraftforge");
             if forge_libs.exists() {
@@ -332,22 +682,58 @@ pub async fn repair_profile(profile_id: String) -> Result<(), String> {
         }
     }
 
-    tracing::info!("Profile repair completed. Next launch will re-download everything.");
-    Ok(())
+    crate::core::minecraft::install_cache::clear(libraries_dir, Some(loader.as_str()), Some(mc_version));
 }
 
-/// Leert den Cache eines Profils (temporäre Dateien, Shader-Cache, etc.)
+/// Installiert nur den Mod-Loader eines Profils neu. Anders als `repair_profile`
+/// bleiben die Vanilla-Version, Mods und Welten unangetastet.
 #[tauri::command]
-pub async fn clear_profile_cache(profile_id: String) -> Result<(), String> {
-    use crate::core::profiles::ProfileManager;
+pub async fn reinstall_loader(profile_id: String, state: tauri::State<'_, crate::gui::state::AppState>) -> Result<(), String> {
+    let profiles = state.profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
+    let profile = profiles.get_profile(&profile_id)
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?
+        .clone();
+
+    if profile.loader.loader == crate::types::version::ModLoader::Vanilla {
+        return Err("Profil verwendet keinen Mod-Loader".to_string());
+    }
+
+    tracing::info!("Reinstalling {:?} for profile {}", profile.loader.loader, profile.name);
+    let libraries_dir = crate::config::defaults::libraries_dir();
+    remove_loader_files(&profile.loader.loader, &profile.minecraft_version, &libraries_dir).await;
+
+    state.launcher.install_loader(&profile).await.map_err(|e| e.to_string())
+}
+
+/// Verwirft die gecachte Forge/NeoForge-Installation eines Profils,
+/// beim nächsten Start wird der Installer neu ausgewertet. Gibt die Anzahl entfernter Einträge zurück.
+#[tauri::command]
+pub async fn refresh_loader_install(profile_id: String, state: tauri::State<'_, crate::gui::state::AppState>) -> Result<usize, String> {
+    let profile_manager = &state.profile_manager;
+    let profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
+
+    let profile = profiles.get_profile(&profile_id)
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?;
 
+    let removed = crate::core::minecraft::install_cache::clear(
+        &crate::config::defaults::libraries_dir(),
+        Some(profile.loader.loader.as_str()),
+        Some(&profile.minecraft_version),
+    );
+    tracing::info!("Cleared {} cached loader installs for profile {}", removed, profile_id);
+    Ok(removed)
+}
+
+/// Leert den Cache eines Profils (temporäre Dateien, Shader-Cache, etc.)
+#[tauri::command]
+pub async fn clear_profile_cache(profile_id: String, state: tauri::State<'_, crate::gui::state::AppState>) -> Result<(), String> {
     tracing::info!("Clearing cache for profile: {}", profile_id);
 
-    let profile_manager = ProfileManager::new().map_err(|e| e.to_string())?;
+    let profile_manager = &state.profile_manager;
     let profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
 
     let profile = profiles.get_profile(&profile_id)
-        .ok_or_else(|| "Profile not found".to_string())?;
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?;
 
     let game_dir = &profile.game_dir;
 
@@ -403,10 +789,24 @@ pub async fn clear_profile_cache(profile_id: String) -> Result<(), String> {
     Ok(())
 }
 
+/// Migration: ersetzt identische Mods/Resourcepacks/Shaderpacks aller Profile
+/// durch Hardlinks aus dem Content-Store
+#[tauri::command]
+pub async fn dedup_profile_files(state: tauri::State<'_, crate::gui::state::AppState>) -> Result<crate::core::fs::dedup::DedupReport, String> {
+    let profile_manager = &state.profile_manager;
+    let profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
+
+    tokio::task::spawn_blocking(move || crate::core::fs::dedup::dedup_profiles(&profiles.profiles))
+        .await
+        .map_err(|e| e.to_string())
+}
+
 // Re-export commands for convenience
 pub use mod_browser::*;
 pub use profile_manager::*;
 pub use settings::*;
+pub use themes::*;
+pub use startup::*;
 
 // ==================== MOD-VERWALTUNG ====================
 
@@ -422,6 +822,36 @@ pub struct InstalledMod {
     pub mod_id: Option<String>,
 }
 
+/// Anzahl und Größe aller Inhalte eines Profils in einem Aufruf (für die Profilkarten)
+#[tauri::command]
+pub async fn get_profile_content_summary(profile_id: String, state: tauri::State<'_, crate::gui::state::AppState>) -> Result<crate::core::profiles::content::ProfileContentSummary, String> {
+    let profiles = state.profile_manager
+        .load_profiles().await.map_err(|e| e.to_string())?;
+    let game_dir = profiles.get_profile(&profile_id)
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?
+        .game_dir
+        .clone();
+
+    crate::utils::compression::run_blocking(move || Ok(crate::core::profiles::content::summarize(&game_dir)))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Speicherbedarf eines Profils; `exclude` z.B. ["logs", "*.log", "saves/*/region"]
+#[tauri::command]
+pub async fn get_profile_disk_usage(profile_id: String, exclude: Option<Vec<String>>, state: tauri::State<'_, crate::gui::state::AppState>) -> Result<u64, String> {
+    let profiles = state.profile_manager
+        .load_profiles().await.map_err(|e| e.to_string())?;
+    let game_dir = profiles.get_profile(&profile_id)
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?
+        .game_dir
+        .clone();
+
+    crate::core::fs::get_directory_size_excluding(&game_dir, exclude.unwrap_or_default())
+        .await
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn get_installed_mods(profile_id: String) -> Result<Vec<InstalledMod>, String> {
     use crate::core::profiles::ProfileManager;
@@ -430,7 +860,7 @@ pub async fn get_installed_mods(profile_id: String) -> Result<Vec<InstalledMod>,
     let profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
 
     let profile = profiles.get_profile(&profile_id)
-        .ok_or_else(|| "Profile not found".to_string())?;
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?;
 
     let mods_dir = profile.game_dir.join("mods");
 
@@ -438,19 +868,8 @@ pub async fn get_installed_mods(profile_id: String) -> Result<Vec<InstalledMod>,
         return Ok(Vec::new());
     }
 
-    // Erstelle modinfos/ Ordner und migriere alte Metadaten
-    let modinfos_dir = profile.game_dir.join("modinfos");
-    if let Err(e) = std::fs::create_dir_all(&modinfos_dir) {
-        tracing::warn!("Failed to create modinfos directory: {}", e);
-    } else {
-        // Migriere alte .jar.meta.json Dateien aus mods/ nach modinfos/
-        migrate_old_metadata(&mods_dir, &modinfos_dir);
-    }
     let mut installed_mods = Vec::new();
 
-    // Erstelle modinfos/ Ordner falls nicht vorhanden
-    let modinfos_dir = profile.game_dir.join("modinfos");
-
     let entries = std::fs::read_dir(&mods_dir).map_err(|e| e.to_string())?;
 
     for entry in entries {
@@ -468,32 +887,15 @@ pub async fn get_installed_mods(profile_id: String) -> Result<Vec<InstalledMod>,
 
                 let disabled = filename.ends_with(".disabled");
 
-                // Suche Metadaten im modinfos/ Ordner
-                let meta_filename = if disabled {
-                    // Für .disabled Dateien: filename.disabled -> filename.json
-                    let base = filename.trim_end_matches(".disabled").trim_end_matches(".jar");
-                    format!("{}.json", base)
-                } else {
-                    // Für normale JARs: filename.jar -> filename.json
-                    filename.trim_end_matches(".jar").to_string() + ".json"
+                // Metadaten aus der Inhalts-Datenbank (Dateiname ohne .disabled)
+                let record = crate::core::mods::content_db::get(&profile.game_dir, &filename);
+                let has_update = record.as_ref().is_some_and(|r| r.has_update());
+                let latest_version = record.as_ref().and_then(|r| r.latest_version.clone()).filter(|_| has_update);
+                let (mut name, mut version, mut mod_id, icon_url) = match record {
+                    Some(r) => (r.mod_name, r.version, r.mod_id, r.icon_url),
+                    None => (None, None, None, None),
                 };
 
-                let meta_path = modinfos_dir.join(&meta_filename);
-
-                let (mut name, mut version, mut mod_id, mut icon_url) = (None, None, None, None);
-
-                // Versuche Metadaten zu laden aus modinfos/
-                if meta_path.exists() {
-                    if let Ok(meta_content) = std::fs::read_to_string(&meta_path) {
-                        if let Ok(meta) = serde_json::from_str::<serde_json::Value>(&meta_content) {
-                            name = meta.get("mod_name").and_then(|v| v.as_str()).map(|s| s.to_string());
-                            version = meta.get("version").and_then(|v| v.as_str()).map(|s| s.to_string());
-                            mod_id = meta.get("mod_id").and_then(|v| v.as_str()).map(|s| s.to_string());
-                            icon_url = meta.get("icon_url").and_then(|v| v.as_str()).map(|s| s.to_string());
-                        }
-                    }
-                }
-
                 // Fallback: Extrahiere aus Dateinamen
                 if name.is_none() || mod_id.is_none() {
                     let clean_name = filename
@@ -518,8 +920,8 @@ pub async fn get_installed_mods(profile_id: String) -> Result<Vec<InstalledMod>,
                     version,
                     disabled,
                     icon_url,
-                    has_update: false,
-                    latest_version: None,
+                    has_update,
+                    latest_version,
                     mod_id,
                 });
             }
@@ -583,6 +985,19 @@ fn extract_mod_info(clean_name: &str) -> (Option<String>, Option<String>, Option
     (Some(clean_name.replace(['-', '_'], " ")), None, mod_id)
 }
 
+/// Dateiname einer Mod nach dem (De-)Aktivieren
+fn toggled_filename(filename: &str, enable: bool) -> String {
+    if enable {
+        // Aktivieren: .jar.disabled -> .jar
+        filename.trim_end_matches(".disabled").to_string()
+    } else if filename.ends_with(".disabled") {
+        filename.to_string()
+    } else {
+        // Deaktivieren: .jar -> .jar.disabled
+        format!("{}.disabled", filename)
+    }
+}
+
 #[tauri::command]
 pub async fn toggle_mod(profile_id: String, filename: String, enable: bool) -> Result<(), String> {
     use crate::core::profiles::ProfileManager;
@@ -591,7 +1006,7 @@ pub async fn toggle_mod(profile_id: String, filename: String, enable: bool) -> R
     let profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
 
     let profile = profiles.get_profile(&profile_id)
-        .ok_or_else(|| "Profile not found".to_string())?;
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?;
 
     let mods_dir = profile.game_dir.join("mods");
     let current_path = mods_dir.join(&filename);
@@ -600,23 +1015,15 @@ pub async fn toggle_mod(profile_id: String, filename: String, enable: bool) -> R
         return Err(format!("Mod-Datei nicht gefunden: {}", filename));
     }
 
-    let new_filename = if enable {
-        // Aktivieren: .jar.disabled -> .jar
-        filename.trim_end_matches(".disabled").to_string()
-    } else {
-        // Deaktivieren: .jar -> .jar.disabled
-        if filename.ends_with(".disabled") {
-            filename.clone()
-        } else {
-            format!("{}.disabled", filename)
-        }
-    };
-
+    let new_filename = toggled_filename(&filename, enable);
     let new_path = mods_dir.join(&new_filename);
 
     if current_path != new_path {
         std::fs::rename(&current_path, &new_path).map_err(|e| e.to_string())?;
         tracing::info!("Mod toggled: {} -> {}", filename, new_filename);
+        if let Err(e) = crate::core::mods::content_db::set_enabled(&profile.game_dir, &new_filename, enable) {
+            tracing::warn!("Could not update metadata of {}: {}", new_filename, e);
+        }
     }
 
     Ok(())
@@ -624,47 +1031,64 @@ pub async fn toggle_mod(profile_id: String, filename: String, enable: bool) -> R
 
 #[tauri::command]
 pub async fn delete_mod(profile_id: String, filename: String) -> Result<(), String> {
+    trash_mod(&profile_id, &filename).await.map(|_| ())
+}
+
+/// Verschiebt eine Mod in den Papierkorb und gibt den Eintrag zurück
+async fn trash_mod(profile_id: &str, filename: &str) -> Result<crate::core::trash::TrashItem, String> {
     use crate::core::profiles::ProfileManager;
+    use crate::core::history::{self, ContentAction, ContentKind, HistoryEntry};
 
     let profile_manager = ProfileManager::new().map_err(|e| e.to_string())?;
     let profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
 
-    let profile = profiles.get_profile(&profile_id)
-        .ok_or_else(|| "Profile not found".to_string())?;
+    let profile = profiles.get_profile(profile_id)
+        .ok_or_else(|| crate::utils::error::profile_not_found(profile_id))?;
 
-    let mod_path = profile.game_dir.join("mods").join(&filename);
+    let mod_path = profile.game_dir.join("mods").join(filename);
 
     if !mod_path.exists() {
         return Err(format!("Mod-Datei nicht gefunden: {}", filename));
     }
 
-    std::fs::remove_file(&mod_path).map_err(|e| e.to_string())?;
-    tracing::info!("Mod deleted: {}", filename);
+    let history_entry = history::with_mod_metadata(
+        HistoryEntry::new(ContentAction::Remove, ContentKind::Mod, filename),
+        &profile.game_dir,
+    );
 
-    // Lösche auch die Metadaten-Datei aus modinfos/
-    let meta_filename = filename.trim_end_matches(".jar").to_string() + ".json";
-    let meta_path = profile.game_dir.join("modinfos").join(&meta_filename);
+    // Der Datenbank-Eintrag bleibt bestehen und gilt wieder, sobald das JAR zurückgelegt wird
+    let item = move_to_trash("mod", filename, profile_id, vec![mod_path]).await?;
+    tracing::info!("Mod moved to trash: {}", filename);
+    history::record(&profile.game_dir, HistoryEntry { trash_id: Some(item.id.clone()), ..history_entry });
 
-    if meta_path.exists() {
-        if let Err(e) = std::fs::remove_file(&meta_path) {
-            tracing::warn!("Failed to delete metadata file: {}", e);
-        } else {
-            tracing::info!("Metadata deleted: {}", meta_filename);
-        }
-    }
+    Ok(item)
+}
 
-    Ok(())
+/// Verschiebt Inhalte eines Profils in den Papierkorb des Launchers
+async fn move_to_trash(
+    kind: &'static str,
+    name: &str,
+    profile_id: &str,
+    paths: Vec<std::path::PathBuf>,
+) -> Result<crate::core::trash::TrashItem, String> {
+    let (name, profile_id) = (name.to_string(), profile_id.to_string());
+    crate::utils::compression::run_blocking(move || {
+        crate::core::trash::move_to_trash(kind, &name, Some(&profile_id), &paths, None)
+    })
+    .await
+    .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
 pub async fn delete_resourcepack(profile_id: String, name: String) -> Result<(), String> {
     use crate::core::profiles::ProfileManager;
+    use crate::core::history::{self, ContentAction, ContentKind, HistoryEntry};
 
     let profile_manager = ProfileManager::new().map_err(|e| e.to_string())?;
     let profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
 
     let profile = profiles.get_profile(&profile_id)
-        .ok_or_else(|| "Profile not found".to_string())?;
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?;
 
     let rp_path = profile.game_dir.join("resourcepacks").join(&name);
 
@@ -672,14 +1096,13 @@ pub async fn delete_resourcepack(profile_id: String, name: String) -> Result<(),
         return Err(format!("Resource Pack nicht gefunden: {}", name));
     }
 
-    // Prüfe ob es ein Ordner oder eine Datei ist
-    if rp_path.is_dir() {
-        std::fs::remove_dir_all(&rp_path).map_err(|e| e.to_string())?;
-    } else {
-        std::fs::remove_file(&rp_path).map_err(|e| e.to_string())?;
-    }
-
-    tracing::info!("Resource Pack deleted: {}", name);
+    // Ordner und Dateien landen im Papierkorb
+    let item = move_to_trash("resourcepack", &name, &profile_id, vec![rp_path]).await?;
+    tracing::info!("Resource Pack moved to trash: {}", name);
+    history::record(&profile.game_dir, HistoryEntry {
+        trash_id: Some(item.id),
+        ..HistoryEntry::new(ContentAction::Remove, ContentKind::ResourcePack, name.as_str())
+    });
 
     Ok(())
 }
@@ -687,12 +1110,13 @@ pub async fn delete_resourcepack(profile_id: String, name: String) -> Result<(),
 #[tauri::command]
 pub async fn delete_shaderpack(profile_id: String, name: String) -> Result<(), String> {
     use crate::core::profiles::ProfileManager;
+    use crate::core::history::{self, ContentAction, ContentKind, HistoryEntry};
 
     let profile_manager = ProfileManager::new().map_err(|e| e.to_string())?;
     let profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
 
     let profile = profiles.get_profile(&profile_id)
-        .ok_or_else(|| "Profile not found".to_string())?;
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?;
 
     let sp_path = profile.game_dir.join("shaderpacks").join(&name);
 
@@ -700,43 +1124,246 @@ pub async fn delete_shaderpack(profile_id: String, name: String) -> Result<(), S
         return Err(format!("Shader Pack nicht gefunden: {}", name));
     }
 
-    // Prüfe ob es ein Ordner oder eine Datei ist
-    if sp_path.is_dir() {
-        std::fs::remove_dir_all(&sp_path).map_err(|e| e.to_string())?;
-    } else {
-        std::fs::remove_file(&sp_path).map_err(|e| e.to_string())?;
-    }
-
-    tracing::info!("Shader Pack deleted: {}", name);
+    // Ordner und Dateien landen im Papierkorb
+    let item = move_to_trash("shaderpack", &name, &profile_id, vec![sp_path]).await?;
+    tracing::info!("Shader Pack moved to trash: {}", name);
+    history::record(&profile.game_dir, HistoryEntry {
+        trash_id: Some(item.id),
+        ..HistoryEntry::new(ContentAction::Remove, ContentKind::ShaderPack, name.as_str())
+    });
 
     Ok(())
 }
 
+/// (De-)Aktiviert mehrere Mods und gibt ein Token für `undo_operation` zurück.
+/// Schlägt eine Mod fehl, werden die bereits geänderten zurückgesetzt.
 #[tauri::command]
-pub async fn bulk_toggle_mods(profile_id: String, filenames: Vec<String>, enable: bool) -> Result<(), String> {
+pub async fn bulk_toggle_mods(profile_id: String, filenames: Vec<String>, enable: bool) -> Result<String, String> {
+    use crate::core::undo::UndoOperation;
+
+    let mut renames = Vec::new();
     for filename in filenames {
-        toggle_mod(profile_id.clone(), filename, enable).await?;
+        let new_filename = toggled_filename(&filename, enable);
+        if let Err(e) = toggle_mod(profile_id.clone(), filename.clone(), enable).await {
+            undo_toggles(&profile_id, &renames).await;
+            return Err(e);
+        }
+        if new_filename != filename {
+            renames.push((filename, new_filename));
+        }
     }
-    Ok(())
+    Ok(crate::core::undo::register(UndoOperation::Toggle { profile_id, renames }))
 }
 
+/// Verschiebt mehrere Mods in den Papierkorb und gibt ein Token für `undo_operation` zurück.
+/// Schlägt eine Mod fehl, werden die bereits gelöschten wiederhergestellt.
 #[tauri::command]
-pub async fn bulk_delete_mods(profile_id: String, filenames: Vec<String>) -> Result<(), String> {
+pub async fn bulk_delete_mods(profile_id: String, filenames: Vec<String>) -> Result<String, String> {
+    use crate::core::undo::UndoOperation;
+
+    let mut trash_ids = Vec::new();
     for filename in filenames {
-        delete_mod(profile_id.clone(), filename).await?;
+        match trash_mod(&profile_id, &filename).await {
+            Ok(item) => trash_ids.push(item.id),
+            Err(e) => {
+                undo_deletes(&trash_ids).await;
+                return Err(e);
+            }
+        }
     }
-    Ok(())
+    Ok(crate::core::undo::register(UndoOperation::Delete { profile_id, trash_ids }))
 }
 
+async fn undo_toggles(profile_id: &str, renames: &[(String, String)]) -> Vec<String> {
+    let mut errors = Vec::new();
+    for (original, current) in renames {
+        let enable = !original.ends_with(".disabled");
+        if let Err(e) = toggle_mod(profile_id.to_string(), current.clone(), enable).await {
+            errors.push(format!("{}: {}", original, e));
+        }
+    }
+    errors
+}
+
+async fn undo_deletes(trash_ids: &[String]) -> Vec<String> {
+    let mut errors = Vec::new();
+    for id in trash_ids {
+        let trash_id = id.clone();
+        if let Err(e) = crate::utils::compression::run_blocking(move || crate::core::trash::restore(&trash_id)).await {
+            errors.push(e.to_string());
+        }
+    }
+    errors
+}
+
+/// Macht eine Sammel-Aktion (bulk_toggle_mods / bulk_delete_mods) anhand ihres Tokens rückgängig
 #[tauri::command]
-pub async fn check_mod_updates(profile_id: String, _mc_version: String, _loader: String) -> Result<Vec<ModUpdateInfo>, String> {
-    use crate::core::profiles::ProfileManager;
+pub async fn undo_operation(token: String) -> Result<(), String> {
+    use crate::core::undo::UndoOperation;
 
-    let profile_manager = ProfileManager::new().map_err(|e| e.to_string())?;
+    let profile_id = crate::core::undo::profile_of(&token)
+        .ok_or_else(|| "Diese Aktion kann nicht mehr rückgängig gemacht werden".to_string())?;
+    if crate::core::minecraft::get_running_profile_ids().contains(&profile_id) {
+        return Err("Aktionen können nicht rückgängig gemacht werden, während das Spiel läuft".to_string());
+    }
+    let operation = crate::core::undo::take(&token)
+        .ok_or_else(|| "Diese Aktion kann nicht mehr rückgängig gemacht werden".to_string())?;
+
+    let errors = match &operation {
+        UndoOperation::Toggle { profile_id, renames } => undo_toggles(profile_id, renames).await,
+        UndoOperation::Delete { trash_ids, .. } => undo_deletes(trash_ids).await,
+    };
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("Nicht alles konnte wiederhergestellt werden:\n{}", errors.join("\n")))
+    }
+}
+
+/// Stellt die zuletzt archivierte Version einer Mod wieder her (z.B. wenn ein Update Probleme macht)
+#[tauri::command]
+pub async fn rollback_mod(
+    profile_id: String,
+    mod_id: String,
+    state: tauri::State<'_, crate::gui::state::AppState>,
+) -> Result<crate::core::mods::version_archive::ModRollbackResult, String> {
+    if crate::core::minecraft::get_running_profile_ids().contains(&profile_id) {
+        return Err("Mods können nicht zurückgesetzt werden, während das Spiel läuft".to_string());
+    }
+
+    let profile_manager = &state.profile_manager;
     let profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
 
-    let _profile = profiles.get_profile(&profile_id)
-        .ok_or_else(|| "Profile not found".to_string())?;
+    let profile = profiles.get_profile(&profile_id)
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?;
+
+    let game_dir = profile.game_dir.clone();
+    crate::utils::compression::run_blocking(move || {
+        crate::core::mods::version_archive::rollback(&game_dir, &mod_id)
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Archivierte (frühere) Versionen einer Mod, neueste zuerst
+#[tauri::command]
+pub async fn get_archived_mod_versions(
+    profile_id: String,
+    mod_id: String,
+    state: tauri::State<'_, crate::gui::state::AppState>,
+) -> Result<Vec<crate::core::mods::version_archive::ArchivedModVersion>, String> {
+    let profile_manager = &state.profile_manager;
+    let profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
+
+    let profile = profiles.get_profile(&profile_id)
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?;
+
+    Ok(crate::core::mods::version_archive::list_archived(&profile.game_dir, &mod_id))
+}
+
+/// Verlauf der installierten, aktualisierten und entfernten Inhalte eines Profils (neueste zuerst)
+#[tauri::command]
+pub async fn get_content_history(profile_id: String, state: tauri::State<'_, crate::gui::state::AppState>) -> Result<Vec<crate::core::history::HistoryEntry>, String> {
+    let profile_manager = &state.profile_manager;
+    let profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
+
+    let profile = profiles.get_profile(&profile_id)
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?;
+
+    let mut entries = crate::core::history::load(&profile.game_dir);
+    entries.reverse();
+    Ok(entries)
+}
+
+/// Macht eine aufgezeichnete Änderung rückgängig: Installationen werden entfernt, Mod-Updates
+/// aus dem Versions-Archiv zurückgesetzt und entfernte Inhalte aus dem Papierkorb geholt
+/// bzw. erneut heruntergeladen
+#[tauri::command]
+pub async fn revert_content_change(profile_id: String, entry_id: String, state: tauri::State<'_, crate::gui::state::AppState>) -> Result<(), String> {
+    use crate::core::history::{self, ContentAction, ContentKind};
+
+    if crate::core::minecraft::get_running_profile_ids().contains(&profile_id) {
+        return Err("Änderungen können nicht rückgängig gemacht werden, während das Spiel läuft".to_string());
+    }
+
+    let profile_manager = &state.profile_manager;
+    let profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
+
+    let game_dir = profiles.get_profile(&profile_id)
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?
+        .game_dir
+        .clone();
+
+    let entry = history::find(&game_dir, &entry_id)
+        .ok_or_else(|| "Eintrag nicht im Verlauf gefunden".to_string())?;
+    if entry.reverted {
+        return Err("Diese Änderung wurde bereits rückgängig gemacht".to_string());
+    }
+
+    match entry.action {
+        ContentAction::Install => {
+            if !game_dir.join(entry.kind.dir_name()).join(&entry.filename).exists() {
+                return Err(format!("{} ist nicht mehr vorhanden", entry.filename));
+            }
+            let filename = entry.filename.clone();
+            match entry.kind {
+                ContentKind::Mod => delete_mod(profile_id, filename).await?,
+                ContentKind::ResourcePack => delete_resourcepack(profile_id, filename).await?,
+                ContentKind::ShaderPack => delete_shaderpack(profile_id, filename).await?,
+            }
+        }
+        ContentAction::Update => {
+            let (Some(mod_id), Some(previous)) = (entry.project_id.clone(), entry.previous_filename.clone()) else {
+                return Err("Für dieses Update ist keine vorherige Version bekannt".to_string());
+            };
+            let dir = game_dir.clone();
+            crate::utils::compression::run_blocking(move || {
+                crate::core::mods::version_archive::restore(&dir, &mod_id, &previous)
+            })
+            .await
+            .map_err(|e| e.to_string())?;
+        }
+        ContentAction::Remove if entry.trash_id.as_deref().is_some_and(crate::core::trash::contains) => {
+            let trash_id = entry.trash_id.clone().unwrap_or_default();
+            crate::utils::compression::run_blocking(move || crate::core::trash::restore(&trash_id))
+                .await
+                .map_err(|e| e.to_string())?;
+        }
+        ContentAction::Remove => {
+            let origin = match entry.project_id {
+                Some(_) => Some(entry.clone()),
+                None => history::find_origin(&game_dir, entry.kind, &entry.filename),
+            };
+            let Some(origin) = origin else {
+                return Err(format!("{} stammt aus einer lokalen Datei und kann nicht wiederhergestellt werden", entry.filename));
+            };
+            let project_id = origin.project_id.unwrap_or_default();
+            match entry.kind {
+                ContentKind::Mod => {
+                    let source = origin.source.unwrap_or_else(|| "modrinth".to_string());
+                    install_mod(profile_id, project_id, origin.version_id, source).await?
+                }
+                ContentKind::ResourcePack => install_resourcepack(profile_id, project_id, origin.version_id).await?,
+                ContentKind::ShaderPack => install_shaderpack(profile_id, project_id, origin.version_id).await?,
+            }
+        }
+    }
+
+    history::mark_reverted(&game_dir, &entry_id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn check_mod_updates(profile_id: String, _mc_version: String, _loader: String, state: tauri::State<'_, crate::gui::state::AppState>) -> Result<Vec<ModUpdateInfo>, String> {
+    let profile_manager = &state.profile_manager;
+    let profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
+
+    let profile = profiles.get_profile(&profile_id)
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?;
+    // Archivierte Profile sind eingefroren
+    if profile.archived {
+        return Ok(Vec::new());
+    }
 
     let mods = get_installed_mods(profile_id.clone()).await?;
     let mut updates = Vec::new();
@@ -746,6 +1373,7 @@ pub async fn check_mod_updates(profile_id: String, _mc_version: String, _loader:
         if let Some(mod_id) = &mod_info.mod_id {
             // Versuche Mod auf Modrinth zu finden
             if let Ok(Some(latest)) = search_modrinth_by_name(mod_id).await {
+                crate::core::mods::content_db::set_update_info(&profile.game_dir, &mod_info.filename, Some(latest.version.clone())).ok();
                 let has_update = mod_info.version.as_ref()
                     .map(|v| v != &latest.version)
                     .unwrap_or(false);
@@ -763,9 +1391,57 @@ pub async fn check_mod_updates(profile_id: String, _mc_version: String, _loader:
         }
     }
 
+    // Mods nur auf CurseForge: per Fingerprint erkennen und dort auf Updates prüfen
+    if let Some(client) = curseforge_client().await {
+        let loader = profile.loader.loader.as_str().to_string();
+        match crate::core::mods::fingerprint::identify_mods(&client, &profile.game_dir, &profile.minecraft_version, &loader, true).await {
+            Ok(identified) => {
+                for m in &identified {
+                    crate::core::mods::content_db::set_update_info(&profile.game_dir, &m.filename, m.latest_version.clone()).ok();
+                }
+                for m in identified.into_iter().filter(|m| m.has_update) {
+                    if updates.iter().any(|u| u.filename == m.filename) {
+                        continue;
+                    }
+                    updates.push(ModUpdateInfo {
+                        filename: m.filename,
+                        current_version: Some(m.version),
+                        latest_version: m.latest_version,
+                        mod_id: m.mod_id,
+                        icon_url: m.icon_url,
+                    });
+                }
+            }
+            Err(e) => tracing::warn!("CurseForge fingerprint check failed: {}", e),
+        }
+    }
+
     Ok(updates)
 }
 
+/// CurseForge-Client, falls ein API-Key eingestellt ist
+async fn curseforge_client() -> Option<crate::api::curseforge::CurseForgeClient> {
+    let api_key = crate::gui::settings::get_config().await.ok()?.mod_sources.curseforge_api_key?;
+    crate::api::curseforge::CurseForgeClient::new(Some(api_key)).ok()
+}
+
+/// Erkennt manuell hinzugefügte Mods per CurseForge-Fingerprint und speichert ihre
+/// Metadaten, damit sie auf Updates geprüft werden können
+#[tauri::command]
+pub async fn identify_curseforge_mods(profile_id: String, state: tauri::State<'_, crate::gui::state::AppState>) -> Result<Vec<crate::core::mods::fingerprint::IdentifiedMod>, String> {
+    let profiles = state.profile_manager
+        .load_profiles().await.map_err(|e| e.to_string())?;
+    let profile = profiles.get_profile(&profile_id)
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?;
+
+    let client = curseforge_client().await
+        .ok_or_else(|| "CurseForge API-Key ist nicht eingerichtet".to_string())?;
+    let loader = profile.loader.loader.as_str().to_string();
+    crate::core::mods::fingerprint::identify_mods(&client, &profile.game_dir, &profile.minecraft_version, &loader, false)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 #[derive(serde::Serialize)]
 pub struct ModUpdateInfo {
     pub filename: String,
@@ -788,12 +1464,9 @@ async fn search_modrinth_by_name(name: &str) -> Result<Option<ModrinthSearchResu
         urlencoding::encode(name)
     );
 
-    let client = reqwest::Client::new();
-    let response = client.get(&url)
-        .header("User-Agent", "Lion-Launcher/1.0")
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
+    // Über das Rate-Limit, damit Update-Checks vieler Mods nicht an 429 scheitern
+    let client = crate::api::modrinth::ModrinthClient::new().map_err(|e| e.to_string())?;
+    let response = client.get(&url).await.map_err(|e| e.to_string())?;
 
     if !response.status().is_success() {
         return Ok(None);
@@ -835,14 +1508,12 @@ pub struct InstalledResourcePack {
 }
 
 #[tauri::command]
-pub async fn get_installed_resourcepacks(profile_id: String) -> Result<Vec<InstalledResourcePack>, String> {
-    use crate::core::profiles::ProfileManager;
-
-    let profile_manager = ProfileManager::new().map_err(|e| e.to_string())?;
+pub async fn get_installed_resourcepacks(profile_id: String, state: tauri::State<'_, crate::gui::state::AppState>) -> Result<Vec<InstalledResourcePack>, String> {
+    let profile_manager = &state.profile_manager;
     let profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
 
     let profile = profiles.get_profile(&profile_id)
-        .ok_or_else(|| "Profile not found".to_string())?;
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?;
 
     let rp_dir = profile.game_dir.join("resourcepacks");
 
@@ -901,17 +1572,40 @@ pub async fn get_installed_resourcepacks(profile_id: String) -> Result<Vec<Insta
     Ok(packs)
 }
 
+/// Importiert ein lokales Resource Pack (ZIP oder Ordner) in ein Profil
+#[tauri::command]
+pub async fn import_local_resourcepack(
+    profile_id: String,
+    path: String,
+    state: tauri::State<'_, crate::gui::state::AppState>,
+) -> Result<crate::core::packs::ResourcePackImport, String> {
+    use crate::core::history::{self, ContentAction, ContentKind, HistoryEntry};
+
+    let profile_manager = &state.profile_manager;
+    let profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
+
+    let profile = profiles.get_profile(&profile_id)
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?;
+
+    let rp_dir = profile.game_dir.join("resourcepacks");
+    let source = std::path::PathBuf::from(path);
+    let pack = crate::utils::compression::run_blocking(move || crate::core::packs::import_resourcepack(&source, &rp_dir))
+        .await
+        .map_err(|e| e.to_string())?;
+
+    history::record(&profile.game_dir, HistoryEntry::new(ContentAction::Install, ContentKind::ResourcePack, pack.file_name.as_str()));
+    Ok(pack)
+}
+
 // ==================== SHADER PACKS ====================
 
 #[tauri::command]
-pub async fn get_installed_shaderpacks(profile_id: String) -> Result<Vec<InstalledResourcePack>, String> {
-    use crate::core::profiles::ProfileManager;
-
-    let profile_manager = ProfileManager::new().map_err(|e| e.to_string())?;
+pub async fn get_installed_shaderpacks(profile_id: String, state: tauri::State<'_, crate::gui::state::AppState>) -> Result<Vec<InstalledResourcePack>, String> {
+    let profile_manager = &state.profile_manager;
     let profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
 
     let profile = profiles.get_profile(&profile_id)
-        .ok_or_else(|| "Profile not found".to_string())?;
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?;
 
     let shader_dir = profile.game_dir.join("shaderpacks");
 
@@ -953,6 +1647,77 @@ pub async fn get_installed_shaderpacks(profile_id: String) -> Result<Vec<Install
     Ok(packs)
 }
 
+/// Importiert ein lokales Shader Pack (ZIP) in ein Profil
+#[tauri::command]
+pub async fn import_local_shaderpack(
+    profile_id: String,
+    path: String,
+    state: tauri::State<'_, crate::gui::state::AppState>,
+) -> Result<crate::core::packs::ShaderPackImport, String> {
+    use crate::core::history::{self, ContentAction, ContentKind, HistoryEntry};
+
+    let profile_manager = &state.profile_manager;
+    let profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
+
+    let profile = profiles.get_profile(&profile_id)
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?;
+
+    let shader_dir = profile.game_dir.join("shaderpacks");
+    let source = std::path::PathBuf::from(path);
+    let pack = crate::utils::compression::run_blocking(move || crate::core::packs::import_shaderpack(&source, &shader_dir))
+        .await
+        .map_err(|e| e.to_string())?;
+
+    history::record(&profile.game_dir, HistoryEntry::new(ContentAction::Install, ContentKind::ShaderPack, pack.file_name.as_str()));
+    Ok(pack)
+}
+
+/// Drag & Drop: erkennt den Typ der abgelegten Datei und importiert sie in das Profil
+#[tauri::command]
+pub async fn classify_and_import(
+    profile_id: String,
+    path: String,
+    state: tauri::State<'_, crate::gui::state::AppState>,
+) -> Result<crate::core::import::ImportOutcome, String> {
+    use crate::core::history::{self, ContentAction, ContentKind, HistoryEntry};
+
+    let profile_manager = &state.profile_manager;
+    let profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
+
+    let profile = profiles.get_profile(&profile_id)
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?
+        .clone();
+
+    let game_dir = profile.game_dir.clone();
+    let source = std::path::PathBuf::from(&path);
+    let mut outcome = crate::utils::compression::run_blocking(move || crate::core::import::classify_and_import(&source, &profile))
+        .await
+        .map_err(|e| e.to_string())?;
+
+    // Modpacks werden nicht ins Profil importiert, sondern als eigenes Profil installiert
+    if outcome.kind == "modpack" {
+        let result = mod_browser::install_modpack_file(path).await?;
+        outcome.imported = true;
+        outcome.message = format!(
+            "Modpack als neues Profil {} installiert",
+            result.get("profile_name").and_then(|n| n.as_str()).unwrap_or_default()
+        );
+        outcome.details = Some(result);
+        return Ok(outcome);
+    }
+
+    let kind = match outcome.kind.as_str() {
+        "mod" => Some(ContentKind::Mod),
+        "resourcepack" => Some(ContentKind::ResourcePack),
+        "shaderpack" => Some(ContentKind::ShaderPack),
+        _ => None,
+    };
+    if let (Some(kind), Some(file_name), true) = (kind, &outcome.file_name, outcome.imported) {
+        history::record(&game_dir, HistoryEntry::new(ContentAction::Install, kind, file_name.as_str()));
+    }
+    Ok(outcome)
+}
+
 // ==================== SETTINGS SYNC ====================
 
 /// Synchronisiert die Minecraft-Einstellungen (options.txt) zwischen Profilen
@@ -967,12 +1732,13 @@ pub async fn sync_settings_to_profile(profile_id: String) -> Result<(), String>
     let profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
 
     let profile = profiles.get_profile(&profile_id)
-        .ok_or_else(|| "Profile not found".to_string())?;
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?;
 
-    if !profile.settings_sync {
+    if !profile.syncs_settings() {
         return Ok(()); // Sync ist für dieses Profil deaktiviert
     }
 
+    let sync_settings = crate::gui::settings::get_config().await?.sync;
     let shared_file = shared_settings_file();
     let profile_options = profile.game_dir.join("options.txt");
 
@@ -994,10 +1760,10 @@ pub async fn sync_settings_to_profile(profile_id: String) -> Result<(), String>
                 .map_err(|e| format!("Konnte existierende options.txt nicht lesen: {}", e))?;
 
             // Merge: Existing bleibt Basis, shared wird darüber gelegt (aber nicht Blacklist)
-            merge_options_content(&existing_content, &shared_content)
+            merge_options_content(&existing_content, &shared_content, profile.sync_mode, &sync_settings)
         } else {
-            // Keine existierende options.txt - einfach shared nehmen
-            shared_content
+            // Keine existierende options.txt - shared nehmen (gefiltert nach Sync-Modus)
+            merge_options_content("", &shared_content, profile.sync_mode, &sync_settings)
         };
 
         tokio::fs::write(&profile_options, &final_content)
@@ -1018,7 +1784,8 @@ pub async fn sync_settings_from_profile(_profile_id: String) -> Result<(), Strin
 
 /// Automatische Settings-Synchronisation:
 /// Sammelt alle options.txt von allen Profilen, sortiert nach Änderungszeit,
-/// und merged sie zusammen. Die neueste hat Vorrang (außer Blacklist-Keys).
+/// und merged sie zusammen. Die neueste hat Vorrang (außer ausgeschlossene Keys
+/// aus `LauncherConfig::sync`).
 /// Dann werden alle Profile mit Sync aktualisiert.
 pub async fn auto_sync_all_settings() -> Result<(), String> {
     use crate::core::profiles::ProfileManager;
@@ -1027,13 +1794,14 @@ pub async fn auto_sync_all_settings() -> Result<(), String> {
 
     let profile_manager = ProfileManager::new().map_err(|e| e.to_string())?;
     let profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
+    let sync_settings = crate::gui::settings::get_config().await?.sync;
 
     // Sammle alle options.txt Pfade mit ihrer Änderungszeit
-    let mut options_files: Vec<(SystemTime, std::path::PathBuf, String)> = Vec::new();
+    let mut options_files: Vec<(SystemTime, std::path::PathBuf, crate::types::profile::SyncMode, String)> = Vec::new();
 
     for profile in &profiles.profiles {
         // Nur Profile mit aktiviertem Sync
-        if !profile.settings_sync {
+        if !profile.syncs_settings() {
             continue;
         }
 
@@ -1049,7 +1817,7 @@ pub async fn auto_sync_all_settings() -> Result<(), String> {
                     time = time.max(created);
                 }
 
-                options_files.push((time, options_path, profile.id.clone()));
+                options_files.push((time, options_path, profile.sync_mode, profile.id.clone()));
             }
         }
     }
@@ -1059,8 +1827,17 @@ pub async fn auto_sync_all_settings() -> Result<(), String> {
         return Ok(());
     }
 
+    // Strategie "Prompt": Konflikte müssen erst in der GUI aufgelöst werden
+    if sync_settings.strategy == crate::config::schema::SyncStrategy::Prompt {
+        let conflicts = collect_settings_conflicts(&profiles.profiles, &sync_settings);
+        if !conflicts.is_empty() {
+            return Err(format!("{} widersprüchliche Einstellungen – bitte zuerst auflösen", conflicts.len()));
+        }
+    }
+
     // Sortiere nach Zeit (älteste zuerst, damit neueste überschreibt)
-    options_files.sort_by_key(|(time, _, _)| *time);
+    options_files.sort_by_key(|(time, _, _, _)| *time);
+    sync_settings.order_by_priority(&mut options_files, |(_, _, _, id)| id.as_str());
 
     tracing::info!("Found {} options.txt files for sync", options_files.len());
 
@@ -1078,11 +1855,15 @@ pub async fn auto_sync_all_settings() -> Result<(), String> {
     }
 
     // Merge alle options.txt (sortiert nach Zeit, neueste zuletzt = überschreibt)
-    for (_, path, _profile_id) in &options_files {
+    for (_, path, mode, _) in &options_files {
         if let Ok(content) = std::fs::read_to_string(path) {
             for (key, value) in parse_options_txt(&content) {
+                // Jedes Profil liefert nur die Keys seines Sync-Modus
+                if !mode.includes_key(&key) {
+                    continue;
+                }
                 // Blacklist-Keys werden nur hinzugefügt wenn sie noch nicht existieren
-                if !is_blacklisted_key(&key) {
+                if !sync_settings.is_excluded(&key) {
                     combined_values.insert(key, value);
                 } else {
                     combined_values.entry(key).or_insert(value);
@@ -1107,7 +1888,7 @@ pub async fn auto_sync_all_settings() -> Result<(), String> {
     // Jetzt alle Profile mit Sync aktualisieren
     let mut synced_count = 0;
     for profile in &profiles.profiles {
-        if !profile.settings_sync {
+        if !profile.syncs_settings() {
             continue;
         }
 
@@ -1116,16 +1897,16 @@ pub async fn auto_sync_all_settings() -> Result<(), String> {
         // Merge: Behalte profil-spezifische Keys (Blacklist)
         let final_content = if profile_options.exists() {
             if let Ok(existing) = std::fs::read_to_string(&profile_options) {
-                merge_options_content(&existing, &combined_content)
+                merge_options_content(&existing, &combined_content, profile.sync_mode, &sync_settings)
             } else {
-                combined_content.clone()
+                merge_options_content("", &combined_content, profile.sync_mode, &sync_settings)
             }
         } else {
             // Erstelle Verzeichnis falls nötig
             if let Some(parent) = profile_options.parent() {
                 tokio::fs::create_dir_all(parent).await.ok();
             }
-            combined_content.clone()
+            merge_options_content("", &combined_content, profile.sync_mode, &sync_settings)
         };
 
         if let Err(e) = tokio::fs::write(&profile_options, &final_content).await {
@@ -1139,6 +1920,104 @@ pub async fn auto_sync_all_settings() -> Result<(), String> {
     Ok(())
 }
 
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SettingsConflictValue {
+    pub profile_id: String,
+    pub profile_name: String,
+    pub value: String,
+}
+
+/// Ein options.txt-Key mit unterschiedlichen Werten in mehreren Sync-Profilen
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SettingsConflict {
+    pub key: String,
+    pub values: Vec<SettingsConflictValue>,
+}
+
+/// Sammelt alle Keys, die in den Sync-Profilen unterschiedliche Werte haben
+pub(crate) fn collect_settings_conflicts(
+    profiles: &[crate::types::profile::Profile],
+    sync_settings: &crate::config::schema::SyncSettings,
+) -> Vec<SettingsConflict> {
+    use std::collections::BTreeMap;
+
+    let mut by_key: BTreeMap<String, Vec<SettingsConflictValue>> = BTreeMap::new();
+
+    for profile in profiles.iter().filter(|p| p.syncs_settings()) {
+        let Ok(content) = std::fs::read_to_string(profile.game_dir.join("options.txt")) else {
+            continue;
+        };
+        for (key, value) in parse_options_txt(&content) {
+            if sync_settings.is_excluded(&key) || !profile.sync_mode.includes_key(&key) {
+                continue;
+            }
+            by_key.entry(key).or_default().push(SettingsConflictValue {
+                profile_id: profile.id.clone(),
+                profile_name: profile.name.clone(),
+                value,
+            });
+        }
+    }
+
+    by_key
+        .into_iter()
+        .filter(|(_, values)| values.iter().any(|v| v.value != values[0].value))
+        .map(|(key, values)| SettingsConflict { key, values })
+        .collect()
+}
+
+/// Gibt die widersprüchlichen Einstellungen zwischen Sync-Profilen zurück (Diff für die GUI)
+#[tauri::command]
+pub async fn get_settings_sync_conflicts(state: tauri::State<'_, crate::gui::state::AppState>) -> Result<Vec<SettingsConflict>, String> {
+    let profile_manager = &state.profile_manager;
+    let profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
+    let sync_settings = crate::gui::settings::get_config().await?.sync;
+
+    Ok(collect_settings_conflicts(&profiles.profiles, &sync_settings))
+}
+
+/// Übernimmt die in der GUI gewählten Werte (Key → Wert) in alle Sync-Profile
+/// und in shared_options.txt
+#[tauri::command]
+pub async fn resolve_settings_sync_conflicts(resolutions: std::collections::HashMap<String, String>, state: tauri::State<'_, crate::gui::state::AppState>) -> Result<(), String> {
+    use crate::config::defaults::shared_settings_file;
+
+    let profile_manager = &state.profile_manager;
+    let profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
+
+    for profile in profiles.profiles.iter().filter(|p| p.syncs_settings()) {
+        let options_path = profile.game_dir.join("options.txt");
+        let Ok(content) = tokio::fs::read_to_string(&options_path).await else {
+            continue;
+        };
+
+        let mut values: std::collections::HashMap<String, String> = parse_options_txt(&content).into_iter().collect();
+        for (key, value) in &resolutions {
+            if profile.sync_mode.includes_key(key) && values.contains_key(key) {
+                values.insert(key.clone(), value.clone());
+            }
+        }
+
+        tokio::fs::write(&options_path, create_options_txt_string(&values))
+            .await
+            .map_err(|e| format!("Konnte options.txt nicht schreiben: {}", e))?;
+    }
+
+    let shared_file = shared_settings_file();
+    let shared_content = tokio::fs::read_to_string(&shared_file).await.unwrap_or_default();
+    let mut shared: std::collections::HashMap<String, String> = parse_options_txt(&shared_content).into_iter().collect();
+    shared.extend(resolutions);
+    if let Some(parent) = shared_file.parent() {
+        tokio::fs::create_dir_all(parent).await.ok();
+    }
+    tokio::fs::write(&shared_file, create_options_txt_string(&shared))
+        .await
+        .map_err(|e| format!("Konnte shared_options.txt nicht schreiben: {}", e))?;
+
+    tracing::info!("Resolved settings sync conflicts");
+    Ok(())
+}
+
 /// Parst eine options.txt in Key-Value Paare
 fn parse_options_txt(content: &str) -> Vec<(String, String)> {
     let mut values = Vec::new();
@@ -1161,17 +2040,9 @@ fn create_options_txt_string(values: &std::collections::HashMap<String, String>)
     lines.join("\n")
 }
 
-/// Prüft ob ein Key in der Blacklist ist (nicht synchronisiert werden soll)
-fn is_blacklisted_key(key: &str) -> bool {
-    // Nur version bleibt profil-spezifisch
-    matches!(key, "version")
-}
-
 #[tauri::command]
-pub async fn toggle_settings_sync(profile_id: String, enabled: bool) -> Result<(), String> {
-    use crate::core::profiles::ProfileManager;
-
-    let profile_manager = ProfileManager::new().map_err(|e| e.to_string())?;
+pub async fn toggle_settings_sync(profile_id: String, enabled: bool, state: tauri::State<'_, crate::gui::state::AppState>) -> Result<(), String> {
+    let profile_manager = &state.profile_manager;
     let mut profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
 
     if let Some(profile) = profiles.get_profile_mut(&profile_id) {
@@ -1186,35 +2057,55 @@ pub async fn toggle_settings_sync(profile_id: String, enabled: bool) -> Result<(
 
         tracing::info!("Settings sync {} for profile", if enabled { "enabled" } else { "disabled" });
     } else {
-        return Err("Profile not found".to_string());
+        return Err(crate::utils::error::profile_not_found(&profile_id));
     }
 
     Ok(())
 }
 
+/// Setzt den Sync-Modus eines Profils ("all", "keybinds_only", "video_only")
 #[tauri::command]
-pub async fn get_settings_sync_status(profile_id: String) -> Result<bool, String> {
-    use crate::core::profiles::ProfileManager;
+pub async fn set_settings_sync_mode(profile_id: String, mode: String, state: tauri::State<'_, crate::gui::state::AppState>) -> Result<(), String> {
+    use crate::types::profile::SyncMode;
 
-    let profile_manager = ProfileManager::new().map_err(|e| e.to_string())?;
+    let sync_mode = SyncMode::parse(&mode)
+        .ok_or_else(|| format!("Unbekannter Sync-Modus: {}", mode))?;
+
+    let profile_manager = &state.profile_manager;
+    let mut profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
+
+    let profile = profiles.get_profile_mut(&profile_id)
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?;
+    profile.sync_mode = sync_mode;
+    profile_manager.save_profiles(&profiles).await.map_err(|e| e.to_string())?;
+
+    tracing::info!("Settings sync mode for profile {} set to {:?}", profile_id, sync_mode);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_settings_sync_status(profile_id: String, state: tauri::State<'_, crate::gui::state::AppState>) -> Result<bool, String> {
+    let profile_manager = &state.profile_manager;
     let profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
 
     let profile = profiles.get_profile(&profile_id)
-        .ok_or_else(|| "Profile not found".to_string())?;
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?;
 
     Ok(profile.settings_sync)
 }
 
 
-/// Interne Merge-Funktion
-fn merge_options_content(existing: &str, new_content: &str) -> String {
+/// Interne Merge-Funktion.
+/// Übernimmt aus `new_content` nur Keys, die der Sync-Modus des Profils einschließt
+/// und die nicht über die Include/Exclude-Listen der Launcher-Config ausgeschlossen sind.
+fn merge_options_content(
+    existing: &str,
+    new_content: &str,
+    mode: crate::types::profile::SyncMode,
+    sync_settings: &crate::config::schema::SyncSettings,
+) -> String {
     use std::collections::HashMap;
 
-    // Keys die NICHT synchronisiert werden sollen (version-spezifisch)
-    let blacklist: Vec<&str> = vec![
-        "version",           // Minecraft version number - bleibt profil-spezifisch
-    ];
-
     // Parse beide in key-value Maps
     let mut settings: HashMap<String, String> = HashMap::new();
 
@@ -1228,11 +2119,14 @@ fn merge_options_content(existing: &str, new_content: &str) -> String {
     // Merge neue Settings (überschreibt existierende, außer Blacklist)
     for line in new_content.lines() {
         if let Some((key, value)) = parse_option_line(line) {
-            // Überspringe Keys in der Blacklist
-            if !blacklist.contains(&key.as_str()) {
-                settings.insert(key, value);
+            // Überspringe ausgeschlossene Keys (z.B. version)
+            if !sync_settings.is_excluded(&key) {
+                // Keys außerhalb des Sync-Modus bleiben profil-spezifisch
+                if mode.includes_key(&key) {
+                    settings.insert(key, value);
+                }
             } else {
-                // Wenn Key in Blacklist ist und noch nicht existiert, füge ihn hinzu
+                // Wenn Key ausgeschlossen ist und noch nicht existiert, füge ihn hinzu
                 // (für neue Profile)
                 if !settings.contains_key(&key) {
                     settings.entry(key).or_insert(value);
@@ -1266,36 +2160,237 @@ fn parse_option_line(line: &str) -> Option<(String, String)> {
     }
 }
 
+// ==================== TRASH ====================
+
+/// Inhalt des Papierkorbs (zuletzt gelöschte zuerst)
+#[tauri::command]
+pub async fn get_trash_items() -> Result<Vec<crate::core::trash::TrashItem>, String> {
+    Ok(crate::core::trash::list())
+}
+
+/// Stellt einen gelöschten Inhalt wieder her; gelöschte Profile werden wieder angelegt
+#[tauri::command]
+pub async fn restore_trash_item(id: String, state: tauri::State<'_, crate::gui::state::AppState>) -> Result<crate::core::trash::TrashItem, String> {
+    let item = crate::utils::compression::run_blocking(move || crate::core::trash::restore(&id))
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if let Some(profile) = item.profile.clone() {
+        let profile_manager = &state.profile_manager;
+        let mut profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
+        if profiles.get_profile(&profile.id).is_none() {
+            profiles.add_profile(profile);
+            profile_manager.save_profiles(&profiles).await.map_err(|e| e.to_string())?;
+        }
+    }
+
+    Ok(item)
+}
+
+/// Löscht einen Eintrag des Papierkorbs endgültig
+#[tauri::command]
+pub async fn delete_trash_item(id: String) -> Result<(), String> {
+    crate::utils::compression::run_blocking(move || crate::core::trash::delete_permanently(&id))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Leert den Papierkorb und gibt die Anzahl der entfernten Einträge zurück
+#[tauri::command]
+pub async fn empty_trash() -> Result<usize, String> {
+    crate::utils::compression::run_blocking(crate::core::trash::empty)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 // ==================== WORLDS ====================
 
 #[tauri::command]
-pub async fn get_worlds(profile_id: String) -> Result<Vec<crate::core::minecraft::worlds::WorldInfo>, String> {
+pub async fn get_worlds(profile_id: String, state: tauri::State<'_, crate::gui::state::AppState>) -> Result<Vec<crate::core::minecraft::worlds::WorldInfo>, String> {
+    let profile_manager = &state.profile_manager;
+    let profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
+
+    let profile = profiles.get_profile(&profile_id)
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?;
+
+    crate::core::minecraft::worlds::get_worlds(&profile.game_dir)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Sichert die angegebenen (oder alle) Welten eines Profils als ZIP und gibt die Backup-Pfade zurück
+#[tauri::command]
+pub async fn backup_worlds(profile_id: String, folder_names: Option<Vec<String>>) -> Result<Vec<String>, String> {
     use crate::core::profiles::ProfileManager;
 
     let profile_manager = ProfileManager::new().map_err(|e| e.to_string())?;
     let profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
 
     let profile = profiles.get_profile(&profile_id)
-        .ok_or_else(|| "Profile not found".to_string())?;
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?;
+
+    let game_dir = profile.game_dir.clone();
+    let folders = match folder_names {
+        // Vor dem ersten Backup prüfen, damit ein ungültiger Name keine halbe Sicherung hinterlässt
+        Some(folders) => {
+            for folder in &folders {
+                crate::core::minecraft::worlds::check_folder_name(folder).map_err(|e| e.to_string())?;
+            }
+            folders
+        }
+        None => crate::core::minecraft::worlds::get_worlds(&game_dir)
+            .await
+            .map_err(|e| e.to_string())?
+            .into_iter()
+            .map(|w| w.folder_name)
+            .collect(),
+    };
+
+    crate::utils::compression::run_blocking(move || {
+        folders.iter()
+            .map(|folder| crate::core::minecraft::worlds::backup_world(&game_dir, folder)
+                .map(|p| p.display().to_string()))
+            .collect::<anyhow::Result<Vec<_>>>()
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Importiert eine gezippte Welt in das Profil und gibt die Infos der neuen Welt zurück
+#[tauri::command]
+pub async fn import_world_zip(
+    profile_id: String,
+    path: String,
+    state: tauri::State<'_, crate::gui::state::AppState>,
+) -> Result<crate::core::minecraft::worlds::WorldInfo, String> {
+    use crate::core::minecraft::worlds;
+
+    let profile_manager = &state.profile_manager;
+    let profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
+
+    let profile = profiles.get_profile(&profile_id)
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?;
 
-    crate::core::minecraft::worlds::get_worlds(&profile.game_dir)
+    let game_dir = profile.game_dir.clone();
+    let zip_path = std::path::PathBuf::from(path);
+    let folder = crate::utils::compression::run_blocking(move || worlds::import_world_zip(&game_dir, &zip_path))
+        .await
+        .map_err(|e| e.to_string())?;
+
+    worlds::get_world_info(&profile.game_dir, &folder)
         .await
         .map_err(|e| e.to_string())
 }
 
+/// Seed einer Welt als Text (zum Kopieren in der Weltenliste)
 #[tauri::command]
-pub async fn launch_world(profile_id: String, world_name: String) -> Result<(), String> {
-    use crate::core::profiles::ProfileManager;
+pub async fn get_world_seed(profile_id: String, folder_name: String, state: tauri::State<'_, crate::gui::state::AppState>) -> Result<String, String> {
+    let profile_manager = &state.profile_manager;
+    let profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
+
+    let profile = profiles.get_profile(&profile_id)
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?;
+
+    crate::core::minecraft::worlds::get_world_seed(&profile.game_dir, &folder_name)
+        .await
+        .map(|seed| seed.to_string())
+        .map_err(|e| e.to_string())
+}
+
+/// Ändert Name, Cheats und Schwierigkeit einer Welt (nicht während das Profil läuft)
+#[tauri::command]
+pub async fn update_world_settings(
+    profile_id: String,
+    folder_name: String,
+    update: crate::core::minecraft::worlds::WorldSettingsUpdate,
+    state: tauri::State<'_, crate::gui::state::AppState>,
+) -> Result<(), String> {
+    if crate::core::minecraft::get_running_profile_ids().contains(&profile_id) {
+        return Err("Welt-Einstellungen können nicht geändert werden, während das Spiel läuft".to_string());
+    }
+
+    let profile_manager = &state.profile_manager;
+    let profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
+
+    let profile = profiles.get_profile(&profile_id)
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?;
+
+    let game_dir = profile.game_dir.clone();
+    crate::utils::compression::run_blocking(move || {
+        crate::core::minecraft::worlds::update_world_settings(&game_dir, &folder_name, &update)
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Aktualisiert alle Chunks einer Welt auf die Profil-Version (Fortschritt als "world-optimize-progress").
+/// `eula_accepted` muss aus einer ausdrücklichen Zustimmung in der GUI stammen.
+#[tauri::command]
+pub async fn optimize_world(
+    app_handle: tauri::AppHandle,
+    profile_id: String,
+    folder_name: String,
+    eula_accepted: bool,
+    state: tauri::State<'_, crate::gui::state::AppState>,
+) -> Result<crate::core::minecraft::world_optimize::WorldOptimizeResult, String> {
+    use tauri::Emitter;
+
+    if crate::core::minecraft::get_running_profile_ids().contains(&profile_id) {
+        return Err("Welten können nicht optimiert werden, während das Spiel läuft".to_string());
+    }
+
+    let profiles = state.profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
+    let profile = profiles.get_profile(&profile_id)
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?;
+
+    state.launcher
+        .optimize_world(&profile.game_dir, &profile.loader.loader, &profile.minecraft_version, &folder_name, eula_accepted, move |progress| {
+            app_handle.emit("world-optimize-progress", &progress).ok();
+        })
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Exportiert eine Welt als ZIP an den gewählten Pfad (Fortschritt als "world-export-progress")
+#[tauri::command]
+pub async fn export_world(
+    app_handle: tauri::AppHandle,
+    profile_id: String,
+    folder_name: String,
+    output_path: String,
+    state: tauri::State<'_, crate::gui::state::AppState>,
+) -> Result<u64, String> {
+    use tauri::Emitter;
+
+    let profile_manager = &state.profile_manager;
+    let profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
+
+    let profile = profiles.get_profile(&profile_id)
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?;
+
+    let game_dir = profile.game_dir.clone();
+    let output = std::path::PathBuf::from(output_path);
+    crate::utils::compression::run_blocking(move || {
+        crate::core::minecraft::worlds::export_world(&game_dir, &folder_name, &output, |progress| {
+            app_handle.emit("world-export-progress", progress).ok();
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn launch_world(profile_id: String, world_name: String, app_state: tauri::State<'_, crate::gui::state::AppState>) -> Result<(), String> {
     use crate::core::minecraft::MinecraftLauncher;
     use crate::gui::auth::AUTH_STATE;
 
     tracing::info!("Launching world '{}' for profile '{}'", world_name, profile_id);
 
-    let profile_manager = ProfileManager::new().map_err(|e| e.to_string())?;
+    let profile_manager = &app_state.profile_manager;
     let profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
 
     let profile = profiles.get_profile(&profile_id)
-        .ok_or_else(|| "Profile not found".to_string())?
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?
         .clone();
 
     // Hole aktiven Account
@@ -1309,6 +2404,10 @@ pub async fn launch_world(profile_id: String, world_name: String) -> Result<(),
     drop(state); // Unlock
 
     // Starte Minecraft mit --quickPlaySingleplayer Argument
+    // (vor 1.20 gibt es kein Gegenstück – das Spiel startet dann im Hauptmenü)
+    if !crate::core::minecraft::supports_quick_play(&profile.minecraft_version) {
+        tracing::warn!("Minecraft {} does not support Quick Play, world will not be opened directly", profile.minecraft_version);
+    }
     let launcher = MinecraftLauncher::new().map_err(|e| e.to_string())?;
 
     launcher.launch_with_extra_args(
@@ -1323,14 +2422,12 @@ pub async fn launch_world(profile_id: String, world_name: String) -> Result<(),
 // ==================== SERVERS ====================
 
 #[tauri::command]
-pub async fn get_servers(profile_id: String) -> Result<Vec<crate::core::minecraft::worlds::ServerInfo>, String> {
-    use crate::core::profiles::ProfileManager;
-
-    let profile_manager = ProfileManager::new().map_err(|e| e.to_string())?;
+pub async fn get_servers(profile_id: String, state: tauri::State<'_, crate::gui::state::AppState>) -> Result<Vec<crate::core::minecraft::worlds::ServerInfo>, String> {
+    let profile_manager = &state.profile_manager;
     let profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
 
     let profile = profiles.get_profile(&profile_id)
-        .ok_or_else(|| "Profile not found".to_string())?;
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?;
 
     crate::core::minecraft::worlds::get_servers(&profile.game_dir)
         .await
@@ -1349,7 +2446,7 @@ pub async fn launch_server(profile_id: String, server_ip: String) -> Result<(),
     let profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
 
     let profile = profiles.get_profile(&profile_id)
-        .ok_or_else(|| "Profile not found".to_string())?
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?
         .clone();
 
     // Hole aktiven Account
@@ -1362,29 +2459,28 @@ pub async fn launch_server(profile_id: String, server_ip: String) -> Result<(),
         .clone();
     drop(state); // Unlock
 
-    // Starte Minecraft mit --quickPlayMultiplayer Argument
+    // Starte Minecraft mit --quickPlayMultiplayer (ab 1.20) bzw. --server/--port
     let launcher = MinecraftLauncher::new().map_err(|e| e.to_string())?;
+    let join_args = crate::core::minecraft::server_join_args(&profile.minecraft_version, &server_ip);
 
     launcher.launch_with_extra_args(
         &profile,
         &account.username,
         &account.uuid,
         Some(&account.access_token),
-        vec!["--quickPlayMultiplayer".to_string(), server_ip]
+        join_args
     ).await.map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-pub async fn add_server(profile_id: String, name: String, ip: String) -> Result<(), String> {
-    use crate::core::profiles::ProfileManager;
-
+pub async fn add_server(profile_id: String, name: String, ip: String, state: tauri::State<'_, crate::gui::state::AppState>) -> Result<(), String> {
     tracing::info!("Adding server '{}' ({}) for profile '{}'", name, ip, profile_id);
 
-    let profile_manager = ProfileManager::new().map_err(|e| e.to_string())?;
+    let profile_manager = &state.profile_manager;
     let profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
 
     let profile = profiles.get_profile(&profile_id)
-        .ok_or_else(|| "Profile not found".to_string())?;
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?;
 
     crate::core::minecraft::worlds::add_server(&profile.game_dir, &name, &ip)
         .await
@@ -1392,48 +2488,88 @@ pub async fn add_server(profile_id: String, name: String, ip: String) -> Result<
 }
 
 #[tauri::command]
-pub async fn remove_server(profile_id: String, ip: String) -> Result<(), String> {
-    use crate::core::profiles::ProfileManager;
-
+pub async fn remove_server(profile_id: String, ip: String, state: tauri::State<'_, crate::gui::state::AppState>) -> Result<(), String> {
     tracing::info!("Removing server '{}' from profile '{}'", ip, profile_id);
 
-    let profile_manager = ProfileManager::new().map_err(|e| e.to_string())?;
+    let profile_manager = &state.profile_manager;
     let profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
 
     let profile = profiles.get_profile(&profile_id)
-        .ok_or_else(|| "Profile not found".to_string())?;
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?;
 
     crate::core::minecraft::worlds::remove_server(&profile.game_dir, &ip)
         .await
         .map_err(|e| e.to_string())
 }
 
-/// Migriert alte .jar.meta.json Dateien aus mods/ nach modinfos/
-fn migrate_old_metadata(mods_dir: &std::path::Path, modinfos_dir: &std::path::Path) {
-    if let Ok(entries) = std::fs::read_dir(mods_dir) {
-        for entry in entries.flatten() {
-            let path = entry.path();
+// ==================== REALMS ====================
 
-            // Suche nach .jar.meta.json Dateien
-            if let Some(filename) = path.file_name() {
-                let filename_str = filename.to_string_lossy();
+/// Lädt den aktiven Account (Realms benötigen einen Microsoft-Account)
+async fn active_microsoft_account() -> Result<crate::core::auth::MinecraftAccount, String> {
+    let (uuid, _, _) = crate::gui::auth::get_active_access_token_refreshed()
+        .await
+        .ok_or_else(|| "Kein aktiver Account".to_string())?;
+
+    let state = crate::gui::auth::AUTH_STATE.lock().await;
+    let account = state.accounts.iter()
+        .find(|a| a.uuid == uuid)
+        .cloned()
+        .ok_or_else(|| "Account not found".to_string())?;
+
+    if !account.is_microsoft {
+        return Err("Realms funktionieren nur mit Microsoft-Accounts".to_string());
+    }
+    Ok(account)
+}
 
-                if filename_str.ends_with(".jar.meta.json") {
-                    // Neuer Dateiname: entferne ".jar.meta" und behalte nur ".json"
-                    let new_filename = filename_str
-                        .trim_end_matches(".jar.meta.json")
-                        .to_string() + ".json";
+#[tauri::command]
+pub async fn get_realms(profile_id: String, state: tauri::State<'_, crate::gui::state::AppState>) -> Result<Vec<crate::core::minecraft::realms::RealmInfo>, String> {
+    let profile_manager = &state.profile_manager;
+    let profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
 
-                    let new_path = modinfos_dir.join(&new_filename);
+    let profile = profiles.get_profile(&profile_id)
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?;
 
-                    // Verschiebe die Datei
-                    if let Err(e) = std::fs::rename(&path, &new_path) {
-                        tracing::warn!("Failed to migrate metadata {}: {}", filename_str, e);
-                    } else {
-                        tracing::info!("✅ Migrated metadata: {} -> modinfos/{}", filename_str, new_filename);
-                    }
-                }
-            }
-        }
+    let account = active_microsoft_account().await?;
+    crate::core::minecraft::realms::list_realms(
+        &account.access_token,
+        &account.uuid,
+        &account.username,
+        &profile.minecraft_version,
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn launch_realm(profile_id: String, realm_id: i64, state: tauri::State<'_, crate::gui::state::AppState>) -> Result<(), String> {
+    use crate::core::minecraft::MinecraftLauncher;
+
+    tracing::info!("Launching realm {} for profile '{}'", realm_id, profile_id);
+
+    let profile_manager = &state.profile_manager;
+    let profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
+
+    let profile = profiles.get_profile(&profile_id)
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?
+        .clone();
+
+    // --quickPlayRealms gibt es erst ab 1.20, davor kein Direktbeitritt
+    if !crate::core::minecraft::supports_quick_play(&profile.minecraft_version) {
+        return Err(format!(
+            "Realms-Direktbeitritt benötigt Minecraft 1.20 oder neuer (Profil: {})",
+            profile.minecraft_version
+        ));
     }
+
+    let account = active_microsoft_account().await?;
+    let launcher = MinecraftLauncher::new().map_err(|e| e.to_string())?;
+
+    launcher.launch_with_extra_args(
+        &profile,
+        &account.username,
+        &account.uuid,
+        Some(&account.access_token),
+        vec!["--quickPlayRealms".to_string(), realm_id.to_string()]
+    ).await.map_err(|e| e.to_string())
 }
\ No newline at end of file
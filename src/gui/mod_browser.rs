@@ -1,5 +1,6 @@
 use serde::Deserialize;
 use crate::core::mods::ModManager;
+use crate::gui::state::AppState;
 use crate::types::mod_info::{ModInfo, ModVersion, ModSearchQuery, SortOption};
 
 // Re-export ModrinthCategory für Frontend
@@ -17,23 +18,85 @@ pub async fn get_modrinth_categories() -> Result<Vec<ModrinthCategory>, String>
     Ok(categories)
 }
 
+/// Filter-Optionen für die Suche (Chips in der GUI)
+#[derive(serde::Serialize)]
+pub struct SearchFilters {
+    pub categories: Vec<ModrinthCategory>,
+    pub licenses: Vec<crate::api::modrinth::ModrinthLicense>,
+    /// Werte für `client_side`/`server_side`
+    pub environments: Vec<&'static str>,
+}
+
+/// Verfügbare Kategorien (für `project_type`, Standard "mod"), Lizenzen und Umgebungen
+#[tauri::command]
+pub async fn get_search_filters(project_type: Option<String>) -> Result<SearchFilters, String> {
+    let client = ModrinthClient::new().map_err(|e| e.to_string())?;
+    let project_type = project_type.unwrap_or_else(|| "mod".to_string());
+
+    let mut categories = client.get_categories().await.map_err(|e| e.to_string())?;
+    categories.retain(|c| c.project_type == project_type);
+    categories.sort_by(|a, b| a.header.cmp(&b.header).then_with(|| a.name.cmp(&b.name)));
+
+    let licenses = client.get_licenses().await.unwrap_or_else(|e| {
+        tracing::warn!("Could not load Modrinth licenses: {}", e);
+        Vec::new()
+    });
+
+    Ok(SearchFilters {
+        categories,
+        licenses,
+        environments: vec!["required", "optional", "unsupported"],
+    })
+}
+
+/// Startseite des Browsers: beliebte, aktuell gefragte oder kürzlich aktualisierte
+/// Projekte eines Typs ohne Suchbegriff (`list`: "popular", "trending", "updated")
+#[tauri::command]
+pub async fn browse_content(
+    project_type: String,
+    list: crate::api::modrinth::BrowseList,
+    game_version: Option<String>,
+    loader: Option<String>,
+    limit: Option<u32>,
+) -> Result<Vec<ModInfo>, String> {
+    if !matches!(project_type.as_str(), "mod" | "resourcepack" | "shader" | "modpack") {
+        return Err(format!("Unbekannter Projekttyp: {}", project_type));
+    }
+    // Loader-Filter nur bei Mods und Modpacks sinnvoll
+    let loader = loader.filter(|_| matches!(project_type.as_str(), "mod" | "modpack"));
+
+    let client = ModrinthClient::new().map_err(|e| e.to_string())?;
+    client
+        .browse(&project_type, list, game_version.as_deref(), loader.as_deref(), limit.unwrap_or(20).min(100))
+        .await
+        .map_err(|e| e.to_string())
+}
+
 // ==================== MODS ====================
 
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn search_mods(
     query: String,
     game_version: Option<String>,
     loader: Option<String>,
     categories: Option<Vec<String>>,
+    license: Option<String>,
+    client_side: Option<String>,
+    server_side: Option<String>,
     sort_by: Option<String>,
     offset: Option<u32>,
     limit: Option<u32>,
+    state: tauri::State<'_, AppState>,
 ) -> Result<Vec<ModInfo>, String> {
     let search_query = ModSearchQuery {
         query,
         game_version,
         loader,
         categories: categories.unwrap_or_default(),
+        license,
+        client_side,
+        server_side,
         offset: offset.unwrap_or(0),
         limit: limit.unwrap_or(20),
         sort_by: match sort_by.as_deref() {
@@ -44,18 +107,21 @@ pub async fn search_mods(
         },
     };
 
-    let manager = ModManager::new(None).map_err(|e| e.to_string())?;
-    manager.search_mods(&search_query, true, false).await.map_err(|e| e.to_string())
+    state.mod_manager.search_mods(&search_query, true, false).await.map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-pub async fn get_mod_versions(mod_id: String, source: String) -> Result<Vec<ModVersion>, String> {
-    let manager = ModManager::new(None).map_err(|e| e.to_string())?;
+pub async fn get_mod_versions(
+    state: tauri::State<'_, AppState>,
+    mod_id: String,
+    source: String,
+) -> Result<Vec<ModVersion>, String> {
+    let manager = &state.mod_manager;
 
     let mod_source = match source.as_str() {
         "modrinth" => crate::types::mod_info::ModSource::Modrinth,
         "curseforge" => crate::types::mod_info::ModSource::CurseForge,
-        _ => return Err("Invalid source".to_string()),
+        _ => return Err(crate::utils::error::invalid_source(&source)),
     };
 
     manager.get_mod_versions_raw(&mod_id, mod_source).await.map_err(|e| e.to_string())
@@ -72,10 +138,41 @@ pub async fn get_mod_info(mod_id: String, source: String) -> Result<ModInfo, Str
         "curseforge" => {
             Err("CurseForge not yet implemented".to_string())
         }
-        _ => Err("Invalid source".to_string()),
+        _ => Err(crate::utils::error::invalid_source(&source)),
     }
 }
 
+/// Projektbeschreibung (Markdown von Modrinth) als bereinigtes HTML für die Detailseite
+#[tauri::command]
+pub async fn get_project_description_html(project_id: String) -> Result<String, String> {
+    let client = ModrinthClient::new().map_err(|e| e.to_string())?;
+    let project = client.get_mod(&project_id).await.map_err(|e| e.to_string())?;
+    let body = project.body.unwrap_or(project.description);
+    Ok(crate::utils::markdown::to_html(&body))
+}
+
+/// Galerie eines Projekts mit lokal gecachten Bildern; ohne Netz die zuletzt geladene
+#[tauri::command]
+pub async fn get_project_gallery(project_id: String) -> Result<Vec<crate::core::artwork::gallery::CachedGalleryImage>, String> {
+    use crate::core::artwork::gallery::{self, CachedGalleryImage};
+
+    let client = ModrinthClient::new().map_err(|e| e.to_string())?;
+    let project = match client.get_mod(&project_id).await {
+        Ok(project) => project,
+        Err(e) => {
+            return gallery::offline_gallery(&project_id).ok_or_else(|| e.to_string());
+        }
+    };
+
+    let images = project.gallery.into_iter().map(|image| CachedGalleryImage {
+        url: image.url,
+        title: image.title,
+        description: image.description,
+        data_url: None,
+    }).collect();
+    Ok(gallery::cache_gallery(&project_id, images).await)
+}
+
 #[tauri::command]
 pub async fn install_mod(
     profile_id: String,
@@ -89,7 +186,7 @@ pub async fn install_mod(
     let mut profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
 
     let profile = profiles.get_profile_mut(&profile_id)
-        .ok_or_else(|| "Profile not found".to_string())?;
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?;
 
     let mods_dir = profile.game_dir.join("mods");
 
@@ -104,7 +201,7 @@ pub async fn install_mod(
     let mod_source = match source.as_str() {
         "modrinth" => crate::types::mod_info::ModSource::Modrinth,
         "curseforge" => crate::types::mod_info::ModSource::CurseForge,
-        _ => return Err("Invalid source".to_string()),
+        _ => return Err(crate::utils::error::invalid_source(&source)),
     };
 
     let manager = ModManager::new(None).map_err(|e| e.to_string())?;
@@ -112,7 +209,11 @@ pub async fn install_mod(
     // Hole Icon-URL und Name von Modrinth (für Metadaten)
     let (icon_url, mod_name) = if mod_source == crate::types::mod_info::ModSource::Modrinth {
         let url = format!("https://api.modrinth.com/v2/project/{}", mod_id);
-        match reqwest::get(&url).await {
+        let response = match ModrinthClient::new() {
+            Ok(client) => client.get(&url).await,
+            Err(e) => Err(e),
+        };
+        match response {
             Ok(response) => {
                 if let Ok(json) = response.json::<serde_json::Value>().await {
                     let icon = json.get("icon_url").and_then(|v| v.as_str()).map(|s| s.to_string());
@@ -194,92 +295,260 @@ pub async fn install_mod(
 
     tracing::info!("Installing version: {} ({})", version.version_number, version.id);
 
-    // Prüfe ob bereits eine Version dieser Mod installiert ist und entferne sie
-    if let Ok(mut entries) = tokio::fs::read_dir(&mods_dir).await {
-        let modinfos_dir = profile.game_dir.join("modinfos");
-        
-        while let Ok(Some(entry)) = entries.next_entry().await {
-            let path = entry.path();
-            if path.extension().and_then(|s| s.to_str()) == Some("jar") {
-                // Prüfe ob dies die gleiche Mod ist (über Metadaten)
-                let filename = path.file_name().unwrap().to_str().unwrap();
-                let meta_filename = filename.replace(".jar", ".json");
-                let meta_path = modinfos_dir.join(&meta_filename);
-                
-                if let Ok(meta_content) = tokio::fs::read_to_string(&meta_path).await {
-                    if let Ok(meta) = serde_json::from_str::<serde_json::Value>(&meta_content) {
-                        if let Some(existing_mod_id) = meta.get("mod_id").and_then(|v| v.as_str()) {
-                            if existing_mod_id == mod_id {
-                                // Gleiche Mod gefunden - lösche alte Version
-                                tracing::info!("🗑️  Removing old version: {}", filename);
-                                if let Err(e) = tokio::fs::remove_file(&path).await {
-                                    tracing::warn!("Failed to remove old mod file: {}", e);
-                                }
-                                // Lösche auch alte Metadaten
-                                let _ = tokio::fs::remove_file(&meta_path).await;
-                            }
-                        }
+    // Prüfe ob bereits eine Version dieser Mod installiert ist. Andere Versionen wandern ins
+    // Versions-Archiv des Profils (für rollback_mod), dieselbe Version wird einfach ersetzt.
+    let mut replaced_filename = None;
+    {
+        use crate::core::mods::{content_db, version_archive};
+
+        for filename in version_archive::installed_files(&profile.game_dir, &mod_id) {
+            let installed_version = content_db::get(&profile.game_dir, &filename).and_then(|r| r.version);
+
+            if installed_version.as_deref() != Some(version.version_number.as_str()) {
+                match version_archive::archive_mod(&profile.game_dir, &mod_id, &filename) {
+                    Ok(_) => {
+                        replaced_filename = Some(filename);
+                        continue;
                     }
+                    Err(e) => tracing::warn!("Could not archive old version {}: {}", filename, e),
                 }
             }
+
+            tracing::info!("🗑️  Removing old version: {}", filename);
+            if let Err(e) = tokio::fs::remove_file(mods_dir.join(&filename)).await {
+                tracing::warn!("Failed to remove old mod file: {}", e);
+            }
+            if let Err(e) = content_db::remove(&profile.game_dir, &filename) {
+                tracing::warn!("Failed to remove metadata of {}: {}", filename, e);
+            }
         }
     }
 
     manager.download_mod(version, &mods_dir)
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(crate::utils::error::download_failed)?;
 
-    // Speichere Metadaten in separatem modinfos/ Ordner
+    // Metadaten landen in der Inhalts-Datenbank des Profils
     let primary_file = version.files.iter().find(|f| f.primary)
         .or_else(|| version.files.first())
         .ok_or_else(|| "No files in version".to_string())?;
 
-    // Erstelle modinfos/ Ordner im Profil-Verzeichnis
-    let modinfos_dir = profile.game_dir.join("modinfos");
-    tokio::fs::create_dir_all(&modinfos_dir).await.map_err(|e| e.to_string())?;
+    // Identische JARs anderer Profile per Hardlink teilen
+    if crate::gui::settings::get_config().await.map(|c| c.hardlink_dedup).unwrap_or(false) {
+        let jar_path = mods_dir.join(&primary_file.filename);
+        match tokio::task::spawn_blocking(move || crate::core::fs::dedup::link_into_store(&jar_path)).await {
+            Ok(Err(e)) => tracing::warn!("Dedup failed: {}", e),
+            Err(e) => tracing::warn!("Dedup task failed: {}", e),
+            _ => {}
+        }
+    }
 
-    // Speichere Metadaten mit gleichem Dateinamen aber in modinfos/
     let jar_filename = &primary_file.filename;
-    let meta_filename = jar_filename.replace(".jar", ".json");
-    let meta_path = modinfos_dir.join(&meta_filename);
-
-    let metadata = serde_json::json!({
-        "mod_id": mod_id,
-        "mod_name": mod_name,
-        "icon_url": icon_url,
-        "version": version.version_number,
-        "source": source,
-        "filename": jar_filename,
-    });
+    let record = crate::core::mods::content_db::ContentRecord {
+        filename: jar_filename.clone(),
+        mod_id: Some(mod_id.clone()),
+        mod_name: mod_name.clone(),
+        icon_url: icon_url.clone(),
+        version: Some(version.version_number.clone()),
+        version_id: Some(version.id.clone()),
+        source: Some(source.clone()),
+        sha1: primary_file.hashes.sha1.clone(),
+        enabled: true,
+        ..Default::default()
+    };
 
-    if let Err(e) = tokio::fs::write(&meta_path, serde_json::to_string_pretty(&metadata).unwrap()).await {
-        tracing::warn!("Failed to write metadata file to {:?}: {}", meta_path, e);
+    if let Err(e) = crate::core::mods::content_db::upsert(&profile.game_dir, record) {
+        tracing::warn!("Failed to save metadata for {}: {}", jar_filename, e);
         // Nicht kritisch, fahre fort
     } else {
-        tracing::info!("✅ Saved metadata to {:?}", meta_path);
+        tracing::info!("✅ Saved metadata for {}", jar_filename);
     }
 
     tracing::info!("Mod {} installed successfully to {:?}", mod_id, mods_dir);
 
+    {
+        use crate::core::history::{self, ContentAction, ContentKind, HistoryEntry};
+        let action = if replaced_filename.is_some() { ContentAction::Update } else { ContentAction::Install };
+        history::record(&profile.game_dir, HistoryEntry {
+            previous_filename: replaced_filename,
+            ..history::with_mod_metadata(HistoryEntry::new(action, ContentKind::Mod, jar_filename.as_str()), &profile.game_dir)
+        });
+    }
+
+    let required_api = required_loader_api(&loader, &version.dependencies);
+    let game_dir = profile.game_dir.clone();
+
     profile.add_mod(mod_id.clone());
     profile_manager.save_profiles(&profiles).await.map_err(|e| e.to_string())?;
 
+    // Fabric API / QSL automatisch nachinstallieren – häufigster Crash bei neuen Nutzern
+    if let Some(api_id) = required_api {
+        if api_id != mod_id && !is_project_installed(&game_dir, api_id) {
+            tracing::info!("Mod {} requires {} – installing it automatically", mod_id, api_id);
+            if let Err(e) = Box::pin(install_mod(profile_id, api_id.to_string(), None, "modrinth".to_string())).await {
+                tracing::warn!("Could not install required API {}: {}", api_id, e);
+            }
+        }
+    }
+
     Ok(())
 }
 
+/// Liest die Abhängigkeiten aller installierten Mods und gibt fehlende Pflicht-Abhängigkeiten zurück
+#[tauri::command]
+pub async fn scan_missing_dependencies(
+    profile_id: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<crate::core::mods::dependencies::MissingDependency>, String> {
+    let profile_manager = &state.profile_manager;
+    let profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
+    let profile = profiles.get_profile(&profile_id)
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?;
+
+    let mods_dir = profile.game_dir.join("mods");
+    tokio::task::spawn_blocking(move || crate::core::mods::dependencies::find_missing_dependencies(&mods_dir))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Installiert fehlende Abhängigkeiten von Modrinth. Die Projekte stammen aus den Modrinth-
+/// Abhängigkeiten der Mods, die sie verlangen (per SHA-1 gefunden) – Mod-IDs werden nicht als
+/// Slugs erraten. Gibt die IDs zurück, die danach weiterhin fehlen.
+#[tauri::command]
+pub async fn install_missing_dependencies(
+    profile_id: String,
+    mod_ids: Vec<String>,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<String>, String> {
+    use crate::core::mods::dependencies::find_missing_dependencies;
+    use crate::types::mod_info::DependencyType;
+
+    let profiles = state.profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
+    let profile = profiles.get_profile(&profile_id)
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?;
+    let game_dir = profile.game_dir.clone();
+    let is_quilt = profile.loader.loader.as_str() == "quilt";
+    let mods_dir = game_dir.join("mods");
+
+    let dir = mods_dir.clone();
+    let missing = tokio::task::spawn_blocking(move || find_missing_dependencies(&dir))
+        .await
+        .map_err(|e| e.to_string())?;
+    let requiring: std::collections::HashSet<&String> = missing.iter()
+        .filter(|d| mod_ids.contains(&d.mod_id))
+        .flat_map(|d| &d.required_by)
+        .collect();
+
+    // Alle JARs hashen: die verlangenden liefern die Abhängigkeiten, die übrigen zeigen,
+    // welche Projekte schon (auch ohne Metadaten) installiert sind
+    let mut hashes: Vec<(String, String)> = Vec::new();
+    let mut entries = tokio::fs::read_dir(&mods_dir).await.map_err(|e| e.to_string())?;
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let filename = entry.file_name().to_string_lossy().to_string();
+        if !filename.ends_with(".jar") {
+            continue;
+        }
+        match crate::core::download::sha1_of_file(&entry.path()).await {
+            Ok(hash) => hashes.push((filename, hash)),
+            Err(e) => tracing::warn!("Could not hash {}: {}", filename, e),
+        }
+    }
+    let all_hashes: Vec<String> = hashes.iter().map(|(_, hash)| hash.clone()).collect();
+    let versions = ModrinthClient::new()
+        .map_err(|e| e.to_string())?
+        .get_versions_by_hashes(&all_hashes)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let installed: std::collections::HashSet<&String> = versions.values().map(|v| &v.mod_id).collect();
+    let projects: std::collections::BTreeSet<String> = hashes.iter()
+        .filter(|(filename, _)| requiring.contains(filename))
+        .filter_map(|(_, hash)| versions.get(hash))
+        .flat_map(|v| &v.dependencies)
+        .filter(|d| d.dependency_type == DependencyType::Required && !d.mod_id.is_empty())
+        .map(|d| if is_quilt && d.mod_id == FABRIC_API_ID { QSL_ID.to_string() } else { d.mod_id.clone() })
+        .filter(|project| !installed.contains(project) && !is_project_installed(&game_dir, project))
+        .collect();
+
+    for project in projects {
+        if let Err(e) = install_mod(profile_id.clone(), project.clone(), None, "modrinth".to_string()).await {
+            tracing::warn!("Could not install dependency project {}: {}", project, e);
+        }
+    }
+
+    // Was jetzt noch fehlt, ließ sich über Modrinth nicht zuordnen
+    let still_missing = tokio::task::spawn_blocking(move || find_missing_dependencies(&mods_dir))
+        .await
+        .map_err(|e| e.to_string())?;
+    let unresolved: Vec<String> = mod_ids.into_iter()
+        .filter(|id| still_missing.iter().any(|d| &d.mod_id == id))
+        .collect();
+    for mod_id in &unresolved {
+        tracing::warn!("Could not resolve dependency {} on Modrinth", mod_id);
+    }
+    Ok(unresolved)
+}
+
+/// Modrinth-Projekt-IDs der Loader-APIs
+pub(crate) const FABRIC_API_ID: &str = "P7dR8mSH";
+pub(crate) const QSL_ID: &str = "qvIfYCYJ";
+
+/// Gibt die Loader-API zurück, die laut Abhängigkeiten installiert sein muss.
+/// Auf Quilt wird statt Fabric API die QSL (Quilted Fabric API) verwendet.
+fn required_loader_api(loader: &str, dependencies: &[crate::types::mod_info::ModDependency]) -> Option<&'static str> {
+    use crate::types::mod_info::DependencyType;
+
+    let requires = |id: &str| dependencies.iter()
+        .any(|d| d.mod_id == id && d.dependency_type == DependencyType::Required);
+
+    match loader {
+        "fabric" if requires(FABRIC_API_ID) => Some(FABRIC_API_ID),
+        "quilt" if requires(FABRIC_API_ID) || requires(QSL_ID) => Some(QSL_ID),
+        _ => None,
+    }
+}
+
+/// Prüft ob ein Modrinth-Projekt im Profil aktiv installiert ist.
+/// Manuell hinzugefügte Loader-APIs (ohne Metadaten) werden am Dateinamen erkannt.
+pub(crate) fn is_project_installed(game_dir: &std::path::Path, project_id: &str) -> bool {
+    if installed_project_ids(game_dir).contains(project_id) {
+        return true;
+    }
+
+    let prefixes: &[&str] = match project_id {
+        FABRIC_API_ID => &["fabric-api"],
+        QSL_ID => &["qfapi", "quilted-fabric-api", "qsl"],
+        _ => return false,
+    };
+    std::fs::read_dir(game_dir.join("mods"))
+        .map(|entries| entries.flatten().any(|e| {
+            let name = e.file_name().to_string_lossy().to_lowercase();
+            name.ends_with(".jar") && prefixes.iter().any(|p| name.starts_with(p))
+        }))
+        .unwrap_or(false)
+}
+
+/// Projekt-IDs aller aktiven Mods laut Inhalts-Datenbank
+pub(crate) fn installed_project_ids(game_dir: &std::path::Path) -> std::collections::HashSet<String> {
+    crate::core::mods::content_db::installed(game_dir)
+        .into_iter()
+        .filter(|record| record.enabled)
+        .filter_map(|record| record.mod_id)
+        .collect()
+}
+
 #[tauri::command]
 pub async fn uninstall_mod(
     profile_id: String,
     mod_id: String,
     mod_filename: String,
+    state: tauri::State<'_, AppState>,
 ) -> Result<(), String> {
-    use crate::core::profiles::ProfileManager;
-
-    let profile_manager = ProfileManager::new().map_err(|e| e.to_string())?;
+    let profile_manager = &state.profile_manager;
     let mut profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
 
     let profile = profiles.get_profile_mut(&profile_id)
-        .ok_or_else(|| "Profile not found".to_string())?;
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?;
 
     let mods_dir = profile.game_dir.join("mods");
 
@@ -306,7 +575,7 @@ pub async fn search_resourcepacks(
     limit: Option<u32>,
 ) -> Result<Vec<ModInfo>, String> {
     // Modrinth API: Resource Packs haben project_type=resourcepack
-    let client = reqwest::Client::new();
+    let client = ModrinthClient::new().map_err(|e| e.to_string())?;
     let url = "https://api.modrinth.com/v2/search";
 
     let sort = match sort_by.as_deref() {
@@ -334,15 +603,13 @@ pub async fn search_resourcepacks(
     let facets_str = format!("[{}]", facets.join(","));
 
     let response = client
-        .get(url)
-        .query(&[
+        .get_query(url, &[
             ("query", query.as_str()),
             ("facets", &facets_str),
             ("index", sort),
             ("offset", &offset.unwrap_or(0).to_string()),
             ("limit", &limit.unwrap_or(20).to_string()),
         ])
-        .send()
         .await
         .map_err(|e| e.to_string())?;
 
@@ -393,6 +660,7 @@ pub async fn search_resourcepacks(
             wiki_url: None,
             discord_url: None,
             gallery: vec![],
+            also_on: vec![],
         }
     }).collect())
 }
@@ -409,7 +677,7 @@ pub async fn install_resourcepack(
     let profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
 
     let profile = profiles.get_profile(&profile_id)
-        .ok_or_else(|| "Profile not found".to_string())?;
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?;
 
     let rp_dir = profile.game_dir.join("resourcepacks");
     tokio::fs::create_dir_all(&rp_dir).await.map_err(|e| e.to_string())?;
@@ -419,11 +687,10 @@ pub async fn install_resourcepack(
     tracing::info!("Installing resource pack {} for {} to {:?}", pack_id, mc_version, rp_dir);
 
     // Hole Versionen von Modrinth
-    let client = reqwest::Client::new();
+    let client = ModrinthClient::new().map_err(|e| e.to_string())?;
     let url = format!("https://api.modrinth.com/v2/project/{}/version", pack_id);
 
     let response = client.get(&url)
-        .send()
         .await
         .map_err(|e| e.to_string())?;
 
@@ -444,6 +711,8 @@ pub async fn install_resourcepack(
         primary: bool,
         #[allow(dead_code)]
         size: u64,
+        #[serde(default)]
+        hashes: std::collections::HashMap<String, String>,
     }
 
     let versions: Vec<Version> = response.json().await.map_err(|e| e.to_string())?;
@@ -466,16 +735,25 @@ pub async fn install_resourcepack(
 
     tracing::info!("Downloading from {} to {:?}", file.url, target_path);
 
-    let response = client.get(&file.url)
-        .send()
+    crate::core::download::DownloadManager::new()
+        .map_err(|e| e.to_string())?
+        .download_cached(&file.url, &target_path, file.hashes.get("sha1").map(String::as_str))
         .await
-        .map_err(|e| e.to_string())?;
-
-    let bytes = response.bytes().await.map_err(|e| e.to_string())?;
-    tokio::fs::write(&target_path, &bytes).await.map_err(|e| e.to_string())?;
+        .map_err(crate::utils::error::download_failed)?;
 
     tracing::info!("Resource pack installed successfully to {:?}", target_path);
 
+    {
+        use crate::core::history::{self, ContentAction, ContentKind, HistoryEntry};
+        history::record(&profile.game_dir, HistoryEntry {
+            project_id: Some(pack_id.clone()),
+            version: Some(version.version_number.clone()),
+            version_id: Some(version.id.clone()),
+            source: Some("modrinth".to_string()),
+            ..HistoryEntry::new(ContentAction::Install, ContentKind::ResourcePack, file.filename.as_str())
+        });
+    }
+
     // META-INF Entfernung deaktiviert - kann Probleme mit eingebetteten Assets verursachen
     // if file.filename.ends_with(".zip") {
     //     if let Err(e) = remove_meta_inf_from_zip(&target_path).await {
@@ -497,7 +775,7 @@ pub async fn search_shaderpacks(
     offset: Option<u32>,
     limit: Option<u32>,
 ) -> Result<Vec<ModInfo>, String> {
-    let client = reqwest::Client::new();
+    let client = ModrinthClient::new().map_err(|e| e.to_string())?;
     let url = "https://api.modrinth.com/v2/search";
 
     let sort = match sort_by.as_deref() {
@@ -525,15 +803,13 @@ pub async fn search_shaderpacks(
     let facets_str = format!("[{}]", facets.join(","));
 
     let response = client
-        .get(url)
-        .query(&[
+        .get_query(url, &[
             ("query", query.as_str()),
             ("facets", &facets_str),
             ("index", sort),
             ("offset", &offset.unwrap_or(0).to_string()),
             ("limit", &limit.unwrap_or(20).to_string()),
         ])
-        .send()
         .await
         .map_err(|e| e.to_string())?;
 
@@ -584,6 +860,7 @@ pub async fn search_shaderpacks(
             wiki_url: None,
             discord_url: None,
             gallery: vec![],
+            also_on: vec![],
         }
     }).collect())
 }
@@ -600,7 +877,7 @@ pub async fn install_shaderpack(
     let profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
 
     let profile = profiles.get_profile(&profile_id)
-        .ok_or_else(|| "Profile not found".to_string())?;
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?;
 
     let shader_dir = profile.game_dir.join("shaderpacks");
     tokio::fs::create_dir_all(&shader_dir).await.map_err(|e| e.to_string())?;
@@ -609,11 +886,10 @@ pub async fn install_shaderpack(
 
     tracing::info!("Installing shader pack {} for {} to {:?}", pack_id, mc_version, shader_dir);
 
-    let client = reqwest::Client::new();
+    let client = ModrinthClient::new().map_err(|e| e.to_string())?;
     let url = format!("https://api.modrinth.com/v2/project/{}/version", pack_id);
 
     let response = client.get(&url)
-        .send()
         .await
         .map_err(|e| e.to_string())?;
 
@@ -631,6 +907,8 @@ pub async fn install_shaderpack(
         url: String,
         filename: String,
         primary: bool,
+        #[serde(default)]
+        hashes: std::collections::HashMap<String, String>,
     }
 
     let versions: Vec<Version> = response.json().await.map_err(|e| e.to_string())?;
@@ -648,16 +926,25 @@ pub async fn install_shaderpack(
 
     let target_path = shader_dir.join(&file.filename);
 
-    let response = client.get(&file.url)
-        .send()
+    crate::core::download::DownloadManager::new()
+        .map_err(|e| e.to_string())?
+        .download_cached(&file.url, &target_path, file.hashes.get("sha1").map(String::as_str))
         .await
-        .map_err(|e| e.to_string())?;
-
-    let bytes = response.bytes().await.map_err(|e| e.to_string())?;
-    tokio::fs::write(&target_path, &bytes).await.map_err(|e| e.to_string())?;
+        .map_err(crate::utils::error::download_failed)?;
 
     tracing::info!("Shader pack installed successfully to {:?}", target_path);
 
+    {
+        use crate::core::history::{self, ContentAction, ContentKind, HistoryEntry};
+        history::record(&profile.game_dir, HistoryEntry {
+            project_id: Some(pack_id.clone()),
+            version: Some(version.version_number.clone()),
+            version_id: Some(version.id.clone()),
+            source: Some("modrinth".to_string()),
+            ..HistoryEntry::new(ContentAction::Install, ContentKind::ShaderPack, file.filename.as_str())
+        });
+    }
+
     // META-INF Entfernung deaktiviert - kann Probleme mit eingebetteten Assets verursachen
     // if file.filename.ends_with(".zip") {
     //     if let Err(e) = remove_meta_inf_from_zip(&target_path).await {
@@ -673,22 +960,16 @@ pub async fn install_shaderpack(
 /// Installiert ein Modrinth Modpack (.mrpack Format):
 /// 1. Holt Projekt-Icon + Versionen von Modrinth
 /// 2. Lädt .mrpack herunter
-/// 3. Liest modrinth.index.json
-/// 4. Erstellt neues Profil mit Modpack-Icon
-/// 5. Lädt alle Dateien aus dem Manifest herunter (mods, configs, ...)
-/// 6. Kopiert ALLE Overrides (overrides/ + client-overrides/ + server-overrides/)
-///    inkl. Unterordner wie config/, saves/, resourcepacks/, options.txt usw.
+/// 3. Liest modrinth.index.json und installiert das Pack (siehe `install_pack`)
 #[tauri::command]
 pub async fn install_modpack(
     pack_id: String,
     pack_name: String,
     version_id: Option<String>,
 ) -> Result<serde_json::Value, String> {
-    use std::io::Read;
     use base64::Engine as _;
     use crate::core::profiles::ProfileManager;
-    use crate::types::profile::Profile;
-    use crate::types::version::ModLoader;
+    use crate::core::mods::modpack_state::ModpackInstallState;
 
     tracing::info!("🎮 Installing modpack: {} ({})", pack_name, pack_id);
 
@@ -703,8 +984,9 @@ pub async fn install_modpack(
         icon_url: Option<String>,
     }
 
+    let modrinth = ModrinthClient::new().map_err(|e| e.to_string())?;
     let project_url = format!("https://api.modrinth.com/v2/project/{}", pack_id);
-    let icon_data_url: Option<String> = match client.get(&project_url).send().await {
+    let icon_data_url: Option<String> = match modrinth.get(&project_url).await {
         Ok(resp) => {
             match resp.json::<ProjectInfo>().await {
                 Ok(info) => {
@@ -763,7 +1045,7 @@ pub async fn install_modpack(
     }
 
     let versions_url = format!("https://api.modrinth.com/v2/project/{}/version", pack_id);
-    let versions_resp = client.get(&versions_url).send().await.map_err(|e| e.to_string())?;
+    let versions_resp = modrinth.get(&versions_url).await.map_err(|e| e.to_string())?;
     let versions: Vec<MrpackVersion> = versions_resp.json().await.map_err(|e| e.to_string())?;
 
     let version = if let Some(vid) = version_id {
@@ -777,20 +1059,65 @@ pub async fn install_modpack(
         .or_else(|| version.files.first())
         .ok_or_else(|| "Keine .mrpack Datei in dieser Version gefunden".to_string())?;
 
-    // ── 2. .mrpack herunterladen in temp-Datei ──────────────────────────────
-    let temp_dir = std::env::temp_dir().join(format!("lion_modpack_{}", uuid::Uuid::new_v4()));
-    tokio::fs::create_dir_all(&temp_dir).await.map_err(|e| e.to_string())?;
-    let mrpack_path = temp_dir.join(&mrpack_file.filename);
+    // ── 2. .mrpack herunterladen in den Arbeitsordner der Installation ──────
+    // Der Ordner bleibt bei einem Abbruch erhalten, damit die Installation fortgesetzt werden kann.
+    let work_dir = ModpackInstallState::work_dir(&pack_id, &version.id);
+    tokio::fs::create_dir_all(&work_dir).await.map_err(|e| e.to_string())?;
+    let mrpack_path = work_dir.join(&mrpack_file.filename);
 
-    tracing::info!("📥 Downloading mrpack from: {}", mrpack_file.url);
+    // Zustand einer unterbrochenen Installation nur verwenden, wenn das Profil noch existiert
+    let profile_manager = ProfileManager::new().map_err(|e| e.to_string())?;
+    let existing_profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
+    let resume_state = ModpackInstallState::load(&pack_id, &version.id)
+        .filter(|state| existing_profiles.get_profile(&state.profile_id).is_some());
+
+    if resume_state.is_some() && crate::utils::compression::is_valid_zip(&mrpack_path) {
+        tracing::info!("♻️ Resuming modpack install, reusing {:?}", mrpack_path);
+    } else {
+        tracing::info!("📥 Downloading mrpack from: {}", mrpack_file.url);
+
+        let resp = client.get(&mrpack_file.url).send().await.map_err(|e| e.to_string())?;
+        let bytes = resp.bytes().await.map_err(|e| e.to_string())?;
+        tokio::fs::write(&mrpack_path, &bytes).await.map_err(|e| e.to_string())?;
 
-    let resp = client.get(&mrpack_file.url).send().await.map_err(|e| e.to_string())?;
-    let bytes = resp.bytes().await.map_err(|e| e.to_string())?;
-    tokio::fs::write(&mrpack_path, &bytes).await.map_err(|e| e.to_string())?;
+        tracing::info!("✅ mrpack downloaded: {} bytes", bytes.len());
+    }
+
+    install_mrpack_archive(&mrpack_path, &pack_id, &version.id, &pack_name, icon_data_url).await
+}
 
-    tracing::info!("✅ mrpack downloaded: {} bytes", bytes.len());
+/// Präfix der Pack-ID für Modpacks aus lokalen Dateien (danach folgt der Datei-Hash)
+const LOCAL_PACK_PREFIX: &str = "file-";
+const LOCAL_PACK_VERSION: &str = "local";
+
+/// Eine Datei aus dem Modpack-Manifest: Pfad im Spielordner, Download-URL, SHA-1
+struct PackFile {
+    path: String,
+    url: String,
+    sha1: Option<String>,
+}
+
+/// Eckdaten eines Modpacks für das neue Profil
+struct PackProfile {
+    name: String,
+    minecraft_version: String,
+    loader: crate::types::version::ModLoader,
+    loader_version: String,
+    icon_data_url: Option<String>,
+    recommended_memory: Option<u32>,
+}
+
+/// Liest modrinth.index.json eines .mrpack und installiert das Pack
+async fn install_mrpack_archive(
+    mrpack_path: &std::path::Path,
+    pack_id: &str,
+    version_id: &str,
+    pack_name: &str,
+    icon_data_url: Option<String>,
+) -> Result<serde_json::Value, String> {
+    use std::io::Read;
+    use crate::types::version::ModLoader;
 
-    // ── 3. modrinth.index.json lesen ────────────────────────────────────────
     #[derive(serde::Deserialize)]
     struct IndexFile {
         path: String,
@@ -820,7 +1147,7 @@ pub async fn install_modpack(
         dependencies: std::collections::HashMap<String, String>,
     }
 
-    let zip_file = std::fs::File::open(&mrpack_path).map_err(|e| e.to_string())?;
+    let zip_file = std::fs::File::open(mrpack_path).map_err(|e| e.to_string())?;
     let mut archive = zip::ZipArchive::new(zip_file).map_err(|e| e.to_string())?;
 
     let index_json = {
@@ -849,90 +1176,427 @@ pub async fn install_modpack(
         (ModLoader::Vanilla, String::new())
     };
 
-    tracing::info!("Modpack: {} – MC {} {:?} {}", pack_name, mc_version, loader, loader_version);
+    let files = index.files.into_iter()
+        .filter_map(|file| Some(PackFile {
+            url: file.downloads.into_iter().next()?,
+            path: file.path,
+            sha1: file.hashes.sha1,
+        }))
+        .collect();
+
+    let pack = PackProfile {
+        name: pack_name.to_string(),
+        minecraft_version: mc_version,
+        loader,
+        loader_version,
+        icon_data_url,
+        // Das .mrpack-Format kennt keine RAM-Empfehlung
+        recommended_memory: None,
+    };
+    install_pack(mrpack_path, pack_id, version_id, pack, files, &["overrides/", "client-overrides/", "server-overrides/"]).await
+}
 
-    // ── 4. Profil erstellen (mit Modpack-Icon) ──────────────────────────────
-    let mut profile = Profile::new(pack_name.clone(), mc_version.clone(), loader, loader_version);
+/// Liest das manifest.json eines CurseForge-Modpacks, löst die Dateien über die
+/// CurseForge-API auf und installiert das Pack
+async fn install_curseforge_archive(
+    zip_path: &std::path::Path,
+    pack_id: &str,
+    version_id: &str,
+    pack_name: &str,
+) -> Result<serde_json::Value, String> {
+    use std::io::Read;
+    use crate::types::version::ModLoader;
 
-    // Modpack-Icon als Profil-Icon setzen (als data-URL in icon_path)
-    if let Some(ref data_url) = icon_data_url {
-        profile.icon_path = Some(std::path::PathBuf::from(data_url.clone()));
-        tracing::info!("✅ Modpack icon set as profile icon");
+    #[derive(serde::Deserialize)]
+    struct ManifestLoader {
+        id: String,
+        #[serde(default)]
+        primary: bool,
     }
 
-    let profile_dir = profile.game_dir.clone();
-    let profile_id = profile.id.clone();
+    #[derive(serde::Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct ManifestMinecraft {
+        version: String,
+        #[serde(default)]
+        mod_loaders: Vec<ManifestLoader>,
+        /// Vom Pack empfohlener RAM in MB
+        recommended_ram: Option<u64>,
+    }
+
+    #[derive(serde::Deserialize)]
+    struct ManifestFile {
+        #[serde(rename = "projectID")]
+        project_id: i32,
+        #[serde(rename = "fileID")]
+        file_id: i32,
+        #[serde(default = "manifest_required_default")]
+        required: bool,
+    }
+
+    fn manifest_required_default() -> bool {
+        true
+    }
+
+    #[derive(serde::Deserialize)]
+    struct Manifest {
+        minecraft: ManifestMinecraft,
+        name: Option<String>,
+        #[serde(default)]
+        files: Vec<ManifestFile>,
+        overrides: Option<String>,
+    }
+
+    let manifest: Manifest = {
+        let zip_file = std::fs::File::open(zip_path).map_err(|e| e.to_string())?;
+        let mut archive = zip::ZipArchive::new(zip_file).map_err(|e| e.to_string())?;
+        let mut entry = archive.by_name("manifest.json")
+            .map_err(|_| "manifest.json nicht im Modpack gefunden".to_string())?;
+        let mut content = String::new();
+        entry.read_to_string(&mut content).map_err(|e| e.to_string())?;
+        serde_json::from_str(&content).map_err(|e| format!("Ungültiges manifest.json: {}", e))?
+    };
 
+    let loader_id = manifest.minecraft.mod_loaders.iter().find(|l| l.primary)
+        .or_else(|| manifest.minecraft.mod_loaders.first())
+        .map(|l| l.id.as_str());
+    let (loader, loader_version) = match loader_id.and_then(|id| id.split_once('-')) {
+        Some(("forge", v)) => (ModLoader::Forge, v.to_string()),
+        Some(("neoforge", v)) => (ModLoader::NeoForge, v.to_string()),
+        Some(("fabric", v)) => (ModLoader::Fabric, v.to_string()),
+        Some(("quilt", v)) => (ModLoader::Quilt, v.to_string()),
+        Some((other, _)) => return Err(format!("Unbekannter Mod-Loader im Modpack: {}", other)),
+        None => (ModLoader::Vanilla, String::new()),
+    };
+
+    let client = super::curseforge_client().await
+        .ok_or_else(|| "Für CurseForge-Modpacks wird ein CurseForge-API-Schlüssel benötigt".to_string())?;
+    let required: Vec<&ManifestFile> = manifest.files.iter().filter(|f| f.required).collect();
+    let file_ids: Vec<i32> = required.iter().map(|f| f.file_id).collect();
+    let project_ids: Vec<i32> = required.iter().map(|f| f.project_id).collect();
+
+    let cf_files: std::collections::HashMap<i32, crate::api::curseforge::CurseForgeFile> = client.get_files(&file_ids)
+        .await
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|f| (f.id, f))
+        .collect();
+    let classes = client.get_mod_classes(&project_ids).await.unwrap_or_else(|e| {
+        tracing::warn!("Could not fetch CurseForge project classes: {}", e);
+        Default::default()
+    });
+
+    let missing: Vec<String> = required.iter()
+        .filter(|f| !cf_files.contains_key(&f.file_id))
+        .map(|f| format!("{}/{}", f.project_id, f.file_id))
+        .collect();
+    if !missing.is_empty() {
+        return Err(format!("{} Dateien sind bei CurseForge nicht mehr verfügbar ({})", missing.len(), missing.join(", ")));
+    }
+
+    let files = required.iter()
+        .filter_map(|f| {
+            let file = cf_files.get(&f.file_id)?;
+            let folder = match classes.get(&f.project_id) {
+                Some(12) => "resourcepacks",
+                Some(6552) => "shaderpacks",
+                _ => "mods",
+            };
+            Some(PackFile {
+                path: format!("{}/{}", folder, file.file_name),
+                url: file.resolved_download_url(),
+                sha1: file.sha1().map(str::to_string),
+            })
+        })
+        .collect();
+
+    let overrides = format!("{}/", manifest.overrides.as_deref().unwrap_or("overrides").trim_end_matches('/'));
+    let pack = PackProfile {
+        name: manifest.name.filter(|n| !n.trim().is_empty()).unwrap_or_else(|| pack_name.to_string()),
+        minecraft_version: manifest.minecraft.version,
+        loader,
+        loader_version,
+        icon_data_url: None,
+        recommended_memory: manifest.minecraft.recommended_ram
+            .filter(|ram| *ram >= 512)
+            .map(|ram| ram.min(u32::MAX as u64) as u32),
+    };
+    install_pack(zip_path, pack_id, version_id, pack, files, &[overrides.as_str()]).await
+}
+
+/// Gemeinsamer Teil der Modpack-Installation:
+/// 1. Erstellt das Profil bzw. verwendet es beim Fortsetzen wieder
+/// 2. Lädt alle Dateien des Manifests herunter (mods, configs, ...)
+/// 3. Kopiert die Overrides inkl. Unterordner wie config/, saves/, resourcepacks/, options.txt
+async fn install_pack(
+    archive_path: &std::path::Path,
+    pack_id: &str,
+    version_id: &str,
+    pack: PackProfile,
+    files: Vec<PackFile>,
+    override_prefixes: &[&str],
+) -> Result<serde_json::Value, String> {
+    use crate::core::profiles::ProfileManager;
+    use crate::types::profile::Profile;
+    use crate::core::mods::modpack_state::ModpackInstallState;
+
+    tracing::info!("Modpack: {} – MC {} {:?} {}", pack.name, pack.minecraft_version, pack.loader, pack.loader_version);
+
+    // Zustand einer unterbrochenen Installation nur verwenden, wenn das Profil noch existiert
     let profile_manager = ProfileManager::new().map_err(|e| e.to_string())?;
-    profile_manager.create_profile(profile).await.map_err(|e| e.to_string())?;
+    let existing_profiles = profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
+    let resume_state = ModpackInstallState::load(pack_id, version_id)
+        .filter(|state| existing_profiles.get_profile(&state.profile_id).is_some());
+
+    // ── 1. Profil erstellen (mit Modpack-Icon) bzw. beim Fortsetzen wiederverwenden ──
+    let mut install_state = match resume_state {
+        Some(state) => {
+            tracing::info!(
+                "♻️ Resuming into profile {} ({}/{} files done)",
+                state.profile_id, state.completed_files.len(), state.total_files
+            );
+            state
+        }
+        None => {
+            let mut profile = Profile::new(pack.name.clone(), pack.minecraft_version.clone(), pack.loader, pack.loader_version.clone());
+
+            // Modpack-Icon als Profil-Icon setzen (als data-URL in icon_path)
+            if let Some(ref data_url) = pack.icon_data_url {
+                profile.icon_path = Some(std::path::PathBuf::from(data_url.clone()));
+                tracing::info!("✅ Modpack icon set as profile icon");
+            }
 
-    // ── 5. Manifest-Dateien herunterladen (Mods + alle anderen Pfade) ────────
+            if let Some(recommended) = pack.recommended_memory {
+                let memory = bounded_memory_mb(recommended);
+                tracing::info!("Modpack recommends {} MB RAM, using {} MB", recommended, memory);
+                profile.memory_mb = Some(memory);
+                profile.recommended_memory_mb = Some(recommended);
+            }
+
+            let state = ModpackInstallState::new(pack_id, version_id, &pack.name, &profile.id, files.len());
+            profile_manager.create_profile(profile).await.map_err(|e| e.to_string())?;
+            state
+        }
+    };
+    install_state.save().map_err(|e| e.to_string())?;
+
+    let profile_id = install_state.profile_id.clone();
+    let profile_dir = profile_manager.load_profiles().await.map_err(|e| e.to_string())?
+        .get_profile(&profile_id)
+        .map(|p| p.game_dir.clone())
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?;
+
+    // ── 2. Manifest-Dateien herunterladen (Mods + alle anderen Pfade) ────────
     // Das Manifest kann nicht nur mods/ enthalten, sondern auch config/, saves/, etc.
     let mods_dir = profile_dir.join("mods");
     tokio::fs::create_dir_all(&mods_dir).await.map_err(|e| e.to_string())?;
 
-    let total = index.files.len();
+    let total = files.len();
     tracing::info!("📦 Downloading {} manifest files...", total);
+    let download_manager = crate::core::download::DownloadManager::new().map_err(|e| e.to_string())?;
+    let mut failed: Vec<String> = Vec::new();
+
+    for (i, file) in files.iter().enumerate() {
+        // Normalisiere Pfad (Windows-Backslashes → Forward Slashes)
+        let normalized_path = file.path.replace('\\', "/");
+
+        // Ziel: immer relativ zum profile_dir (game directory), nie außerhalb
+        let relative = std::path::Path::new(&normalized_path);
+        if !relative.components().all(|c| matches!(c, std::path::Component::Normal(_))) {
+            tracing::warn!("Skipping manifest file outside the game directory: {}", normalized_path);
+            failed.push(normalized_path);
+            continue;
+        }
+        let target_path = profile_dir.join(relative);
 
-    for (i, file) in index.files.iter().enumerate() {
-        if let Some(download_url) = file.downloads.first() {
-            // Normalisiere Pfad (Windows-Backslashes → Forward Slashes)
-            let normalized_path = file.path.replace('\\', "/");
+        // Stelle sicher dass alle Parent-Ordner existieren
+        if let Some(parent) = target_path.parent() {
+            if let Err(e) = std::fs::create_dir_all(parent) {
+                tracing::warn!("Could not create dir {:?}: {}", parent, e);
+            }
+        }
 
-            // Ziel: immer relativ zum profile_dir (game directory)
-            let target_path = profile_dir.join(&normalized_path);
+        if install_state.is_file_complete(&normalized_path, &target_path, file.sha1.as_deref()) {
+            tracing::debug!("[{}/{}] Already installed: {}", i + 1, total, normalized_path);
+            install_state.completed_files.insert(normalized_path);
+            continue;
+        }
 
-            // Stelle sicher dass alle Parent-Ordner existieren
-            if let Some(parent) = target_path.parent() {
-                if let Err(e) = std::fs::create_dir_all(parent) {
-                    tracing::warn!("Could not create dir {:?}: {}", parent, e);
-                }
-            }
+        tracing::info!("[{}/{}] Downloading: {}", i + 1, total, normalized_path);
 
-            tracing::info!("[{}/{}] Downloading: {}", i + 1, total, normalized_path);
-
-            let resp = client.get(download_url).send().await;
-            match resp {
-                Ok(r) => {
-                    match r.bytes().await {
-                        Ok(file_bytes) => {
-                            if let Err(e) = tokio::fs::write(&target_path, &file_bytes).await {
-                                tracing::warn!("Failed to write {}: {}", normalized_path, e);
-                            } else if let Some(expected_sha1) = &file.hashes.sha1 {
-                                use sha1::Digest;
-                                let hash = sha1::Sha1::digest(&file_bytes);
-                                let actual = hex::encode(hash);
-                                if &actual != expected_sha1 {
-                                    tracing::warn!("⚠️ SHA1 mismatch for {}", normalized_path);
-                                }
-                            }
-                        }
-                        Err(e) => tracing::warn!("Failed to read bytes for {}: {}", normalized_path, e),
-                    }
+        // Über den Content-Cache: Mods die schon in einem anderen Profil liegen werden kopiert
+        match download_manager.download_cached(&file.url, &target_path, file.sha1.as_deref()).await {
+            Ok(_) => {
+                install_state.completed_files.insert(normalized_path);
+                if install_state.completed_files.len() % 10 == 0 {
+                    install_state.save().ok();
                 }
-                Err(e) => tracing::warn!("Failed to download {}: {}", normalized_path, e),
+            }
+            Err(e) => {
+                tracing::warn!("Failed to download {}: {}", normalized_path, e);
+                failed.push(normalized_path);
             }
         }
     }
+    install_state.save().map_err(|e| e.to_string())?;
+
+    // Zustand bleibt erhalten, damit `resume_modpack_install` die fehlenden Dateien nachholt
+    if !failed.is_empty() {
+        return Err(format!(
+            "{} von {} Dateien konnten nicht heruntergeladen werden ({}). Die Installation kann fortgesetzt werden.",
+            failed.len(),
+            total,
+            failed.join(", ")
+        ));
+    }
 
-    // ── 6. Overrides kopieren (ALLE Typen + ALLE Unterordner) ───────────────
-    // overrides/          → alles (config/, mods/, options.txt, ...)
-    // client-overrides/   → client-seitige Dateien
-    // server-overrides/   → serverseitige Dateien (auch relevant für configs)
-    //
+    // ── 3. Overrides kopieren (ALLE Typen + ALLE Unterordner) ───────────────
     // Alle Pfad-Komponenten bleiben erhalten:
     //   overrides/config/sodium/sodium-options.json → profile_dir/config/sodium/sodium-options.json
     //   overrides/options.txt                       → profile_dir/options.txt
     //   overrides/resourcepacks/MyPack.zip          → profile_dir/resourcepacks/MyPack.zip
-    let zip_file2 = std::fs::File::open(&mrpack_path).map_err(|e| e.to_string())?;
-    let mut archive2 = zip::ZipArchive::new(zip_file2).map_err(|e| e.to_string())?;
+    let overrides_copied = if install_state.overrides_done {
+        0
+    } else {
+        let (archive_path, profile_dir) = (archive_path.to_path_buf(), profile_dir.clone());
+        let prefixes: Vec<String> = override_prefixes.iter().map(|p| p.to_string()).collect();
+        let copied = crate::utils::compression::run_blocking(move || {
+            extract_pack_overrides(&archive_path, &profile_dir, &prefixes)
+        })
+        .await
+        .map_err(|e| e.to_string())?;
+        install_state.overrides_done = true;
+        install_state.save().map_err(|e| e.to_string())?;
+        copied
+    };
+
+    tracing::info!("✅ Overrides kopiert: {} Dateien", overrides_copied);
+
+    // ── 4. Arbeitsordner und Installationszustand aufräumen ─────────────────
+    ModpackInstallState::remove(pack_id, version_id);
+
+    tracing::info!("🎉 Modpack '{}' erfolgreich installiert! Profil-ID: {}", pack.name, profile_id);
+
+    Ok(serde_json::json!({
+        "success": true,
+        "profile_id": profile_id,
+        "profile_name": pack.name,
+        "minecraft_version": pack.minecraft_version,
+        "mods_downloaded": total,
+        "overrides_copied": overrides_copied,
+        "has_icon": pack.icon_data_url.is_some(),
+        "recommended_memory_mb": pack.recommended_memory,
+    }))
+}
+
+/// Installiert ein .mrpack oder CurseForge-ZIP aus dem Arbeitsordner je nach Inhalt
+async fn install_modpack_archive(
+    archive_path: &std::path::Path,
+    pack_id: &str,
+    version_id: &str,
+    pack_name: &str,
+) -> Result<serde_json::Value, String> {
+    let names: Vec<String> = {
+        let zip_file = std::fs::File::open(archive_path).map_err(|e| e.to_string())?;
+        let archive = zip::ZipArchive::new(zip_file).map_err(|_| "Datei ist kein gültiges ZIP-Archiv".to_string())?;
+        archive.file_names().map(str::to_string).collect()
+    };
+
+    if names.iter().any(|n| n == "modrinth.index.json") {
+        install_mrpack_archive(archive_path, pack_id, version_id, pack_name, None).await
+    } else if names.iter().any(|n| n == "manifest.json") {
+        install_curseforge_archive(archive_path, pack_id, version_id, pack_name).await
+    } else {
+        Err("Kein Modpack (weder modrinth.index.json noch manifest.json gefunden)".to_string())
+    }
+}
+
+/// Installiert ein Modpack aus einer lokalen Datei (.mrpack oder CurseForge-ZIP) als neues Profil.
+/// Die Datei wird in den Arbeitsordner kopiert, damit die Installation fortgesetzt werden kann.
+#[tauri::command]
+pub async fn install_modpack_file(path: String) -> Result<serde_json::Value, String> {
+    use crate::core::mods::modpack_state::ModpackInstallState;
+
+    let source = std::path::PathBuf::from(&path);
+    let file_name = source.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .ok_or_else(|| "Ungültiger Dateipfad".to_string())?;
+    let pack_name = source.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_else(|| file_name.clone());
+
+    let hash = crate::core::download::sha1_of_file(&source).await.map_err(|e| e.to_string())?;
+    let pack_id = format!("{}{}", LOCAL_PACK_PREFIX, &hash[..16]);
+
+    let work_dir = ModpackInstallState::work_dir(&pack_id, LOCAL_PACK_VERSION);
+    tokio::fs::create_dir_all(&work_dir).await.map_err(|e| e.to_string())?;
+    let archive_path = work_dir.join(&file_name);
+    if !archive_path.is_file() {
+        tokio::fs::copy(&source, &archive_path).await.map_err(|e| e.to_string())?;
+    }
+
+    tracing::info!("🎮 Installing modpack from file: {:?}", source);
+    install_modpack_archive(&archive_path, &pack_id, LOCAL_PACK_VERSION, &pack_name).await
+}
+
+/// Begrenzt die Empfehlung auf den System-RAM; 2 GB bleiben für das System frei
+fn bounded_memory_mb(recommended: u32) -> u32 {
+    let mut sys = sysinfo::System::new();
+    sys.refresh_memory();
+    let total_mb = sys.total_memory() / 1024 / 1024;
+    if total_mb == 0 {
+        return recommended;
+    }
+    let limit = total_mb.saturating_sub(2048).max(1024);
+    (recommended as u64).min(limit) as u32
+}
+
+/// Unterbrochene Modpack-Installationen (können mit `resume_modpack_install` fortgesetzt werden)
+#[tauri::command]
+pub async fn get_pending_modpack_installs() -> Result<Vec<crate::core::mods::modpack_state::ModpackInstallState>, String> {
+    Ok(crate::core::mods::modpack_state::pending_installs())
+}
+
+/// Setzt eine unterbrochene Installation fort; bereits geprüfte Dateien werden übersprungen
+#[tauri::command]
+pub async fn resume_modpack_install(pack_id: String, version_id: String) -> Result<serde_json::Value, String> {
+    use crate::core::mods::modpack_state::ModpackInstallState;
+
+    let state = ModpackInstallState::load(&pack_id, &version_id)
+        .ok_or_else(|| "Keine unterbrochene Installation für dieses Modpack gefunden".to_string())?;
+    if !pack_id.starts_with(LOCAL_PACK_PREFIX) {
+        return install_modpack(pack_id, state.pack_name, Some(version_id)).await;
+    }
+
+    // Modpack aus lokaler Datei: das Archiv liegt im Arbeitsordner
+    let archive_path = std::fs::read_dir(ModpackInstallState::work_dir(&pack_id, &version_id))
+        .map_err(|e| e.to_string())?
+        .flatten()
+        .map(|e| e.path())
+        .find(|p| p.extension().is_some_and(|ext| ext == "mrpack" || ext == "zip"))
+        .ok_or_else(|| "Modpack-Datei der Installation nicht mehr vorhanden".to_string())?;
+    install_modpack_archive(&archive_path, &pack_id, &version_id, &state.pack_name).await
+}
+
+/// Verwirft den Zustand einer unterbrochenen Installation (das Profil bleibt erhalten)
+#[tauri::command]
+pub async fn discard_modpack_install(pack_id: String, version_id: String) -> Result<(), String> {
+    crate::core::mods::modpack_state::ModpackInstallState::remove(&pack_id, &version_id);
+    Ok(())
+}
+
+/// Kopiert die Overrides eines Modpacks ins Profil (blockierend, läuft via `run_blocking`).
+/// Der Fortschritt wird als "extraction-progress" an die GUI gemeldet.
+fn extract_pack_overrides(mrpack_path: &std::path::Path, profile_dir: &std::path::Path, override_prefixes: &[String]) -> anyhow::Result<usize> {
+    use std::io::Read;
+
+    let zip_file = std::fs::File::open(mrpack_path)?;
+    let mut archive = zip::ZipArchive::new(zip_file)?;
 
-    // Alle drei Override-Typen unterstützen
-    let override_prefixes: &[&str] = &["overrides/", "client-overrides/", "server-overrides/"];
     let mut overrides_copied = 0;
 
-    for i in 0..archive2.len() {
-        let mut entry = archive2.by_index(i).map_err(|e| e.to_string())?;
+    let mut tracker = crate::utils::compression::ExtractionTracker::for_archive(mrpack_path, &mut archive);
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i)?;
+        tracker.advance(entry.size());
         let raw_name = entry.name().to_string();
 
         // Normalisiere Backslashes
@@ -944,7 +1608,7 @@ pub async fn install_modpack(
         }
 
         // Suche passenden Override-Prefix
-        let matched_prefix = override_prefixes.iter().find(|&&prefix| entry_name.starts_with(prefix));
+        let matched_prefix = override_prefixes.iter().find(|prefix| entry_name.starts_with(prefix.as_str()));
 
         if let Some(prefix) = matched_prefix {
             // Relative Pfadkomponente nach dem Prefix
@@ -971,7 +1635,8 @@ pub async fn install_modpack(
                 continue;
             }
 
-            match std::fs::write(&target, &content) {
+            // Bestehende Dateien können per Hardlink mit anderen Profilen geteilt sein
+            match crate::core::fs::dedup::write_unshared(&target, &content) {
                 Ok(_) => {
                     tracing::debug!("Override: {} → {:?}", rel, target);
                     overrides_copied += 1;
@@ -980,23 +1645,9 @@ pub async fn install_modpack(
             }
         }
     }
+    tracker.finish();
 
-    tracing::info!("✅ Overrides kopiert: {} Dateien", overrides_copied);
-
-    // ── 7. Temp-Ordner aufräumen ────────────────────────────────────────────
-    tokio::fs::remove_dir_all(&temp_dir).await.ok();
-
-    tracing::info!("🎉 Modpack '{}' erfolgreich installiert! Profil-ID: {}", pack_name, profile_id);
-
-    Ok(serde_json::json!({
-        "success": true,
-        "profile_id": profile_id,
-        "profile_name": pack_name,
-        "minecraft_version": mc_version,
-        "mods_downloaded": total,
-        "overrides_copied": overrides_copied,
-        "has_icon": icon_data_url.is_some(),
-    }))
+    Ok(overrides_copied)
 }
 
 #[tauri::command]
@@ -1009,7 +1660,7 @@ pub async fn search_modpacks(
     offset: Option<u32>,
     limit: Option<u32>,
 ) -> Result<Vec<ModInfo>, String> {
-    let client = reqwest::Client::new();
+    let client = ModrinthClient::new().map_err(|e| e.to_string())?;
     let url = "https://api.modrinth.com/v2/search";
 
     let sort = match sort_by.as_deref() {
@@ -1041,15 +1692,13 @@ pub async fn search_modpacks(
     let facets_str = format!("[{}]", facets.join(","));
 
     let response = client
-        .get(url)
-        .query(&[
+        .get_query(url, &[
             ("query", query.as_str()),
             ("facets", &facets_str),
             ("index", sort),
             ("offset", &offset.unwrap_or(0).to_string()),
             ("limit", &limit.unwrap_or(20).to_string()),
         ])
-        .send()
         .await
         .map_err(|e| e.to_string())?;
 
@@ -1100,6 +1749,7 @@ pub async fn search_modpacks(
             wiki_url: None,
             discord_url: None,
             gallery: vec![],
+            also_on: vec![],
         }
     }).collect())
 }
@@ -1,23 +1,30 @@
+use crate::config::schema::{ConfigFileSync, FileConflictStrategy, SyncSettings, SyncStrategy};
 use crate::core::profiles::ProfileManager;
-use crate::types::profile::{Profile, ProfileList};
+use crate::gui::state::AppState;
+use crate::types::profile::{Profile, ProfileList, SyncMode};
 use crate::types::version::ModLoader;
 use std::time::SystemTime;
 use std::collections::HashMap;
 
+/// Alle Profile; archivierte nur mit `include_archived`
 #[tauri::command]
-pub async fn get_profiles() -> Result<ProfileList, String> {
-    let manager = ProfileManager::new().map_err(|e| e.to_string())?;
-    manager.load_profiles().await.map_err(|e| e.to_string())
+pub async fn get_profiles(state: tauri::State<'_, AppState>, include_archived: Option<bool>) -> Result<ProfileList, String> {
+    let mut profiles = state.profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
+    if !include_archived.unwrap_or(false) {
+        profiles.profiles.retain(|p| !p.archived);
+    }
+    Ok(profiles)
 }
 
 #[tauri::command]
 pub async fn create_profile(
+    state: tauri::State<'_, AppState>,
     name: String,
     minecraft_version: String,
     loader: String,
     loader_version: String,
 ) -> Result<ProfileList, String> {
-    let manager = ProfileManager::new().map_err(|e| e.to_string())?;
+    let manager = &state.profile_manager;
 
     let mod_loader = match loader.as_str() {
         "vanilla" => ModLoader::Vanilla,
@@ -33,18 +40,111 @@ pub async fn create_profile(
 }
 
 #[tauri::command]
-pub async fn delete_profile(profile_id: String) -> Result<ProfileList, String> {
-    let manager = ProfileManager::new().map_err(|e| e.to_string())?;
-    manager.delete_profile(&profile_id).await.map_err(|e| e.to_string())
+pub async fn delete_profile(state: tauri::State<'_, AppState>, profile_id: String) -> Result<ProfileList, String> {
+    state.profile_manager.delete_profile(&profile_id).await.map_err(|e| e.to_string())
+}
+
+/// Archiviert ein Profil (ausgeblendet, keine Updates/Sync); `compress` packt den Spielordner als ZIP
+#[tauri::command]
+pub async fn archive_profile(
+    state: tauri::State<'_, AppState>,
+    profile_id: String,
+    compress: Option<bool>,
+) -> Result<ProfileList, String> {
+    if crate::core::minecraft::get_running_profile_ids().contains(&profile_id) {
+        return Err("Ein laufendes Profil kann nicht archiviert werden".to_string());
+    }
+    state.profile_manager
+        .archive_profile(&profile_id, compress.unwrap_or(false))
+        .await
+        .map_err(crate::utils::error::command_error)
+}
+
+#[tauri::command]
+pub async fn unarchive_profile(state: tauri::State<'_, AppState>, profile_id: String) -> Result<ProfileList, String> {
+    state.profile_manager.unarchive_profile(&profile_id).await.map_err(crate::utils::error::command_error)
+}
+
+/// Verschiebt den Spielordner eines Profils (Kopieren + Prüfen bei anderem Laufwerk)
+#[tauri::command]
+pub async fn move_profile_dir(
+    state: tauri::State<'_, AppState>,
+    profile_id: String,
+    target_dir: String,
+) -> Result<crate::core::fs::relocate::MoveReport, String> {
+    if crate::core::minecraft::get_running_profile_ids().contains(&profile_id) {
+        return Err("Der Spielordner kann nicht verschoben werden, während das Spiel läuft".to_string());
+    }
+    state.profile_manager
+        .move_game_dir(&profile_id, std::path::PathBuf::from(target_dir))
+        .await
+        .map_err(crate::utils::error::command_error)
+}
+
+/// Setzt ein Banner- oder Hintergrundbild (`kind` = "banner" | "background").
+/// `image` ist eine Data-URL oder ein lokaler Pfad (PNG, JPEG, WebP oder GIF). Gibt den gespeicherten Pfad zurück.
+#[tauri::command]
+pub async fn set_profile_artwork(profile_id: String, kind: String, image: String, state: tauri::State<'_, AppState>) -> Result<String, String> {
+    use crate::core::artwork::ArtworkKind;
+    use base64::{Engine as _, engine::general_purpose};
+
+    let artwork_kind = ArtworkKind::parse(&kind).ok_or_else(|| format!("Invalid artwork kind: {}", kind))?;
+
+    let bytes = if image.starts_with("data:image") {
+        let b64 = image.split(',').nth(1).unwrap_or_default();
+        general_purpose::STANDARD.decode(b64).map_err(|e| format!("Invalid image data: {}", e))?
+    } else {
+        tokio::fs::read(&image).await.map_err(|e| e.to_string())?
+    };
+
+    let manager = &state.profile_manager;
+    let mut profiles = manager.load_profiles().await.map_err(|e| e.to_string())?;
+    let profile = profiles.get_profile_mut(&profile_id)
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?;
+
+    let id = profile_id.clone();
+    let path = tokio::task::spawn_blocking(move || crate::core::artwork::store_artwork(&id, artwork_kind, &bytes))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())?;
+
+    match artwork_kind {
+        ArtworkKind::Banner => profile.banner_path = Some(path.clone()),
+        ArtworkKind::Background => profile.background_path = Some(path.clone()),
+    }
+    manager.save_profiles(&profiles).await.map_err(|e| e.to_string())?;
+
+    Ok(path.to_string_lossy().to_string())
+}
+
+#[tauri::command]
+pub async fn remove_profile_artwork(profile_id: String, kind: String, state: tauri::State<'_, AppState>) -> Result<(), String> {
+    use crate::core::artwork::ArtworkKind;
+
+    let artwork_kind = ArtworkKind::parse(&kind).ok_or_else(|| format!("Invalid artwork kind: {}", kind))?;
+
+    let manager = &state.profile_manager;
+    let mut profiles = manager.load_profiles().await.map_err(|e| e.to_string())?;
+    let profile = profiles.get_profile_mut(&profile_id)
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?;
+
+    crate::core::artwork::remove_artwork(&profile_id, artwork_kind);
+    match artwork_kind {
+        ArtworkKind::Banner => profile.banner_path = None,
+        ArtworkKind::Background => profile.background_path = None,
+    }
+    manager.save_profiles(&profiles).await.map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-pub async fn update_profile(profile_id: String, updates: serde_json::Value) -> Result<ProfileList, String> {
+pub async fn update_profile(profile_id: String, updates: serde_json::Value) -> Result<ProfileUpdateResult, String> {
     let manager = ProfileManager::new().map_err(|e| e.to_string())?;
     let mut profiles = manager.load_profiles().await.map_err(|e| e.to_string())?;
 
     let profile = profiles.get_profile_mut(&profile_id)
-        .ok_or_else(|| "Profile not found".to_string())?;
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?;
+    let previous_loader = profile.loader.loader.clone();
+    let previous_version = profile.minecraft_version.clone();
 
     // Update fields from JSON
     if let Some(name) = updates.get("name").and_then(|v| v.as_str()) {
@@ -84,6 +184,57 @@ pub async fn update_profile(profile_id: String, updates: serde_json::Value) -> R
         profile.java_args = if args.is_empty() { None } else { Some(args) };
     }
 
+    // Java-Override: Pfad bzw. Hauptversion, null/"" zum Entfernen
+    if let Some(java_path) = updates.get("java_path") {
+        profile.java_path = java_path.as_str().filter(|s| !s.is_empty()).map(std::path::PathBuf::from);
+    }
+
+    if let Some(java_version) = updates.get("java_version") {
+        profile.java_version = java_version.as_u64().filter(|v| *v > 0).map(|v| v as u32);
+    }
+
+    if let Some(gc) = updates.get("garbage_collector").and_then(|v| v.as_str()) {
+        profile.garbage_collector = crate::types::profile::GarbageCollector::parse(gc)
+            .ok_or_else(|| format!("Unbekannter Garbage Collector: {}", gc))?;
+    }
+
+    if let Some(debug_options) = updates.get("debug_options") {
+        profile.debug_options = serde_json::from_value(debug_options.clone())
+            .map_err(|e| format!("Ungültige Debug-Optionen: {}", e))?;
+    }
+
+    // Gebundener Account: UUID oder null/"" zum Entfernen
+    if let Some(account) = updates.get("account_uuid") {
+        profile.account_uuid = account.as_str().filter(|s| !s.is_empty()).map(String::from);
+    }
+
+    if let Some(display_options) = updates.get("display_options") {
+        profile.display_options = serde_json::from_value(display_options.clone())
+            .map_err(|e| format!("Ungültige Display-Optionen: {}", e))?;
+    }
+
+    if let Some(gamescope) = updates.get("gamescope") {
+        profile.gamescope = serde_json::from_value(gamescope.clone())
+            .map_err(|e| format!("Ungültige gamescope-Optionen: {}", e))?;
+    }
+
+    if let Some(mode) = updates.get("sync_mode").and_then(|v| v.as_str()) {
+        profile.sync_mode = SyncMode::parse(mode)
+            .ok_or_else(|| format!("Unbekannter Sync-Modus: {}", mode))?;
+    }
+
+    if let Some(sync_servers) = updates.get("sync_servers").and_then(|v| v.as_bool()) {
+        profile.sync_servers = sync_servers;
+    }
+
+    if let Some(sync_resourcepacks) = updates.get("sync_resourcepacks").and_then(|v| v.as_bool()) {
+        profile.sync_resourcepacks = sync_resourcepacks;
+    }
+
+    if let Some(sync_configs) = updates.get("sync_configs").and_then(|v| v.as_bool()) {
+        profile.sync_configs = sync_configs;
+    }
+
     // Icon path wird als Base64 Data URL gespeichert
     if let Some(icon) = updates.get("icon_path").and_then(|v| v.as_str()) {
         if icon.starts_with("data:image") {
@@ -91,8 +242,190 @@ pub async fn update_profile(profile_id: String, updates: serde_json::Value) -> R
         }
     }
 
+    let java_args_warnings = crate::core::minecraft::jvm_lint::lint_java_args(
+        profile.java_args.as_deref().unwrap_or_default(),
+        profile.memory_mb,
+        profile.garbage_collector,
+    );
+    if !java_args_warnings.is_empty() {
+        tracing::warn!("Java arguments of {}: {}", profile.name, java_args_warnings.join("; "));
+    }
+
+    let loader_changed = profile.loader.loader != previous_loader;
+    let version_changed = profile.minecraft_version != previous_version;
+    let (new_loader, new_version, game_dir) =
+        (profile.loader.loader.clone(), profile.minecraft_version.clone(), profile.game_dir.clone());
+
     manager.save_profiles(&profiles).await.map_err(|e| e.to_string())?;
-    Ok(profiles)
+
+    // Loader- oder Versionswechsel: inkompatible Mods deaktivieren und alte Loader-Dateien aufräumen
+    let migration = if loader_changed || version_changed {
+        let (from_loader, from_version) = (previous_loader.clone(), previous_version.clone());
+        let (to_loader, to_version) = (new_loader, new_version);
+        let mut summary = tokio::task::spawn_blocking(move || {
+            crate::core::mods::migration::migrate_profile_mods(
+                &game_dir,
+                (&from_loader, &from_version),
+                (&to_loader, &to_version),
+            )
+        })
+        .await
+        .map_err(|e| e.to_string())?;
+
+        let libraries_dir = crate::config::defaults::libraries_dir();
+        crate::core::minecraft::install_cache::clear(&libraries_dir, Some(previous_loader.as_str()), Some(&previous_version));
+
+        // Libraries des alten Loaders nur entfernen, wenn kein anderes Profil ihn noch nutzt
+        let still_used = profiles.profiles.iter().any(|p| p.loader.loader == previous_loader);
+        if loader_changed && !still_used && previous_loader != crate::types::version::ModLoader::Vanilla {
+            crate::gui::remove_loader_files(&previous_loader, &previous_version, &libraries_dir).await;
+            summary.removed_loader_files = true;
+        }
+
+        tracing::info!(
+            "Profile {} migrated: {} mods disabled, {} need review",
+            profile_id, summary.disabled_mods.len(), summary.needs_review.len()
+        );
+        Some(summary)
+    } else {
+        None
+    };
+
+    Ok(ProfileUpdateResult { profiles, migration, java_args_warnings })
+}
+
+/// Rückgabe von `update_profile`: die Profil-Liste (wie bisher) plus ggf. die
+/// Zusammenfassung der Loader-/Versions-Migration
+#[derive(serde::Serialize)]
+pub struct ProfileUpdateResult {
+    #[serde(flatten)]
+    pub profiles: ProfileList,
+    pub migration: Option<crate::core::mods::migration::LoaderMigrationSummary>,
+    /// Hinweise zu den eigenen JVM-Argumenten (gespeichert wird trotzdem)
+    pub java_args_warnings: Vec<String>,
+}
+
+/// Prüft JVM-Argumente vor dem Speichern (z.B. live im Eingabefeld)
+#[tauri::command]
+pub async fn validate_java_args(
+    java_args: Vec<String>,
+    memory_mb: Option<u32>,
+    garbage_collector: Option<String>,
+) -> Result<Vec<String>, String> {
+    let gc = match garbage_collector {
+        Some(gc) => crate::types::profile::GarbageCollector::parse(&gc)
+            .ok_or_else(|| format!("Unbekannter Garbage Collector: {}", gc))?,
+        None => crate::types::profile::GarbageCollector::default(),
+    };
+    Ok(crate::core::minecraft::jvm_lint::lint_java_args(&java_args, memory_mb, gc))
+}
+
+#[derive(serde::Serialize)]
+pub struct ModUpgradeStatus {
+    pub filename: String,
+    pub mod_id: String,
+    pub mod_name: Option<String>,
+    pub current_version: Option<String>,
+    /// Passende Version für die Ziel-Version (None = keine kompatible Version gefunden)
+    pub compatible_version_id: Option<String>,
+    pub compatible_version_number: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+pub struct VersionUpgradePreview {
+    pub current_version: String,
+    pub target_version: String,
+    /// true wenn die Ziel-Version neuer ist – dann sollten die Welten gesichert werden
+    pub is_upgrade: bool,
+    pub worlds: Vec<String>,
+    pub mods: Vec<ModUpgradeStatus>,
+}
+
+/// Vorschau für einen Versionswechsel: welche Welten betroffen sind und welche
+/// installierten Mods eine kompatible Version für die Ziel-Version haben
+#[tauri::command]
+pub async fn preview_version_upgrade(
+    profile_id: String,
+    target_version: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<VersionUpgradePreview, String> {
+    let profiles = state.profile_manager.load_profiles().await.map_err(|e| e.to_string())?;
+    let profile = profiles.get_profile(&profile_id)
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?;
+
+    let worlds = crate::core::minecraft::worlds::get_worlds(&profile.game_dir)
+        .await
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|w| w.folder_name)
+        .collect();
+
+    // Installierte, aktive Modrinth-Mods laut Inhalts-Datenbank
+    let installed: Vec<_> = crate::core::mods::content_db::installed(&profile.game_dir)
+        .into_iter()
+        .filter(|record| record.enabled && record.source.as_deref() != Some("curseforge"))
+        .collect();
+
+    let loader = profile.loader.loader.as_str().to_string();
+    let checks = installed.iter().map(|meta| {
+        let mod_id = meta.mod_id.clone().unwrap_or_default();
+        let (loader, target_version) = (&loader, &target_version);
+        let mod_manager = &state.mod_manager;
+        async move {
+            let compatible = mod_manager
+                .get_mod_versions_raw(&mod_id, crate::types::mod_info::ModSource::Modrinth)
+                .await
+                .unwrap_or_default()
+                .into_iter()
+                .find(|v| v.game_versions.contains(target_version) && v.loaders.contains(loader));
+
+            ModUpgradeStatus {
+                filename: meta.filename.clone(),
+                mod_name: meta.mod_name.clone(),
+                current_version: meta.version.clone(),
+                compatible_version_id: compatible.as_ref().map(|v| v.id.clone()),
+                compatible_version_number: compatible.map(|v| v.version_number),
+                mod_id,
+            }
+        }
+    });
+    let mods = futures_util::future::join_all(checks).await;
+
+    let is_upgrade = crate::core::mods::migration::compare_minecraft_versions(&target_version, &profile.minecraft_version)
+        .is_some_and(|o| o == std::cmp::Ordering::Greater);
+
+    Ok(VersionUpgradePreview {
+        current_version: profile.minecraft_version.clone(),
+        target_version,
+        is_upgrade,
+        worlds,
+        mods,
+    })
+}
+
+#[derive(serde::Serialize)]
+pub struct VersionUpgradeResult {
+    pub backups: Vec<String>,
+    #[serde(flatten)]
+    pub update: ProfileUpdateResult,
+}
+
+/// Wechselt die Minecraft-Version eines Profils; bei `backup_worlds` werden
+/// vorher alle Welten gesichert (bricht ab, wenn ein Backup fehlschlägt)
+#[tauri::command]
+pub async fn upgrade_profile_version(
+    profile_id: String,
+    target_version: String,
+    backup_worlds: bool,
+) -> Result<VersionUpgradeResult, String> {
+    let backups = if backup_worlds {
+        crate::gui::backup_worlds(profile_id.clone(), None).await?
+    } else {
+        Vec::new()
+    };
+
+    let update = update_profile(profile_id, serde_json::json!({ "minecraft_version": target_version })).await?;
+    Ok(VersionUpgradeResult { backups, update })
 }
 
 #[tauri::command]
@@ -100,23 +433,49 @@ pub async fn launch_profile(
     app_handle: tauri::AppHandle,
     profile_id: String,
     username: String,
+    demo: Option<bool>,
 ) -> Result<(), String> {
     let manager = ProfileManager::new().map_err(|e| e.to_string())?;
     let mut profiles = manager.load_profiles().await.map_err(|e| e.to_string())?;
 
     // Clone profile for launching
     let profile_to_launch = profiles.get_profile(&profile_id)
-        .ok_or_else(|| "Profile not found".to_string())?
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?
         .clone();
 
+    if profile_to_launch.archived {
+        return Err(format!("Profil '{}' ist archiviert – bitte zuerst wiederherstellen", profile_to_launch.name));
+    }
+
+    // Fehlende Pflicht-Abhängigkeiten melden – die GUI bietet die Installation
+    // vorher über scan_missing_dependencies an
+    let mods_dir = profile_to_launch.game_dir.join("mods");
+    if let Ok(missing) = tokio::task::spawn_blocking(move || {
+        crate::core::mods::dependencies::find_missing_dependencies(&mods_dir)
+    }).await {
+        for dep in &missing {
+            tracing::warn!("Missing required dependency '{}' (needed by {})", dep.mod_id, dep.required_by.join(", "));
+        }
+    }
+
     // Settings-Sync VOR dem Start: Sammle alle options.txt und merge
     if profile_to_launch.settings_sync {
         tracing::info!("Running auto-sync before launch...");
 
         // 1. OPTIONS.TXT - Sammle alle und merge (neueste gewinnt)
-        let combined = create_combined_options(&profiles.profiles).await;
+        let sync_settings = crate::gui::settings::get_config().await
+            .map(|c| c.sync)
+            .unwrap_or_default();
+        let combined = create_combined_options(&profiles.profiles, &sync_settings).await;
+
+        // Strategie "Prompt": bei offenen Konflikten nicht automatisch mergen
+        let has_conflicts = sync_settings.strategy == SyncStrategy::Prompt
+            && !crate::gui::collect_settings_conflicts(&profiles.profiles, &sync_settings).is_empty();
+        if has_conflicts {
+            tracing::warn!("Settings sync has unresolved conflicts – skipping options.txt merge");
+        }
 
-        if !combined.is_empty() {
+        if !combined.is_empty() && !has_conflicts {
             let profile_options = profile_to_launch.game_dir.join("options.txt");
 
             // Stelle sicher, dass das Profil-Verzeichnis existiert
@@ -125,12 +484,12 @@ pub async fn launch_profile(
             // Merge mit existierenden Profil-Settings (behält version etc.)
             let final_content = if profile_options.exists() {
                 if let Ok(existing) = tokio::fs::read_to_string(&profile_options).await {
-                    merge_for_profile(&existing, &combined)
+                    merge_for_profile(&existing, &combined, profile_to_launch.sync_mode, &sync_settings)
                 } else {
-                    combined.clone()
+                    merge_for_profile("", &combined, profile_to_launch.sync_mode, &sync_settings)
                 }
             } else {
-                combined.clone()
+                merge_for_profile("", &combined, profile_to_launch.sync_mode, &sync_settings)
             };
 
             tokio::fs::write(&profile_options, &final_content).await.ok();
@@ -144,20 +503,41 @@ pub async fn launch_profile(
             tokio::fs::write(&shared_file, &combined).await.ok();
         }
 
-        // 2. SERVERS.DAT - Kopiere die neueste Server-Liste
-        if let Some(latest_servers) = find_latest_file("servers.dat", &profiles.profiles).await {
-            let target = profile_to_launch.game_dir.join("servers.dat");
-            if latest_servers != target {
-                if let Err(e) = tokio::fs::copy(&latest_servers, &target).await {
-                    tracing::warn!("Failed to sync servers.dat: {}", e);
-                } else {
-                    tracing::info!("Synced servers.dat from {:?}", latest_servers);
+        // 2. SERVERS.DAT - Kopiere die neueste Server-Liste (nur zwischen Profilen mit sync_servers)
+        if profile_to_launch.sync_servers {
+            let server_profiles: Vec<Profile> = profiles.profiles.iter()
+                .filter(|p| p.sync_servers && !p.archived)
+                .cloned()
+                .collect();
+            if let Some(latest_servers) = find_latest_file("servers.dat", &server_profiles).await {
+                let target = profile_to_launch.game_dir.join("servers.dat");
+                if latest_servers != target {
+                    if let Err(e) = tokio::fs::copy(&latest_servers, &target).await {
+                        tracing::warn!("Failed to sync servers.dat: {}", e);
+                    } else {
+                        tracing::info!("Synced servers.dat from {:?}", latest_servers);
+                    }
                 }
             }
         }
 
-        // 3. RESOURCEPACKS - Kopiere/Sync den resourcepacks Ordner
-        sync_resourcepacks(&profiles.profiles, &profile_to_launch.game_dir).await;
+        // 3. RESOURCEPACKS - Kopiere/Sync den resourcepacks Ordner (nur zwischen Profilen mit sync_resourcepacks)
+        if profile_to_launch.sync_resourcepacks {
+            let pack_profiles: Vec<Profile> = profiles.profiles.iter()
+                .filter(|p| p.sync_resourcepacks && !p.archived)
+                .cloned()
+                .collect();
+            sync_resourcepacks(&pack_profiles, &profile_to_launch.game_dir).await;
+        }
+
+        // 4. MOD-CONFIGS - Ausgewählte Dateien unter config/ (nur zwischen Profilen mit sync_configs)
+        if profile_to_launch.sync_configs && !sync_settings.config_files.is_empty() {
+            let config_profiles: Vec<Profile> = profiles.profiles.iter()
+                .filter(|p| p.sync_configs && !p.archived)
+                .cloned()
+                .collect();
+            sync_config_files(&config_profiles, &profile_to_launch.game_dir, &sync_settings.config_files).await;
+        }
     }
 
     // Update last played
@@ -166,11 +546,24 @@ pub async fn launch_profile(
     }
     manager.save_profiles(&profiles).await.map_err(|e| e.to_string())?;
 
-    // Hole Account-Daten (UUID, Username, Token) vom aktiven Account
-    // WICHTIG: Verwende refreshed Funktion um abgelaufene Tokens automatisch zu erneuern!
+    // Hole Account-Daten (UUID, Username, Token) – bevorzugt den ans Profil gebundenen Account,
+    // sonst den aktiven. WICHTIG: refreshed Funktion erneuert abgelaufene Tokens automatisch!
+    let bound_account = match &profile_to_launch.account_uuid {
+        Some(uuid) => {
+            let account = crate::gui::auth::get_access_token_refreshed(uuid).await;
+            if account.is_none() {
+                tracing::warn!("Account {} of profile {} not found, using active account", uuid, profile_to_launch.name);
+            }
+            account
+        }
+        None => None,
+    };
+    let active_account = match bound_account {
+        Some(account) => Some(account),
+        None => crate::gui::auth::get_active_access_token_refreshed().await,
+    };
     let (account_uuid, account_username, access_token) =
-        crate::gui::auth::get_active_access_token_refreshed()
-            .await
+        active_account
             .unwrap_or_else(|| {
                 // Fallback für Offline-Accounts
                 let uuid = uuid::Uuid::new_v4().to_string().replace("-", "");
@@ -184,6 +577,25 @@ pub async fn launch_profile(
         access_token != "0"
     );
 
+    // Demo-Modus: explizit angefordert oder Microsoft-Account ohne Spielbesitz.
+    // Schlägt die Prüfung fehl (z.B. offline), wird normal gestartet.
+    let is_demo_user = match demo {
+        Some(demo) => demo,
+        None if access_token != "0" => {
+            match crate::core::auth::MinecraftAuth::new().check_game_ownership(&access_token).await {
+                Ok(owns_game) => !owns_game,
+                Err(e) => {
+                    tracing::warn!("Could not check game ownership: {}", e);
+                    false
+                }
+            }
+        }
+        None => false,
+    };
+    if is_demo_user {
+        tracing::info!("Launching in demo mode");
+    }
+
     // ── Fortschritts-Kanal aufbauen ───────────────────────────────────────────
     // Erstelle einen synchronen Kanal (bounded=8), den MinecraftLauncher
     // für Fortschrittsmeldungen nutzen kann ohne AppHandle zu kennen.
@@ -204,15 +616,17 @@ pub async fn launch_profile(
     });
     // ─────────────────────────────────────────────────────────────────────────
 
-    let launcher = crate::core::minecraft::MinecraftLauncher::new().map_err(|e| e.to_string())?;
-    let result = launcher.launch(
+    use tauri::Manager;
+    let state = app_handle.state::<AppState>();
+    let result = state.launcher.launch_with_features(
         &profile_to_launch,
         &account_username,
         &account_uuid,
-        if access_token == "0" { None } else { Some(&access_token) }
+        if access_token == "0" { None } else { Some(&access_token) },
+        crate::core::minecraft::LaunchFeatures { is_demo_user },
     )
     .await
-    .map_err(|e| e.to_string());
+    .map_err(crate::utils::error::launch_failed);
 
     // Sender entfernen damit der Empfänger-Thread sauber beendet
     crate::core::minecraft::clear_launch_progress_sender();
@@ -220,17 +634,72 @@ pub async fn launch_profile(
     result.map(|_| ())
 }
 
+/// Bricht die Startvorbereitung (Downloads von Assets, Libraries, Loader) eines Profils ab.
+/// `launch_profile` kehrt danach mit einem Fehler zurück. `false`, wenn kein Start läuft.
+#[tauri::command]
+pub async fn cancel_launch(profile_id: String) -> Result<bool, String> {
+    Ok(crate::core::minecraft::cancel_launch(&profile_id))
+}
+
+/// Daten für den Schnellstart-Eintrag (Tray/Menü)
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct QuickLaunchInfo {
+    pub profile_id: String,
+    pub name: String,
+    pub minecraft_version: String,
+    pub loader: String,
+    pub last_played: String,
+}
+
+/// Zuletzt gespieltes Profil (RFC3339-Zeitstempel sind lexikographisch sortierbar)
+pub(crate) fn most_recent_profile(profiles: &ProfileList) -> Option<&Profile> {
+    profiles.profiles.iter()
+        .filter(|p| p.last_played.is_some() && !p.archived)
+        .max_by(|a, b| a.last_played.cmp(&b.last_played))
+}
+
+#[tauri::command]
+pub async fn get_last_played_profile(state: tauri::State<'_, AppState>) -> Result<Option<QuickLaunchInfo>, String> {
+    let manager = &state.profile_manager;
+    let profiles = manager.load_profiles().await.map_err(|e| e.to_string())?;
+
+    Ok(most_recent_profile(&profiles).map(|p| QuickLaunchInfo {
+        profile_id: p.id.clone(),
+        name: p.name.clone(),
+        minecraft_version: p.minecraft_version.clone(),
+        loader: p.loader.loader.to_string(),
+        last_played: p.last_played.clone().unwrap_or_default(),
+    }))
+}
+
+/// Startet das zuletzt gespielte Profil mit dem aktiven Account. Gibt die Profil-ID zurück.
+#[tauri::command]
+pub async fn launch_last_played(app_handle: tauri::AppHandle, state: tauri::State<'_, AppState>) -> Result<String, String> {
+    let manager = &state.profile_manager;
+    let profiles = manager.load_profiles().await.map_err(|e| e.to_string())?;
+
+    let profile_id = most_recent_profile(&profiles)
+        .map(|p| p.id.clone())
+        .ok_or_else(|| "Noch kein Profil gespielt".to_string())?;
+
+    let account = crate::gui::auth::get_active_account().await?
+        .ok_or_else(|| "Kein aktiver Account".to_string())?;
+
+    launch_profile(app_handle, profile_id.clone(), account.username, None).await?;
+    Ok(profile_id)
+}
+
 // ==================== SETTINGS SYNC FUNKTIONEN ====================
 
 
 /// Sammelt alle options.txt von allen Profilen mit Sync und merged sie.
-/// Die neueste Änderung hat Vorrang.
-async fn create_combined_options(profiles: &[Profile]) -> String {
+/// Die neueste Änderung hat Vorrang (bzw. das Master-Profil, je nach Strategie).
+async fn create_combined_options(profiles: &[Profile], sync_settings: &SyncSettings) -> String {
     // Sammle alle options.txt mit Zeitstempel
-    let mut all_options: Vec<(SystemTime, std::path::PathBuf)> = Vec::new();
+    let mut all_options: Vec<(SystemTime, std::path::PathBuf, SyncMode, String)> = Vec::new();
 
     for profile in profiles {
-        if !profile.settings_sync {
+        if !profile.syncs_settings() {
             continue;
         }
 
@@ -241,7 +710,7 @@ async fn create_combined_options(profiles: &[Profile]) -> String {
                 if let Ok(modified) = metadata.modified() {
                     time = time.max(modified);
                 }
-                all_options.push((time, options_path));
+                all_options.push((time, options_path, profile.sync_mode, profile.id.clone()));
             }
         }
     }
@@ -252,7 +721,8 @@ async fn create_combined_options(profiles: &[Profile]) -> String {
     }
 
     // Sortiere nach Zeit (älteste zuerst, damit neueste überschreibt)
-    all_options.sort_by_key(|(time, _)| *time);
+    all_options.sort_by_key(|(time, _, _, _)| *time);
+    sync_settings.order_by_priority(&mut all_options, |(_, _, _, id)| id.as_str());
 
     tracing::info!("Found {} options.txt files for sync", all_options.len());
 
@@ -269,11 +739,13 @@ async fn create_combined_options(profiles: &[Profile]) -> String {
         }
     }
 
-    // Merge alle (sortiert nach Zeit)
-    for (_, path) in &all_options {
+    // Merge alle (sortiert nach Zeit) – jedes Profil liefert nur die Keys seines Sync-Modus
+    for (_, path, mode, _) in &all_options {
         if let Ok(content) = std::fs::read_to_string(path) {
             for (key, value) in parse_options(&content) {
-                combined.insert(key, value);
+                if mode.includes_key(&key) {
+                    combined.insert(key, value);
+                }
             }
         }
     }
@@ -289,32 +761,20 @@ async fn create_combined_options(profiles: &[Profile]) -> String {
     lines.join("\n")
 }
 
-/// Merged combined options in ein Profil, behält aber profil-spezifische Keys
-fn merge_for_profile(existing: &str, combined: &str) -> String {
-    let mut values: HashMap<String, String> = HashMap::new();
-
-    // Blacklist: Diese Keys werden nicht überschrieben (version-spezifisch)
-    let blacklist = ["version"];
-
-    // Lese existierende Werte
-    let existing_values: HashMap<String, String> = parse_options(existing).into_iter().collect();
+/// Merged combined options in ein Profil, behält aber profil-spezifische Keys.
+/// Es werden nur Keys übernommen, die der Sync-Modus des Profils einschließt.
+/// Ausgeschlossene Keys (Launcher-Config, Standard: version) werden nicht überschrieben.
+fn merge_for_profile(existing: &str, combined: &str, mode: SyncMode, sync_settings: &SyncSettings) -> String {
+    // Starte mit den existierenden Werten des Profils
+    let mut values: HashMap<String, String> = parse_options(existing).into_iter().collect();
 
-    // Speichere Blacklist-Werte vom existierenden Profil
-    let mut preserved: HashMap<String, String> = HashMap::new();
-    for key in &blacklist {
-        if let Some(value) = existing_values.get(*key) {
-            preserved.insert(key.to_string(), value.clone());
-        }
-    }
-
-    // Übernehme alle combined Werte
     for (key, value) in parse_options(combined) {
-        values.insert(key, value);
-    }
-
-    // Stelle Blacklist-Werte wieder her
-    for (key, value) in preserved {
-        values.insert(key, value);
+        if sync_settings.is_excluded(&key) {
+            // Ausgeschlossene Werte nur setzen wenn das Profil noch keinen hat
+            values.entry(key).or_insert(value);
+        } else if mode.includes_key(&key) {
+            values.insert(key, value);
+        }
     }
 
     // Erstelle String
@@ -338,13 +798,90 @@ fn parse_options(content: &str) -> Vec<(String, String)> {
     values
 }
 
+// ==================== OPTIONS.TXT EDITOR ====================
+
+#[derive(serde::Serialize)]
+pub struct ProfileOption {
+    pub key: String,
+    pub value: String,
+}
+
+/// Liest die options.txt eines Profils als Key-Value Liste (Reihenfolge wie in der Datei)
+#[tauri::command]
+pub async fn get_profile_options(profile_id: String, state: tauri::State<'_, AppState>) -> Result<Vec<ProfileOption>, String> {
+    let manager = &state.profile_manager;
+    let profiles = manager.load_profiles().await.map_err(|e| e.to_string())?;
+
+    let profile = profiles.get_profile(&profile_id)
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?;
+
+    let options_path = profile.game_dir.join("options.txt");
+    if !options_path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = tokio::fs::read_to_string(&options_path)
+        .await
+        .map_err(|e| format!("Konnte options.txt nicht lesen: {}", e))?;
+
+    Ok(parse_options(&content)
+        .into_iter()
+        .map(|(key, value)| ProfileOption { key, value })
+        .collect())
+}
+
+/// Setzt einen einzelnen Wert in der options.txt eines Profils.
+/// Alle anderen Zeilen bleiben unverändert; unbekannte Keys werden angehängt.
+#[tauri::command]
+pub async fn set_profile_option(profile_id: String, key: String, value: String, state: tauri::State<'_, AppState>) -> Result<(), String> {
+    let key = key.trim().to_string();
+    if key.is_empty() || key.contains(':') || key.contains('\n') || key.contains('\r') {
+        return Err(format!("Ungültiger Options-Key: {}", key));
+    }
+    if value.contains('\n') || value.contains('\r') {
+        return Err("Options-Werte dürfen keine Zeilenumbrüche enthalten".to_string());
+    }
+
+    let manager = &state.profile_manager;
+    let profiles = manager.load_profiles().await.map_err(|e| e.to_string())?;
+
+    let profile = profiles.get_profile(&profile_id)
+        .ok_or_else(|| crate::utils::error::profile_not_found(&profile_id))?;
+
+    let options_path = profile.game_dir.join("options.txt");
+    let content = tokio::fs::read_to_string(&options_path).await.unwrap_or_default();
+    let mut lines: Vec<String> = content.lines().map(|l| l.to_string()).collect();
+
+    let new_line = format!("{}:{}", key, value);
+    let existing = lines.iter().position(|l| {
+        parse_options(l).first().is_some_and(|(k, _)| *k == key)
+    });
+    match existing {
+        Some(pos) => lines[pos] = new_line,
+        None => lines.push(new_line),
+    }
+
+    // Erst in Temp-Datei schreiben, dann umbenennen – options.txt ist nie halb geschrieben
+    tokio::fs::create_dir_all(&profile.game_dir).await.map_err(|e| e.to_string())?;
+    let tmp_path = profile.game_dir.join("options.txt.tmp");
+    tokio::fs::write(&tmp_path, lines.join("\n") + "\n")
+        .await
+        .map_err(|e| format!("Konnte options.txt nicht schreiben: {}", e))?;
+    tokio::fs::rename(&tmp_path, &options_path)
+        .await
+        .map_err(|e| format!("Konnte options.txt nicht schreiben: {}", e))?;
+
+    tracing::info!("options.txt updated for profile {}: {}", profile_id, key);
+    Ok(())
+}
+
 /// Findet die neueste Version einer Datei über alle Profile
 async fn find_latest_file(filename: &str, profiles: &[Profile]) -> Option<std::path::PathBuf> {
     let mut latest_time = SystemTime::UNIX_EPOCH;
     let mut latest_path: Option<std::path::PathBuf> = None;
 
     for profile in profiles {
-        if !profile.settings_sync {
+        if !profile.syncs_settings() {
             continue;
         }
 
@@ -384,7 +921,7 @@ async fn sync_resourcepacks(profiles: &[Profile], target_game_dir: &std::path::P
     let mut all_packs: HashMap<String, (SystemTime, std::path::PathBuf)> = HashMap::new();
 
     for profile in profiles {
-        if !profile.settings_sync {
+        if !profile.syncs_settings() {
             continue;
         }
 
@@ -461,6 +998,39 @@ async fn sync_resourcepacks(profiles: &[Profile], target_game_dir: &std::path::P
     }
 }
 
+/// Synchronisiert ausgewählte Mod-Konfigurationsdateien in das Ziel-Profil
+async fn sync_config_files(profiles: &[Profile], target_game_dir: &std::path::Path, files: &[ConfigFileSync]) {
+    for file in files {
+        // Nur relative Pfade innerhalb des Spielordners erlauben
+        let rel = std::path::Path::new(&file.path);
+        if rel.is_absolute() || file.path.contains("..") {
+            tracing::warn!("Ignoring invalid config sync path: {}", file.path);
+            continue;
+        }
+
+        let target = target_game_dir.join(rel);
+        if file.strategy == FileConflictStrategy::KeepExisting && target.exists() {
+            continue;
+        }
+
+        let Some(source) = find_latest_file(&file.path, profiles).await else {
+            continue;
+        };
+        if source == target {
+            continue;
+        }
+
+        if let Some(parent) = target.parent() {
+            tokio::fs::create_dir_all(parent).await.ok();
+        }
+        if let Err(e) = tokio::fs::copy(&source, &target).await {
+            tracing::warn!("Failed to sync config {}: {}", file.path, e);
+        } else {
+            tracing::info!("Synced config {} from {:?}", file.path, source);
+        }
+    }
+}
+
 /// Kopiert einen Ordner rekursiv
 async fn copy_dir_recursive(src: &std::path::Path, dst: &std::path::Path) -> std::io::Result<()> {
     tokio::fs::create_dir_all(dst).await?;
@@ -1,5 +1,5 @@
 use crate::config::schema::LauncherConfig;
-use crate::types::version::MinecraftVersion;
+use crate::types::version::{MinecraftVersion, VersionType};
 
 #[tauri::command]
 pub async fn get_config() -> Result<LauncherConfig, String> {
@@ -20,31 +20,73 @@ pub async fn get_config() -> Result<LauncherConfig, String> {
 }
 
 #[tauri::command]
-pub async fn save_config(config: LauncherConfig) -> Result<(), String> {
-    let config_path = crate::config::defaults::launcher_dir().join("config.json");
-    
-    if let Some(parent) = config_path.parent() {
-        tokio::fs::create_dir_all(parent)
+pub async fn save_config(mut config: LauncherConfig) -> Result<(), String> {
+    // WebDAV-Passwort gehört in den Schlüsselbund; leer = gespeichertes Passwort behalten
+    let password = std::mem::take(&mut config.cloud_sync.password);
+    if !password.is_empty() {
+        crate::utils::compression::run_blocking(move || crate::core::cloud::keyring::store_password(&password))
             .await
-            .map_err(|e| e.to_string())?;
+            .map_err(|e| format!("Passwort konnte nicht im Schlüsselbund gespeichert werden: {}", e))?;
     }
 
+    let config_path = crate::config::defaults::launcher_dir().join("config.json");
+
     let content = serde_json::to_string_pretty(&config)
         .map_err(|e| e.to_string())?;
-    
-    tokio::fs::write(&config_path, content)
+
+    crate::config::backup::write_with_backup(&config_path, &content)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?;
+
+    crate::utils::i18n::set_language(&config.appearance.language);
+    crate::core::telemetry::set_enabled(config.telemetry.enabled);
+    crate::core::minecraft::set_verify_existing(config.verify_existing);
+    Ok(())
 }
 
+/// Meldungskatalog (Fehlercode → Meldung) für die angegebene oder eingestellte Sprache
 #[tauri::command]
-pub async fn get_minecraft_versions() -> Result<Vec<MinecraftVersion>, String> {
-    let client = crate::api::mojang::MojangClient::new()
-        .map_err(|e| e.to_string())?;
-    
-    client.get_version_manifest()
-        .await
-        .map_err(|e| e.to_string())
+pub async fn get_error_messages(language: Option<String>) -> Result<std::collections::HashMap<String, String>, String> {
+    let language = language.unwrap_or_else(crate::utils::i18n::current_language);
+    Ok(crate::utils::i18n::catalog(&language))
+}
+
+/// Zuletzt geladenes Versions-Manifest (wird beim Start im Hintergrund vorgeladen)
+static VERSION_MANIFEST: std::sync::OnceLock<tokio::sync::RwLock<Option<Vec<MinecraftVersion>>>> =
+    std::sync::OnceLock::new();
+
+fn version_manifest_cache() -> &'static tokio::sync::RwLock<Option<Vec<MinecraftVersion>>> {
+    VERSION_MANIFEST.get_or_init(|| tokio::sync::RwLock::new(None))
+}
+
+/// Lädt das Versions-Manifest neu und legt es im Cache ab
+pub(crate) async fn refresh_version_manifest() -> anyhow::Result<Vec<MinecraftVersion>> {
+    let client = crate::api::mojang::MojangClient::new()?;
+    let versions = client.get_version_manifest().await?;
+    *version_manifest_cache().write().await = Some(versions.clone());
+    Ok(versions)
+}
+
+#[tauri::command]
+pub async fn get_minecraft_versions(version_types: Option<Vec<String>>) -> Result<Vec<MinecraftVersion>, String> {
+    // Filter: "release", "snapshot", "beta", "alpha" – ohne Angabe alle Typen
+    let filter = version_types
+        .map(|types| {
+            types.iter()
+                .map(|t| VersionType::parse(t).ok_or_else(|| format!("Unbekannter Versionstyp: {}", t)))
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .transpose()?;
+
+    let versions = match version_manifest_cache().read().await.clone() {
+        Some(versions) => versions,
+        None => refresh_version_manifest().await.map_err(|e| e.to_string())?,
+    };
+
+    Ok(match filter {
+        Some(types) => versions.into_iter().filter(|v| types.contains(&v.version_type)).collect(),
+        None => versions,
+    })
 }
 
 #[tauri::command]
@@ -194,9 +236,113 @@ pub async fn get_system_memory() -> Result<u64, String> {
     Ok(total_memory_mb)
 }
 
+/// Startet einen manuellen Cloud-Sync (WebDAV) und gibt hoch-/heruntergeladene
+/// Dateien sowie Konflikte zurück
+#[tauri::command]
+pub async fn cloud_sync_now() -> Result<crate::core::cloud::CloudSyncReport, String> {
+    let config = get_config().await?;
+    if !config.cloud_sync.enabled {
+        return Err("Cloud-Sync ist deaktiviert".to_string());
+    }
+
+    crate::core::cloud::sync_now(&config.cloud_sync)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Löst einen Cloud-Sync-Konflikt auf (keep_local = lokale Datei hochladen)
+#[tauri::command]
+pub async fn resolve_cloud_sync_conflict(path: String, keep_local: bool) -> Result<(), String> {
+    let config = get_config().await?;
+    if !config.cloud_sync.enabled {
+        return Err("Cloud-Sync ist deaktiviert".to_string());
+    }
+
+    crate::core::cloud::resolve_conflict(&config.cloud_sync, &path, keep_local)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Verschiebt den kompletten Datenordner (Libraries, Assets, Profile) nach `target_dir`,
+/// passt die gespeicherten Pfade an und prüft das Ergebnis
+#[tauri::command]
+pub async fn migrate_launcher_data(target_dir: String) -> Result<crate::core::fs::data_migration::MigrationReport, String> {
+    if !crate::core::minecraft::get_running_profile_ids().is_empty() {
+        return Err("Bitte zuerst alle laufenden Spiele beenden".to_string());
+    }
+
+    let target = std::path::PathBuf::from(target_dir);
+    let report = crate::utils::compression::run_blocking(move || {
+        crate::core::fs::data_migration::migrate_launcher_data(&target)
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+
+    crate::core::profiles::ProfileManager::invalidate_cache().await;
+    Ok(report)
+}
+
+/// Exportiert die Launcher-Einstellungen (optional mit Accounts ohne Tokens) in eine Datei
+#[tauri::command]
+pub async fn export_launcher_settings(path: String, include_accounts: bool) -> Result<(), String> {
+    let config = get_config().await?;
+    let accounts = if include_accounts {
+        crate::gui::auth::export_accounts().await
+    } else {
+        Vec::new()
+    };
+
+    let export = crate::config::transfer::SettingsExport::new(config, accounts);
+    let content = serde_json::to_string_pretty(&export).map_err(|e| e.to_string())?;
+    tokio::fs::write(&path, content).await.map_err(|e| e.to_string())
+}
+
+/// Importiert eine mit `export_launcher_settings` erstellte Datei
+#[tauri::command]
+pub async fn import_launcher_settings(path: String) -> Result<crate::config::transfer::SettingsImportResult, String> {
+    let content = tokio::fs::read_to_string(&path).await.map_err(|e| e.to_string())?;
+    let export: crate::config::transfer::SettingsExport = serde_json::from_str(&content)
+        .map_err(|e| format!("Keine gültige Einstellungsdatei: {}", e))?;
+    if export.format_version > crate::config::transfer::FORMAT_VERSION {
+        return Err(format!(
+            "Die Datei stammt aus einer neueren Launcher-Version ({})",
+            export.launcher_version
+        ));
+    }
+
+    let config = export.merged_config(&get_config().await?);
+    save_config(config).await?;
+    crate::gui::auth::import_accounts(&export.accounts).await
+}
+
+/// Sicherungen von profiles.json und config.json, neueste zuerst
+#[tauri::command]
+pub async fn list_config_backups() -> Result<Vec<crate::config::backup::ConfigBackup>, String> {
+    Ok(crate::config::backup::list())
+}
+
+/// Stellt profiles.json, config.json bzw. auth.json aus einer Sicherung wieder her
+#[tauri::command]
+pub async fn restore_config_backup(name: String) -> Result<(), String> {
+    let file = crate::utils::compression::run_blocking(move || crate::config::backup::restore(&name))
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if file == "profiles.json" {
+        crate::core::profiles::ProfileManager::invalidate_cache().await;
+    } else if file == "config.json" {
+        let config = get_config().await?;
+        crate::utils::i18n::set_language(&config.appearance.language);
+        crate::core::telemetry::set_enabled(config.telemetry.enabled);
+        crate::core::minecraft::set_verify_existing(config.verify_existing);
+    }
+    Ok(())
+}
+
+/// Vorschau des Telemetrie-Berichts – genau diese Daten würden gesendet
 #[tauri::command]
-pub async fn initialize_launcher() -> Result<(), String> {
-    crate::core::fs::ensure_launcher_dirs()
+pub async fn get_telemetry_preview() -> Result<crate::core::telemetry::TelemetryReport, String> {
+    crate::utils::compression::run_blocking(|| Ok(crate::core::telemetry::preview()))
         .await
         .map_err(|e| e.to_string())
 }
@@ -0,0 +1,180 @@
+//! Start im Hintergrund: `initialize_launcher` prüft den Datenordner, legt die Ordner an und kehrt sofort zurück.
+//! Versions-Manifest, Profil-Scan und Update-Prüfung laufen als eigene Tasks und melden
+//! sich per "startup-task"-Event; sind alle fertig, folgt "launcher-ready".
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use tauri::Emitter;
+
+const RELEASES_URL: &str = "https://api.github.com/repos/TheLion102009/Lion-Launcher/releases/latest";
+
+/// Hintergrund-Tasks beim Start
+const STARTUP_TASKS: [&str; 3] = ["manifest", "profiles", "update_check"];
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct StartupTaskStatus {
+    pub task: String,
+    pub ok: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct LauncherUpdateInfo {
+    pub current_version: String,
+    pub latest_version: String,
+    pub url: String,
+    pub update_available: bool,
+}
+
+static STARTUP_STATUS: OnceLock<Mutex<HashMap<String, StartupTaskStatus>>> = OnceLock::new();
+static LAUNCHER_UPDATE: OnceLock<Mutex<Option<LauncherUpdateInfo>>> = OnceLock::new();
+static INTEGRITY_REPORT: Mutex<Option<crate::core::fs::integrity::IntegrityReport>> = Mutex::new(None);
+
+fn startup_status() -> &'static Mutex<HashMap<String, StartupTaskStatus>> {
+    STARTUP_STATUS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn launcher_update() -> &'static Mutex<Option<LauncherUpdateInfo>> {
+    LAUNCHER_UPDATE.get_or_init(|| Mutex::new(None))
+}
+
+/// Speichert das Ergebnis eines Tasks und meldet es an die GUI
+fn finish_task(app_handle: &tauri::AppHandle, task: &str, result: anyhow::Result<()>) {
+    let status = StartupTaskStatus {
+        task: task.to_string(),
+        ok: result.is_ok(),
+        error: result.err().map(|e| e.to_string()),
+    };
+    if let Some(error) = &status.error {
+        tracing::warn!("Startup task '{}' failed: {}", task, error);
+    }
+    app_handle.emit("startup-task", &status).ok();
+
+    let all_done = {
+        let Ok(mut guard) = startup_status().lock() else {
+            tracing::warn!("Startup status lock poisoned, '{}' not recorded", task);
+            return;
+        };
+        guard.insert(task.to_string(), status);
+        STARTUP_TASKS.iter().all(|t| guard.contains_key(*t))
+    };
+    if all_done {
+        tracing::info!("Background startup finished");
+        app_handle.emit("launcher-ready", ()).ok();
+    }
+}
+
+/// Vergleicht "1.2.10" mit "1.2.9" numerisch (ein führendes "v" wird ignoriert)
+fn is_newer_version(latest: &str, current: &str) -> bool {
+    let parse = |v: &str| -> Vec<u64> {
+        v.trim_start_matches('v')
+            .split(['.', '-'])
+            .map_while(|part| part.parse().ok())
+            .collect()
+    };
+    parse(latest) > parse(current)
+}
+
+async fn check_launcher_update() -> anyhow::Result<()> {
+    #[derive(serde::Deserialize)]
+    struct Release {
+        tag_name: String,
+        html_url: String,
+    }
+
+    let client = crate::api::client::ApiClient::new()?;
+    let release: Release = client.get_json(RELEASES_URL).await?;
+    let current = env!("CARGO_PKG_VERSION").to_string();
+
+    let info = LauncherUpdateInfo {
+        update_available: is_newer_version(&release.tag_name, &current),
+        current_version: current,
+        latest_version: release.tag_name.trim_start_matches('v').to_string(),
+        url: release.html_url,
+    };
+    if info.update_available {
+        tracing::info!("Launcher update available: {} -> {}", info.current_version, info.latest_version);
+    }
+    *launcher_update().lock().map_err(|e| anyhow::anyhow!(e.to_string()))? = Some(info);
+    Ok(())
+}
+
+/// Lädt die Profile in den Cache und legt fehlende Spielordner an
+async fn scan_profiles(app_handle: &tauri::AppHandle) -> anyhow::Result<()> {
+    use tauri::Manager;
+
+    let state = app_handle.state::<crate::gui::state::AppState>();
+    let profiles = state.profile_manager.load_profiles().await?;
+    // Komprimiert archivierte Profile haben absichtlich keinen Spielordner
+    for profile in profiles.profiles.iter().filter(|p| p.archive_path.is_none()) {
+        tokio::fs::create_dir_all(&profile.game_dir).await.ok();
+    }
+    tracing::info!("Startup scan: {} profiles", profiles.profiles.len());
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn initialize_launcher(app_handle: tauri::AppHandle) -> Result<(), String> {
+    // Kaputte Dateien reparieren, bevor irgendetwas sie lädt
+    let report = crate::utils::compression::run_blocking(|| Ok(crate::core::fs::integrity::check_launcher_data()))
+        .await
+        .map_err(|e| e.to_string())?;
+    if !report.fixes.is_empty() || !report.errors.is_empty() {
+        crate::core::profiles::ProfileManager::invalidate_cache().await;
+        app_handle.emit("integrity-report", &report).ok();
+    }
+    *INTEGRITY_REPORT.lock().map_err(|e| e.to_string())? = Some(report);
+
+    crate::core::fs::ensure_launcher_dirs()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    startup_status().lock().map_err(|e| e.to_string())?.clear();
+
+    let handle = app_handle.clone();
+    tokio::spawn(async move {
+        let result = crate::gui::settings::refresh_version_manifest().await.map(|_| ());
+        finish_task(&handle, "manifest", result);
+    });
+
+    let handle = app_handle.clone();
+    tokio::spawn(async move {
+        let result = scan_profiles(&handle).await;
+        finish_task(&handle, "profiles", result);
+    });
+
+    let handle = app_handle;
+    tokio::spawn(async move {
+        let result = check_launcher_update().await;
+        finish_task(&handle, "update_check", result);
+    });
+
+    // Opt-in-Telemetrie: fälligen Bericht senden (ohne Zustimmung passiert hier nichts)
+    tokio::spawn(async {
+        let Ok(config) = crate::gui::settings::get_config().await else { return };
+        if let Err(e) = crate::core::telemetry::send_if_due(&config.telemetry).await {
+            tracing::debug!("Telemetry report not sent: {}", e);
+        }
+    });
+
+    Ok(())
+}
+
+/// Stand der Hintergrund-Tasks (falls die GUI Events vor dem Registrieren verpasst hat)
+#[tauri::command]
+pub async fn get_startup_status() -> Result<Vec<StartupTaskStatus>, String> {
+    let guard = startup_status().lock().map_err(|e| e.to_string())?;
+    Ok(STARTUP_TASKS.iter().filter_map(|t| guard.get(*t).cloned()).collect())
+}
+
+/// Ergebnis der Update-Prüfung beim Start (None solange noch nicht geprüft)
+#[tauri::command]
+pub async fn get_launcher_update() -> Result<Option<LauncherUpdateInfo>, String> {
+    Ok(launcher_update().lock().map_err(|e| e.to_string())?.clone())
+}
+
+/// Ergebnis der Integritätsprüfung beim Start (None solange noch nicht geprüft)
+#[tauri::command]
+pub async fn get_integrity_report() -> Result<Option<crate::core::fs::integrity::IntegrityReport>, String> {
+    Ok(INTEGRITY_REPORT.lock().map_err(|e| e.to_string())?.clone())
+}
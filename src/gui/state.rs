@@ -0,0 +1,28 @@
+use crate::core::auth::device_flow::DeviceLoginTracker;
+use crate::core::minecraft::MinecraftLauncher;
+use crate::core::mods::ModManager;
+use crate::core::profiles::ProfileManager;
+
+/// Langlebige Manager, die über `tauri::State` von allen Commands geteilt werden.
+/// HTTP-Clients (Modrinth, Downloads) werden so nur einmal aufgebaut; die Profil-Liste
+/// hält der ProfileManager im Speicher.
+/// Commands, die auch intern oder aus dem CLI aufgerufen werden, nutzen `ProfileManager::new()`;
+/// das ist nur ein Handle auf denselben prozessweiten Profil-Cache.
+pub struct AppState {
+    pub profile_manager: ProfileManager,
+    pub mod_manager: ModManager,
+    pub launcher: MinecraftLauncher,
+    /// Laufender Microsoft-Login (Device Code Flow)
+    pub login: tokio::sync::Mutex<DeviceLoginTracker>,
+}
+
+impl AppState {
+    pub fn new() -> anyhow::Result<Self> {
+        Ok(Self {
+            profile_manager: ProfileManager::new()?,
+            mod_manager: ModManager::new(None)?,
+            launcher: MinecraftLauncher::new()?,
+            login: tokio::sync::Mutex::new(DeviceLoginTracker::default()),
+        })
+    }
+}
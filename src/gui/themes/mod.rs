@@ -1 +1,180 @@
-// Themes will be implemented in the frontend CSS
+// Themes werden im Frontend per CSS-Variablen angewendet.
+// Hier werden eigene Themes (Farben, Hintergrund) im Launcher-Ordner gespeichert,
+// importiert und exportiert.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Dateiendung exportierter Themes
+const THEME_EXTENSION: &str = "lltheme";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Theme {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub author: Option<String>,
+    /// CSS-Variablen ohne `--`, z.B. `bg-dark` → `#1a1a1a`
+    #[serde(default)]
+    pub colors: HashMap<String, String>,
+    /// Hintergrundbild als Data-URL, damit exportierte Themes eigenständig sind
+    #[serde(default)]
+    pub background: Option<String>,
+    /// Eingebaute Themes (dark/light) können nicht gelöscht oder überschrieben werden
+    #[serde(default)]
+    pub builtin: bool,
+}
+
+fn builtin_themes() -> Vec<Theme> {
+    let colors = |pairs: &[(&str, &str)]| pairs.iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect::<HashMap<_, _>>();
+
+    vec![
+        Theme {
+            id: "dark".to_string(),
+            name: "Dark".to_string(),
+            author: None,
+            colors: colors(&[
+                ("bg-dark", "#1a1a1a"), ("bg-medium", "#2d2d2d"), ("bg-light", "#2d2d2d"),
+                ("text-primary", "#ffffff"), ("text-secondary", "#b0b0b0"),
+            ]),
+            background: None,
+            builtin: true,
+        },
+        Theme {
+            id: "light".to_string(),
+            name: "Light".to_string(),
+            author: None,
+            colors: colors(&[
+                ("bg-dark", "#f5f5f5"), ("bg-medium", "#e8e8e8"), ("bg-light", "#d0d0d0"),
+                ("text-primary", "#1a1a1a"), ("text-secondary", "#555555"),
+            ]),
+            background: None,
+            builtin: true,
+        },
+    ]
+}
+
+fn themes_dir() -> PathBuf {
+    crate::config::defaults::launcher_dir().join("themes")
+}
+
+/// Erzeugt eine dateisystem-sichere ID aus dem Theme-Namen
+fn theme_id_from_name(name: &str) -> String {
+    let id: String = name.trim().to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    let id = id.trim_matches('-').to_string();
+    if id.is_empty() { uuid::Uuid::new_v4().to_string() } else { id }
+}
+
+fn validate_theme(theme: &Theme) -> Result<(), String> {
+    if theme.name.trim().is_empty() {
+        return Err("Theme name must not be empty".to_string());
+    }
+    for (key, value) in &theme.colors {
+        let valid_key = key.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+        // Keine Zeichen die aus der CSS-Deklaration ausbrechen könnten
+        let valid_value = !value.contains([';', '{', '}', '<', '>']);
+        if !valid_key || !valid_value {
+            return Err(format!("Invalid theme color: {} = {}", key, value));
+        }
+    }
+    if let Some(background) = &theme.background {
+        if !background.starts_with("data:image/") {
+            return Err("Theme background must be an image data URL".to_string());
+        }
+    }
+    Ok(())
+}
+
+async fn write_theme(mut theme: Theme) -> Result<Theme, String> {
+    if theme.id.trim().is_empty() {
+        theme.id = theme_id_from_name(&theme.name);
+    } else {
+        theme.id = theme_id_from_name(&theme.id);
+    }
+    if builtin_themes().iter().any(|t| t.id == theme.id) {
+        theme.id = format!("{}-custom", theme.id);
+    }
+    theme.builtin = false;
+    validate_theme(&theme)?;
+
+    let dir = themes_dir();
+    tokio::fs::create_dir_all(&dir).await.map_err(|e| e.to_string())?;
+    let content = serde_json::to_string_pretty(&theme).map_err(|e| e.to_string())?;
+    tokio::fs::write(dir.join(format!("{}.json", theme.id)), content)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    tracing::info!("Saved theme '{}' ({})", theme.name, theme.id);
+    Ok(theme)
+}
+
+/// Listet eingebaute und eigene Themes für die Einstellungen
+#[tauri::command]
+pub async fn list_themes() -> Result<Vec<Theme>, String> {
+    let mut themes = builtin_themes();
+
+    if let Ok(mut entries) = tokio::fs::read_dir(themes_dir()).await {
+        let mut custom = Vec::new();
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let path = entry.path();
+            if path.extension().is_some_and(|e| e == "json") {
+                match tokio::fs::read_to_string(&path).await.map(|c| serde_json::from_str::<Theme>(&c)) {
+                    Ok(Ok(theme)) => custom.push(theme),
+                    _ => tracing::warn!("Skipping invalid theme file {:?}", path),
+                }
+            }
+        }
+        custom.sort_by_key(|t| t.name.to_lowercase());
+        themes.extend(custom);
+    }
+
+    Ok(themes)
+}
+
+/// Speichert ein eigenes Theme (legt es an oder überschreibt es)
+#[tauri::command]
+pub async fn save_theme(theme: Theme) -> Result<Theme, String> {
+    write_theme(theme).await
+}
+
+#[tauri::command]
+pub async fn delete_theme(theme_id: String) -> Result<(), String> {
+    if builtin_themes().iter().any(|t| t.id == theme_id) {
+        return Err("Built-in themes cannot be deleted".to_string());
+    }
+    let path = themes_dir().join(format!("{}.json", theme_id_from_name(&theme_id)));
+    tokio::fs::remove_file(&path).await.map_err(|e| e.to_string())
+}
+
+/// Exportiert ein Theme als `.lltheme`-Datei
+#[tauri::command]
+pub async fn export_theme(theme_id: String, target_path: String) -> Result<String, String> {
+    let theme = list_themes().await?
+        .into_iter()
+        .find(|t| t.id == theme_id)
+        .ok_or_else(|| format!("Theme not found: {}", theme_id))?;
+
+    let mut target = PathBuf::from(target_path);
+    if target.extension().is_none() {
+        target.set_extension(THEME_EXTENSION);
+    }
+    let content = serde_json::to_string_pretty(&theme).map_err(|e| e.to_string())?;
+    tokio::fs::write(&target, content).await.map_err(|e| e.to_string())?;
+
+    Ok(target.to_string_lossy().to_string())
+}
+
+/// Importiert ein Theme aus einer `.lltheme`/`.json`-Datei
+#[tauri::command]
+pub async fn import_theme(source_path: String) -> Result<Theme, String> {
+    let content = tokio::fs::read_to_string(&source_path).await.map_err(|e| e.to_string())?;
+    let theme: Theme = serde_json::from_str(&content)
+        .map_err(|e| format!("Invalid theme file: {}", e))?;
+    write_theme(theme).await
+}
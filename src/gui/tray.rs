@@ -0,0 +1,50 @@
+//! Tray-Icon des Launchers: holt das mit `LaunchBehavior::Hide` ausgeblendete Fenster
+//! zurück (Klick oder "Anzeigen") und erlaubt das Beenden ohne sichtbares Fenster.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::menu::{Menu, MenuItem};
+use tauri::tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent};
+use tauri::{AppHandle, Manager};
+
+/// Ob das Tray-Icon angelegt werden konnte (unter Linux z.B. nicht ohne AppIndicator)
+static TRAY_AVAILABLE: AtomicBool = AtomicBool::new(false);
+
+pub fn is_available() -> bool {
+    TRAY_AVAILABLE.load(Ordering::Relaxed)
+}
+
+/// Zeigt das Hauptfenster wieder an und holt es in den Vordergrund
+pub fn show_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        window.show().ok();
+        window.unminimize().ok();
+        window.set_focus().ok();
+    }
+}
+
+pub fn create(app: &tauri::App) -> tauri::Result<()> {
+    let show = MenuItem::with_id(app, "show", "Launcher anzeigen", true, None::<&str>)?;
+    let quit = MenuItem::with_id(app, "quit", "Beenden", true, None::<&str>)?;
+    let menu = Menu::with_items(app, &[&show, &quit])?;
+
+    let mut builder = TrayIconBuilder::with_id("main")
+        .tooltip("Lion Launcher")
+        .menu(&menu)
+        .show_menu_on_left_click(false)
+        .on_menu_event(|app, event| match event.id().as_ref() {
+            "show" => show_main_window(app),
+            "quit" => app.exit(0),
+            _ => {}
+        })
+        .on_tray_icon_event(|tray, event| {
+            if let TrayIconEvent::Click { button: MouseButton::Left, button_state: MouseButtonState::Up, .. } = event {
+                show_main_window(tray.app_handle());
+            }
+        });
+    if let Ok(icon) = tauri::image::Image::from_bytes(include_bytes!("../../icons/icon.png")) {
+        builder = builder.icon(icon);
+    }
+    builder.build(app)?;
+    TRAY_AVAILABLE.store(true, Ordering::Relaxed);
+    Ok(())
+}
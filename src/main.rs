@@ -11,8 +11,14 @@ mod api;
 mod utils;
 mod types;
 mod config;
+mod cli;
 
 fn main() {
+    // Headless-Befehle (update-all-mods, verify-profile, export-profile) ohne Fenster ausführen
+    if let Some(code) = cli::run_from_args() {
+        std::process::exit(code);
+    }
+
     #[cfg(target_os = "linux")]
     {
         std::env::set_var("WEBKIT_DISABLE_DMABUF_RENDERER", "1");
@@ -24,8 +30,30 @@ fn main() {
 
     utils::logging::init_logging();
 
+    // lionlauncher://-Links werden vom Betriebssystem als Argument übergeben;
+    // läuft der Launcher schon, bekommt die vorhandene Instanz den Link
+    let uri = core::uri::find_uri_in_args(std::env::args().skip(1));
+    if core::uri::forward_to_running_instance(uri.as_deref()) {
+        tracing::info!("Forwarded start to the running launcher instance");
+        return;
+    }
+    core::uri::register_uri_scheme();
+    if let Some(uri) = uri {
+        tracing::info!("Started with launcher link: {}", uri);
+        core::uri::set_pending_uri(uri);
+    }
+
+    let app_state = match gui::state::AppState::new() {
+        Ok(state) => state,
+        Err(e) => {
+            tracing::error!("Failed to initialize launcher state: {}", e);
+            std::process::exit(1);
+        }
+    };
+
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
+        .manage(app_state)
         .setup(|app| {
             // Fenster-Icon aus eingebetteten Bytes setzen (Titelleiste / Taskleiste)
             let window = app.get_webview_window("main").unwrap();
@@ -35,6 +63,59 @@ fn main() {
             }
             #[cfg(debug_assertions)]
             window.open_devtools();
+
+            // Tray-Icon, über das ein ausgeblendetes Fenster zurückgeholt werden kann
+            if let Err(e) = gui::tray::create(app) {
+                tracing::warn!("Could not create tray icon: {}", e);
+            }
+
+            // Weitere Starts (z.B. über lionlauncher://-Links) holen dieses Fenster nach vorne
+            let handle = app.handle().clone();
+            core::uri::listen_for_forwarded_uris(move |uri| {
+                use tauri::Emitter;
+                gui::tray::show_main_window(&handle);
+                if let Some(uri) = uri {
+                    tracing::info!("Received launcher link: {}", uri);
+                    core::uri::set_pending_uri(uri);
+                    handle.emit("launcher-uri", ()).ok();
+                }
+            });
+
+            // Externe Änderungen an profiles.json an die GUI melden
+            let handle = app.handle().clone();
+            core::profiles::watch_profiles_file(move || {
+                use tauri::Emitter;
+                handle.emit("profiles-changed", ()).ok();
+            });
+
+            // Entpack-Fortschritt (Installer, Modpack-Overrides) an die GUI weiterleiten
+            let handle = app.handle().clone();
+            utils::compression::set_extraction_progress_listener(move |progress| {
+                use tauri::Emitter;
+                handle.emit("extraction-progress", progress).ok();
+            });
+
+            // Start/Ende von Instanzen melden und Fenster-Verhalten (minimieren, ausblenden, schließen) umsetzen
+            let handle = app.handle().clone();
+            core::minecraft::set_lifecycle_listener(move |event| {
+                use tauri::Emitter;
+                handle.emit("instance-lifecycle", event).ok();
+                if matches!(event, core::minecraft::InstanceLifecycle::Exited { .. }) {
+                    handle.emit("instance-exited", event).ok();
+                }
+                let handle = handle.clone();
+                let event = event.clone();
+                tauri::async_runtime::spawn(async move {
+                    gui::apply_launch_behavior(&handle, &event).await;
+                });
+            });
+
+            // Speicher/CPU laufender Instanzen für das Performance-Overlay
+            let handle = app.handle().clone();
+            core::minecraft::monitor::start(move |metrics| {
+                use tauri::Emitter;
+                handle.emit("instance-metrics", metrics).ok();
+            });
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
@@ -42,9 +123,21 @@ fn main() {
             gui::greet,
             gui::get_embedded_logo_data_url,
             gui::initialize_launcher,
+            gui::get_startup_status,
+            gui::get_launcher_update,
+            gui::get_integrity_report,
+            gui::take_pending_uri_action,
+            gui::parse_launcher_uri,
+            gui::execute_uri_action,
             // Settings
             gui::get_config,
             gui::save_config,
+            gui::get_error_messages,
+            gui::list_themes,
+            gui::save_theme,
+            gui::delete_theme,
+            gui::export_theme,
+            gui::import_theme,
             gui::get_minecraft_versions,
             gui::get_fabric_versions,
             gui::get_quilt_versions,
@@ -59,52 +152,103 @@ fn main() {
             gui::get_profiles,
             gui::create_profile,
             gui::delete_profile,
+            gui::archive_profile,
+            gui::unarchive_profile,
+            gui::move_profile_dir,
             gui::update_profile,
+            gui::validate_java_args,
+            gui::preview_version_upgrade,
+            gui::upgrade_profile_version,
+            gui::set_profile_artwork,
+            gui::remove_profile_artwork,
             gui::launch_profile,
+            gui::cancel_launch,
+            gui::get_last_played_profile,
+            gui::launch_last_played,
+            gui::get_profile_options,
+            gui::set_profile_option,
             // Mods - Browser
             gui::get_modrinth_categories,
+            gui::get_search_filters,
+            gui::browse_content,
             gui::search_mods,
             gui::get_mod_info,
+            gui::get_project_description_html,
+            gui::get_project_gallery,
             gui::get_mod_versions,
             gui::install_mod,
             gui::uninstall_mod,
+            gui::scan_missing_dependencies,
+            gui::install_missing_dependencies,
             // Mods - Verwaltung
             gui::get_installed_mods,
+            gui::get_profile_content_summary,
+            gui::get_profile_disk_usage,
             gui::toggle_mod,
             gui::delete_mod,
             gui::bulk_toggle_mods,
             gui::bulk_delete_mods,
+            gui::undo_operation,
             gui::check_mod_updates,
+            gui::identify_curseforge_mods,
+            gui::rollback_mod,
+            gui::get_archived_mod_versions,
+            gui::get_content_history,
+            gui::revert_content_change,
             // Resource Packs
             gui::get_installed_resourcepacks,
             gui::search_resourcepacks,
             gui::install_resourcepack,
             gui::delete_resourcepack,
+            gui::import_local_resourcepack,
             // Shader Packs
             gui::search_shaderpacks,
             gui::install_shaderpack,
             gui::get_installed_shaderpacks,
             gui::delete_shaderpack,
+            gui::import_local_shaderpack,
+            gui::classify_and_import,
             // Modpacks
             gui::search_modpacks,
             gui::install_modpack,
+            gui::install_modpack_file,
+            gui::get_pending_modpack_installs,
+            gui::resume_modpack_install,
+            gui::discard_modpack_install,
+            // Trash
+            gui::get_trash_items,
+            gui::restore_trash_item,
+            gui::delete_trash_item,
+            gui::empty_trash,
             // Worlds
             gui::get_worlds,
+            gui::backup_worlds,
+            gui::import_world_zip,
+            gui::get_world_seed,
+            gui::update_world_settings,
+            gui::optimize_world,
+            gui::export_world,
             gui::launch_world,
             // Servers
             gui::get_servers,
             gui::launch_server,
             gui::add_server,
             gui::remove_server,
+            // Realms
+            gui::get_realms,
+            gui::launch_realm,
             // Auth
             gui::auth::get_accounts,
             gui::auth::get_active_account,
             gui::auth::set_active_account,
             gui::auth::begin_microsoft_login,
             gui::auth::poll_microsoft_login,
+            gui::auth::copy_microsoft_login_code,
+            gui::auth::cancel_microsoft_login,
             gui::auth::add_offline_account,
             gui::auth::remove_account,
             gui::auth::refresh_account,
+            gui::auth::refresh_account_profile,
             gui::auth::open_auth_url,
             gui::auth::upload_skin_file,
             gui::auth::apply_skin_from_url,
@@ -115,20 +259,48 @@ fn main() {
             gui::auth::delete_saved_skin,
             // Logs & Folders
             gui::get_profile_logs,
+            gui::get_profile_log_chunk,
+            gui::get_profile_log_entries,
+            gui::search_profile_logs,
+            gui::list_crash_reports,
+            gui::get_crash_report,
             gui::get_live_launcher_logs,
             gui::open_profile_folder,
             gui::get_log_files,
+            gui::list_profile_log_files,
+            gui::export_support_bundle,
+            gui::get_gpu_info,
             // Instance Management
             gui::stop_profile,
             gui::get_running_profiles,
+            gui::get_running_instances,
+            gui::kill_instance,
+            gui::get_instance_metrics,
+            gui::get_last_exit_report,
+            gui::get_launcher_stats,
             // Profile Maintenance
             gui::repair_profile,
+            gui::verify_profile_files,
+            gui::reinstall_loader,
             gui::clear_profile_cache,
+            gui::dedup_profile_files,
+            gui::refresh_loader_install,
             // Settings Sync
             gui::sync_settings_to_profile,
             gui::sync_settings_from_profile,
             gui::toggle_settings_sync,
             gui::get_settings_sync_status,
+            gui::set_settings_sync_mode,
+            gui::get_settings_sync_conflicts,
+            gui::resolve_settings_sync_conflicts,
+            gui::cloud_sync_now,
+            gui::resolve_cloud_sync_conflict,
+            gui::migrate_launcher_data,
+            gui::export_launcher_settings,
+            gui::import_launcher_settings,
+            gui::list_config_backups,
+            gui::restore_config_backup,
+            gui::get_telemetry_preview,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -36,6 +36,19 @@ pub struct ModInfo {
     pub discord_url: Option<String>,
     #[serde(default)]
     pub gallery: Vec<GalleryImage>,
+    /// Dasselbe Projekt auf anderen Plattformen (bei kombinierter Suche zusammengeführt)
+    #[serde(default)]
+    pub also_on: Vec<ModListing>,
+}
+
+/// Verweis auf ein Duplikat eines Projekts bei einer anderen Quelle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModListing {
+    pub source: ModSource,
+    pub id: String,
+    pub slug: String,
+    pub project_url: String,
+    pub downloads: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -107,6 +120,11 @@ pub struct ModSearchQuery {
     pub game_version: Option<String>,
     pub loader: Option<String>,
     pub categories: Vec<String>,
+    /// SPDX-Kennung, z.B. "mit" oder "lgpl-3.0"
+    pub license: Option<String>,
+    /// "required", "optional" oder "unsupported"
+    pub client_side: Option<String>,
+    pub server_side: Option<String>,
     pub offset: u32,
     pub limit: u32,
     pub sort_by: SortOption,
@@ -128,6 +146,9 @@ impl Default for ModSearchQuery {
             game_version: None,
             loader: None,
             categories: Vec::new(),
+            license: None,
+            client_side: None,
+            server_side: None,
             offset: 0,
             limit: 20,
             sort_by: SortOption::Relevance,
@@ -11,6 +11,10 @@ pub struct Profile {
     pub minecraft_version: String,
     pub loader: LoaderVersion,
     pub icon_path: Option<PathBuf>,
+    #[serde(default)]
+    pub banner_path: Option<PathBuf>, // Lokales Banner-Bild für die Profilkarte
+    #[serde(default)]
+    pub background_path: Option<PathBuf>, // Lokales Hintergrundbild des Profils
     pub created_at: String,
     pub last_played: Option<String>,
     pub mods: Vec<String>, // Mod IDs
@@ -18,7 +22,317 @@ pub struct Profile {
     pub java_args: Option<Vec<String>>,
     pub memory_mb: Option<u32>,
     #[serde(default)]
+    pub java_path: Option<PathBuf>, // Feste Java-Installation (None = automatisch)
+    #[serde(default)]
+    pub java_version: Option<u32>, // Java-Hauptversion erzwingen (None = laut Minecraft-Version)
+    #[serde(default)]
+    pub recommended_memory_mb: Option<u32>, // Vom Modpack empfohlener RAM (nur Anzeige)
+    #[serde(default)]
     pub settings_sync: bool, // Sync MC settings (options.txt) with global settings
+    #[serde(default)]
+    pub sync_mode: SyncMode, // Welche options.txt-Keys synchronisiert werden
+    #[serde(default = "default_true")]
+    pub sync_servers: bool, // servers.dat mit anderen Sync-Profilen teilen
+    #[serde(default = "default_true")]
+    pub sync_resourcepacks: bool, // resourcepacks/ mit anderen Sync-Profilen teilen
+    #[serde(default)]
+    pub sync_configs: bool, // Ausgewählte Mod-Configs (config/) mit anderen Profilen teilen
+    #[serde(default)]
+    pub launch_count: u64, // Anzahl beendeter Spielsitzungen
+    #[serde(default)]
+    pub total_playtime_secs: u64,
+    #[serde(default)]
+    pub last_session_secs: Option<u64>, // Dauer der letzten Sitzung
+    #[serde(default)]
+    pub last_exit_status: Option<i32>, // Exit-Code der letzten Sitzung (None = unbekannt/abgebrochen)
+    #[serde(default)]
+    pub debug_options: LaunchDebugOptions, // Zusätzliche Logging-/Debug-Flags für die Fehlersuche
+    #[serde(default)]
+    pub display_options: LinuxDisplayOptions, // Wayland/X11-Auswahl (nur Linux)
+    #[serde(default)]
+    pub gamescope: GamescopeOptions, // Start in einer gamescope-Sitzung (nur Linux)
+    #[serde(default)]
+    pub account_uuid: Option<String>, // Account für dieses Profil (None = aktiver Account)
+    #[serde(default)]
+    pub garbage_collector: GarbageCollector,
+    #[serde(default)]
+    pub archived: bool, // Archiviert: ausgeblendet, keine Updates/Sync
+    #[serde(default)]
+    pub archive_path: Option<PathBuf>, // Komprimierter Spielordner eines archivierten Profils
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Umfang der Settings-Synchronisation eines Profils
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncMode {
+    /// Alle Einstellungen (außer Blacklist)
+    #[default]
+    All,
+    /// Nur Tastenbelegungen (key_*)
+    KeybindsOnly,
+    /// Nur Grafik-/Video-Einstellungen
+    VideoOnly,
+}
+
+/// Garbage Collector der JVM
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GarbageCollector {
+    #[default]
+    G1,
+    /// Niedrige Pausenzeiten, ab Java 11 (ab 15 produktiv)
+    Zgc,
+    /// Niedrige Pausenzeiten, ab Java 12 (nicht in allen JDK-Builds enthalten)
+    Shenandoah,
+}
+
+impl GarbageCollector {
+    pub fn parse(gc: &str) -> Option<Self> {
+        match gc {
+            "g1" => Some(GarbageCollector::G1),
+            "zgc" => Some(GarbageCollector::Zgc),
+            "shenandoah" => Some(GarbageCollector::Shenandoah),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GarbageCollector::G1 => "g1",
+            GarbageCollector::Zgc => "zgc",
+            GarbageCollector::Shenandoah => "shenandoah",
+        }
+    }
+
+    /// Mindest-Java-Version, ab der der Collector verfügbar ist
+    pub fn min_java_version(&self) -> u32 {
+        match self {
+            GarbageCollector::G1 => 8,
+            GarbageCollector::Zgc => 11,
+            GarbageCollector::Shenandoah => 12,
+        }
+    }
+}
+
+/// Debug-Schalter für eine Fehlersuche-Sitzung
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LaunchDebugOptions {
+    /// Debug-Logging des Loaders (Fabric/Quilt: fabric.log.level, Forge/NeoForge: Konsolen-Level + Marker)
+    pub loader_debug_logging: bool,
+    /// GC-Log nach logs/gc.log schreiben
+    pub verbose_gc: bool,
+    /// JDWP-Debugger auf 127.0.0.1:<port> lauschen lassen
+    pub jdwp_port: Option<u16>,
+    /// Mit dem Start warten bis ein Debugger verbunden ist
+    pub jdwp_suspend: bool,
+}
+
+impl LaunchDebugOptions {
+    pub fn is_enabled(&self) -> bool {
+        self.loader_debug_logging || self.verbose_gc || self.jdwp_port.is_some()
+    }
+
+    /// JVM-Argumente für die aktiven Schalter. Müssen nach den Standard-Properties
+    /// übergeben werden, da bei doppelten `-D` die letzte Angabe gilt.
+    pub fn jvm_args(&self, loader: &ModLoader, java_version: u32, game_dir: &std::path::Path) -> Vec<String> {
+        let mut args = Vec::new();
+
+        if self.loader_debug_logging {
+            match loader {
+                ModLoader::Fabric | ModLoader::Quilt => {
+                    args.push("-Dfabric.log.level=debug".to_string());
+                }
+                ModLoader::Forge | ModLoader::NeoForge => {
+                    args.push("-Dforge.logging.console.level=debug".to_string());
+                    args.push("-Dforge.logging.markers=SCAN,REGISTRIES,REGISTRYDUMP".to_string());
+                }
+                ModLoader::Vanilla => {}
+            }
+        }
+
+        if self.verbose_gc {
+            let gc_log = game_dir.join("logs").join("gc.log");
+            if java_version >= 9 {
+                // Pfad in Anführungszeichen: sonst zerlegt -Xlog ihn an `:` (Laufwerksbuchstaben)
+                args.push(format!("-Xlog:gc*:file=\"{}\":time,uptime:filecount=5,filesize=10M", gc_log.display()));
+            } else {
+                args.push("-verbose:gc".to_string());
+                args.push("-XX:+PrintGCDetails".to_string());
+                args.push("-XX:+PrintGCDateStamps".to_string());
+                args.push(format!("-Xloggc:{}", gc_log.display()));
+            }
+        }
+
+        if let Some(port) = self.jdwp_port {
+            args.push(format!(
+                "-agentlib:jdwp=transport=dt_socket,server=y,suspend={},address=127.0.0.1:{}",
+                if self.jdwp_suspend { "y" } else { "n" },
+                port
+            ));
+        }
+
+        args
+    }
+}
+
+/// Fenstersystem des Spiels unter Linux
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DisplayBackend {
+    /// GLFW entscheidet (mit WAYLAND_DISPLAY meist XWayland)
+    #[default]
+    Auto,
+    /// X11 bzw. XWayland erzwingen
+    X11,
+    /// Natives Wayland (benötigt in der Regel eine System-GLFW)
+    Wayland,
+}
+
+/// Wayland/X11-Optionen eines Profils, nur unter Linux wirksam
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LinuxDisplayOptions {
+    pub backend: DisplayBackend,
+    /// Die GLFW des Systems statt der mitgelieferten laden (behebt Skalierung/Fensterrahmen unter Wayland)
+    pub use_system_glfw: bool,
+    /// Eigener Pfad zur libglfw.so (leer = bekannte Systempfade durchsuchen)
+    pub glfw_library: Option<String>,
+}
+
+/// Übliche Pfade der System-GLFW (Arch, Fedora, Debian/Ubuntu)
+const SYSTEM_GLFW_PATHS: &[&str] = &[
+    "/usr/lib/libglfw.so.3",
+    "/usr/lib64/libglfw.so.3",
+    "/usr/lib/x86_64-linux-gnu/libglfw.so.3",
+    "/usr/lib/aarch64-linux-gnu/libglfw.so.3",
+];
+
+impl LinuxDisplayOptions {
+    /// Pfad der zu ladenden GLFW-Bibliothek, falls eine System-GLFW gewünscht ist
+    pub fn glfw_library_path(&self) -> Option<String> {
+        if let Some(path) = self.glfw_library.as_ref().filter(|p| !p.trim().is_empty()) {
+            return Some(path.trim().to_string());
+        }
+        if !self.use_system_glfw && self.backend != DisplayBackend::Wayland {
+            return None;
+        }
+        SYSTEM_GLFW_PATHS.iter()
+            .find(|p| std::path::Path::new(p).exists())
+            .map(|p| p.to_string())
+    }
+
+    /// JVM-Argumente (`-Dorg.lwjgl.glfw.libname`)
+    pub fn jvm_args(&self) -> Vec<String> {
+        if !cfg!(target_os = "linux") {
+            return Vec::new();
+        }
+        match self.glfw_library_path() {
+            Some(path) => vec![format!("-Dorg.lwjgl.glfw.libname={}", path)],
+            None => {
+                if self.backend == DisplayBackend::Wayland {
+                    tracing::warn!("Native Wayland requested but no system GLFW found – falling back to bundled GLFW");
+                }
+                Vec::new()
+            }
+        }
+    }
+
+    /// Setzt bzw. entfernt die Umgebungsvariablen für GLFW/SDL.
+    /// Muss nach den Standard-Display-Variablen aufgerufen werden.
+    pub fn apply_env(&self, cmd: &mut std::process::Command) {
+        if !cfg!(target_os = "linux") {
+            return;
+        }
+        match self.backend {
+            DisplayBackend::Auto => {}
+            DisplayBackend::X11 => {
+                // Ohne WAYLAND_DISPLAY wählt GLFW immer X11/XWayland
+                cmd.env_remove("WAYLAND_DISPLAY");
+                cmd.env("SDL_VIDEODRIVER", "x11");
+                cmd.env("GDK_BACKEND", "x11");
+            }
+            DisplayBackend::Wayland => {
+                cmd.env("SDL_VIDEODRIVER", "wayland");
+                cmd.env("GDK_BACKEND", "wayland");
+                // Fensterrahmen vom Compositor statt libdecor-Fallback
+                cmd.env("LIBDECOR_FORCE_CSD", "0");
+            }
+        }
+    }
+}
+
+/// Start des Spiels innerhalb von gamescope (Linux)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GamescopeOptions {
+    pub enabled: bool,
+    /// Auflösung des Spielfensters (und der Ausgabe)
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    /// FPS-Begrenzung
+    pub fps_limit: Option<u32>,
+    pub fullscreen: bool,
+    /// Weitere gamescope-Argumente (z.B. "--adaptive-sync")
+    pub extra_args: Vec<String>,
+}
+
+impl GamescopeOptions {
+    /// Argumente für gamescope vor dem `--`, hinter dem das Java-Kommando folgt
+    pub fn wrapper_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if let (Some(width), Some(height)) = (self.width, self.height) {
+            args.extend([
+                "-W".to_string(), width.to_string(), "-H".to_string(), height.to_string(),
+                "-w".to_string(), width.to_string(), "-h".to_string(), height.to_string(),
+            ]);
+        }
+        if let Some(fps) = self.fps_limit.filter(|f| *f > 0) {
+            args.push("-r".to_string());
+            args.push(fps.to_string());
+        }
+        if self.fullscreen {
+            args.push("-f".to_string());
+        }
+        args.extend(self.extra_args.iter().filter(|a| !a.trim().is_empty()).cloned());
+        args.push("--".to_string());
+        args
+    }
+}
+
+/// options.txt-Keys die zu den Video-Einstellungen gehören
+const VIDEO_OPTION_KEYS: &[&str] = &[
+    "ao", "biomeBlendRadius", "bobView", "chunkUpdates",
+    "cloudRange", "darknessEffectScale", "enableVsync", "entityDistanceScaling",
+    "entityShadows", "fov", "fovEffectScale", "fullscreen", "fullscreenResolution",
+    "gamma", "glintSpeed", "glintStrength", "graphicsMode", "graphicsPreset",
+    "maxFps", "menuBackgroundBlurriness", "mipmapLevels", "particles",
+    "prioritizeChunkUpdates", "renderClouds", "renderDistance", "screenEffectScale",
+    "simulationDistance", "textureFiltering",
+];
+
+impl SyncMode {
+    /// Prüft ob ein options.txt-Key in diesem Modus synchronisiert wird
+    pub fn includes_key(&self, key: &str) -> bool {
+        match self {
+            SyncMode::All => true,
+            SyncMode::KeybindsOnly => key.starts_with("key_"),
+            SyncMode::VideoOnly => VIDEO_OPTION_KEYS.contains(&key),
+        }
+    }
+
+    pub fn parse(mode: &str) -> Option<Self> {
+        match mode {
+            "all" => Some(SyncMode::All),
+            "keybinds_only" => Some(SyncMode::KeybindsOnly),
+            "video_only" => Some(SyncMode::VideoOnly),
+            _ => None,
+        }
+    }
 }
 
 impl Profile {
@@ -42,20 +356,53 @@ impl Profile {
                 minecraft_version,
             },
             icon_path: None,
+            banner_path: None,
+            background_path: None,
             created_at,
             last_played: None,
             mods: Vec::new(),
             game_dir,
             java_args: None,
             memory_mb: None,
+            java_path: None,
+            java_version: None,
+            recommended_memory_mb: None,
             settings_sync: true, // Standardmäßig aktiviert
+            sync_mode: SyncMode::All,
+            sync_servers: true,
+            sync_resourcepacks: true,
+            sync_configs: false,
+            launch_count: 0,
+            total_playtime_secs: 0,
+            last_session_secs: None,
+            last_exit_status: None,
+            debug_options: LaunchDebugOptions::default(),
+            display_options: LinuxDisplayOptions::default(),
+            gamescope: GamescopeOptions::default(),
+            account_uuid: None,
+            garbage_collector: GarbageCollector::G1,
+            archived: false,
+            archive_path: None,
         }
     }
 
+    /// Nimmt am Settings-Sync teil (archivierte Profile nie)
+    pub fn syncs_settings(&self) -> bool {
+        self.settings_sync && !self.archived
+    }
+
     pub fn update_last_played(&mut self) {
         self.last_played = Some(chrono::Utc::now().to_rfc3339());
     }
 
+    /// Übernimmt die Daten einer beendeten Spielsitzung
+    pub fn record_session(&mut self, duration_secs: u64, exit_code: Option<i32>) {
+        self.launch_count += 1;
+        self.total_playtime_secs += duration_secs;
+        self.last_session_secs = Some(duration_secs);
+        self.last_exit_status = exit_code;
+    }
+
     pub fn add_mod(&mut self, mod_id: String) {
         if !self.mods.contains(&mod_id) {
             self.mods.push(mod_id);
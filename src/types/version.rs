@@ -8,17 +8,55 @@ pub struct MinecraftVersion {
     pub version_type: VersionType,
     pub release_time: String,
     pub url: Option<String>,
+    /// Benötigte Java-Major-Version (javaVersion.majorVersion)
+    #[serde(default = "default_java_version")]
+    pub java_version: u32,
+}
+
+fn default_java_version() -> u32 {
+    8
+}
+
+/// Leitet die benötigte Java-Version aus dem Release-Datum ab, solange die Version-JSON
+/// noch nicht geladen ist (das Manifest selbst enthält kein javaVersion-Feld).
+/// Die Grenzen entsprechen den Werten aus den version.json-Dateien von Mojang.
+pub fn java_version_for_release(release_time: &str) -> u32 {
+    let date = release_time.get(..10).unwrap_or(release_time);
+    if date >= "2024-04-03" {
+        21 // ab 24w14a / 1.20.5
+    } else if date >= "2021-11-16" {
+        17 // ab 1.18-pre2
+    } else if date >= "2021-05-12" {
+        16 // ab 21w19a / 1.17
+    } else {
+        8
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "lowercase")]
+#[serde(rename_all = "snake_case")]
 pub enum VersionType {
     Release,
     Snapshot,
+    #[serde(alias = "oldbeta")]
     OldBeta,
+    #[serde(alias = "oldalpha")]
     OldAlpha,
 }
 
+impl VersionType {
+    /// Typ aus dem Manifest ("old_beta") oder aus der Oberfläche ("beta")
+    pub fn parse(version_type: &str) -> Option<Self> {
+        match version_type.to_lowercase().as_str() {
+            "release" => Some(VersionType::Release),
+            "snapshot" => Some(VersionType::Snapshot),
+            "beta" | "old_beta" | "oldbeta" => Some(VersionType::OldBeta),
+            "alpha" | "old_alpha" | "oldalpha" => Some(VersionType::OldAlpha),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum ModLoader {
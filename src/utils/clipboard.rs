@@ -0,0 +1,46 @@
+//! Text in die Zwischenablage kopieren über die System-Tools
+//! (clip, pbcopy, wl-copy/xclip/xsel) – ohne zusätzliche Abhängigkeit.
+
+use anyhow::{Result, bail};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+fn pipe_to(program: &str, args: &[&str], text: &str) -> Result<()> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    child.stdin.take().map(|mut stdin| stdin.write_all(text.as_bytes())).transpose()?;
+    let status = child.wait()?;
+    if !status.success() {
+        bail!("{} exited with {}", program, status);
+    }
+    Ok(())
+}
+
+/// Kopiert Text in die Zwischenablage. Blockierend (wartet auf das Tool).
+pub fn copy_text(text: &str) -> Result<()> {
+    let candidates: &[(&str, &[&str])] = match std::env::consts::OS {
+        "windows" => &[("clip", &[])],
+        "macos" => &[("pbcopy", &[])],
+        _ if std::env::var_os("WAYLAND_DISPLAY").is_some() => &[
+            ("wl-copy", &[]),
+            ("xclip", &["-selection", "clipboard"]),
+            ("xsel", &["--clipboard", "--input"]),
+        ],
+        _ => &[
+            ("xclip", &["-selection", "clipboard"]),
+            ("xsel", &["--clipboard", "--input"]),
+        ],
+    };
+
+    for (program, args) in candidates {
+        match pipe_to(program, args, text) {
+            Ok(()) => return Ok(()),
+            Err(e) => tracing::debug!("Clipboard via {} failed: {}", program, e),
+        }
+    }
+    bail!("Kein Zwischenablage-Tool gefunden (unter Linux wl-clipboard oder xclip installieren)")
+}
@@ -1,18 +1,139 @@
 #![allow(dead_code)]
 
-use anyhow::Result;
-use std::path::Path;
+use anyhow::{Result, bail};
+use std::path::{Path, PathBuf};
 use std::fs::File;
+use std::io::{Read, Seek};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+/// Mindestabstand zwischen zwei Fortschritts-Events pro Archiv
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(150);
+
+/// Fortschritt beim Entpacken eines Archivs (wird als "extraction-progress" an die GUI gesendet)
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ExtractionProgress {
+    pub archive: String,
+    pub entries_done: usize,
+    pub entries_total: usize,
+    pub bytes_done: u64,
+    pub bytes_total: u64,
+    pub finished: bool,
+}
+
+type ProgressListener = Box<dyn Fn(&ExtractionProgress) + Send + Sync>;
+
+static EXTRACTION_LISTENER: OnceLock<ProgressListener> = OnceLock::new();
+
+/// Registriert den Empfänger für Entpack-Fortschritt (einmalig beim Start).
+/// Der Core kennt keinen AppHandle, daher leitet main.rs die Meldungen als Event weiter.
+pub fn set_extraction_progress_listener(listener: impl Fn(&ExtractionProgress) + Send + Sync + 'static) {
+    EXTRACTION_LISTENER.set(Box::new(listener)).ok();
+}
+
+/// Zählt entpackte Einträge/Bytes und meldet sie gedrosselt an den Listener
+pub struct ExtractionTracker {
+    progress: ExtractionProgress,
+    last_emit: Option<Instant>,
+}
+
+impl ExtractionTracker {
+    pub fn new(archive_path: &Path, entries_total: usize, bytes_total: u64) -> Self {
+        let archive = archive_path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        Self {
+            progress: ExtractionProgress {
+                archive,
+                entries_done: 0,
+                entries_total,
+                bytes_done: 0,
+                bytes_total,
+                finished: false,
+            },
+            last_emit: None,
+        }
+    }
+
+    /// Erstellt einen Tracker für ein geöffnetes Archiv (Gesamtgröße = Summe der unkomprimierten Einträge)
+    pub fn for_archive<R: Read + Seek>(archive_path: &Path, archive: &mut zip::ZipArchive<R>) -> Self {
+        let bytes_total = (0..archive.len())
+            .filter_map(|i| archive.by_index_raw(i).ok().map(|e| e.size()))
+            .sum();
+        Self::new(archive_path, archive.len(), bytes_total)
+    }
+
+    /// Ein Eintrag wurde bearbeitet (`bytes` = entpackte Größe, 0 bei übersprungenen Einträgen)
+    pub fn advance(&mut self, bytes: u64) {
+        self.progress.entries_done += 1;
+        self.progress.bytes_done += bytes;
+        if self.last_emit.is_none_or(|t| t.elapsed() >= PROGRESS_INTERVAL) {
+            self.emit();
+        }
+    }
+
+    pub fn finish(mut self) {
+        self.progress.finished = true;
+        self.progress.entries_done = self.progress.entries_total;
+        self.emit();
+        tracing::debug!(
+            "Extracted {}: {} entries, {} KB",
+            self.progress.archive, self.progress.entries_total, self.progress.bytes_done / 1024
+        );
+    }
+
+    fn emit(&mut self) {
+        self.last_emit = Some(Instant::now());
+        if let Some(listener) = EXTRACTION_LISTENER.get() {
+            listener(&self.progress);
+        }
+    }
+}
+
+/// Führt blockierende Arbeit (ZIP-I/O) im Blocking-Threadpool von Tokio aus,
+/// damit die async Runtime während großer Installationen nicht hängt.
+pub async fn run_blocking<T, F>(task: F) -> Result<T>
+where
+    F: FnOnce() -> Result<T> + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(task)
+        .await
+        .map_err(|e| anyhow::anyhow!("Blocking task failed: {}", e))?
+}
+
+/// Prüft ob eine Datei ein vollständig lesbares ZIP ist (z.B. nach abgebrochenem Download)
+pub fn is_valid_zip(path: &Path) -> bool {
+    File::open(path)
+        .ok()
+        .and_then(|file| zip::ZipArchive::new(file).ok())
+        .is_some()
+}
 
 pub fn extract_zip(zip_path: &Path, destination: &Path) -> Result<()> {
+    extract_zip_with_progress(zip_path, destination, |_, _| {})
+}
+
+/// Wie `extract_zip`, meldet aber nach jedem Eintrag (erledigt, gesamt)
+pub fn extract_zip_with_progress<F>(zip_path: &Path, destination: &Path, mut on_progress: F) -> Result<()>
+where
+    F: FnMut(usize, usize),
+{
     let file = File::open(zip_path)?;
     let mut archive = zip::ZipArchive::new(file)?;
+    let total = archive.len();
+    let mut tracker = ExtractionTracker::for_archive(zip_path, &mut archive);
 
-    for i in 0..archive.len() {
+    for i in 0..total {
         let mut file = archive.by_index(i)?;
-        let outpath = destination.join(file.name());
+        // Zip-Slip: Einträge mit ".." oder absolutem Pfad dürfen nicht aus dem Ziel ausbrechen
+        let Some(relative) = file.enclosed_name() else {
+            bail!("Unsicherer Pfad im Archiv: {}", file.name());
+        };
+        let outpath = destination.join(relative);
 
-        if file.name().ends_with('/') {
+        if file.is_dir() {
             std::fs::create_dir_all(&outpath)?;
         } else {
             if let Some(parent) = outpath.parent() {
@@ -21,11 +142,22 @@ pub fn extract_zip(zip_path: &Path, destination: &Path) -> Result<()> {
             let mut outfile = File::create(&outpath)?;
             std::io::copy(&mut file, &mut outfile)?;
         }
+        tracker.advance(file.size());
+        on_progress(i + 1, total);
     }
+    tracker.finish();
 
     Ok(())
 }
 
+/// Entpackt ein ZIP auf einem Blocking-Thread
+pub async fn extract_zip_async<F>(zip_path: PathBuf, destination: PathBuf, on_progress: F) -> Result<()>
+where
+    F: FnMut(usize, usize) + Send + 'static,
+{
+    run_blocking(move || extract_zip_with_progress(&zip_path, &destination, on_progress)).await
+}
+
 pub fn compress_directory(source: &Path, output: &Path) -> Result<()> {
     let file = File::create(output)?;
     let mut zip = zip::ZipWriter::new(file);
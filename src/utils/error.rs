@@ -31,8 +31,143 @@ pub enum LauncherError {
     #[error("Configuration error: {0}")]
     ConfigError(String),
 
+    #[error("Invalid source: {0}")]
+    InvalidSource(String),
+
     #[error("{0}")]
     Other(String),
 }
 
 pub type Result<T> = std::result::Result<T, LauncherError>;
+
+/// Stabile Fehlercodes, die die GUI unabhängig von der Sprache auswerten kann
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorCode {
+    ProfileNotFound,
+    ModNotFound,
+    VersionNotFound,
+    DownloadFailed,
+    LaunchFailed,
+    ConfigError,
+    Network,
+    Io,
+    Json,
+    InvalidSource,
+    Unknown,
+}
+
+impl ErrorCode {
+    pub const ALL: [ErrorCode; 11] = [
+        ErrorCode::ProfileNotFound,
+        ErrorCode::ModNotFound,
+        ErrorCode::VersionNotFound,
+        ErrorCode::DownloadFailed,
+        ErrorCode::LaunchFailed,
+        ErrorCode::ConfigError,
+        ErrorCode::Network,
+        ErrorCode::Io,
+        ErrorCode::Json,
+        ErrorCode::InvalidSource,
+        ErrorCode::Unknown,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCode::ProfileNotFound => "PROFILE_NOT_FOUND",
+            ErrorCode::ModNotFound => "MOD_NOT_FOUND",
+            ErrorCode::VersionNotFound => "VERSION_NOT_FOUND",
+            ErrorCode::DownloadFailed => "DOWNLOAD_FAILED",
+            ErrorCode::LaunchFailed => "LAUNCH_FAILED",
+            ErrorCode::ConfigError => "CONFIG_ERROR",
+            ErrorCode::Network => "NETWORK",
+            ErrorCode::Io => "IO",
+            ErrorCode::Json => "JSON",
+            ErrorCode::InvalidSource => "INVALID_SOURCE",
+            ErrorCode::Unknown => "UNKNOWN",
+        }
+    }
+}
+
+impl LauncherError {
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            LauncherError::Network(_) => ErrorCode::Network,
+            LauncherError::Io(_) => ErrorCode::Io,
+            LauncherError::Json(_) => ErrorCode::Json,
+            LauncherError::ProfileNotFound(_) => ErrorCode::ProfileNotFound,
+            LauncherError::ModNotFound(_) => ErrorCode::ModNotFound,
+            LauncherError::VersionNotFound(_) => ErrorCode::VersionNotFound,
+            LauncherError::DownloadFailed(_) => ErrorCode::DownloadFailed,
+            LauncherError::LaunchFailed(_) => ErrorCode::LaunchFailed,
+            LauncherError::ConfigError(_) => ErrorCode::ConfigError,
+            LauncherError::InvalidSource(_) => ErrorCode::InvalidSource,
+            LauncherError::Other(_) => ErrorCode::Unknown,
+        }
+    }
+
+    /// Meldung in der eingestellten Sprache inkl. Detail (z.B. Profil-ID)
+    pub fn localized(&self) -> String {
+        let message = crate::utils::i18n::message(self.code(), &crate::utils::i18n::current_language());
+        let detail = match self {
+            LauncherError::Network(e) => e.to_string(),
+            LauncherError::Io(e) => e.to_string(),
+            LauncherError::Json(e) => e.to_string(),
+            LauncherError::ProfileNotFound(d)
+            | LauncherError::ModNotFound(d)
+            | LauncherError::VersionNotFound(d)
+            | LauncherError::DownloadFailed(d)
+            | LauncherError::LaunchFailed(d)
+            | LauncherError::ConfigError(d)
+            | LauncherError::InvalidSource(d) => d.clone(),
+            LauncherError::Other(d) => return d.clone(),
+        };
+        if detail.is_empty() {
+            message.to_string()
+        } else {
+            format!("{}: {}", message, detail)
+        }
+    }
+}
+
+/// Fehler für Tauri-Commands: `CODE: übersetzte Meldung`
+impl From<LauncherError> for String {
+    fn from(error: LauncherError) -> Self {
+        format!("{}: {}", error.code().as_str(), error.localized())
+    }
+}
+
+pub fn profile_not_found(profile_id: &str) -> String {
+    LauncherError::ProfileNotFound(profile_id.to_string()).into()
+}
+
+pub fn invalid_source(source: &str) -> String {
+    LauncherError::InvalidSource(source.to_string()).into()
+}
+
+/// Fehler aus dem Core für Commands: `LauncherError` behält seinen Code, alles andere bleibt Text
+pub fn command_error(error: anyhow::Error) -> String {
+    match error.downcast::<LauncherError>() {
+        Ok(error) => error.into(),
+        Err(error) => error.to_string(),
+    }
+}
+
+/// Fehlgeschlagener Download; ein Abbruch durch den Nutzer bleibt ohne Code
+pub fn download_failed(error: anyhow::Error) -> String {
+    if error.is::<crate::core::download::Cancelled>() {
+        return error.to_string();
+    }
+    LauncherError::DownloadFailed(format!("{:#}", error)).into()
+}
+
+/// Fehlgeschlagener Spielstart; ein Abbruch durch den Nutzer bleibt ohne Code
+pub fn launch_failed(error: anyhow::Error) -> String {
+    if error.is::<crate::core::download::Cancelled>() {
+        return error.to_string();
+    }
+    match error.downcast::<LauncherError>() {
+        Ok(error) => error.into(),
+        Err(error) => LauncherError::LaunchFailed(format!("{:#}", error)).into(),
+    }
+}
@@ -0,0 +1,64 @@
+//! Erkennung der Flatpak-Sandbox. Dort gibt es kein System-Java unter /usr/lib/jvm,
+//! Host-Programme sind nur über `flatpak-spawn --host` erreichbar und `xdg-open`
+//! leitet an das OpenURI-Portal weiter.
+
+use anyhow::{Result, bail};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Läuft der Launcher als Flatpak?
+pub fn is_flatpak() -> bool {
+    static FLATPAK: OnceLock<bool> = OnceLock::new();
+    *FLATPAK.get_or_init(|| {
+        cfg!(target_os = "linux")
+            && (Path::new("/.flatpak-info").exists() || std::env::var_os("FLATPAK_ID").is_some())
+    })
+}
+
+/// Java aus der Sandbox: OpenJDK-SDK-Erweiterungen (/usr/lib/sdk/openjdk21) und ins App-Bundle gepacktes JRE
+pub fn sandbox_java_candidates() -> Vec<PathBuf> {
+    let mut candidates = vec![PathBuf::from("/app/jre/bin/java")];
+    if let Ok(entries) = std::fs::read_dir("/usr/lib/sdk") {
+        let mut sdks: Vec<PathBuf> = entries
+            .flatten()
+            .filter(|e| e.file_name().to_string_lossy().starts_with("openjdk"))
+            .map(|e| e.path().join("bin").join("java"))
+            .collect();
+        // Neueste Erweiterung zuerst (openjdk21 vor openjdk17)
+        sdks.sort();
+        sdks.reverse();
+        candidates.extend(sdks);
+    }
+    candidates.retain(|p| p.exists());
+    candidates
+}
+
+/// Öffnet einen Ordner im Dateimanager. In der Sandbox zuerst über den Host
+/// (benötigt `--talk-name=org.freedesktop.Flatpak`), sonst über das Portal.
+pub async fn open_folder(path: &Path) -> Result<()> {
+    if is_flatpak() {
+        let host = tokio::process::Command::new("flatpak-spawn")
+            .arg("--host")
+            .arg("xdg-open")
+            .arg(path)
+            .status()
+            .await;
+        if host.is_ok_and(|s| s.success()) {
+            return Ok(());
+        }
+        tracing::debug!("flatpak-spawn --host not permitted, using the OpenURI portal");
+
+        // Das xdg-open der Runtime spricht das Portal an; Ordner als file://-URI
+        // (prozentkodiert, damit Leerzeichen, # und ? im Pfad den URI nicht zerlegen)
+        let uri = url::Url::from_file_path(path)
+            .map_err(|_| anyhow::anyhow!("Kein absoluter Pfad: {}", path.display()))?;
+        let status = tokio::process::Command::new("xdg-open").arg(uri.as_str()).status().await?;
+        if !status.success() {
+            bail!("Ordner konnte über das Portal nicht geöffnet werden: {}", path.display());
+        }
+        return Ok(());
+    }
+
+    std::process::Command::new("xdg-open").arg(path).spawn()?;
+    Ok(())
+}
@@ -0,0 +1,77 @@
+#![allow(dead_code)]
+
+//! Meldungskatalog für Fehlercodes. Das Backend liefert `CODE: Meldung` zurück;
+//! die GUI kann den Code auswerten oder die bereits übersetzte Meldung anzeigen.
+
+use std::collections::HashMap;
+use std::sync::{OnceLock, RwLock};
+use crate::utils::error::ErrorCode;
+
+/// Unterstützte Sprachen – alles andere fällt auf Englisch zurück
+pub const SUPPORTED_LANGUAGES: [&str; 2] = ["en", "de"];
+
+static LANGUAGE: OnceLock<RwLock<String>> = OnceLock::new();
+
+fn language_lock() -> &'static RwLock<String> {
+    LANGUAGE.get_or_init(|| {
+        // Beim ersten Zugriff die Sprache aus der gespeicherten Konfiguration lesen
+        let config_path = crate::config::defaults::launcher_dir().join("config.json");
+        let language = std::fs::read_to_string(config_path)
+            .ok()
+            .and_then(|c| serde_json::from_str::<crate::config::schema::LauncherConfig>(&c).ok())
+            .map(|c| c.appearance.language)
+            .unwrap_or_else(|| "en".to_string());
+        RwLock::new(language)
+    })
+}
+
+pub fn current_language() -> String {
+    language_lock().read().map(|l| l.clone()).unwrap_or_else(|_| "en".to_string())
+}
+
+pub fn set_language(language: &str) {
+    if let Ok(mut current) = language_lock().write() {
+        *current = language.to_string();
+    }
+}
+
+/// Übersetzte Meldung für einen Fehlercode
+pub fn message(code: ErrorCode, language: &str) -> &'static str {
+    if language.starts_with("de") {
+        match code {
+            ErrorCode::ProfileNotFound => "Profil nicht gefunden",
+            ErrorCode::ModNotFound => "Mod nicht gefunden",
+            ErrorCode::VersionNotFound => "Version nicht gefunden",
+            ErrorCode::DownloadFailed => "Download fehlgeschlagen",
+            ErrorCode::LaunchFailed => "Start fehlgeschlagen",
+            ErrorCode::ConfigError => "Fehler in der Konfiguration",
+            ErrorCode::Network => "Netzwerkfehler",
+            ErrorCode::Io => "Dateisystemfehler",
+            ErrorCode::Json => "Ungültige JSON-Daten",
+            ErrorCode::InvalidSource => "Ungültige Quelle",
+            ErrorCode::Unknown => "Unbekannter Fehler",
+        }
+    } else {
+        match code {
+            ErrorCode::ProfileNotFound => "Profile not found",
+            ErrorCode::ModNotFound => "Mod not found",
+            ErrorCode::VersionNotFound => "Version not found",
+            ErrorCode::DownloadFailed => "Download failed",
+            ErrorCode::LaunchFailed => "Launch failed",
+            ErrorCode::ConfigError => "Configuration error",
+            ErrorCode::Network => "Network error",
+            ErrorCode::Io => "File system error",
+            ErrorCode::Json => "Invalid JSON data",
+            ErrorCode::InvalidSource => "Invalid source",
+            ErrorCode::Unknown => "Unknown error",
+        }
+    }
+}
+
+/// Kompletter Katalog (Code → Meldung) für die GUI
+pub fn catalog(language: &str) -> HashMap<String, String> {
+    ErrorCode::ALL
+        .iter()
+        .map(|code| (code.as_str().to_string(), message(*code, language).to_string()))
+        .collect()
+}
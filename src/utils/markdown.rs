@@ -0,0 +1,163 @@
+//! Markdown → HTML für Projektbeschreibungen (Modrinth-Bodies), ohne JS-Bibliothek
+//! im Webview. Gerendert wird mit pulldown-cmark (GitHub-Erweiterungen wie Tabellen und
+//! Durchstreichen), das Ergebnis inklusive eingebettetem HTML geht danach durch ammonia und
+//! wird auf eine Allowlist von Tags und Attributen reduziert. Skripte, Styles,
+//! Event-Handler und `javascript:`-URLs kommen nie durch.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
+
+/// Tags, deren Inhalt komplett entfernt wird
+const DROPPED_WITH_CONTENT: &[&str] = &["script", "style", "iframe", "object", "embed", "noscript", "template", "textarea"];
+
+const ALLOWED_TAGS: &[&str] = &[
+    "a", "b", "i", "em", "strong", "u", "s", "del", "ins", "mark", "small", "sub", "sup", "kbd", "code", "pre",
+    "p", "div", "span", "center", "br", "hr", "img", "h1", "h2", "h3", "h4", "h5", "h6",
+    "ul", "ol", "li", "blockquote", "table", "thead", "tbody", "tr", "th", "td", "details", "summary",
+];
+
+pub fn to_html(markdown: &str) -> String {
+    use pulldown_cmark::{html, Options, Parser};
+
+    let options = Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH;
+    let mut rendered = String::with_capacity(markdown.len() * 3 / 2);
+    html::push_html(&mut rendered, Parser::new_ext(markdown, options));
+    sanitizer().clean(&rendered).to_string()
+}
+
+fn sanitizer() -> &'static ammonia::Builder<'static> {
+    static SANITIZER: OnceLock<ammonia::Builder<'static>> = OnceLock::new();
+    SANITIZER.get_or_init(|| {
+        let tag_attributes: HashMap<&str, HashSet<&str>> = [
+            ("a", &["href", "title"][..]),
+            ("img", &["src", "alt", "width", "height", "title"][..]),
+            ("td", &["colspan", "rowspan"][..]),
+            ("th", &["colspan", "rowspan"][..]),
+            ("ol", &["start"][..]),
+            ("details", &["open"][..]),
+        ]
+        .into_iter()
+        .map(|(tag, attributes)| (tag, attributes.iter().copied().collect()))
+        .collect();
+
+        let mut builder = ammonia::Builder::empty();
+        builder
+            .tags(ALLOWED_TAGS.iter().copied().collect())
+            .clean_content_tags(DROPPED_WITH_CONTENT.iter().copied().collect())
+            .tag_attributes(tag_attributes)
+            .generic_attributes(["align"].into_iter().collect())
+            .url_schemes(["http", "https", "mailto"].into_iter().collect())
+            // Relative URLs nur als Sprungmarke – alles andere würde gegen den Webview aufgelöst
+            .url_relative(ammonia::UrlRelative::Custom(Box::new(anchor_only)))
+            .attribute_filter(|element, attribute, value| match (element, attribute) {
+                // Bilder nur über http(s), kein mailto: o.ä.
+                ("img", "src") => {
+                    let lower = value.trim().to_ascii_lowercase();
+                    (lower.starts_with("https://") || lower.starts_with("http://")).then(|| value.into())
+                }
+                _ => Some(value.into()),
+            })
+            .link_rel(Some("noopener noreferrer"))
+            .set_tag_attribute_value("a", "target", "_blank");
+        builder
+    })
+}
+
+fn anchor_only(url: &str) -> Option<std::borrow::Cow<'_, str>> {
+    url.starts_with('#').then_some(std::borrow::Cow::Borrowed(url))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::to_html;
+
+    #[test]
+    fn renders_common_markdown() {
+        let html = to_html("# Title\n\n**bold** and [link](https://modrinth.com)\n\n| a | b |\n|---|---|\n| 1 | 2 |");
+        assert!(html.contains("<h1>Title</h1>"));
+        assert!(html.contains("<strong>bold</strong>"));
+        assert!(html.contains("href=\"https://modrinth.com\""));
+        assert!(html.contains("target=\"_blank\""));
+        assert!(html.contains("rel=\"noopener noreferrer\""));
+        assert!(html.contains("<td>2</td>"));
+    }
+
+    #[test]
+    fn drops_javascript_urls() {
+        for input in [
+            "[x](javascript:alert(1))",
+            "<a href=\"javascript:alert(1)\">x</a>",
+            "<a href=\"JaVaScRiPt:alert(1)\">x</a>",
+            "<a href=\"&#106;avascript:alert(1)\">x</a>",
+            "<a href=\"&#x6A;avascript&#58;alert(1)\">x</a>",
+            "<a href=\"java&#x09;script:alert(1)\">x</a>",
+            "<a href=\" javascript:alert(1)\">x</a>",
+            "<img src=\"data:image/svg+xml;base64,PHN2Zz4=\">",
+        ] {
+            let html = to_html(input).to_ascii_lowercase();
+            assert!(!html.contains("javascript"), "{} → {}", input, html);
+            assert!(!html.contains("data:"), "{} → {}", input, html);
+        }
+    }
+
+    #[test]
+    fn drops_event_handlers() {
+        let html = to_html("<img src=\"https://x/a.png\" onerror=\"alert(1)\"><div onclick=alert(1)>x</div>");
+        assert!(!html.contains("onerror"));
+        assert!(!html.contains("onclick"));
+        assert!(html.contains("src=\"https://x/a.png\""));
+    }
+
+    #[test]
+    fn drops_scripts_even_when_unclosed() {
+        for input in ["<script>alert(1)</script>", "<script>alert(1)", "<script src=x", "<SCRIPT>alert(1)</SCRIPT >", "<style>*{}</style>"] {
+            let html = to_html(input).to_ascii_lowercase();
+            assert!(!html.contains("<script"), "{} → {}", input, html);
+            assert!(!html.contains("<style"), "{} → {}", input, html);
+            assert!(!html.contains("alert(1)") || !html.contains('<'), "{} → {}", input, html);
+        }
+    }
+
+    #[test]
+    fn attribute_values_cannot_break_out() {
+        for input in [
+            "<img src=\"https://x/a.png\" alt='\"><script>alert(1)</script>'>",
+            "<a href=\"https://x/\" title='\" onmouseover=\"alert(1)'>x</a>",
+            "[x](https://x/\"onmouseover=\"alert(1))",
+            "![a\"onerror=\"alert(1)](https://x/a.png)",
+        ] {
+            let html = to_html(input).to_ascii_lowercase();
+            assert!(!html.contains("<script"), "{} → {}", input, html);
+            // Im Wert escapt (&quot;) ist harmlos, ein echtes Attribut nicht
+            assert!(!html.contains("onmouseover=\""), "{} → {}", input, html);
+            assert!(!html.contains("onerror=\""), "{} → {}", input, html);
+        }
+    }
+
+    #[test]
+    fn drops_svg_and_math() {
+        for input in [
+            "<svg><script>alert(1)</script></svg>",
+            "<svg onload=alert(1)>",
+            "<math><mtext><img src=x onerror=alert(1)></mtext></math>",
+        ] {
+            let html = to_html(input).to_ascii_lowercase();
+            assert!(!html.contains("<svg"), "{} → {}", input, html);
+            assert!(!html.contains("<math"), "{} → {}", input, html);
+            assert!(!html.contains("onload"), "{} → {}", input, html);
+            assert!(!html.contains("onerror"), "{} → {}", input, html);
+        }
+    }
+
+    #[test]
+    fn survives_deep_nesting() {
+        let quotes = format!("{}deep", "> ".repeat(2_000));
+        assert!(to_html(&quotes).contains("deep"));
+
+        let divs = format!("{}x{}", "<div>".repeat(2_000), "</div>".repeat(2_000));
+        assert!(to_html(&divs).contains('x'));
+
+        let emphasis = format!("{}x{}", "*_".repeat(2_000), "_*".repeat(2_000));
+        assert!(to_html(&emphasis).contains('x'));
+    }
+}
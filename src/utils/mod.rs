@@ -1,4 +1,10 @@
 pub mod logging;
 pub mod error;
+pub mod i18n;
 pub mod threading;
 pub mod compression;
+pub mod nbt;
+pub mod flatpak;
+pub mod clipboard;
+pub mod markdown;
+pub mod murmur2;
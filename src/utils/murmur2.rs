@@ -0,0 +1,36 @@
+//! MurmurHash2 (32 Bit) für CurseForge-Fingerprints. CurseForge entfernt vor dem Hashen
+//! alle Whitespace-Bytes (Tab, LF, CR, Leerzeichen) und hasht mit Seed 1.
+
+const M: u32 = 0x5bd1e995;
+const SEED: u32 = 1;
+
+pub fn hash(data: &[u8], seed: u32) -> u32 {
+    let mut h = seed ^ data.len() as u32;
+
+    let mut chunks = data.chunks_exact(4);
+    for chunk in &mut chunks {
+        let mut k = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        k = k.wrapping_mul(M);
+        k ^= k >> 24;
+        k = k.wrapping_mul(M);
+        h = h.wrapping_mul(M) ^ k;
+    }
+
+    let rest = chunks.remainder();
+    if !rest.is_empty() {
+        for (i, byte) in rest.iter().enumerate() {
+            h ^= (*byte as u32) << (8 * i);
+        }
+        h = h.wrapping_mul(M);
+    }
+
+    h ^= h >> 13;
+    h = h.wrapping_mul(M);
+    h ^ (h >> 15)
+}
+
+/// Fingerprint einer Datei wie von der CurseForge-API erwartet
+pub fn curseforge_fingerprint(data: &[u8]) -> u32 {
+    let normalized: Vec<u8> = data.iter().copied().filter(|b| !matches!(b, 9 | 10 | 13 | 32)).collect();
+    hash(&normalized, SEED)
+}
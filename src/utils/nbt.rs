@@ -0,0 +1,338 @@
+#![allow(dead_code)]
+
+use anyhow::{Result, bail};
+use std::io::Read;
+
+// Minimaler NBT-Reader/-Writer (Named Binary Tag, Big-Endian) für level.dat, servers.dat & Co.
+// Compounds behalten die Reihenfolge ihrer Einträge bei.
+
+/// Schutz vor präparierten Dateien mit endlos verschachtelten Tags
+const MAX_DEPTH: usize = 512;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Tag {
+    Byte(i8),
+    Short(i16),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    ByteArray(Vec<i8>),
+    String(String),
+    List(Vec<Tag>),
+    Compound(Vec<(String, Tag)>),
+    IntArray(Vec<i32>),
+    LongArray(Vec<i64>),
+}
+
+impl Tag {
+    /// Eintrag eines Compounds
+    pub fn get(&self, key: &str) -> Option<&Tag> {
+        match self {
+            Tag::Compound(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut Tag> {
+        match self {
+            Tag::Compound(entries) => entries.iter_mut().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    /// Setzt oder ersetzt einen Eintrag eines Compounds (Reihenfolge bleibt erhalten)
+    pub fn set(&mut self, key: &str, value: Tag) {
+        if let Tag::Compound(entries) = self {
+            match entries.iter_mut().find(|(k, _)| k == key) {
+                Some((_, existing)) => *existing = value,
+                None => entries.push((key.to_string(), value)),
+            }
+        }
+    }
+
+    /// Verschachtelter Eintrag, z.B. `["Data", "Version", "Name"]`
+    pub fn get_path(&self, path: &[&str]) -> Option<&Tag> {
+        path.iter().try_fold(self, |tag, key| tag.get(key))
+    }
+
+    /// Ganzzahliger Wert unabhängig von der Breite (Byte/Short/Int/Long)
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Tag::Byte(v) => Some(*v as i64),
+            Tag::Short(v) => Some(*v as i64),
+            Tag::Int(v) => Some(*v as i64),
+            Tag::Long(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    /// Boolesche Werte werden in NBT als Byte gespeichert
+    pub fn as_bool(&self) -> Option<bool> {
+        self.as_i64().map(|v| v != 0)
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Tag::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    fn type_id(&self) -> u8 {
+        match self {
+            Tag::Byte(_) => 1,
+            Tag::Short(_) => 2,
+            Tag::Int(_) => 3,
+            Tag::Long(_) => 4,
+            Tag::Float(_) => 5,
+            Tag::Double(_) => 6,
+            Tag::ByteArray(_) => 7,
+            Tag::String(_) => 8,
+            Tag::List(_) => 9,
+            Tag::Compound(_) => 10,
+            Tag::IntArray(_) => 11,
+            Tag::LongArray(_) => 12,
+        }
+    }
+}
+
+/// Liest eine NBT-Datei; gzip-komprimierte Daten (wie level.dat) werden erkannt und entpackt.
+/// Gibt Name und Inhalt des Root-Tags zurück.
+pub fn read(data: &[u8]) -> Result<(String, Tag)> {
+    if data.starts_with(&[0x1f, 0x8b]) {
+        let mut decompressed = Vec::new();
+        flate2::read::GzDecoder::new(data).read_to_end(&mut decompressed)?;
+        return read_uncompressed(&decompressed);
+    }
+    read_uncompressed(data)
+}
+
+/// Schreibt einen Root-Compound unkomprimiert
+pub fn write(name: &str, root: &Tag) -> Vec<u8> {
+    let mut out = vec![root.type_id()];
+    write_string(&mut out, name);
+    write_payload(&mut out, root);
+    out
+}
+
+/// Schreibt einen Root-Compound gzip-komprimiert (Format von level.dat)
+pub fn write_compressed(name: &str, root: &Tag) -> Result<Vec<u8>> {
+    use std::io::Write;
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(&write(name, root))?;
+    Ok(encoder.finish()?)
+}
+
+fn write_string(out: &mut Vec<u8>, s: &str) {
+    let bytes = encode_modified_utf8(s);
+    let mut len = bytes.len().min(u16::MAX as usize);
+    // Nicht mitten in einem Zeichen abschneiden: Folgebytes (10xxxxxx) gehören zum Zeichen davor,
+    // und ein High-Surrogat (ED A0..AF xx) ohne sein Low-Surrogat ist ebenfalls unvollständig
+    while len < bytes.len() && len > 0 && bytes[len] & 0xC0 == 0x80 {
+        len -= 1;
+    }
+    if len < bytes.len() && len >= 3 && bytes[len - 3] == 0xED && bytes[len - 2] & 0xF0 == 0xA0 {
+        len -= 3;
+    }
+    out.extend_from_slice(&(len as u16).to_be_bytes());
+    out.extend_from_slice(&bytes[..len]);
+}
+
+/// Java Modified UTF-8: NUL als 0xC0 0x80, Zeichen außerhalb der BMP als Surrogat-Paar
+fn encode_modified_utf8(s: &str) -> Vec<u8> {
+    if !s.chars().any(|c| c == '\0' || c.len_utf8() == 4) {
+        return s.as_bytes().to_vec();
+    }
+    let mut out = Vec::with_capacity(s.len() + 8);
+    for unit in s.encode_utf16() {
+        match unit {
+            0x0001..=0x007F => out.push(unit as u8),
+            0x0000 | 0x0080..=0x07FF => {
+                out.push(0xC0 | (unit >> 6) as u8);
+                out.push(0x80 | (unit & 0x3F) as u8);
+            }
+            _ => {
+                out.push(0xE0 | (unit >> 12) as u8);
+                out.push(0x80 | ((unit >> 6) & 0x3F) as u8);
+                out.push(0x80 | (unit & 0x3F) as u8);
+            }
+        }
+    }
+    out
+}
+
+fn decode_modified_utf8(bytes: &[u8]) -> String {
+    if let Ok(s) = std::str::from_utf8(bytes) {
+        return s.to_string();
+    }
+    let mut units = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let b = bytes[i] as u16;
+        let (unit, len) = match b {
+            0x00..=0x7F => (b, 1),
+            0xC0..=0xDF if i + 1 < bytes.len() => (((b & 0x1F) << 6) | (bytes[i + 1] as u16 & 0x3F), 2),
+            0xE0..=0xEF if i + 2 < bytes.len() => (
+                ((b & 0x0F) << 12) | ((bytes[i + 1] as u16 & 0x3F) << 6) | (bytes[i + 2] as u16 & 0x3F),
+                3,
+            ),
+            _ => (0xFFFD, 1),
+        };
+        units.push(unit);
+        i += len;
+    }
+    String::from_utf16_lossy(&units)
+}
+
+fn write_payload(out: &mut Vec<u8>, tag: &Tag) {
+    match tag {
+        Tag::Byte(v) => out.push(*v as u8),
+        Tag::Short(v) => out.extend_from_slice(&v.to_be_bytes()),
+        Tag::Int(v) => out.extend_from_slice(&v.to_be_bytes()),
+        Tag::Long(v) => out.extend_from_slice(&v.to_be_bytes()),
+        Tag::Float(v) => out.extend_from_slice(&v.to_be_bytes()),
+        Tag::Double(v) => out.extend_from_slice(&v.to_be_bytes()),
+        Tag::ByteArray(values) => {
+            out.extend_from_slice(&(values.len() as i32).to_be_bytes());
+            out.extend(values.iter().map(|b| *b as u8));
+        }
+        Tag::String(s) => write_string(out, s),
+        Tag::List(items) => {
+            // Leere Listen werden als TAG_End-Liste geschrieben
+            out.push(items.first().map(Tag::type_id).unwrap_or(0));
+            out.extend_from_slice(&(items.len() as i32).to_be_bytes());
+            for item in items {
+                write_payload(out, item);
+            }
+        }
+        Tag::Compound(entries) => {
+            for (name, value) in entries {
+                out.push(value.type_id());
+                write_string(out, name);
+                write_payload(out, value);
+            }
+            out.push(0);
+        }
+        Tag::IntArray(values) => {
+            out.extend_from_slice(&(values.len() as i32).to_be_bytes());
+            for v in values {
+                out.extend_from_slice(&v.to_be_bytes());
+            }
+        }
+        Tag::LongArray(values) => {
+            out.extend_from_slice(&(values.len() as i32).to_be_bytes());
+            for v in values {
+                out.extend_from_slice(&v.to_be_bytes());
+            }
+        }
+    }
+}
+
+fn read_uncompressed(data: &[u8]) -> Result<(String, Tag)> {
+    let mut reader = Reader { data, pos: 0 };
+    let tag_type = reader.u8()?;
+    if tag_type != 10 {
+        bail!("NBT root is not a compound (type {})", tag_type);
+    }
+    let name = reader.string()?;
+    let root = reader.payload(tag_type, 0)?;
+    Ok((name, root))
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self.pos.checked_add(len).filter(|end| *end <= self.data.len());
+        let Some(end) = end else {
+            bail!("Unexpected end of NBT data at offset {}", self.pos);
+        };
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        Ok(self.take(N)?.try_into()?)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn i32(&mut self) -> Result<i32> {
+        Ok(i32::from_be_bytes(self.array()?))
+    }
+
+    fn length(&mut self) -> Result<usize> {
+        let len = self.i32()?;
+        // Negative Längen kommen bei leeren Listen vor
+        Ok(len.max(0) as usize)
+    }
+
+    fn string(&mut self) -> Result<String> {
+        let len = u16::from_be_bytes(self.array()?) as usize;
+        Ok(decode_modified_utf8(self.take(len)?))
+    }
+
+    fn payload(&mut self, tag_type: u8, depth: usize) -> Result<Tag> {
+        if depth > MAX_DEPTH {
+            bail!("NBT nesting too deep");
+        }
+        Ok(match tag_type {
+            1 => Tag::Byte(self.u8()? as i8),
+            2 => Tag::Short(i16::from_be_bytes(self.array()?)),
+            3 => Tag::Int(self.i32()?),
+            4 => Tag::Long(i64::from_be_bytes(self.array()?)),
+            5 => Tag::Float(f32::from_be_bytes(self.array()?)),
+            6 => Tag::Double(f64::from_be_bytes(self.array()?)),
+            7 => {
+                let len = self.length()?;
+                Tag::ByteArray(self.take(len)?.iter().map(|b| *b as i8).collect())
+            }
+            8 => Tag::String(self.string()?),
+            9 => {
+                let element_type = self.u8()?;
+                let len = self.length()?;
+                let mut items = Vec::with_capacity(len.min(4096));
+                for _ in 0..len {
+                    items.push(self.payload(element_type, depth + 1)?);
+                }
+                Tag::List(items)
+            }
+            10 => {
+                let mut entries = Vec::new();
+                loop {
+                    let entry_type = self.u8()?;
+                    if entry_type == 0 {
+                        break;
+                    }
+                    let name = self.string()?;
+                    entries.push((name, self.payload(entry_type, depth + 1)?));
+                }
+                Tag::Compound(entries)
+            }
+            11 => {
+                let len = self.length()?;
+                let mut values = Vec::with_capacity(len.min(4096));
+                for _ in 0..len {
+                    values.push(self.i32()?);
+                }
+                Tag::IntArray(values)
+            }
+            12 => {
+                let len = self.length()?;
+                let mut values = Vec::with_capacity(len.min(4096));
+                for _ in 0..len {
+                    values.push(i64::from_be_bytes(self.array()?));
+                }
+                Tag::LongArray(values)
+            }
+            other => bail!("Unknown NBT tag type {} at offset {}", other, self.pos),
+        })
+    }
+}
@@ -3226,6 +3226,10 @@ async function loadWorlds(profileId) {
                             <span><i class="bi bi-box"></i> ${sizeStr}</span>
                         </div>
                     </div>
+                    <button class="btn btn-secondary" onclick="copyWorldSeed('${profileId}', '${world.folder_name}')" title="Seed kopieren"
+                            style="padding: 8px 12px; font-size: 12px;">
+                        <i class="bi bi-clipboard"></i> Seed
+                    </button>
                     <button class="btn btn-gold" onclick="launchWorld('${profileId}', '${world.folder_name}')" 
                             style="padding: 8px 16px; font-size: 12px;">
                         <i class="bi bi-play-fill"></i> Play
@@ -3261,6 +3265,17 @@ async function openWorldsFolder(profileId) {
     }
 }
 
+async function copyWorldSeed(profileId, folderName) {
+    try {
+        const seed = await invoke('get_world_seed', { profileId, folderName });
+        await navigator.clipboard.writeText(seed);
+        showToast(`Seed kopiert: ${seed}`, 'success', 2000);
+    } catch (error) {
+        debugLog('Failed to copy world seed: ' + error, 'error');
+        showToast('Seed konnte nicht gelesen werden: ' + error, 'error', 3000);
+    }
+}
+
 async function launchWorld(profileId, worldName) {
     try {
         showToast(`Starte Welt "${worldName}"...`, 'info', 3000);
@@ -3930,7 +3945,7 @@ async function updateVersionSelects() {
     if (profileSelect) {
         debugLog('Found profile-mc-version select, updating...', 'success');
         profileSelect.innerHTML = filteredVersions.slice(0, versionLimit).map(v =>
-            `<option value="${v.id}">${v.id}${v.version_type !== 'Release' ? ' (' + v.version_type + ')' : ''}</option>`
+            `<option value="${v.id}">${v.id}${v.version_type !== 'release' ? ' (' + v.version_type + ')' : ''}</option>`
         ).join('');
     } else {
         debugLog('profile-mc-version select NOT FOUND!', 'error');
@@ -3941,7 +3956,7 @@ async function updateVersionSelects() {
     if (filterSelect) {
         filterSelect.innerHTML = '<option value="">All Versions</option>' +
             filteredVersions.slice(0, 30).map(v =>
-                `<option value="${v.id}">${v.id}${v.version_type !== 'Release' ? ' (' + v.version_type + ')' : ''}</option>`
+                `<option value="${v.id}">${v.id}${v.version_type !== 'release' ? ' (' + v.version_type + ')' : ''}</option>`
             ).join('');
     }
 }